@@ -89,9 +89,16 @@ Create the name of the service account to use
 Generate the TLS certificates
 */}}
 {{- define "freqtrade-operator-webhook.tls" -}}
+{{- $secret := lookup "v1" "Secret" .Release.Namespace (printf "%s-tls" (include "freqtrade-operator-webhook.fullname" . )) -}}
+{{- if $secret -}}
+{{- /* Keep the certificate across upgrades, the Bot CRD's conversion webhook reads its CA from the Secret */ -}}
+{{- $dict := dict "cert" (index $secret.data "tls.crt" | b64dec) "key" (index $secret.data "tls.key" | b64dec) "ca" (index $secret.data "ca.crt" | b64dec) -}}
+{{- $dict | toYaml -}}
+{{- else -}}
 {{- $ca := genCA "freqtrade-operator" 365 -}}
 {{- $altNames := list ( printf "%s.%s.svc" (include "freqtrade-operator-webhook.fullname" . ) .Release.Namespace) (printf "%s.%s" (include "freqtrade-operator-webhook.fullname" . ) .Release.Namespace) }}
 {{- $cert := genSignedCert (printf "%s-tls" (include "freqtrade-operator-webhook.fullname" . )) nil $altNames 365 $ca -}}
 {{- $dict := dict "cert" $cert.Cert "key" $cert.Key "ca" $ca.Cert -}}
 {{- $dict | toYaml -}}
+{{- end -}}
 {{- end -}}
\ No newline at end of file
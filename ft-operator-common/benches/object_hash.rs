@@ -0,0 +1,61 @@
+// SPDX-FileCopyrightText: 2025 Timothy Pogue
+//
+// SPDX-License-Identifier: ISC
+
+use std::hint::black_box;
+use criterion::{criterion_group, criterion_main, Criterion};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use ft_operator_common::utils::compute_object_hash;
+
+/// A Bot ConfigMap embedding a strategy source of a few hundred KB, as hashed on every reconcile
+fn bot_config_map() -> Value {
+    let strategy = "    def populate_indicators(self, dataframe, metadata):\n        return dataframe\n".repeat(4000);
+
+    json!({
+        "apiVersion": "v1",
+        "kind": "ConfigMap",
+        "metadata": {
+            "name": "bot-config",
+            "namespace": "trading",
+            "labels": {"app.kubernetes.io/name": "bot", "app.kubernetes.io/managed-by": "freqtrade-operator"},
+        },
+        "data": {
+            "config.json": json!({
+                "dry_run": true,
+                "stake_currency": "USDT",
+                "exchange": {"name": "binance", "pair_whitelist": ["BTC/USDT", "ETH/USDT", "SOL/USDT"]},
+                "telegram": {"enabled": true, "chat_id": "1234"},
+            }).to_string(),
+            "SampleStrategy.py": strategy,
+        },
+    })
+}
+
+/// The previous implementation, re-parsing the serialized object and sorting it before hashing
+fn reparsed_object_hash<T: Serialize>(object: &T) -> String {
+    fn sort_json(value: Value) -> Value {
+        match value {
+            Value::Object(map) => Value::Object(map.into_iter().map(|(k, v)| (k, sort_json(v))).collect()),
+            Value::Array(arr) => Value::Array(arr.into_iter().map(sort_json).collect()),
+            _ => value,
+        }
+    }
+
+    let value: Value = serde_json::from_str(&serde_json::to_string(object).unwrap()).unwrap();
+    blake3::hash(serde_json::to_string(&sort_json(value)).unwrap().as_bytes()).to_hex().to_string()
+}
+
+fn object_hash(c: &mut Criterion) {
+    let config_map = bot_config_map();
+    assert_eq!(compute_object_hash(&config_map).unwrap(), reparsed_object_hash(&config_map));
+
+    let mut group = c.benchmark_group("object_hash");
+    group.bench_function("streamed", |b| b.iter(|| compute_object_hash(black_box(&config_map)).unwrap()));
+    group.bench_function("reparsed", |b| b.iter(|| reparsed_object_hash(black_box(&config_map))));
+    group.finish();
+}
+
+criterion_group!(benches, object_hash);
+criterion_main!(benches);
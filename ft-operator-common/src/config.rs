@@ -2,11 +2,13 @@
 //
 // SPDX-License-Identifier: ISC
 
+use std::collections::BTreeMap;
 use std::path::Path;
 use serde::{Serialize, Deserialize};
 use figment::{Figment, Error, providers::{Format, Json, Yaml, Env, Serialized}};
 
-use crate::constant::ENV_PREFIX;
+use crate::constant::{ENV_PREFIX, WATCH_NAMESPACE_ENV};
+use crate::telemetry::parse_log_filter;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[allow(unused)]
@@ -16,6 +18,8 @@ pub struct AppConfig {
     pub controller: ControllerConfig,
     #[serde(default)]
     pub webhook: WebhookConfig,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
 }
 
 
@@ -26,6 +30,55 @@ pub struct ControllerConfig {
     pub default_image_repo: String,
     #[serde(default)]
     pub default_image_tag: String,
+    #[serde(default)]
+    pub default_image_pull_policy: Option<String>,
+    #[serde(default)]
+    pub default_image_pull_secrets: Vec<String>,
+    #[serde(default)]
+    pub default_revision_history_limit: i32,
+    // Merged into the metadata of every resource the controller creates, the Bot's own win
+    #[serde(default)]
+    pub default_labels: BTreeMap<String, String>,
+    #[serde(default)]
+    pub default_annotations: BTreeMap<String, String>,
+    #[serde(default)]
+    pub error_backoff_base_seconds: u64,
+    #[serde(default)]
+    pub error_backoff_max_seconds: u64,
+    #[serde(default)]
+    pub watch_namespaces: Vec<String>,
+    #[serde(default)]
+    pub watch_label_selector: Option<String>,
+    #[serde(default)]
+    pub cleanup_timeout_seconds: u64,
+    #[serde(default)]
+    pub conflict_policy: ConflictPolicy,
+    #[serde(default)]
+    pub max_concurrent_reconciles: u16,
+    #[serde(default)]
+    pub requeue_jitter_percent: u8,
+    #[serde(default)]
+    pub reconcile_debounce_millis: u64,
+    #[serde(default)]
+    pub deletion_propagation: DeletionPropagationConfig,
+    #[serde(default)]
+    pub health: HealthConfig,
+    // Exposes the phase and reconciles of every bot, can be disabled for very large fleets
+    #[serde(default)]
+    pub per_bot_metrics: bool,
+    // Injected into the bot pods, whose egress to the exchanges may have to go through a proxy
+    #[serde(default)]
+    pub proxy: ProxyConfig,
+}
+
+impl ControllerConfig {
+    /// The namespaces the controllers watch, `None` standing for all namespaces when none are configured
+    pub fn watched_namespaces(&self) -> Vec<Option<String>> {
+        match self.watch_namespaces.is_empty() {
+            true => vec![None],
+            false => self.watch_namespaces.iter().cloned().map(Some).collect(),
+        }
+    }
 }
 
 impl Default for ControllerConfig {
@@ -33,10 +86,119 @@ impl Default for ControllerConfig {
         ControllerConfig {
             default_image_repo: "freqtradeorg/freqtrade".to_string(),
             default_image_tag: "stable".to_string(),
+            default_image_pull_policy: None,
+            default_image_pull_secrets: vec![],
+            default_revision_history_limit: 3,
+            default_labels: BTreeMap::new(),
+            default_annotations: BTreeMap::new(),
+            error_backoff_base_seconds: 5,
+            error_backoff_max_seconds: 600,
+            watch_namespaces: vec![],
+            watch_label_selector: None,
+            cleanup_timeout_seconds: 120,
+            conflict_policy: ConflictPolicy::default(),
+            max_concurrent_reconciles: 10,
+            requeue_jitter_percent: 20,
+            reconcile_debounce_millis: 1000,
+            deletion_propagation: DeletionPropagationConfig::default(),
+            health: HealthConfig::default(),
+            per_bot_metrics: true,
+            proxy: ProxyConfig::default(),
+        }
+    }
+}
+
+/// How to apply a resource when an existing object of the same name has fields managed by someone else
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictPolicy {
+    /// Leave the existing object alone and report the conflict
+    #[default]
+    Fail,
+    /// Take over the conflicting fields
+    Force,
+    /// Take over the conflicting fields unless another controller owns the object
+    AdoptIfUnowned,
+}
+
+/// How the dependents of a deleted resource are deleted
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeletionPropagation {
+    /// Delete the resource right away and its dependents in the background
+    #[default]
+    Background,
+    /// Keep the resource until its dependents are deleted
+    Foreground,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[allow(unused)]
+pub struct DeletionPropagationConfig {
+    #[serde(default)]
+    pub deployment: DeletionPropagation,
+    #[serde(default)]
+    pub service: DeletionPropagation,
+}
+
+impl Default for DeletionPropagationConfig {
+    fn default() -> Self {
+        DeletionPropagationConfig {
+            deployment: DeletionPropagation::Foreground,
+            service: DeletionPropagation::Background,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[allow(unused)]
+pub struct HealthConfig {
+    #[serde(default)]
+    pub host: String,
+    #[serde(default)]
+    pub port: u16,
+    #[serde(default)]
+    pub activity_timeout_seconds: u64,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        HealthConfig {
+            host: "0.0.0.0".to_string(),
+            port: 8080,
+            activity_timeout_seconds: 0,
         }
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[allow(unused)]
+pub struct ProxyConfig {
+    #[serde(default)]
+    pub http_proxy: Option<String>,
+    #[serde(default)]
+    pub https_proxy: Option<String>,
+    #[serde(default)]
+    pub no_proxy: Option<String>,
+}
+
+impl ProxyConfig {
+    /// The proxy env vars to set, by name
+    ///
+    /// # Returns
+    /// The `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY` env vars that are configured
+    pub fn env_vars(&self) -> Vec<(&'static str, String)> {
+        [
+            ("HTTP_PROXY", &self.http_proxy),
+            ("HTTPS_PROXY", &self.https_proxy),
+            ("NO_PROXY", &self.no_proxy),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.clone().map(|value| (name, value)))
+        .collect()
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[allow(unused)]
 pub struct WebhookConfig {
@@ -46,6 +208,21 @@ pub struct WebhookConfig {
     pub port: u16,
     #[serde(default)]
     pub tls: TLSConfig,
+    #[serde(default)]
+    pub allowed_exchanges: Vec<String>,
+    #[serde(default)]
+    pub shutdown_timeout_seconds: u64,
+    #[serde(default)]
+    pub max_body_bytes: usize,
+    #[serde(default)]
+    pub strict_strategy_validation: bool,
+    #[serde(default)]
+    pub max_strategy_source_bytes: usize,
+    #[serde(default)]
+    pub allow_validation_bypass: bool,
+    // Denies the pod settings of a Bot that escape the namespace's pod policies
+    #[serde(default)]
+    pub restricted_mode: bool,
 }
 
 impl Default for WebhookConfig {
@@ -54,30 +231,135 @@ impl Default for WebhookConfig {
             host: "0.0.0.0".to_string(),
             port: 8443,
             tls: TLSConfig::default(),
+            allowed_exchanges: vec![],
+            shutdown_timeout_seconds: 10,
+            // An AdmissionReview of an update holds both the old and the new object
+            max_body_bytes: 8 * 1024 * 1024,
+            strict_strategy_validation: false,
+            // The source is rendered into a ConfigMap, which is limited to 1MiB
+            max_strategy_source_bytes: 512 * 1024,
+            allow_validation_bypass: false,
+            restricted_mode: false,
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[allow(unused)]
 pub struct TLSConfig {
+    #[serde(default)]
+    pub enabled: bool,
     #[serde(default)]
     pub cert_file: String,
     #[serde(default)]
     pub key_file: String,
+    #[serde(default)]
+    pub reload_interval_seconds: u64,
 }
 
 impl Default for TLSConfig {
     fn default() -> Self {
         TLSConfig {
+            enabled: true,
             cert_file: "/etc/ssl/certs/tls.crt".to_string(),
             key_file: "/etc/ssl/certs/tls.key".to_string(),
+            reload_interval_seconds: 60,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[allow(unused)]
+pub struct TelemetryConfig {
+    // Log level per target, e.g. `kube: warn`, on top of the `LOG_LEVEL` directives
+    #[serde(default)]
+    pub log_filters: BTreeMap<String, String>,
+}
+
+impl AppConfig {
+    /// The problems of the config that deserialized fine but can't work
+    ///
+    /// # Arguments
+    /// * `check_tls_files` - Whether the webhook's TLS files must exist, only the webhook reads them
+    pub fn problems(&self, check_tls_files: bool) -> Vec<String> {
+        let controller = &self.controller;
+        let webhook = &self.webhook;
+        let mut problems = vec![];
+
+        if controller.default_image_repo.is_empty() {
+            problems.push("controller.default_image_repo must not be empty".to_string());
+        }
+        if controller.default_image_tag.is_empty() {
+            problems.push("controller.default_image_tag must not be empty".to_string());
+        }
+        if controller.error_backoff_base_seconds == 0 {
+            problems.push("controller.error_backoff_base_seconds must be greater than 0".to_string());
+        }
+        if controller.error_backoff_max_seconds < controller.error_backoff_base_seconds {
+            problems.push("controller.error_backoff_max_seconds must not be less than controller.error_backoff_base_seconds".to_string());
+        }
+        if controller.cleanup_timeout_seconds == 0 {
+            problems.push("controller.cleanup_timeout_seconds must be greater than 0".to_string());
+        }
+        if controller.requeue_jitter_percent > 100 {
+            problems.push("controller.requeue_jitter_percent must not be greater than 100".to_string());
         }
+        if controller.health.port == 0 {
+            problems.push("controller.health.port must be greater than 0".to_string());
+        }
+        if webhook.port == 0 {
+            problems.push("webhook.port must be greater than 0".to_string());
+        }
+        for (target, level) in &self.telemetry.log_filters {
+            if let Err(e) = parse_log_filter(target, level) {
+                problems.push(format!("telemetry.log_filters.{} {} is not a valid log filter: {}", target, level, e));
+            }
+        }
+        if check_tls_files && webhook.tls.enabled {
+            for (field, path) in [("webhook.tls.cert_file", &webhook.tls.cert_file), ("webhook.tls.key_file", &webhook.tls.key_file)] {
+                if !Path::new(path).is_file() {
+                    problems.push(format!("{} {} does not exist", field, path));
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// The settings changed from a running config which only take effect on a restart
+    ///
+    /// The servers are bound and the controllers' watches are created once at startup, so a
+    /// reloaded config can't change them.
+    ///
+    /// # Arguments
+    /// * `running` - The config the operator is running with
+    pub fn restart_required_changes(&self, running: &AppConfig) -> Vec<&'static str> {
+        let (controller, running_controller) = (&self.controller, &running.controller);
+        let (webhook, running_webhook) = (&self.webhook, &running.webhook);
+
+        [
+            ("controller.watch_namespaces", controller.watch_namespaces != running_controller.watch_namespaces),
+            ("controller.watch_label_selector", controller.watch_label_selector != running_controller.watch_label_selector),
+            ("controller.max_concurrent_reconciles", controller.max_concurrent_reconciles != running_controller.max_concurrent_reconciles),
+            ("controller.reconcile_debounce_millis", controller.reconcile_debounce_millis != running_controller.reconcile_debounce_millis),
+            ("controller.health", controller.health != running_controller.health),
+            ("webhook.host", webhook.host != running_webhook.host),
+            ("webhook.port", webhook.port != running_webhook.port),
+            ("webhook.tls", webhook.tls != running_webhook.tls),
+            ("webhook.shutdown_timeout_seconds", webhook.shutdown_timeout_seconds != running_webhook.shutdown_timeout_seconds),
+            ("webhook.max_body_bytes", webhook.max_body_bytes != running_webhook.max_body_bytes),
+        ]
+            .into_iter()
+            .filter_map(|(field, changed)| changed.then_some(field))
+            .collect()
     }
 }
 
 pub struct AppConfigBuilder {
     figment: Figment,
+    // An error found while adding a source, returned when building
+    error: Option<Error>,
+    check_tls_files: bool,
 }
 
 impl AppConfigBuilder {
@@ -87,19 +369,48 @@ impl AppConfigBuilder {
             .and_then(|ext| ext.to_str())
             .unwrap_or_default();
 
-        self.figment = match extension {
-            "json" => self.figment.clone().merge(Json::file(path).nested()),
-            "yaml" | "yml" => self.figment.clone().merge(Yaml::file(path).nested()),
-            _ => self.figment.clone(),
+        // A missing file would otherwise be silently treated as empty
+        if !Path::new(path).is_file() {
+            self.error.get_or_insert_with(|| Error::from(format!("config file {} does not exist", path)));
+            return self;
+        }
+
+        match extension {
+            "json" => self.figment = self.figment.clone().merge(Json::file_exact(path)),
+            "yaml" | "yml" => self.figment = self.figment.clone().merge(Yaml::file_exact(path)),
+            _ => {
+                self.error.get_or_insert_with(|| Error::from(format!(
+                    "config file {} has an unsupported extension, expected .json, .yaml or .yml",
+                    path
+                )));
+            },
         };
         self
     }
 
+    pub fn with_file_option(&mut self, path: Option<&str>) -> &mut Self {
+        if let Some(path) = path {
+            self.with_file(path);
+        }
+        self
+    }
+
     pub fn with_env(&mut self) -> &mut Self {
         self.figment = self.figment.clone().merge(Env::prefixed(&format!("{}__", ENV_PREFIX)).split("__"));
         self
     }
 
+    pub fn with_watch_namespace_env(&mut self) -> &mut Self {
+        // WATCH_NAMESPACE is the conventional operator variable, a comma separated list of namespaces
+        if let Ok(value) = std::env::var(WATCH_NAMESPACE_ENV) {
+            let namespaces = parse_watch_namespaces(&value);
+            if !namespaces.is_empty() {
+                self.figment = self.figment.clone().merge(Serialized::default("controller.watch_namespaces", namespaces));
+            }
+        }
+        self
+    }
+
     pub fn with_override_option(&mut self, key: &str, value: Option<&str>) -> &mut Self {
         if let Some(value) = value {
             self.figment = self.figment.clone().merge(Serialized::default(key, value));
@@ -107,15 +418,134 @@ impl AppConfigBuilder {
         self
     }
 
+    /// Check the webhook's TLS files exist when building, for the webhook which serves them
+    pub fn with_tls_files_check(&mut self) -> &mut Self {
+        self.check_tls_files = true;
+        self
+    }
+
+    // figment::Error is large, but the config is only built at startup and on a reload, which are
+    // rare enough for its size not to matter
+    #[allow(clippy::result_large_err)]
     pub fn build(&self) -> Result<AppConfig, Error> {
-        self.figment.extract()
+        if let Some(error) = &self.error {
+            return Err(error.clone());
+        }
+
+        let config: AppConfig = self.figment.extract()?;
+        let problems = config.problems(self.check_tls_files);
+        if !problems.is_empty() {
+            return Err(Error::from(format!("invalid config:\n- {}", problems.join("\n- "))));
+        }
+
+        Ok(config)
     }
 }
 
+/// Parse the namespaces of the WATCH_NAMESPACE variable
+///
+/// # Arguments
+/// * `value` - A comma separated list of namespaces
+///
+/// # Returns
+/// The namespaces, without blanks and surrounding whitespace
+fn parse_watch_namespaces(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|namespace| !namespace.is_empty())
+        .map(String::from)
+        .collect()
+}
+
 impl Default for AppConfigBuilder {
     fn default() -> Self {
         AppConfigBuilder {
-            figment: Figment::from(Serialized::defaults(AppConfig::default()))
+            figment: Figment::from(Serialized::defaults(AppConfig::default())),
+            error: None,
+            check_tls_files: false,
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watch_namespace_is_a_comma_separated_list() {
+        assert_eq!(parse_watch_namespaces("team-a, team-b,,team-c "), vec!["team-a", "team-b", "team-c"]);
+        assert!(parse_watch_namespaces(" , ").is_empty());
+    }
+
+    #[test]
+    fn all_namespaces_are_watched_by_default() {
+        let mut config = ControllerConfig::default();
+        assert_eq!(config.watched_namespaces(), vec![None]);
+
+        config.watch_namespaces = vec!["team-a".to_string(), "team-b".to_string()];
+        assert_eq!(config.watched_namespaces(), vec![Some("team-a".to_string()), Some("team-b".to_string())]);
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn build_config(file: Option<&str>) -> Result<AppConfig, Error> {
+        AppConfigBuilder::default()
+            .with_file_option(file)
+            .with_env()
+            .build()
+    }
+
+    #[test]
+    fn config_is_loaded_from_a_file() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file("config.yaml", "controller:\n  default_image_repo: file/freqtrade\n")?;
+            jail.create_file("config.json", r#"{"controller": {"default_image_repo": "json/freqtrade"}}"#)?;
+
+            assert_eq!(build_config(Some("config.yaml"))?.controller.default_image_repo, "file/freqtrade");
+            assert_eq!(build_config(Some("config.json"))?.controller.default_image_repo, "json/freqtrade");
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn config_is_loaded_from_the_env() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("FTO__CONTROLLER__DEFAULT_IMAGE_REPO", "env/freqtrade");
+
+            assert_eq!(build_config(None)?.controller.default_image_repo, "env/freqtrade");
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn env_overrides_the_file() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file("config.yaml", "controller:\n  default_image_repo: file/freqtrade\n  default_image_tag: file\n")?;
+            jail.set_env("FTO__CONTROLLER__DEFAULT_IMAGE_REPO", "env/freqtrade");
+
+            let config = build_config(Some("config.yaml"))?;
+            assert_eq!(config.controller.default_image_repo, "env/freqtrade");
+            assert_eq!(config.controller.default_image_tag, "file");
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn missing_config_files_are_an_error() {
+        figment::Jail::expect_with(|_| {
+            let error = build_config(Some("missing.yaml")).unwrap_err();
+            assert_eq!(error.to_string(), "config file missing.yaml does not exist");
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn unsupported_config_files_are_an_error() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file("config.toml", "")?;
+
+            let error = build_config(Some("config.toml")).unwrap_err();
+            assert_eq!(error.to_string(), "config file config.toml has an unsupported extension, expected .json, .yaml or .yml");
+            Ok(())
+        });
+    }
+}
@@ -3,4 +3,5 @@
 // SPDX-License-Identifier: ISC
 
 pub const ENV_PREFIX: &str = "FTO";
-pub const APP_NAME: &str = "freqtrade-operator";
\ No newline at end of file
+pub const APP_NAME: &str = "freqtrade-operator";
+pub const WATCH_NAMESPACE_ENV: &str = "WATCH_NAMESPACE";
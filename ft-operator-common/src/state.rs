@@ -2,9 +2,244 @@
 //
 // SPDX-License-Identifier: ISC
 
+use std::collections::BTreeMap;
+use std::sync::{Arc, PoisonError, RwLock, atomic::{AtomicU64, Ordering}};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use arc_swap::ArcSwap;
+use figment::Error;
+use tokio::fs;
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::time::interval;
+
 use crate::config::AppConfig;
+use crate::telemetry::{error, info, set_log_filters, warn};
 
-#[derive(Clone, Default, Debug)]
+#[derive(Default, Debug)]
 pub struct State {
-    pub config: AppConfig,
+    // Swapped as a whole when the config is reloaded
+    config: ArcSwap<AppConfig>,
+    pub diagnostics: Diagnostics,
+}
+
+impl State {
+    pub fn new(config: AppConfig) -> Self {
+        Self { config: ArcSwap::from_pointee(config), diagnostics: Diagnostics::default() }
+    }
+
+    /// A snapshot of the current config
+    ///
+    /// The config may be reloaded at any time, so the snapshot should be held across a whole
+    /// reconcile or request to keep seeing a consistent config. Taking one only clones the `Arc`.
+    pub fn config(&self) -> Arc<AppConfig> {
+        self.config.load_full()
+    }
+
+    /// Replace the current config
+    ///
+    /// # Arguments
+    /// * `config` - The new config
+    pub fn set_config(&self, config: AppConfig) {
+        self.config.store(Arc::new(config));
+    }
+}
+
+/// Runtime data shared across the controllers or the webhook handlers, exposed by their
+/// readiness probe and metrics
+#[derive(Default, Debug)]
+pub struct Diagnostics {
+    // Unix timestamp of the last successful reconcile per kind
+    last_reconciles: RwLock<BTreeMap<&'static str, u64>>,
+    // Whether a CRD was installed when last checked, by CRD name
+    crds: RwLock<BTreeMap<String, bool>>,
+    // Number of Bots admitted with validations bypassed
+    validation_bypasses: AtomicU64,
+}
+
+impl Diagnostics {
+    /// Record a successful reconcile of a kind
+    ///
+    /// # Arguments
+    /// * `kind` - The kind of the reconciled resource, e.g. `Bot`
+    pub fn record_reconcile(&self, kind: &'static str) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        self.last_reconciles.write().unwrap_or_else(PoisonError::into_inner).insert(kind, now);
+    }
+
+    /// The Unix timestamp of the last successful reconcile per kind
+    pub fn last_reconciles(&self) -> BTreeMap<&'static str, u64> {
+        self.last_reconciles.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Record whether a CRD is installed
+    ///
+    /// # Arguments
+    /// * `name` - The name of the CRD
+    /// * `installed` - Whether the CRD was found
+    pub fn record_crd(&self, name: &str, installed: bool) {
+        self.crds.write().unwrap_or_else(PoisonError::into_inner).insert(name.to_string(), installed);
+    }
+
+    /// Whether each CRD checked was installed when last checked
+    pub fn crds(&self) -> BTreeMap<String, bool> {
+        self.crds.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Record a Bot admitted with validations bypassed
+    pub fn record_validation_bypass(&self) {
+        self.validation_bypasses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The number of Bots admitted with validations bypassed
+    pub fn validation_bypasses(&self) -> u64 {
+        self.validation_bypasses.load(Ordering::Relaxed)
+    }
+}
+
+/// Reload the config when its file changes or on SIGHUP
+///
+/// The file's modification time is checked every `period`. A config that fails to build is
+/// logged and the current one kept. The settings which only take effect on a restart are
+/// logged as a warning, the reloaded config is swapped in as a whole regardless, and its log
+/// filters applied.
+///
+/// # Arguments
+/// * `state` - The state holding the config
+/// * `config_file` - The path to the config file
+/// * `period` - How often the file is checked
+/// * `build` - Builds the config from its sources, the same way as at startup
+pub async fn reload_config<F>(state: Arc<State>, config_file: String, period: Duration, build: F)
+where
+    F: Fn() -> Result<AppConfig, Error>,
+{
+    async fn modified(path: &str) -> Option<SystemTime> {
+        fs::metadata(path).await.and_then(|metadata| metadata.modified()).ok()
+    }
+
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(hangup) => Some(hangup),
+        Err(e) => {
+            error!(event = "Error", error = %e);
+            None
+        },
+    };
+    let mut last_modified = modified(&config_file).await;
+    let mut ticker = interval(period);
+    // The first tick completes immediately
+    ticker.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                let current = modified(&config_file).await;
+                if current == last_modified {
+                    continue;
+                }
+                last_modified = current;
+            },
+            Some(_) = async { hangup.as_mut()?.recv().await } => (),
+        }
+
+        let config = match build() {
+            Ok(config) => config,
+            // The file may be mid-update, the current config is kept until the next change
+            Err(e) => {
+                error!(event = "ConfigReloadFailed", config_file = config_file.as_str(), error = %e);
+                continue;
+            },
+        };
+
+        for field in config.restart_required_changes(&state.config()) {
+            warn!(
+                event = "ConfigChangeRequiresRestart",
+                field = field,
+                message = "The setting only takes effect when the operator is restarted",
+            );
+        }
+
+        set_log_filters(&config.telemetry.log_filters);
+        state.set_config(config);
+        info!(event = "ConfigReloaded", config_file = config_file.as_str());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{File, write};
+    use std::future::Future;
+    use tempfile::TempDir;
+    use tokio::time::{sleep, timeout};
+
+    use crate::config::AppConfigBuilder;
+    use crate::telemetry::tests::Logs;
+
+    // Rewrite the file, moving its modification time forward for the change to be seen even
+    // within the filesystem's timestamp resolution
+    fn rewrite(path: &str, contents: &str, version: u64) {
+        write(path, contents).unwrap();
+        File::options().write(true).open(path).unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(version))
+            .unwrap();
+    }
+
+    async fn eventually(condition: impl Fn() -> bool) -> bool {
+        timeout(Duration::from_secs(5), async {
+            while !condition() {
+                sleep(Duration::from_millis(10)).await;
+            }
+        }).await.is_ok()
+    }
+
+    fn with_logs<F: Future>(logs: &Logs, test: F) -> impl Future<Output = F::Output> {
+        let subscriber = tracing_subscriber::fmt().json().with_writer(logs.clone()).finish();
+        let guard = tracing::subscriber::set_default(subscriber);
+        async move {
+            let output = test.await;
+            drop(guard);
+            output
+        }
+    }
+
+    #[tokio::test]
+    async fn config_file_changes_are_reloaded() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.yaml").to_string_lossy().to_string();
+        let build = {
+            let path = path.clone();
+            move || AppConfigBuilder::default().with_file(&path).build()
+        };
+        rewrite(&path, "controller:\n  default_image_repo: first/freqtrade\n", 0);
+        let state = Arc::new(State::new(build().unwrap()));
+        let image_repo = || state.config().controller.default_image_repo.clone();
+        let logs = Logs::default();
+        let logged = |event: &str| String::from_utf8(logs.0.lock().unwrap().clone()).unwrap()
+            .lines()
+            .filter(|line| line.contains(&format!(r#""event":"{}""#, event)))
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        with_logs(&logs, async {
+            let reloading = tokio::spawn(reload_config(state.clone(), path.clone(), Duration::from_millis(10), build));
+            // Let the reload read the file's modification time before it changes
+            sleep(Duration::from_millis(100)).await;
+
+            rewrite(&path, "controller:\n  default_image_repo: second/freqtrade\n", 1);
+            assert!(eventually(|| image_repo() == "second/freqtrade").await, "{}", image_repo());
+
+            // A config that doesn't build keeps the current one
+            rewrite(&path, "controller: [\n", 2);
+            assert!(eventually(|| !logged("ConfigReloadFailed").is_empty()).await);
+            assert_eq!(image_repo(), "second/freqtrade");
+
+            // A reloaded setting only read at startup is swapped in, with a warning
+            rewrite(&path, "controller:\n  default_image_repo: third/freqtrade\nwebhook:\n  port: 9443\n", 3);
+            assert!(eventually(|| image_repo() == "third/freqtrade").await, "{}", image_repo());
+            assert_eq!(state.config().webhook.port, 9443);
+            let warnings = logged("ConfigChangeRequiresRestart");
+            assert_eq!(warnings.len(), 1, "{:?}", warnings);
+            assert!(warnings[0].contains(r#""field":"webhook.port""#), "{}", warnings[0]);
+
+            reloading.abort();
+        }).await;
+    }
 }
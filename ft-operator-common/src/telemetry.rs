@@ -2,45 +2,266 @@
 //
 // SPDX-License-Identifier: ISC
 
+use std::collections::BTreeMap;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::sync::OnceLock;
+use http::{HeaderMap, Request};
+use opentelemetry::{global, KeyValue, propagation::Extractor, trace::{TraceError, TracerProvider as _}};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{propagation::TraceContextPropagator, runtime, trace::{Config, Sampler}, Resource};
 use tower_http::{
     LatencyUnit,
-    trace::{TraceLayer, DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse},
+    trace::{TraceLayer, DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, MakeSpan},
        classify::SharedClassifier,
        classify::ServerErrorsAsFailures,
 };
-use tracing::Level;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-use tracing_subscriber::fmt::format::FmtSpan;
-use tracing_subscriber::fmt::time::ChronoUtc;
-use tracing_subscriber::{EnvFilter, Layer};
+use serde_json::{Map, Value};
+use tracing::{Event, Level, Span, Subscriber, field::{Field, Visit}};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{layer::SubscriberExt, registry::LookupSpan, util::SubscriberInitExt};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::fmt::format::{FmtSpan, JsonFields, Writer};
+use tracing_subscriber::fmt::time::{ChronoUtc, FormatTime};
+use tracing_subscriber::{EnvFilter, Layer, Registry, reload};
+use tracing_subscriber::filter::{Directive, ParseError};
 
-pub use tracing::{error, info, warn, debug, trace};
+use crate::constant::APP_NAME;
+
+pub use tracing::{error, info, warn, debug, trace, info_span, field, Instrument};
+
+// The format of the logs, `json` (default), `pretty` or `compact`
+const LOG_FORMAT_ENV: &str = "LOG_FORMAT";
+// The span events logged, `close` (default) or `none`
+const LOG_SPAN_EVENTS_ENV: &str = "LOG_SPAN_EVENTS";
+// Traces are only exported when the endpoint of an OTLP collector is set, the exporter reads
+// the headers to send from `OTEL_EXPORTER_OTLP_HEADERS`
+const OTLP_ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+// The ratio of traces sampled, from 0 to 1, defaults to sampling every trace
+const OTLP_SAMPLE_RATIO_ENV: &str = "OTEL_TRACES_SAMPLER_ARG";
+
+// The filter of the logger, replaced once the log filters of the config are known
+static LOG_FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
 
 // This function initializes the global logger
 pub fn setup_logging() {
-    let fmt_layer = tracing_subscriber::fmt::layer()
-        .json()
-        .flatten_event(true)
-        .with_timer(ChronoUtc::rfc_3339())
-        .with_span_events(FmtSpan::CLOSE)
-        .with_ansi(false)
-        .boxed();
+    let fmt_layer = create_fmt_layer(
+        env::var(LOG_FORMAT_ENV).ok().as_deref(),
+        env::var(LOG_SPAN_EVENTS_ENV).ok().as_deref(),
+        std::io::stdout,
+    );
+
+    let (env_filter, handle) = reload::Layer::new(base_env_filter());
+    let _ = LOG_FILTER.set(handle);
 
-    let env_filter = EnvFilter::try_from_env("LOG_LEVEL")
-        .unwrap_or_else(|_| EnvFilter::new("info"));
+    // The exporter can only fail once the logger is set, so its error is logged afterwards
+    let (otlp_layer, otlp_error) = match create_otlp_layer() {
+        Ok(layer) => (layer, None),
+        Err(e) => (None, Some(e)),
+    };
 
     tracing_subscriber::registry()
         .with(env_filter)
         .with(fmt_layer)
+        .with(otlp_layer)
         .init();
+
+    if let Some(e) = otlp_error {
+        error!(event = "TraceExportDisabled", error = %e);
+    }
+}
+
+/// Create the layer writing the logs
+///
+/// # Arguments
+/// * `format` - The format of the logs, `pretty`, `compact` or JSON by default
+/// * `span_events` - `none` to not log the spans closing
+/// * `writer` - Where the logs are written
+fn create_fmt_layer<S, W>(format: Option<&str>, span_events: Option<&str>, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    let span_events = match span_events {
+        Some("none") => FmtSpan::NONE,
+        _ => FmtSpan::CLOSE,
+    };
+    // The human readable formats are meant for running the operator locally
+    match format {
+        Some("pretty") => tracing_subscriber::fmt::layer()
+            .pretty()
+            .with_span_events(span_events)
+            .with_writer(writer)
+            .boxed(),
+        Some("compact") => tracing_subscriber::fmt::layer()
+            .compact()
+            .with_span_events(span_events)
+            .with_writer(writer)
+            .boxed(),
+        _ => tracing_subscriber::fmt::layer()
+            .fmt_fields(JsonFields::new())
+            .with_span_events(span_events)
+            .with_ansi(false)
+            .event_format(FlattenedJson { timer: ChronoUtc::rfc_3339() })
+            .with_writer(writer)
+            .boxed(),
+    }
+}
+
+/// Formats the events as JSON, with the fields of the spans they are in flattened next to theirs
+///
+/// The built-in JSON format nests the span fields, so the bot a log line is about can't be
+/// filtered on the same field across lines. A field of an inner span overrides the outer
+/// spans', and the event's own fields override them all.
+struct FlattenedJson {
+    timer: ChronoUtc,
+}
+
+impl<S, N> FormatEvent<S, N> for FlattenedJson
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    N: for<'writer> FormatFields<'writer> + 'static,
+{
+    fn format_event(&self, ctx: &FmtContext<'_, S, N>, mut writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        let mut fields = Map::new();
+
+        let mut timestamp = String::new();
+        self.timer.format_time(&mut Writer::new(&mut timestamp))?;
+        fields.insert("timestamp".to_string(), Value::String(timestamp));
+        fields.insert("level".to_string(), Value::String(event.metadata().level().to_string()));
+        fields.insert("target".to_string(), Value::String(event.metadata().target().to_string()));
+
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                fields.insert("span".to_string(), Value::String(span.name().to_string()));
+
+                // The fields of the spans are recorded as JSON by the JsonFields formatter
+                let extensions = span.extensions();
+                let span_fields = extensions
+                    .get::<FormattedFields<N>>()
+                    .and_then(|span_fields| serde_json::from_str::<Map<String, Value>>(span_fields).ok());
+                fields.extend(span_fields.unwrap_or_default());
+            }
+        }
+
+        event.record(&mut JsonVisitor(&mut fields));
+
+        writeln!(writer, "{}", Value::Object(fields))
+    }
+}
+
+/// Records the fields of an event into a JSON object
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn Error + 'static)) {
+        self.0.insert(field.name().to_string(), Value::String(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().to_string(), Value::String(format!("{:?}", value)));
+    }
+}
+
+fn base_env_filter() -> EnvFilter {
+    EnvFilter::try_from_env("LOG_LEVEL")
+        .unwrap_or_else(|_| EnvFilter::new("info"))
+}
+
+/// Parse the log level of a target into a filter directive
+///
+/// # Arguments
+/// * `target` - The target, e.g. `kube` or `ft_operator_controller::controller::bot`
+/// * `level` - The level, e.g. `warn`
+pub fn parse_log_filter(target: &str, level: &str) -> Result<Directive, ParseError> {
+    format!("{}={}", target, level).parse()
+}
+
+/// Add the log levels of targets to the `LOG_LEVEL` directives
+///
+/// The filters are expected to be validated with the config, an invalid one is skipped.
+///
+/// # Arguments
+/// * `filters` - The log level per target, replacing the ones set before
+pub fn set_log_filters(filters: &BTreeMap<String, String>) {
+    let filter = filters
+        .iter()
+        .filter_map(|(target, level)| parse_log_filter(target, level).ok())
+        .fold(base_env_filter(), EnvFilter::add_directive);
+
+    if let Some(Err(e)) = LOG_FILTER.get().map(|handle| handle.reload(filter)) {
+        error!(event = "Error", error = %e);
+    }
+}
+
+/// Create the layer exporting spans to an OTLP collector
+///
+/// The trace context of incoming requests is only propagated when the spans are exported.
+///
+/// # Returns
+/// The layer, or `None` when no collector endpoint is set
+fn create_otlp_layer<S>() -> Result<Option<impl Layer<S>>, TraceError>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let Some(endpoint) = env::var(OTLP_ENDPOINT_ENV).ok().filter(|endpoint| !endpoint.is_empty()) else {
+        return Ok(None);
+    };
+    let sample_ratio = env::var(OTLP_SAMPLE_RATIO_ENV)
+        .ok()
+        .and_then(|ratio| ratio.parse::<f64>().ok())
+        .unwrap_or(1.0);
+
+    let provider = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(endpoint))
+        .with_trace_config(
+            Config::default()
+                // Follow the sampling decision of the caller, e.g. the API server
+                .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(sample_ratio))))
+                .with_resource(Resource::new(vec![KeyValue::new("service.name", APP_NAME)]))
+        )
+        .install_batch(runtime::Tokio)?;
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(provider.clone());
+
+    Ok(Some(tracing_opentelemetry::layer().with_tracer(provider.tracer(APP_NAME))))
+}
+
+/// Flush the spans not exported yet, to call before exiting
+pub fn shutdown_telemetry() {
+    global::shutdown_tracer_provider();
 }
 
 /// This function creates a TraceLayer with a global configuration
 /// for logging HTTP requests and responses.
-pub fn create_trace_layer() -> TraceLayer<SharedClassifier<ServerErrorsAsFailures>> {
+pub fn create_trace_layer() -> TraceLayer<SharedClassifier<ServerErrorsAsFailures>, TraceContextMakeSpan> {
     TraceLayer::new_for_http()
         .make_span_with(
-            DefaultMakeSpan::new().include_headers(false)
+            TraceContextMakeSpan(DefaultMakeSpan::new().include_headers(false))
         )
         .on_request(
             DefaultOnRequest::new().level(Level::INFO)
@@ -50,4 +271,108 @@ pub fn create_trace_layer() -> TraceLayer<SharedClassifier<ServerErrorsAsFailure
                 .level(Level::INFO)
                 .latency_unit(LatencyUnit::Millis)
         )
- }
\ No newline at end of file
+ }
+
+/// Makes the request spans, continuing the trace of the caller from its `traceparent` header
+#[derive(Clone, Debug)]
+pub struct TraceContextMakeSpan(DefaultMakeSpan);
+
+impl<B> MakeSpan<B> for TraceContextMakeSpan {
+    fn make_span(&mut self, request: &Request<B>) -> Span {
+        let span = self.0.make_span(request);
+        // The default propagator extracts nothing when the spans aren't exported
+        let context = global::get_text_map_propagator(|propagator| {
+            propagator.extract(&HeaderExtractor(request.headers()))
+        });
+        span.set_parent(context);
+        span
+    }
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    // Collects the logs written by a subscriber
+    #[derive(Clone, Default)]
+    pub(crate) struct Logs(pub(crate) Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Logs {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'writer> MakeWriter<'writer> for Logs {
+        type Writer = Logs;
+
+        fn make_writer(&'writer self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    /// The lines logged for an event in a span, with the given LOG_FORMAT and LOG_SPAN_EVENTS
+    fn logged(format: Option<&str>, span_events: Option<&str>) -> Vec<String> {
+        let logs = Logs::default();
+        let subscriber = tracing_subscriber::registry().with(create_fmt_layer(format, span_events, logs.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            info_span!("reconcile", bot = "bot").in_scope(|| info!(event = "Reconciled"));
+        });
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        logs.lines().filter(|line| !line.trim().is_empty()).map(String::from).collect()
+    }
+
+    #[test]
+    fn logs_are_json_by_default() {
+        for format in [None, Some("json"), Some("unknown")] {
+            let lines = logged(format, None);
+            let event: Value = serde_json::from_str(&lines[0]).unwrap();
+
+            assert_eq!(event["event"], "Reconciled");
+            assert_eq!(event["span"], "reconcile");
+            assert_eq!(event["bot"], "bot");
+            assert_eq!(event["level"], "INFO");
+            assert!(lines.iter().all(|line| serde_json::from_str::<Value>(line).is_ok()));
+        }
+    }
+
+    #[test]
+    fn logs_can_be_human_readable() {
+        for format in ["pretty", "compact"] {
+            let lines = logged(Some(format), None);
+
+            assert!(lines.iter().any(|line| line.contains("Reconciled")), "{format}: {lines:?}");
+            assert!(lines.iter().all(|line| serde_json::from_str::<Value>(line).is_err()), "{format}: {lines:?}");
+        }
+    }
+
+    #[test]
+    fn span_close_events_can_be_disabled() {
+        for format in [None, Some("pretty"), Some("compact")] {
+            let closes = |span_events| logged(format, span_events).iter().filter(|line| line.contains("close")).count();
+
+            assert_eq!(closes(None), 1, "{format:?}");
+            assert_eq!(closes(Some("none")), 0, "{format:?}");
+        }
+    }
+}
@@ -2,26 +2,152 @@
 //
 // SPDX-License-Identifier: ISC
 
+use std::io::{self, Write};
 use serde::Serialize;
-use blake3::hash as blake3_hash;
+use blake3::Hasher;
 use serde_json::Value;
 
 /// Compute a hash for any serializable object
+///
+/// The object is hashed as compact JSON with its object keys sorted, written straight into the
+/// hasher, so the hash doesn't depend on the order of the maps.
 pub fn compute_object_hash<T>(object: &T) -> Result<String, Box<dyn std::error::Error>>
 where
     T: Serialize,
 {
-    let value: Value = serde_json::from_str(&serde_json::to_string(object)?)?;
-    let hash = blake3_hash(serde_json::to_string(&sort_json(value))?.as_bytes());
+    let mut hasher = Hasher::new();
+    write_canonical_json(&mut hasher, &serde_json::to_value(object)?)?;
 
-    Ok(hash.to_hex().to_string())
+    Ok(hasher.finalize().to_hex().to_string())
 }
 
-/// Recursively sort JSON objects
-pub fn sort_json(value: Value) -> Value {
+/// Write a JSON value in its canonical form, compact with the object keys sorted
+fn write_canonical_json<W: Write>(writer: &mut W, value: &Value) -> io::Result<()> {
     match value {
-        Value::Object(map) => Value::Object(map.into_iter().map(|(k, v)| (k, sort_json(v))).collect()),
-        Value::Array(arr) => Value::Array(arr.into_iter().map(sort_json).collect()),
-        _ => value,
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_unstable_by_key(|(key, _)| *key);
+
+            writer.write_all(b"{")?;
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    writer.write_all(b",")?;
+                }
+                serde_json::to_writer(&mut *writer, key)?;
+                writer.write_all(b":")?;
+                write_canonical_json(writer, value)?;
+            }
+            writer.write_all(b"}")
+        },
+        Value::Array(values) => {
+            writer.write_all(b"[")?;
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    writer.write_all(b",")?;
+                }
+                write_canonical_json(writer, value)?;
+            }
+            writer.write_all(b"]")
+        },
+        _ => Ok(serde_json::to_writer(writer, value)?),
+    }
+}
+
+/// Parse a Kubernetes resource Quantity (e.g. `1Gi`, `500m`, `1e3`) into its value in base units
+///
+/// Returns None if the quantity is not in a format accepted by Kubernetes.
+pub fn parse_quantity(quantity: &str) -> Option<f64> {
+    let quantity = quantity.trim();
+    let number_end = quantity
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '+' || c == '-'))
+        .unwrap_or(quantity.len());
+    let (number, suffix) = quantity.split_at(number_end);
+
+    if number.is_empty() || number.chars().skip(1).any(|c| c == '+' || c == '-') {
+        return None;
+    }
+    let number: f64 = number.parse().ok()?;
+
+    let multiplier = match suffix {
+        "" => 1.0,
+        "n" => 1e-9,
+        "u" => 1e-6,
+        "m" => 1e-3,
+        "k" => 1e3,
+        "M" => 1e6,
+        "G" => 1e9,
+        "T" => 1e12,
+        "P" => 1e15,
+        "E" => 1e18,
+        "Ki" => 1024_f64,
+        "Mi" => 1024_f64.powi(2),
+        "Gi" => 1024_f64.powi(3),
+        "Ti" => 1024_f64.powi(4),
+        "Pi" => 1024_f64.powi(5),
+        "Ei" => 1024_f64.powi(6),
+        _ => match suffix.strip_prefix(['e', 'E']) {
+            Some(exponent) => 10_f64.powi(exponent.parse().ok()?),
+            None => return None,
+        },
+    };
+
+    Some(number * multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[derive(Serialize)]
+    struct Forward {
+        a: u32,
+        b: Value,
     }
-}
\ No newline at end of file
+
+    #[derive(Serialize)]
+    struct Backward {
+        b: Value,
+        a: u32,
+    }
+
+    #[test]
+    fn hash_ignores_the_order_of_keys() {
+        let forward = Forward { a: 1, b: json!({ "x": [1, 2], "y": { "z": true } }) };
+        let backward = Backward { b: json!({ "y": { "z": true }, "x": [1, 2] }), a: 1 };
+
+        assert_eq!(compute_object_hash(&forward).unwrap(), compute_object_hash(&backward).unwrap());
+    }
+
+    #[test]
+    fn hash_ignores_the_iteration_order_of_maps() {
+        let map: HashMap<String, u32> = (0..100).map(|i| (i.to_string(), i)).collect();
+        let reversed: HashMap<String, u32> = (0..100).rev().map(|i| (i.to_string(), i)).collect();
+
+        assert_eq!(compute_object_hash(&map).unwrap(), compute_object_hash(&reversed).unwrap());
+    }
+
+    #[test]
+    fn hash_keeps_the_order_of_arrays() {
+        assert_ne!(compute_object_hash(&json!([1, 2])).unwrap(), compute_object_hash(&json!([2, 1])).unwrap());
+    }
+
+    #[test]
+    fn canonical_json_is_compact_and_sorted() {
+        let mut canonical = vec![];
+        write_canonical_json(&mut canonical, &json!({ "b": [1, { "d": null, "c": "x" }], "a": 1.5 })).unwrap();
+
+        assert_eq!(String::from_utf8(canonical).unwrap(), r#"{"a":1.5,"b":[1,{"c":"x","d":null}]}"#);
+    }
+
+    #[test]
+    fn quantities_are_parsed_with_their_suffix() {
+        assert_eq!(parse_quantity("10Gi"), Some(10.0 * 1024_f64.powi(3)));
+        assert_eq!(parse_quantity("1G"), Some(1e9));
+        assert_eq!(parse_quantity("100m"), Some(0.1));
+        assert_eq!(parse_quantity("1e3"), Some(1000.0));
+        assert_eq!(parse_quantity("10GB"), None);
+        assert_eq!(parse_quantity("Gi"), None);
+    }
+}
@@ -0,0 +1,390 @@
+use kube::{
+    api::{Api, DeleteParams, PostParams, ResourceExt},
+    runtime::{
+        controller::{Action, Controller},
+        finalizer::{finalizer, Event as Finalizer},
+        watcher,
+    },
+};
+use k8s_openapi::api::batch::v1::Job;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+use chrono::Utc;
+use serde_json::json;
+use std::sync::Arc;
+use tokio::time::Duration;
+
+use ft_operator_common::config::AppConfig;
+use ft_operator_common::telemetry::info;
+
+use crate::controller::{
+    bot::USER_DATA_PATH,
+    context::Context,
+    job::{bot_job, strategy_path},
+    strategy::resolve_strategy,
+    utils::{patch_status, requeue, runtime_config, scoped_api, FIELD_MANAGER},
+};
+use crate::crd::{
+    NamespacedCustomResource,
+    hub::{backtest::{Backtest, BacktestPhase}, bot::Bot},
+    v1alpha1::bot::Bot as V1Alpha1Bot,
+};
+use crate::error::{ControllerError, Result};
+
+pub static FINALIZER: &str = "backtests.finalizers.freqtrade.io";
+
+
+/// The path of the results file of a backtest, relative to the root of the bot's PVC
+///
+/// # Arguments
+/// * `name` - The name of the backtest
+pub fn results_path(name: &str) -> String {
+    format!("backtest_results/{}.json", name)
+}
+
+/// Create the backtest Job from a Backtest Hub and the Bot it references
+///
+/// The Job runs `freqtrade backtesting` once, writing the results into the backtest results
+/// directory of the bot's PVC.
+///
+/// # Arguments
+/// * `backtest` - The Backtest Hub to create the Job from
+/// * `bot` - The Bot Hub being backtested
+/// * `name` - The name of the Job
+/// * `namespace` - The namespace of the Job
+/// * `owner_ref` - The owner reference for the Job
+/// * `config` - The application configuration
+///
+/// # Returns
+/// The Job resource
+pub fn backtest_job(backtest: &Backtest, bot: &Bot, name: &str, namespace: &str, owner_ref: OwnerReference, config: &AppConfig) -> Job {
+    let command = vec![
+        "freqtrade".to_string(),
+        "backtesting".to_string(),
+        "--config".to_string(),
+        "/etc/freqtrade/config.json".to_string(),
+        "--userdir".to_string(),
+        USER_DATA_PATH.to_string(),
+        "--strategy".to_string(),
+        bot.spec.strategy.name.clone(),
+        "--strategy-path".to_string(),
+        strategy_path(bot).to_string(),
+        "--export".to_string(),
+        "trades".to_string(),
+        "--export-filename".to_string(),
+        format!("{}/{}", USER_DATA_PATH, results_path(name)),
+    ]
+    .into_iter()
+    .chain(backtest.spec.timerange.clone().map(|timerange| vec!["--timerange".to_string(), timerange]).into_iter().flatten())
+    .chain(backtest.spec.timeframe.clone().map(|timeframe| vec!["--timeframe".to_string(), timeframe]).into_iter().flatten())
+    .chain(
+        backtest.spec.max_open_trades
+            .map(|max_open_trades| vec!["--max-open-trades".to_string(), max_open_trades.to_string()])
+            .into_iter()
+            .flatten()
+    )
+    .collect();
+
+    bot_job(bot, name, namespace, owner_ref, "backtest", command, "backtest_results", config)
+}
+
+pub struct BacktestController;
+
+impl BacktestController {
+    /// Create a new controller for the Backtest resource
+    ///
+    /// # Arguments
+    /// * `ctx` - The controller context
+    /// * `namespace` - The namespace to watch, or `None` to watch all namespaces
+    ///
+    /// # Returns
+    /// The controller for the Backtest resource
+    pub async fn create_controller<T>(ctx: Arc<Context>, namespace: Option<&str>) -> Controller<T>
+    where
+        T: NamespacedCustomResource,
+        Backtest: From<T>,
+    {
+        let client = ctx.client.clone();
+        let backtest = scoped_api::<T>(client.clone(), namespace);
+        let job = scoped_api::<Job>(client.clone(), namespace);
+
+        Controller::new(backtest, watcher::Config::default())
+            .with_config(runtime_config(&ctx))
+            .owns(job, watcher::Config::default())
+    }
+
+    /// Reconcile the Backtest resource
+    ///
+    /// This function is responsible for reconciling the Backtest resource.
+    ///
+    /// # Arguments
+    /// * `backtest` - The Backtest resource to reconcile
+    /// * `ctx` - The controller context
+    ///
+    /// # Returns
+    /// An action to take after reconciling the Backtest resource
+    pub async fn reconcile<T>(backtest: Arc<T>, ctx: Arc<Context>) -> Result<Action>
+    where
+        T: NamespacedCustomResource,
+        Backtest: From<T>,
+    {
+        let _in_flight = ctx.track_reconcile();
+        let namespace = backtest.namespace().ok_or(
+            ControllerError::MissingObjectKeyError(
+                "Expected Backtest to be namespaced via metadata.namespace"
+            )
+        )?;
+        let owner_ref = backtest.controller_owner_ref(&()).ok_or_else(|| {
+            ControllerError::MissingObjectKeyError(
+                "Expected Backtest to have an owner reference"
+            )
+        })?;
+        let api = Api::<T>::namespaced(ctx.client.clone(), &namespace);
+
+        finalizer(&api, FINALIZER, backtest, |event| async {
+            match event {
+                Finalizer::Apply(backtest) => reconcile_backtest(&backtest, &ctx, &namespace, &owner_ref).await,
+                Finalizer::Cleanup(backtest) => cleanup_backtest(&backtest, &ctx, &namespace).await,
+            }
+        })
+        .await
+        .map_err(|e| ControllerError::FinalizerError(e.to_string()))
+    }
+}
+
+/// Reconcile the backtest resource
+///
+/// This function is responsible for creating the backtest Job once and tracking its phase.
+/// Jobs are immutable, so an existing Job is never updated.
+///
+/// # Arguments
+/// * `backtest` - The backtest resource to reconcile
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the backtest resource
+/// * `owner_ref` - The owner reference for the Job
+///
+/// # Returns
+/// An action to take after reconciling the backtest resource
+async fn reconcile_backtest<T>(backtest: &T, ctx: &Context, namespace: &str, owner_ref: &OwnerReference) -> Result<Action>
+where
+    T: NamespacedCustomResource,
+    Backtest: From<T>,
+{
+    let name = backtest.name_any();
+    let hub = Backtest::from(backtest.clone());
+    let job_api = Api::<Job>::namespaced(ctx.client.clone(), namespace);
+
+    let job = match job_api.get_opt(&name).await? {
+        Some(job) => job,
+        None => {
+            // Bots are read at their storage version
+            let mut bot = match Api::<V1Alpha1Bot>::namespaced(ctx.client.clone(), namespace)
+                .get_opt(&hub.spec.bot_ref.name)
+                .await?
+            {
+                Some(bot) => Bot::from(bot),
+                None => {
+                    update_status(backtest, ctx, namespace, BacktestPhase::Pending, Some(format!("Bot `{}` was not found", hub.spec.bot_ref.name))).await?;
+                    return Ok(requeue(backtest, ctx, Duration::from_secs(30)));
+                },
+            };
+
+            if !bot.spec.pvc.enabled {
+                update_status(backtest, ctx, namespace, BacktestPhase::Failed, Some(format!("Bot `{}` has no PVC to read data from", hub.spec.bot_ref.name))).await?;
+                return Ok(Action::await_change());
+            }
+
+            let condition = resolve_strategy(&mut bot, &ctx.client, namespace).await?;
+            if let Some(condition) = condition.filter(|condition| condition.status == "False") {
+                update_status(backtest, ctx, namespace, BacktestPhase::Pending, condition.message).await?;
+                return Ok(requeue(backtest, ctx, Duration::from_secs(30)));
+            }
+
+            info!(
+                event = "CreatingBacktestJob",
+                backtest = name.as_str()
+            );
+            job_api.create(
+                &PostParams { field_manager: Some(FIELD_MANAGER.to_string()), ..Default::default() },
+                &backtest_job(&hub, &bot, &name, namespace, owner_ref.clone(), &ctx.state.config()),
+            ).await?
+        },
+    };
+
+    let job_status = job.status.unwrap_or_default();
+    let phase = if job_status.succeeded.unwrap_or(0) > 0 {
+        BacktestPhase::Succeeded
+    } else if job_status.failed.unwrap_or(0) > 0 {
+        BacktestPhase::Failed
+    } else if job_status.active.unwrap_or(0) > 0 {
+        BacktestPhase::Running
+    } else {
+        BacktestPhase::Pending
+    };
+
+    update_status(backtest, ctx, namespace, phase, None).await?;
+
+    Ok(Action::await_change())
+}
+
+/// Cleanup the backtest resource
+///
+/// This function is responsible for deleting the backtest Job along with its pods.
+///
+/// # Arguments
+/// * `backtest` - The backtest resource to cleanup
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the backtest resource
+///
+/// # Returns
+/// An action to take after cleaning up the backtest resource
+async fn cleanup_backtest<T>(backtest: &T, ctx: &Context, namespace: &str) -> Result<Action>
+where
+    T: NamespacedCustomResource,
+    Backtest: From<T>,
+{
+    let job_api = Api::<Job>::namespaced(ctx.client.clone(), namespace);
+
+    if job_api.get_opt(&backtest.name_any()).await?.is_some() {
+        info!(
+            event = "DeletingBacktestJob",
+            backtest = backtest.name_any().as_str()
+        );
+        job_api.delete(&backtest.name_any(), &DeleteParams::background()).await?;
+    }
+
+    Ok(Action::await_change())
+}
+
+/// Update the status of the backtest resource
+///
+/// The completion time and results path are set once the Job finishes, and nothing is
+/// patched when the phase and message are unchanged to avoid triggering the watch.
+///
+/// # Arguments
+/// * `backtest` - The backtest resource to update
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the backtest resource
+/// * `phase` - The phase to set the backtest resource to
+/// * `message` - Details about the phase
+///
+/// # Returns
+/// A result indicating success or failure
+async fn update_status<T>(backtest: &T, ctx: &Context, namespace: &str, phase: BacktestPhase, message: Option<String>) -> Result<()>
+where
+    T: NamespacedCustomResource,
+    Backtest: From<T>,
+{
+    let name = backtest.name_any();
+    let status = Backtest::from(backtest.clone()).status;
+
+    if status.as_ref().is_some_and(|status| status.phase == phase.to_string() && status.message == message) {
+        return Ok(());
+    }
+
+    let finished = matches!(phase, BacktestPhase::Succeeded | BacktestPhase::Failed);
+    let completion_time = status
+        .and_then(|status| status.completion_time)
+        .or_else(|| finished.then(Utc::now));
+    let results_path = (phase == BacktestPhase::Succeeded).then(|| results_path(&name));
+
+    patch_status(&Api::<T>::namespaced(ctx.client.clone(), namespace), &name, json!({
+        "phase": phase.to_string(),
+        "message": message,
+        "completionTime": completion_time,
+        "resultsPath": results_path,
+        "lastUpdated": Utc::now(),
+    })).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use crate::crd::v1alpha1::backtest::Backtest as V1Alpha1Backtest;
+
+    fn backtest(spec: Value) -> Backtest {
+        Backtest::from(serde_json::from_value::<V1Alpha1Backtest>(json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "Backtest",
+            "metadata": { "name": "backtest", "namespace": "default", "uid": "backtest-uid" },
+            "spec": spec,
+        })).unwrap())
+    }
+
+    fn bot(strategy: Value) -> Bot {
+        Bot::from(serde_json::from_value::<V1Alpha1Bot>(json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "Bot",
+            "metadata": { "name": "bot", "namespace": "default" },
+            "spec": { "exchange": "binance", "strategy": strategy, "image": { "tag": "2024.1" } },
+        })).unwrap())
+    }
+
+    fn job(backtest: &Backtest, bot: &Bot) -> Job {
+        let owner_ref = OwnerReference { kind: "Backtest".to_string(), name: "backtest".to_string(), uid: "backtest-uid".to_string(), ..Default::default() };
+        backtest_job(backtest, bot, "backtest", "default", owner_ref, &AppConfig::default())
+    }
+
+    #[test]
+    fn backtest_runs_once_with_the_bot_strategy() {
+        let job = job(&backtest(json!({ "botRef": { "name": "bot" } })), &bot(json!({ "name": "SampleStrategy", "source": "class SampleStrategy(IStrategy): pass" })));
+        let spec = job.spec.unwrap();
+        let pod_spec = spec.template.spec.unwrap();
+        let container = &pod_spec.containers[0];
+
+        assert_eq!(job.metadata.owner_references.unwrap()[0].uid, "backtest-uid");
+        assert_eq!(job.metadata.labels.unwrap()["freqtrade.io/backtest-name"], "backtest");
+        assert_eq!(spec.backoff_limit, Some(0));
+        assert_eq!(pod_spec.restart_policy.as_deref(), Some("Never"));
+        assert_eq!(container.name, "backtest");
+        assert_eq!(container.image.as_deref(), Some("freqtradeorg/freqtrade:2024.1"));
+        assert_eq!(container.command.clone().unwrap(), vec![
+            "freqtrade", "backtesting",
+            "--config", "/etc/freqtrade/config.json",
+            "--userdir", USER_DATA_PATH,
+            "--strategy", "SampleStrategy",
+            "--strategy-path", "/etc/freqtrade",
+            "--export", "trades",
+            "--export-filename", &format!("{}/backtest_results/backtest.json", USER_DATA_PATH),
+        ]);
+    }
+
+    #[test]
+    fn backtest_options_are_passed_on() {
+        let backtest = backtest(json!({ "botRef": { "name": "bot" }, "timerange": "20240101-20240201", "timeframe": "5m", "maxOpenTrades": 3 }));
+        let job = job(&backtest, &bot(json!({ "name": "SampleStrategy", "configMapName": "strategy" })));
+        let command = job.spec.unwrap().template.spec.unwrap().containers[0].command.clone().unwrap();
+
+        assert_eq!(command[8..10], ["--strategy-path", "/etc/freqtrade/strategy"]);
+        assert_eq!(command[14..], ["--timerange", "20240101-20240201", "--timeframe", "5m", "--max-open-trades", "3"]);
+    }
+
+    #[test]
+    fn backtest_reads_the_data_and_writes_only_its_results() {
+        let job = job(&backtest(json!({ "botRef": { "name": "bot" } })), &bot(json!({ "name": "SampleStrategy", "configMapName": "strategy" })));
+        let pod_spec = job.spec.unwrap().template.spec.unwrap();
+        let mounts: Vec<_> = pod_spec.containers[0].volume_mounts.clone().unwrap()
+            .into_iter()
+            .map(|mount| (mount.name, mount.mount_path, mount.sub_path, mount.read_only.unwrap_or(false)))
+            .collect();
+
+        assert_eq!(mounts, vec![
+            ("config".to_string(), "/etc/freqtrade".to_string(), None, true),
+            ("user-data".to_string(), format!("{}/data", USER_DATA_PATH), Some("data".to_string()), true),
+            ("user-data".to_string(), format!("{}/backtest_results", USER_DATA_PATH), Some("backtest_results".to_string()), false),
+            ("strategy".to_string(), "/etc/freqtrade/strategy".to_string(), None, true),
+        ]);
+        let claim = pod_spec.volumes.unwrap()
+            .into_iter()
+            .find_map(|volume| volume.persistent_volume_claim)
+            .unwrap();
+        assert_eq!(claim.claim_name, "bot");
+    }
+
+    #[test]
+    fn results_are_written_under_the_backtest_name() {
+        assert_eq!(results_path("backtest"), "backtest_results/backtest.json");
+    }
+}
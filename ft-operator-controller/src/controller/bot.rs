@@ -1,18 +1,25 @@
 use kube::{
-    api::{Api, Patch, PatchParams, ResourceExt, ObjectMeta},
+    api::{Api, DeleteParams, DynamicObject, ListParams, PartialObjectMeta, Patch, PostParams, ResourceExt, ObjectMeta},
     runtime::{
         controller::{Action, Controller},
+        events::EventType,
         finalizer::{finalizer, Event as Finalizer},
+        metadata_watcher,
+        reflector::{self, ObjectRef},
         watcher,
+        WatchStreamExt,
     },
 };
-use k8s_openapi::{api::apps::v1::{Deployment, DeploymentSpec, DeploymentStatus}, apimachinery::pkg::api::resource::Quantity};
+use k8s_openapi::{api::apps::v1::{Deployment, DeploymentSpec, DeploymentStatus, DeploymentStrategy}, apimachinery::pkg::api::resource::Quantity};
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
+use k8s_openapi::ByteString;
+use k8s_openapi::api::networking::v1::Ingress;
 use k8s_openapi::api::core::v1::{
-    Service, ServiceSpec, ServicePort, ConfigMap, PersistentVolumeClaim, Secret,
+    Service, ServiceSpec, ServicePort, ConfigMap, PersistentVolumeClaim, Secret, Pod,
     PodSpec, PodTemplateSpec, Container, EnvVar, EnvVarSource, ConfigMapVolumeSource,
     ContainerPort, VolumeMount, Volume, PersistentVolumeClaimSpec, VolumeResourceRequirements,
     PersistentVolumeClaimVolumeSource, KeyToPath, SecretKeySelector, LocalObjectReference,
+    ResourceRequirements,
 };
 use k8s_openapi::apimachinery::pkg::{
     apis::meta::v1::OwnerReference,
@@ -21,51 +28,194 @@ use k8s_openapi::apimachinery::pkg::{
 use std::sync::Arc;
 use std::string::ToString;
 use std::collections::BTreeMap;
-use chrono::Utc;
+use std::time::Instant;
+use futures::{future, StreamExt};
+use chrono::{TimeDelta, Utc};
+use rand::{distributions::Alphanumeric, Rng};
 use tokio::time::Duration;
-use serde_json::json;
+use serde_json::{json, Value};
 
-use ft_operator_common::config::AppConfig;
-use ft_operator_common::telemetry::info;
-use ft_operator_common::utils::compute_object_hash;
+use ft_operator_common::config::{AppConfig, ControllerConfig, ProxyConfig};
+use ft_operator_common::telemetry::{field, info, info_span, Instrument};
+use ft_operator_common::utils::{compute_object_hash, parse_quantity};
 
-use crate::controller::{context::Context, traits::{FromHub, ResourceDrift}, utils::{apply, delete, rollout, patch, FIELD_MANAGER}};
-use crate::crd::{NamespacedCustomResource, hub::bot::{Bot, BotPhase, BotStatus}, hub::common::SecretItem};
+use crate::controller::{context::Context, traits::{FromHub, ResourceDrift, TryFromHub}, utils::{apply, apply_with_conflict_policy, delete, delete_params, label_selector, metadata_drifted, rollout, patch, patch_status, requeue, runtime_config, merge_pull_secrets, scoped_api, with_defaults}};
+use crate::controller::database::{cnpg_cluster_resource, cnpg_installed, reconcile_database};
+use crate::controller::ui::{reconcile_ui, UI_READY_CONDITION};
+use crate::controller::bot_api::{bot_trading_summary, bot_version, ping_bot, TradingSummary};
+use crate::controller::lifecycle::{open_trades, stop_bot, stop_entries};
+use crate::controller::strategy::{references_strategy, resolve_strategy};
+use crate::controller::conditions::Conditions;
+use crate::controller::exchange_credentials::{credentials_reference_secret, references_exchange_credentials, resolve_exchange_credentials};
+use crate::controller::signals::{
+    consumes_from, resolve_producers, signals_secret, signals_secret_name,
+    PRODUCERS_RESOLVED_CONDITION, SIGNALS_SECRET_KEY,
+};
+use crate::crd::{NamespacedCustomResource, hub::bot::{ApiSecrets, Bot, BotPhase, BotStatus, DeploymentUpdateStrategy, PvcDeletionPolicy}, hub::common::{Condition, SecretItem, SecretKeyRef}, hub::exchange_credentials::ExchangeCredentials};
+use crate::crd::v1alpha1::{exchange_credentials::ExchangeCredentials as V1Alpha1ExchangeCredentials, strategy::Strategy as V1Alpha1Strategy};
 use crate::error::{Result, ControllerError};
 
 
 pub static FINALIZER: &str = "bots.finalizers.freqtrade.io";
 pub static CONFIG_HASH_ANNOTATION: &str = "bots.freqtrade.io/config-hash";
+pub static SECRETS_HASH_ANNOTATION: &str = "bots.freqtrade.io/secrets-hash";
+// The UID of the generated API credentials Secret the bot's pods were started with
+pub static API_CREDENTIALS_UID_ANNOTATION: &str = "bots.freqtrade.io/api-credentials-uid";
+pub static PAUSED_ANNOTATION: &str = "bots.freqtrade.io/paused";
+// Changing its value on a Bot restarts the bot, the handled value is mirrored on the Deployment
+pub static RESTARTED_AT_ANNOTATION: &str = "bots.freqtrade.io/restartedAt";
+pub static USER_DATA_PATH: &str = "/freqtrade/user_data";
+// A comma separated list of the validations a Bot opts out of in the webhook
+pub static SKIP_VALIDATION_ANNOTATION: &str = "bots.freqtrade.io/skip-validation";
+// Skips the reserved config keys and env vars
+pub static RESERVED_KEYS_SCOPE: &str = "reserved-keys";
+// The env vars of the main container the Bot's `deployment.env` can't override
+pub static RESERVED_ENV_VARS: &[&str] = &[
+    "FREQTRADE__STRATEGY",
+    "FREQTRADE__STRATEGY_PATH",
+    "FREQTRADE__DB_URL",
+    "FREQTRADE__BOT_NAME",
+    "FREQTRADE__API_SERVER__ENABLED",
+    "FREQTRADE__API_SERVER__LISTEN_IP_ADDRESS",
+    "FREQTRADE__API_SERVER__LISTEN_PORT",
+    "FREQTRADE__API_SERVER__USERNAME",
+    "FREQTRADE__API_SERVER__PASSWORD",
+    "FREQTRADE__API_SERVER__JWT_SECRET_KEY",
+    "FREQTRADE__API_SERVER__WS_TOKEN",
+    "FREQTRADE__EXCHANGE__NAME",
+    "FREQTRADE__EXCHANGE__KEY",
+    "FREQTRADE__EXCHANGE__SECRET",
+    "FREQTRADE__EXCHANGE__PASSWORD",
+    "FREQTRADE__EXCHANGE__UID",
+    "FREQTRADE__TELEGRAM__TOKEN",
+    "FREQTRADE__TELEGRAM__CHAT_ID",
+    "FREQTRADE__DISCORD__WEBHOOK_URL",
+    "FREQTRADE__SLACK__WEBHOOK_URL",
+];
+// The keys of the Secret holding the generated API credentials
+pub static API_CREDENTIAL_KEYS: &[&str] = &["username", "password", "jwt_secret_key", "ws_token"];
+static API_CREDENTIAL_LENGTH: usize = 32;
+// How long the API health check may take, so an unresponsive bot doesn't hold up the reconcile
+static HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+// The shortest interval the trades of a bot are reported at, as often as the bot is requeued
+pub static MIN_STATUS_REPORTING_INTERVAL_SECONDS: u64 = 30;
+// The progress deadline of the Deployment, FreqAI bots load or train their model before they are available
+static DEFAULT_PROGRESS_DEADLINE_SECONDS: i32 = 600;
+static MODEL_PROGRESS_DEADLINE_SECONDS: i32 = 3600;
+// How many changes a subscriber of a shared watch may lag behind before the watch waits on it
+static SHARED_WATCH_BUFFER_SIZE: usize = 256;
+// The pods of the bots, their UI and job pods carry the bot's name too
+static BOT_POD_WATCH_SELECTOR: &str = "freqtrade.io/bot-name,app.kubernetes.io/component=bot";
+pub static CONFIG_MAP_READY_CONDITION: &str = "ConfigMapReady";
+pub static PVC_BOUND_CONDITION: &str = "PVCBound";
+pub static DEPLOYMENT_AVAILABLE_CONDITION: &str = "DeploymentAvailable";
+pub static DEPLOYMENT_APPLIED_CONDITION: &str = "DeploymentApplied";
+pub static SERVICE_READY_CONDITION: &str = "ServiceReady";
+pub static API_HEALTHY_CONDITION: &str = "ApiHealthy";
+pub static FREQTRADE_VERSION_CONDITION: &str = "FreqtradeVersionReported";
+pub static DEGRADED_CONDITION: &str = "Degraded";
+pub static RECONCILIATION_PAUSED_CONDITION: &str = "ReconciliationPaused";
+pub static DELETING_CONDITION: &str = "Deleting";
+pub static ADOPTION_BLOCKED_CONDITION: &str = "AdoptionBlocked";
+pub static SUSPENDED_CONDITION: &str = "Suspended";
+// The reasons a container waits for that need a change to the bot to resolve
+pub static FAILED_WAITING_REASONS: &[&str] = &[
+    "ErrImagePull",
+    "ImagePullBackOff",
+    "InvalidImageName",
+    "CrashLoopBackOff",
+    "CreateContainerConfigError",
+    "CreateContainerError",
+];
 
-impl From<DeploymentStatus> for BotPhase {
-    /// Convert a DeploymentStatus to a BotPhase
-    /// This function is responsible for converting a DeploymentStatus to a BotPhase.
-    /// 
+/// The observed state of the bot's Deployment and pods, and the config they run, surfaced in its status
+#[derive(Clone, Debug, Default, PartialEq)]
+struct BotWorkload {
+    ready_replicas: Option<i32>,
+    updated_replicas: Option<i32>,
+    pod_name: Option<String>,
+    applied_config_hash: Option<String>,
+    strategy_hash: Option<String>,
+    api_credentials_secret: Option<String>,
+    api_endpoint: Option<String>,
+    freqtrade_version: Option<String>,
+    version_pod_template_hash: Option<String>,
+    // Only set when the trades were just queried, so they are left out of the comparison with
+    // the current status and written at most once per reporting interval
+    trading_summary: Option<TradingSummary>,
+}
+
+impl From<&BotStatus> for BotWorkload {
+    fn from(status: &BotStatus) -> Self {
+        BotWorkload {
+            ready_replicas: status.ready_replicas,
+            updated_replicas: status.updated_replicas,
+            pod_name: status.pod_name.clone(),
+            applied_config_hash: status.applied_config_hash.clone(),
+            strategy_hash: status.strategy_hash.clone(),
+            api_credentials_secret: status.api_credentials_secret.clone(),
+            api_endpoint: status.api_endpoint.clone(),
+            freqtrade_version: status.freqtrade_version.clone(),
+            version_pod_template_hash: status.version_pod_template_hash.clone(),
+            trading_summary: None,
+        }
+    }
+}
+
+/// The phase of a bot along with the reason and message explaining it
+struct BotPhaseStatus {
+    phase: BotPhase,
+    reason: Option<String>,
+    message: Option<String>,
+}
+
+impl From<&Conditions> for BotPhaseStatus {
+    /// Derive the phase of a bot from its conditions
+    ///
+    /// A bot being cleaned up is deleting, a degraded bot is errored, a suspended bot is
+    /// suspended, and a bot whose Deployment is available is running. The reason and message are taken from the condition the phase
+    /// was derived from.
+    ///
     /// # Arguments
-    /// * `status` - The DeploymentStatus to convert
-    /// 
+    /// * `conditions` - The conditions of the bot
+    ///
     /// # Returns
-    /// The BotPhase
-    fn from(status: DeploymentStatus) -> Self {
-        match status.conditions {
-            Some(conditions) => {
-                if conditions.iter().any(|c| c.type_ == "Progressing" && c.status == "False") {
-                    BotPhase::Error
-                } else if conditions.iter().any(|c| c.type_ == "Available" && c.status == "True") {
-                    BotPhase::Running
-                } else {
-                    BotPhase::Pending // Default to Pending if no conditions match
-                }
+    /// The BotPhaseStatus
+    fn from(conditions: &Conditions) -> Self {
+        let explained_by = |phase: BotPhase, type_: &str| {
+            let condition = conditions.get(type_);
+            BotPhaseStatus {
+                phase,
+                reason: condition.and_then(|c| c.reason.clone()),
+                message: condition.and_then(|c| c.message.clone()),
             }
-            None => BotPhase::Pending, // Default to Pending if no conditions are available
+        };
+
+        if conditions.is_true(DELETING_CONDITION) {
+            explained_by(BotPhase::Deleting, DELETING_CONDITION)
+        } else if conditions.is_true(DEGRADED_CONDITION) {
+            explained_by(BotPhase::Error, DEGRADED_CONDITION)
+        } else if conditions.is_true(SUSPENDED_CONDITION) {
+            explained_by(BotPhase::Suspended, SUSPENDED_CONDITION)
+        } else if conditions.is_true(DEPLOYMENT_AVAILABLE_CONDITION) {
+            BotPhaseStatus::from(BotPhase::Running)
+        } else {
+            explained_by(BotPhase::Pending, DEPLOYMENT_AVAILABLE_CONDITION)
         }
     }
 }
 
-impl FromHub<Bot> for ConfigMap {
+impl From<BotPhase> for BotPhaseStatus {
+    fn from(phase: BotPhase) -> Self {
+        BotPhaseStatus { phase, reason: None, message: None }
+    }
+}
+
+impl TryFromHub<Bot> for ConfigMap {
     /// Create a ConfigMap resource from a Bot Hub
     /// 
-    /// This function is responsible for creating a ConfigMap resource from a Bot Hub.
+    /// This function is responsible for creating a ConfigMap resource from a Bot Hub. It fails
+    /// rather than writing an empty config.json when the config can't be serialized.
     /// 
     /// # Arguments
     /// * `bot` - The Bot CRD to create the ConfigMap resource from
@@ -75,21 +225,26 @@ impl FromHub<Bot> for ConfigMap {
     /// * `config` - The application configuration
     /// 
     /// # Returns
-    /// The ConfigMap resource
-    fn from_hub(bot: &Bot, name: &str, namespace: &str, owner_ref: OwnerReference, _config: &AppConfig) -> Self {
-        let config_data = bot.spec.config.clone();
+    /// A Result containing the ConfigMap resource
+    fn try_from_hub(bot: &Bot, name: &str, namespace: &str, owner_ref: OwnerReference, config: &AppConfig) -> Result<Self> {
+        let config_data = serde_json::to_string(&bot.spec.config)?;
         let strategy = bot.spec.strategy.clone();
         let model = bot.spec.model.clone();
+        let parameters = strategy_parameters_key(bot)
+            .map(|key| serde_json::to_string(&strategy.parameters).map(|parameters| (key, parameters)))
+            .transpose()?;
 
-        ConfigMap {
+        Ok(ConfigMap {
             metadata: ObjectMeta {
                 name: Some(name.to_string()),
                 namespace: Some(namespace.to_string()),
                 owner_references: Some(vec![owner_ref]),
+                annotations: with_defaults(&config.controller.default_annotations, None),
+                labels: with_defaults(&config.controller.default_labels, None),
                 ..Default::default()
             },
             data: Some(BTreeMap::from([
-                ("config.json".to_string(), serde_json::to_string(&config_data).unwrap_or_default()),
+                ("config.json".to_string(), config_data),
             ])
             .into_iter()
             .chain(
@@ -100,6 +255,7 @@ impl FromHub<Bot> for ConfigMap {
                         strategy.source.unwrap_or_default(),
                     ))
             )
+            .chain(parameters)
             .chain(
                 model
                     .as_ref()
@@ -111,7 +267,7 @@ impl FromHub<Bot> for ConfigMap {
             )
             .collect()),
             ..Default::default()
-        }
+        })
     }
 }
 
@@ -126,7 +282,7 @@ impl ResourceDrift<Bot> for ConfigMap {
     /// Whether the ConfigMap resource has drifted from the other ConfigMap resource
     fn has_drifted(&self, other: &Self) -> bool {
         // We just compare the data field which should have only 1-2 keys, with strings as values
-        self.data != other.data
+        self.data != other.data || metadata_drifted(&self.metadata, &other.metadata)
     }
 }
 
@@ -144,7 +300,7 @@ impl FromHub<Bot> for PersistentVolumeClaim {
     /// 
     /// # Returns
     /// The PersistentVolumeClaim resource
-    fn from_hub(bot: &Bot, name: &str, namespace: &str, owner_ref: OwnerReference, _config: &AppConfig) -> Self {
+    fn from_hub(bot: &Bot, name: &str, namespace: &str, owner_ref: OwnerReference, config: &AppConfig) -> Self {
         let pvc = bot.spec.pvc.clone();
 
         PersistentVolumeClaim {
@@ -152,8 +308,8 @@ impl FromHub<Bot> for PersistentVolumeClaim {
                 name: Some(name.to_string()),
                 namespace: Some(namespace.to_string()),
                 owner_references: Some(vec![owner_ref]),
-                annotations: pvc.annotations.clone(),
-                labels: pvc.labels.clone(),
+                annotations: with_defaults(&config.controller.default_annotations, pvc.annotations.clone()),
+                labels: with_defaults(&config.controller.default_labels, pvc.labels.clone()),
                 ..Default::default()
             },
             spec: Some(PersistentVolumeClaimSpec {
@@ -180,6 +336,10 @@ impl ResourceDrift<Bot> for PersistentVolumeClaim {
     /// # Returns
     /// Whether the PersistentVolumeClaim resource has drifted from the other PersistentVolumeClaim resource
     fn has_drifted(&self, other: &Self) -> bool {
+        if metadata_drifted(&self.metadata, &other.metadata) {
+            return true;
+        }
+
         match (&self.spec, &other.spec) {
             // If both specs are Some, compare the storage class name, and resources
             (Some(spec), Some(other_spec)) => {
@@ -219,15 +379,13 @@ impl FromHub<Bot> for Deployment {
         let pvc = bot.spec.pvc.clone();
         let deployment = bot.spec.deployment.clone();
         let secrets = bot.spec.secrets.clone();
+        let data = bot.spec.data.clone();
 
-        let image_repo = image.repository.unwrap_or(config.controller.default_image_repo.clone());
-        let image_tag = image.tag.unwrap_or(config.controller.default_image_tag.clone());
+        let image_name = image.image_name(&config.controller.default_image_repo, &config.controller.default_image_tag);
+        let image_pull_policy = image.pull_policy.or(config.controller.default_image_pull_policy.clone());
+        let image_pull_secrets = merge_pull_secrets(&config.controller.default_image_pull_secrets, image.pull_secrets);
 
-        let identifying_labels = BTreeMap::from([
-            ("freqtrade.io/bot-name".to_string(), name.to_string()),
-            ("app.kubernetes.io/name".to_string(), name.to_string()),
-            ("app.kubernetes.io/instance".to_string(), name.to_string()),
-        ]);
+        let identifying_labels = bot_pod_labels(name);
         let metadata_labels = BTreeMap::from([
             ("app.kubernetes.io/component".to_string(), "bot".to_string()),
             ("app.kubernetes.io/part-of".to_string(), "freqtrade".to_string()),
@@ -260,8 +418,8 @@ impl FromHub<Bot> for Deployment {
                 name: Some(name.to_string()),
                 namespace: Some(namespace.to_string()),
                 owner_references: Some(vec![owner_ref]),
-                annotations: deployment.annotations.clone(),
-                labels: deployment.labels.clone()
+                annotations: with_defaults(&config.controller.default_annotations, deployment.annotations.clone()),
+                labels: with_defaults(&config.controller.default_labels, deployment.labels.clone()
                     .map_or(
                         Some(identifying_labels.clone()
                             .into_iter()
@@ -272,21 +430,33 @@ impl FromHub<Bot> for Deployment {
                             labels.extend(identifying_labels.clone());
                             labels.extend(metadata_labels.clone());
                             Some(labels)
-                    }),
+                    })),
                 ..Default::default()
             },
             spec: Some(DeploymentSpec {
                 // The Bot instance will always have only 1 replica, as Freqtrade can not inherently
-                // scale horizontally.
-                replicas: Some(1),
+                // scale horizontally. A suspended bot is scaled to zero.
+                replicas: Some(if bot.spec.suspend { 0 } else { 1 }),
+                // Every config change rolls the bot, so the old ReplicaSets would pile up
+                revision_history_limit: Some(
+                    deployment.revision_history_limit.unwrap_or(config.controller.default_revision_history_limit)
+                ),
+                min_ready_seconds: Some(deployment.min_ready_seconds.unwrap_or(0)),
+                progress_deadline_seconds: Some(deployment.progress_deadline_seconds.unwrap_or(
+                    if model.is_some() { MODEL_PROGRESS_DEADLINE_SECONDS } else { DEFAULT_PROGRESS_DEADLINE_SECONDS }
+                )),
+                strategy: Some(DeploymentStrategy {
+                    type_: Some(deployment_update_strategy(bot).to_string()),
+                    ..Default::default()
+                }),
                 selector: LabelSelector {
                     match_labels: Some(identifying_labels.clone()),
                     ..Default::default()
                 },
                 template: PodTemplateSpec {
                     metadata: Some(ObjectMeta {
-                        annotations: deployment.annotations.clone(),
-                        labels: deployment.labels.clone()
+                        annotations: deployment.pod_annotations.clone(),
+                        labels: with_defaults(&config.controller.default_labels, deployment.labels.clone()
                             .map_or(
                                 Some(identifying_labels.clone()
                                     .into_iter()
@@ -298,11 +468,11 @@ impl FromHub<Bot> for Deployment {
                                     labels.extend(metadata_labels.clone());
                                     Some(labels)
                                 }
-                            ),
+                            )),
                         ..Default::default()
                     }),
                     spec: Some(PodSpec {
-                        image_pull_secrets: image.pull_secrets.as_ref().map(|secrets| secrets.iter().map(|secret| {
+                        image_pull_secrets: image_pull_secrets.as_ref().map(|secrets| secrets.iter().map(|secret| {
                             LocalObjectReference {
                                 name: secret.clone(),
                             }
@@ -310,8 +480,13 @@ impl FromHub<Bot> for Deployment {
                         containers: vec![
                             Container {
                                 name: name.to_string(),
-                                image: Some(format!("{}:{}", image_repo, image_tag)),
-                                image_pull_policy: image.pull_policy,
+                                image: Some(image_name.clone()),
+                                image_pull_policy: image_pull_policy.clone(),
+                                // Model specific resources (e.g. GPUs for FreqAI training) take precedence
+                                resources: model
+                                    .as_ref()
+                                    .and_then(|m| m.resources.clone())
+                                    .or(deployment.resources.clone()),
                                 command: Some(match &deployment.command {
                                     Some(cmd) => cmd
                                         .iter()
@@ -322,67 +497,7 @@ impl FromHub<Bot> for Deployment {
                                         .collect(),
                                     None => default_command.clone(),
                                 }),
-                                env: Some(vec![
-                                    // Environment variables
-                                    create_env_var("FREQTRADE__STRATEGY", Some(strategy.name)),
-                                    create_env_var("FREQTRADE__STRATEGY_PATH", Some("/etc/freqtrade".to_string())),
-                                    create_env_var("FREQTRADE__FREQAIMODEL_PATH", Some("/etc/freqtrade".to_string())),
-                                    create_env_var("FREQTRADE__DB_URL", Some(bot.spec.database.to_string())),
-                                    create_env_var("FREQTRADE__BOT_NAME", Some(name.to_string())),
-                                    create_env_var("FREQTRADE__API_SERVER__ENABLED", Some(api.enabled.to_string())),
-                                    create_env_var("FREQTRADE__API_SERVER__LISTEN_IP_ADDRESS", Some(api.host.to_string())),
-                                    create_env_var("FREQTRADE__API_SERVER__LISTEN_PORT", Some(api.port.to_string())),
-                                    create_env_var("FREQTRADE__EXCHANGE__NAME", Some(bot.spec.exchange.to_string())),
-                                    secrets.telegram.as_ref().map_or_else(
-                                        || create_env_var("FREQTRADE__TELEGRAM__CHAT_ID", None),
-                                        |t| create_env_var("FREQTRADE__TELEGRAM__CHAT_ID", Some(t.chat_id.clone().unwrap_or_default()))
-                                    ),
-                                    // Secret-based environment variables
-                                    secrets.api.as_ref().map_or_else(
-                                        || create_env_var("FREQTRADE__API_SERVER__USERNAME", None),
-                                        |a| create_secret_env_var("FREQTRADE__API_SERVER__USERNAME", &a.username)
-                                    ),
-                                    secrets.api.as_ref().map_or_else(
-                                        || create_env_var("FREQTRADE__API_SERVER__PASSWORD", None),
-                                        |a| create_secret_env_var("FREQTRADE__API_SERVER__PASSWORD", &a.password)
-                                    ),
-                                    secrets.api.as_ref().map_or_else(
-                                        || create_env_var("FREQTRADE__API_SERVER__WS_TOKEN", None),
-                                        |a| create_secret_env_var("FREQTRADE__API_SERVER__WS_TOKEN", &a.ws_token)
-                                    ),
-                                    secrets.api.as_ref().map_or_else(
-                                        || create_env_var("FREQTRADE__API_SERVER__JWT_SECRET_KEY", None),
-                                        |a| create_secret_env_var("FREQTRADE__API_SERVER__JWT_SECRET_KEY", &a.jwt_secret_key)
-                                    ),
-                                    secrets.telegram.as_ref().map_or_else(
-                                        || create_env_var("FREQTRADE__TELEGRAM__TOKEN", None),
-                                        |t| create_secret_env_var("FREQTRADE__TELEGRAM__TOKEN", &t.token)
-                                    ),
-                                    secrets.exchange.as_ref().map_or_else(
-                                        || create_env_var("FREQTRADE__EXCHANGE__KEY", None),
-                                        |e| create_secret_env_var("FREQTRADE__EXCHANGE__KEY", &e.key)
-                                    ),
-                                    secrets.exchange.as_ref().map_or_else(
-                                        || create_env_var("FREQTRADE__EXCHANGE__SECRET", None),
-                                        |e| create_secret_env_var("FREQTRADE__EXCHANGE__SECRET", &e.secret)
-                                    ),
-                                    secrets.exchange.as_ref().map_or_else(
-                                        || create_env_var("FREQTRADE__EXCHANGE__PASSWORD", None),
-                                        |e| create_secret_env_var("FREQTRADE__EXCHANGE__PASSWORD", &e.password)
-                                    ),
-                                    secrets.exchange.as_ref().map_or_else(
-                                        || create_env_var("FREQTRADE__EXCHANGE__UID", None),
-                                        |e| create_secret_env_var("FREQTRADE__EXCHANGE__UID", &e.uid)
-                                    ),
-                                ]
-                                .into_iter()
-                                .chain(
-                                    model
-                                        .as_ref()
-                                        .map(|_| create_env_var("FREQTRADE__FREQAI__ENABLED", Some("true".to_string())))
-                                )
-                                .chain(deployment.env.clone().into_iter())
-                                .collect()),
+                                env: Some(bot_env_vars(bot, name, config)),
                                 ports: Some(vec![
                                     ContainerPort {
                                         container_port: api.port as i32,
@@ -398,18 +513,141 @@ impl FromHub<Bot> for Deployment {
                                     },
                                 ]
                                 .into_iter()
+                                .chain(
+                                    pvc.enabled
+                                        .then(|| VolumeMount {
+                                            name: "user-data".to_string(),
+                                            mount_path: USER_DATA_PATH.to_string(),
+                                            ..Default::default()
+                                        })
+                                )
                                 .chain(deployment.volume_mounts.clone().into_iter())
                                 .collect()),
                                 ..Default::default()
                             },
                         ]
                         .into_iter()
+                        .chain(
+                            // The exporter scrapes the bot's API over localhost using the same credentials
+                            bot.spec.monitoring
+                                .enabled_exporter()
+                                .map(|exporter| Container {
+                                    name: "exporter".to_string(),
+                                    image: Some(exporter.image.clone()),
+                                    env: Some(vec![
+                                        create_env_var("FREQTRADE_API_URL", Some(format!("http://localhost:{}/api/v1", api.port))),
+                                        create_env_var("EXPORTER_PORT", Some(exporter.port.to_string())),
+                                        secrets.api.as_ref().map_or_else(
+                                            || create_env_var("FREQTRADE_USERNAME", None),
+                                            |a| create_secret_env_var("FREQTRADE_USERNAME", &a.username)
+                                        ),
+                                        secrets.api.as_ref().map_or_else(
+                                            || create_env_var("FREQTRADE_PASSWORD", None),
+                                            |a| create_secret_env_var("FREQTRADE_PASSWORD", &a.password)
+                                        ),
+                                    ]),
+                                    ports: Some(vec![
+                                        ContainerPort {
+                                            container_port: exporter.port as i32,
+                                            name: Some("metrics".to_string()),
+                                            ..Default::default()
+                                        },
+                                    ]),
+                                    ..Default::default()
+                                })
+                        )
                         .chain(deployment.containers.clone())
                         .collect(),
-                        init_containers: match deployment.init_containers.is_empty() {
-                            true => None,
-                            false => Some(deployment.init_containers.clone()),
-                        },
+                        init_containers: Some(
+                            // A fresh PVC lacks the `user_data` directory structure freqtrade expects,
+                            // so it gets created before any other init container runs
+                            (pvc.enabled && pvc.bootstrap)
+                                .then(|| Container {
+                                    name: "bootstrap-user-data".to_string(),
+                                    image: Some(image_name.clone()),
+                                    image_pull_policy: image_pull_policy.clone(),
+                                    command: Some(vec![
+                                        "freqtrade".to_string(),
+                                        "create-userdir".to_string(),
+                                        "--userdir".to_string(),
+                                        USER_DATA_PATH.to_string(),
+                                    ]),
+                                    volume_mounts: Some(vec![
+                                        VolumeMount {
+                                            name: "user-data".to_string(),
+                                            mount_path: USER_DATA_PATH.to_string(),
+                                            ..Default::default()
+                                        },
+                                    ]),
+                                    ..Default::default()
+                                })
+                                .into_iter()
+                                .chain(
+                                    // Candle data is downloaded onto the PVC so it is present before
+                                    // the trade container starts
+                                    data.download
+                                        .as_ref()
+                                        .filter(|download| download.enabled && pvc.enabled)
+                                        .map(|download| Container {
+                                            name: "download-data".to_string(),
+                                            image: Some(image_name.clone()),
+                                            image_pull_policy: image_pull_policy.clone(),
+                                            command: Some(
+                                                vec![
+                                                    "freqtrade".to_string(),
+                                                    "download-data".to_string(),
+                                                    "--config".to_string(),
+                                                    "/etc/freqtrade/config.json".to_string(),
+                                                    "--userdir".to_string(),
+                                                    USER_DATA_PATH.to_string(),
+                                                ]
+                                                .into_iter()
+                                                .chain(
+                                                    download.timerange
+                                                        .as_ref()
+                                                        .map(|timerange| vec!["--timerange".to_string(), timerange.clone()])
+                                                        .into_iter()
+                                                        .flatten()
+                                                )
+                                                .chain(
+                                                    (!download.timeframes.is_empty())
+                                                        .then(|| std::iter::once("--timeframes".to_string()).chain(download.timeframes.clone()))
+                                                        .into_iter()
+                                                        .flatten()
+                                                )
+                                                .chain(
+                                                    download.pairs
+                                                        .as_ref()
+                                                        .filter(|pairs| !pairs.is_empty())
+                                                        .map(|pairs| std::iter::once("--pairs".to_string()).chain(pairs.clone()))
+                                                        .into_iter()
+                                                        .flatten()
+                                                )
+                                                .collect()
+                                            ),
+                                            env: Some(vec![
+                                                create_env_var("FREQTRADE__EXCHANGE__NAME", Some(bot.spec.exchange.to_string())),
+                                            ]),
+                                            volume_mounts: Some(vec![
+                                                VolumeMount {
+                                                    name: "config".to_string(),
+                                                    mount_path: "/etc/freqtrade".to_string(),
+                                                    ..Default::default()
+                                                },
+                                                VolumeMount {
+                                                    name: "user-data".to_string(),
+                                                    mount_path: USER_DATA_PATH.to_string(),
+                                                    ..Default::default()
+                                                },
+                                            ]),
+                                            ..Default::default()
+                                        })
+                                )
+                                .chain(deployment.init_containers.clone())
+                                .map(|container| with_proxy_env(container, &config.controller.proxy))
+                                .collect::<Vec<Container>>()
+                        )
+                        .filter(|init_containers| !init_containers.is_empty()),
                         volumes: Some(
                             vec![
                                 Volume {
@@ -434,6 +672,13 @@ impl FromHub<Bot> for Deployment {
                                                         ..Default::default()
                                                     })
                                             )
+                                            .chain(
+                                                strategy_parameters_key(bot).map(|key| KeyToPath {
+                                                    key: key.clone(),
+                                                    path: key,
+                                                    ..Default::default()
+                                                })
+                                            )
                                             .chain(
                                                 model
                                                     .as_ref()
@@ -513,6 +758,21 @@ impl ResourceDrift<Bot> for Deployment {
     /// # Returns
     /// `true` if there is a drift, `false` otherwise.
     fn has_drifted(&self, other: &Self) -> bool {
+        // Compare the labels and annotations set by the operator
+        if metadata_drifted(&self.metadata, &other.metadata) {
+            return true;
+        }
+
+        // Compare the labels and annotations of the pod template, such as deployment.podAnnotations.
+        // The rollouts add their restartedAt annotation, which the subset comparison ignores.
+        let template_metadata = |deployment: &Deployment| deployment.spec
+            .as_ref()
+            .and_then(|spec| spec.template.metadata.clone())
+            .unwrap_or_default();
+        if metadata_drifted(&template_metadata(self), &template_metadata(other)) {
+            return true;
+        }
+
         // Compare spec.replicas
         if self.spec
             .as_ref()
@@ -524,6 +784,21 @@ impl ResourceDrift<Bot> for Deployment {
             return true;
         }
 
+        // Compare spec.revisionHistoryLimit, spec.minReadySeconds and spec.progressDeadlineSeconds
+        let rollout_settings = |deployment: &Deployment| deployment.spec.as_ref().map(|spec| (
+            spec.revision_history_limit,
+            spec.min_ready_seconds,
+            spec.progress_deadline_seconds,
+        ));
+        if rollout_settings(self) != rollout_settings(other) {
+            return true;
+        }
+
+        // Compare spec.strategy.type, the rolling update parameters are defaulted by the API server
+        if deployment_strategy_type(self) != deployment_strategy_type(other) {
+            return true;
+        }
+
         // Compare container configuration (image, env, command, ports, etc.)
         let self_containers = self
             .spec
@@ -569,10 +844,41 @@ impl ResourceDrift<Bot> for Deployment {
                 return true;
             }
 
-            // Compare container resources (CPU/Memory limits and requests). Unless both
+            // Compare container resources (CPU/Memory/extended limits and requests). Unless both
             // sides have values, we consider them equal. If both sides do have values, then
             // we compare them.
-            if self_container.resources.is_some() && other_container.resources.is_some() && self_container.resources != other_container.resources {
+            if compare_resources(self_container.resources.as_ref(), other_container.resources.as_ref()) {
+                return true;
+            }
+        }
+
+        // Compare init containers (image, command, mounts, env)
+        let self_init_containers = self
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.template.spec.as_ref())
+            .and_then(|pod_spec| pod_spec.init_containers.clone())
+            .unwrap_or_default();
+
+        let other_init_containers = other
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.template.spec.as_ref())
+            .and_then(|pod_spec| pod_spec.init_containers.clone())
+            .unwrap_or_default();
+
+        if self_init_containers.len() != other_init_containers.len() {
+            return true;
+        }
+
+        for (self_container, other_container) in self_init_containers.iter().zip(&other_init_containers) {
+            if self_container.name != other_container.name
+                || self_container.image != other_container.image
+                || self_container.command != other_container.command
+                || self_container.args != other_container.args
+                || self_container.volume_mounts != other_container.volume_mounts
+                || compare_env_vars(self_container.env.as_ref(), other_container.env.as_ref())
+            {
                 return true;
             }
         }
@@ -609,10 +915,9 @@ impl ResourceDrift<Bot> for Deployment {
                 .and_then(|spec| spec.template.spec.as_ref())
                 .and_then(|pod_spec| pod_spec.node_selector.as_ref()
             ),
-        ) {
-            if self_node_selector != other_node_selector {
-                return true;
-            }
+        ) && self_node_selector != other_node_selector
+        {
+            return true;
         }
 
         // Compare affinity
@@ -719,7 +1024,7 @@ impl FromHub<Bot> for Service {
     /// 
     /// # Returns
     /// The Service resource
-    fn from_hub(bot: &Bot, name: &str, namespace: &str, owner_ref: OwnerReference, _config: &AppConfig) -> Self {
+    fn from_hub(bot: &Bot, name: &str, namespace: &str, owner_ref: OwnerReference, config: &AppConfig) -> Self {
         let mut service = bot.spec.service.clone();
         let api = bot.spec.api.clone();
 
@@ -727,6 +1032,10 @@ impl FromHub<Bot> for Service {
             service.ensure_api_port(api.port);
         }
 
+        if let Some(exporter) = bot.spec.monitoring.enabled_exporter() {
+            service.ensure_metrics_port(exporter.port);
+        }
+
         let identifying_labels = BTreeMap::from([
             ("freqtrade.io/bot-name".to_string(), name.to_string()),
             ("app.kubernetes.io/name".to_string(), name.to_string()),
@@ -738,8 +1047,8 @@ impl FromHub<Bot> for Service {
                 name: Some(name.to_string()),
                 namespace: Some(namespace.to_string()),
                 owner_references: Some(vec![owner_ref]),
-                annotations: service.annotations.clone(),
-                labels: service.labels.clone(),
+                annotations: with_defaults(&config.controller.default_annotations, service.annotations.clone()),
+                labels: with_defaults(&config.controller.default_labels, service.labels.clone()),
                 ..Default::default()
             },
             spec: Some(ServiceSpec {
@@ -750,7 +1059,11 @@ impl FromHub<Bot> for Service {
                     .map(|port| Some(ServicePort {
                         name: Some(port.name.clone()),
                         port: port.port as i32,
-                        target_port: Some(IntOrString::String(port.target_port.clone())),
+                        // A numeric target port is a port number rather than a container port name
+                        target_port: Some(port.target_port.parse().map_or_else(
+                            |_| IntOrString::String(port.target_port.clone()),
+                            IntOrString::Int,
+                        )),
                         ..Default::default()
                     }))
                     .collect(),
@@ -771,6 +1084,11 @@ impl ResourceDrift<Bot> for Service {
     /// # Returns
     /// Whether the Service resource has drifted from the other Service resource
     fn has_drifted(&self, other: &Self) -> bool {
+        // Compare the labels and annotations set by the operator
+        if metadata_drifted(&self.metadata, &other.metadata) {
+            return true;
+        }
+
         // Compare service type
         if self.spec.as_ref().and_then(|spec| spec.type_.as_ref()) != other.spec.as_ref().and_then(|spec| spec.type_.as_ref()) {
             return true;
@@ -782,6 +1100,12 @@ impl ResourceDrift<Bot> for Service {
         }
 
         // Compare ports
+        if self.spec.as_ref().and_then(|spec| spec.ports.as_ref()).map_or(0, |ports| ports.len())
+            != other.spec.as_ref().and_then(|spec| spec.ports.as_ref()).map_or(0, |ports| ports.len())
+        {
+            return true;
+        }
+
         for (self_port, other_port) in self.spec.as_ref().and_then(|spec| spec.ports.as_ref()).unwrap_or(&vec![]).iter()
             .zip(other.spec.as_ref().and_then(|spec| spec.ports.as_ref()).unwrap_or(&vec![]).iter())
             {
@@ -800,8 +1124,65 @@ impl ResourceDrift<Bot> for Service {
     }
 }
 
+impl FromHub<Bot> for Secret {
+    /// Create the Secret holding the generated API credentials from a Bot Hub
+    ///
+    /// Every credential is a new random value, so the Secret is only created when missing and
+    /// never applied over an existing one.
+    ///
+    /// # Arguments
+    /// * `bot` - The Bot CRD to create the Secret resource from
+    /// * `name` - The name of the Secret resource
+    /// * `namespace` - The namespace of the Secret resource
+    /// * `owner_ref` - The owner reference for the Secret resource
+    /// * `config` - The application configuration
+    ///
+    /// # Returns
+    /// The Secret resource
+    fn from_hub(_bot: &Bot, name: &str, namespace: &str, owner_ref: OwnerReference, config: &AppConfig) -> Self {
+        let random_value = || rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(API_CREDENTIAL_LENGTH)
+            .map(char::from)
+            .collect::<String>();
+
+        Secret {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some(namespace.to_string()),
+                owner_references: Some(vec![owner_ref]),
+                annotations: with_defaults(&config.controller.default_annotations, None),
+                labels: with_defaults(&config.controller.default_labels, None),
+                ..Default::default()
+            },
+            string_data: Some(
+                API_CREDENTIAL_KEYS
+                    .iter()
+                    .map(|key| (key.to_string(), random_value()))
+                    .collect()
+            ),
+            ..Default::default()
+        }
+    }
+}
+
 pub struct BotController;
 
+/// The configuration of the watches of the Bots
+///
+/// Only the Bots matching the label selector are managed by this operator instance.
+///
+/// # Arguments
+/// * `config` - The controller configuration
+///
+/// # Returns
+/// The watcher configuration, filtered by the label selector if one is configured
+fn bot_watcher_config(config: &ControllerConfig) -> watcher::Config {
+    config.watch_label_selector
+        .as_ref()
+        .map_or_else(watcher::Config::default, |selector| watcher::Config::default().labels(selector))
+}
+
 impl BotController {
     /// Create a new controller for the Bot resource
     /// 
@@ -809,29 +1190,238 @@ impl BotController {
     /// 
     /// # Arguments
     /// * `ctx` - The controller context
+    /// * `namespace` - The namespace to watch, or `None` to watch all namespaces
     /// 
     /// # Returns
     /// The controller for the Bot resource
-    pub async fn create_controller<T>(ctx: Arc<Context>) -> Controller<T>
+    pub async fn create_controller<T>(ctx: Arc<Context>, namespace: Option<&str>) -> Controller<T>
     where
-        T: NamespacedCustomResource
+        T: NamespacedCustomResource,
+        Bot: From<T>,
     {
         let client = ctx.client.clone();
-        let bot = Api::<T>::all(client.clone());
+        let bot = scoped_api::<T>(client.clone(), namespace);
+
+        // The owned resources don't carry the Bot's labels so their watches aren't filtered, but a
+        // change to one only triggers a reconcile when its owner is in the Bot store.
+        let bot_watcher_config = bot_watcher_config(&ctx.controller_config());
         
-        let deployment = Api::<Deployment>::all(client.clone());
-        let service = Api::<Service>::all(client.clone());
-        let config_map = Api::<ConfigMap>::all(client.clone());
-        let pvc = Api::<PersistentVolumeClaim>::all(client.clone());
-        let secret = Api::<Secret>::all(client.clone());
+        let deployment = scoped_api::<Deployment>(client.clone(), namespace);
+        let service = scoped_api::<Service>(client.clone(), namespace);
+        let config_map = scoped_api::<ConfigMap>(client.clone(), namespace);
+        let pvc = scoped_api::<PersistentVolumeClaim>(client.clone(), namespace);
+        let secret = scoped_api::<Secret>(client.clone(), namespace);
+        let ingress = scoped_api::<Ingress>(client.clone(), namespace);
+        let pod = scoped_api::<Pod>(client.clone(), namespace);
+
+        // The watches of the owned Deployments, Services, PVCs and Ingresses also fill the stores the bots'
+        // current child resources are read from, rather than a GET of each on every reconcile.
+        // ConfigMaps and Secrets are only watched for their metadata, since their bodies can be
+        // large and a change is all that triggers a reconcile, their metadata also tells when a
+        // referenced one has to be read again. A single watch of each is shared by the owner
+        // and reference triggers below, rather than one watch per trigger.
+        let (deployment_cache, deployment_writer) = reflector::store();
+        let (service_cache, service_writer) = reflector::store();
+        let (pvc_cache, pvc_writer) = reflector::store();
+        let (ingress_cache, ingress_writer) = reflector::store();
+        let (pod_cache, pod_writer) = reflector::store();
+        ctx.child_stores.deployments.add(deployment_cache);
+        ctx.child_stores.services.add(service_cache);
+        ctx.child_stores.pvcs.add(pvc_cache);
+        ctx.child_stores.ingresses.add(ingress_cache);
+        ctx.child_stores.pods.add(pod_cache);
+
+        let (config_map_cache, config_map_writer) = reflector::store_shared::<PartialObjectMeta<ConfigMap>>(SHARED_WATCH_BUFFER_SIZE);
+        let (secret_cache, secret_writer) = reflector::store_shared::<PartialObjectMeta<Secret>>(SHARED_WATCH_BUFFER_SIZE);
+        ctx.child_stores.config_maps.add(config_map_cache);
+        ctx.child_stores.secrets.add(secret_cache);
+        let config_maps = config_map_writer.subscribe().expect("a shared store can be subscribed to");
+        let secrets = secret_writer.subscribe().expect("a shared store can be subscribed to");
+
+        // The subscribers only receive objects while the shared watches are polled
+        tokio::spawn(
+            metadata_watcher(config_map, watcher::Config::default())
+                .default_backoff()
+                .reflect_shared(config_map_writer)
+                .for_each(|_| future::ready(()))
+        );
+        tokio::spawn(
+            metadata_watcher(secret, watcher::Config::default())
+                .default_backoff()
+                .reflect_shared(secret_writer)
+                .for_each(|_| future::ready(()))
+        );
+
+        // The bots' pods are only cached for the status, a change to one doesn't trigger a
+        // reconcile since it is reflected in the status of its Deployment
+        tokio::spawn(
+            watcher(pod, watcher::Config::default().labels(BOT_POD_WATCH_SELECTOR))
+                .default_backoff()
+                .modify(|pod| pod.managed_fields_mut().clear())
+                .reflect(pod_writer)
+                .for_each(|_| future::ready(()))
+        );
 
         // Create the controller and watchers for the bot resource
-        Controller::new(bot, watcher::Config::default())
-            .owns(deployment, watcher::Config::default())
-            .owns(service, watcher::Config::default())
-            .owns(config_map, watcher::Config::default())
-            .owns(pvc, watcher::Config::default())
-            .owns(secret, watcher::Config::default())
+        let controller = Controller::new(bot, bot_watcher_config.clone())
+            .with_config(runtime_config(&ctx))
+            .owns_stream(
+                watcher(deployment, watcher::Config::default())
+                    .default_backoff()
+                    .modify(|deployment| deployment.managed_fields_mut().clear())
+                    .reflect(deployment_writer)
+                    .touched_objects()
+            )
+            .owns_stream(
+                watcher(service, watcher::Config::default())
+                    .default_backoff()
+                    .modify(|service| service.managed_fields_mut().clear())
+                    .reflect(service_writer)
+                    .touched_objects()
+            )
+            .owns_shared_stream(config_maps.clone())
+            .owns_stream(
+                watcher(pvc, watcher::Config::default())
+                    .default_backoff()
+                    .modify(|pvc| pvc.managed_fields_mut().clear())
+                    .reflect(pvc_writer)
+                    .touched_objects()
+            )
+            .owns_shared_stream(secrets.clone())
+            .owns_stream(
+                watcher(ingress, watcher::Config::default())
+                    .default_backoff()
+                    .modify(|ingress| ingress.managed_fields_mut().clear())
+                    .reflect(ingress_writer)
+                    .touched_objects()
+            );
+
+        // Changes to a producer bot (port, ws_token) must re-reconcile the bots consuming from it
+        let store = controller.store();
+        let strategy_store = store.clone();
+        let exchange_credentials_store = store.clone();
+        let config_map_store = store.clone();
+        let secret_store = store.clone();
+        let controller = controller.watches(scoped_api::<T>(client.clone(), namespace), bot_watcher_config, move |producer: T| {
+            let producer_name = producer.name_any();
+            let producer_namespace = producer.namespace().unwrap_or_default();
+
+            store
+                .state()
+                .into_iter()
+                .filter(|consumer| consumes_from(
+                    &Bot::from(consumer.as_ref().clone()),
+                    &consumer.namespace().unwrap_or_default(),
+                    &producer_name,
+                    &producer_namespace,
+                ))
+                .map(|consumer| ObjectRef::from_obj(consumer.as_ref()))
+                .collect::<Vec<_>>()
+        });
+
+        // Changes to a Strategy must re-reconcile the bots referencing it, using the bot store
+        // as the index from strategies to bots
+        let controller = controller.watches(scoped_api::<V1Alpha1Strategy>(client.clone(), namespace), watcher::Config::default(), move |strategy: V1Alpha1Strategy| {
+            let strategy_name = strategy.name_any();
+            let strategy_namespace = strategy.namespace().unwrap_or_default();
+
+            strategy_store
+                .state()
+                .into_iter()
+                .filter(|bot| references_strategy(
+                    &Bot::from(bot.as_ref().clone()),
+                    &bot.namespace().unwrap_or_default(),
+                    &strategy_name,
+                    &strategy_namespace,
+                ))
+                .map(|bot| ObjectRef::from_obj(bot.as_ref()))
+                .collect::<Vec<_>>()
+        });
+
+        // Changes to a user managed strategy or model ConfigMap must re-reconcile the bots mounting
+        // it. Bots referencing a Strategy backed by a ConfigMap pick it up on their periodic requeue.
+        let controller = controller.watches_shared_stream(config_maps, move |config_map: Arc<PartialObjectMeta<ConfigMap>>| {
+            let config_map_name = config_map.name_any();
+            let config_map_namespace = config_map.namespace().unwrap_or_default();
+
+            config_map_store
+                .state()
+                .into_iter()
+                .filter(|bot| references_config_map(
+                    &Bot::from(bot.as_ref().clone()),
+                    &bot.namespace().unwrap_or_default(),
+                    &config_map_name,
+                    &config_map_namespace,
+                ))
+                .map(|bot| ObjectRef::from_obj(bot.as_ref()))
+                .collect::<Vec<_>>()
+        });
+
+        // The ExchangeCredentials are cached so a change to a Secret they reference re-reconciles
+        // the bots using them
+        let (secret_credentials_cache, credentials_writer) = reflector::store::<V1Alpha1ExchangeCredentials>();
+
+        // Changes to a referenced Secret must re-reconcile the bots referencing it, directly or
+        // through ExchangeCredentials, so a missing Secret is picked up once created and the bots
+        // opting in are rolled
+        let controller = controller.watches_shared_stream(secrets, move |secret: Arc<PartialObjectMeta<Secret>>| {
+            let secret_name = secret.name_any();
+            let secret_namespace = secret.namespace().unwrap_or_default();
+            let credentials = secret_credentials_cache
+                .state()
+                .into_iter()
+                .filter(|credentials| credentials_reference_secret(
+                    &ExchangeCredentials::from(credentials.as_ref().clone()),
+                    &secret_name,
+                    &secret_namespace,
+                ))
+                .map(|credentials| credentials.name_any())
+                .collect::<Vec<_>>();
+
+            secret_store
+                .state()
+                .into_iter()
+                .filter(|bot| {
+                    let hub = Bot::from(bot.as_ref().clone());
+                    references_secret(&hub, &bot.namespace().unwrap_or_default(), &secret_name, &secret_namespace)
+                        || credentials.iter().any(|name| references_exchange_credentials(&hub, name))
+                })
+                .map(|bot| ObjectRef::from_obj(bot.as_ref()))
+                .collect::<Vec<_>>()
+        });
+
+        // Changes to ExchangeCredentials must re-reconcile the bots referencing them
+        let credentials = watcher(Api::<V1Alpha1ExchangeCredentials>::all(client.clone()), watcher::Config::default())
+            .default_backoff()
+            .reflect(credentials_writer)
+            .touched_objects();
+        let controller = controller.watches_stream(credentials, move |credentials: V1Alpha1ExchangeCredentials| {
+            let credentials_name = credentials.name_any();
+
+            exchange_credentials_store
+                .state()
+                .into_iter()
+                .filter(|bot| references_exchange_credentials(&Bot::from(bot.as_ref().clone()), &credentials_name))
+                .map(|bot| ObjectRef::from_obj(bot.as_ref()))
+                .collect::<Vec<_>>()
+        });
+
+        // Only watch CloudNativePG Clusters when the CRD is installed, otherwise the
+        // watcher would error continuously. This is only checked once: when CloudNativePG is
+        // installed after the controller started, its Clusters aren't watched until the operator
+        // restarts. The bots still pick up their database on the periodic requeue, only a
+        // change to a Cluster isn't reacted to right away.
+        if cnpg_installed(&client, &ctx.state.diagnostics).await.unwrap_or(false) {
+            let cluster_resource = cnpg_cluster_resource();
+            let cluster = match namespace {
+                Some(namespace) => Api::<DynamicObject>::namespaced_with(client.clone(), namespace, &cluster_resource),
+                None => Api::<DynamicObject>::all_with(client.clone(), &cluster_resource),
+            };
+
+            controller.owns_with(cluster, cluster_resource, watcher::Config::default())
+        } else {
+            controller
+        }
     }
 
     /// Reconcile the bot resource
@@ -849,6 +1439,7 @@ impl BotController {
         T: NamespacedCustomResource,
         Bot: From<T>,
     {
+        let _in_flight = ctx.track_reconcile();
         let client = ctx.client.clone();
 
         // The Bot resource is namespaced, so we need to verify that it is specified
@@ -871,16 +1462,42 @@ impl BotController {
             )
         })?;
         let api = Api::<T>::namespaced(client.clone(), &namespace);
+        // Every log of the reconcile carries the bot's identity, and the span's close event
+        // its duration
+        let span = info_span!(
+            "reconcile",
+            kind = "Bot",
+            name = bot.name_any().as_str(),
+            namespace = namespace.as_str(),
+            generation = bot.meta().generation,
+            resource_version = bot.meta().resource_version.as_deref(),
+            duration_ms = field::Empty,
+        );
+        let started = Instant::now();
 
-        // Determine the action to take
-        finalizer(&api, FINALIZER, bot, |event| async {
-            match event {
-                Finalizer::Apply(bot) => reconcile_bot(&bot, &ctx, &namespace, &owner_ref).await,
-                Finalizer::Cleanup(bot) => cleanup_bot(&bot, &ctx, &namespace).await,
+        // Determine the action to take. The cleanup waits on the bot's trades, pods and PVC
+        // across reconciles, and the finalizer removes itself as soon as its cleanup succeeds,
+        // so the cleanup only reaches it once nothing is left to wait on.
+        let result = async {
+            let deleting = bot.meta().deletion_timestamp.is_some() && bot.finalizers().iter().any(|name| name == FINALIZER);
+            if deleting && let Some(action) = cleanup_bot(&bot, &ctx, &namespace).await? {
+                return Ok(action);
             }
-        })
-        .await
-        .map_err(|e| ControllerError::FinalizerError(e.to_string()))
+
+            finalizer(&api, FINALIZER, bot, |event| async {
+                match event {
+                    Finalizer::Apply(bot) => reconcile_bot(&bot, &ctx, &namespace, &owner_ref).await,
+                    Finalizer::Cleanup(bot) => cleaned_up(&bot, &ctx, &namespace).await,
+                }
+            })
+            .await
+            .map_err(|e| ControllerError::FinalizerError(e.to_string()))
+        }
+        .instrument(span.clone())
+        .await;
+
+        span.record("duration_ms", started.elapsed().as_millis() as u64);
+        result
     }
 }
 
@@ -909,41 +1526,159 @@ where
     let pvc_api = Api::<PersistentVolumeClaim>::namespaced(ctx.client.clone(), namespace);
     let service_api = Api::<Service>::namespaced(ctx.client.clone(), namespace);
 
-    let hub = Bot::from(bot.clone());
+    let mut hub = Bot::from(bot.clone());
+    let name = hub.child_name();
+    let mut conditions = Conditions::new(hub.status.as_ref().and_then(|status| status.conditions.clone()));
+
+    // A paused bot's resources are left as they are so they can be edited by hand, the drift
+    // is reconciled away once the annotation is removed
+    if bot.annotations().get(PAUSED_ANNOTATION).is_some_and(|paused| paused == "true") {
+        info!(
+            event = "ReconciliationPaused",
+            bot = bot.name_any().as_str()
+        );
+        conditions.set(Condition {
+            type_: RECONCILIATION_PAUSED_CONDITION.to_string(),
+            status: "True".to_string(),
+            reason: Some("PausedByAnnotation".to_string()),
+            message: Some(format!("Remove the `{}` annotation to resume reconciliation", PAUSED_ANNOTATION)),
+            last_transition_time: None,
+        });
+        update_status(bot, ctx, namespace, &BotPhaseStatus::from(&conditions), &conditions, None).await?;
+        return Ok(requeue(bot, ctx, Duration::from_secs(300)));
+    }
+    conditions.remove(RECONCILIATION_PAUSED_CONDITION);
+
+    // A referenced Strategy is inlined into the hub, so it is rendered into the ConfigMap
+    // and config hash like an inline strategy and editing it rolls the bot
+    if let Some(condition) = resolve_strategy(&mut hub, &ctx.client, namespace).await? {
+        let resolved = condition.status == "True";
+        let message = condition.message.clone();
+
+        conditions.set(condition);
+
+        if !resolved {
+            info!(
+                event = "UnresolvedStrategy",
+                bot = bot.name_any().as_str()
+            );
+            conditions.set(degraded_condition("UnresolvedStrategy", message));
+            update_status(bot, ctx, namespace, &BotPhaseStatus::from(&conditions), &conditions, None).await?;
+            return Ok(requeue(bot, ctx, Duration::from_secs(30)));
+        }
+    }
+
+    // Referenced ExchangeCredentials are copied into a Secret in the bot's namespace, and the hub
+    // is rewired to it so the env vars are rendered like inline exchange secrets
+    let exchange_secret = match resolve_exchange_credentials::<V1Alpha1ExchangeCredentials>(
+        &mut hub,
+        &ctx.client,
+        &name,
+        namespace,
+        owner_ref
+    ).await? {
+        Some(resolved) => {
+            let message = resolved.condition.message.clone();
 
-    let config_map_object = ConfigMap::from_hub(
+            conditions.set(resolved.condition);
+
+            if resolved.secret.is_none() {
+                info!(
+                    event = "UnresolvedExchangeCredentials",
+                    bot = bot.name_any().as_str()
+                );
+                conditions.set(degraded_condition("UnresolvedExchangeCredentials", message));
+                update_status(bot, ctx, namespace, &BotPhaseStatus::from(&conditions), &conditions, None).await?;
+                return Ok(requeue(bot, ctx, Duration::from_secs(30)));
+            }
+
+            resolved.secret
+        },
+        None => None,
+    };
+
+    // A single snapshot so a config reload mid-reconcile can't mix two configs
+    let app_config = ctx.state.config();
+    let conflict_policy = app_config.controller.conflict_policy.clone();
+
+    // A bot with its API enabled but no API secrets gets generated credentials. The Secret is
+    // only created when missing, so they are never rotated unless it is deleted, and the hub
+    // is rewired to it so the env vars are rendered like referenced API secrets.
+    let mut api_credentials_uid = None;
+    let api_credentials_secret = match generates_api_credentials(&hub) {
+        true => {
+            let secret_api = Api::<Secret>::namespaced(ctx.client.clone(), namespace);
+            let secret_name = api_credentials_secret_name(&name);
+
+            let secret = match ctx.child_stores.secrets.get(&secret_api, namespace, &secret_name).await? {
+                Some(secret) => secret,
+                None => {
+                    info!(
+                        event = "CreatingApiCredentialsSecret",
+                        bot = bot.name_any().as_str()
+                    );
+                    let secret_object = Secret::from_hub(
+                        &hub,
+                        &secret_name,
+                        namespace,
+                        owner_ref.clone(),
+                        &app_config
+                    );
+                    let secret = secret_api.create(&PostParams::default(), &secret_object).await?;
+                    ctx.publish_event(bot, EventType::Normal, "ApiCredentialsGenerated", "Create", Some(format!("Generated the API credentials in Secret `{}`", secret_name))).await;
+                    secret
+                },
+            };
+            api_credentials_uid = secret.uid();
+
+            hub.spec.secrets.api = Some(generated_api_secrets(&secret_name));
+            Some(secret_name)
+        },
+        false => None,
+    };
+
+    // A config that can't be serialized is reported on the bot rather than rolled out as an
+    // empty config.json, which freqtrade would fail on with an unrelated error
+    let config_map_object = match ConfigMap::try_from_hub(
         &hub,
-        bot.name_any().as_str(),
+        &name,
         namespace,
         owner_ref.clone(),
-        &ctx.state.as_ref().unwrap().config
-    );
+        &app_config
+    ) {
+        Ok(config_map_object) => config_map_object,
+        Err(e) => return serialization_failed(bot, ctx, namespace, &mut conditions, &e).await,
+    };
     let deployment_object = Deployment::from_hub(
         &hub,
-        bot.name_any().as_str(),
+        &name,
         namespace,
         owner_ref.clone(),
-        &ctx.state.as_ref().unwrap().config
+        &app_config
     );
     let service_object = Service::from_hub(
         &hub,
-        bot.name_any().as_str(),
+        &name,
         namespace,
         owner_ref.clone(),
-        &ctx.state.as_ref().unwrap().config
+        &app_config
     );
     let pvc_object = PersistentVolumeClaim::from_hub(
         &hub,
-        bot.name_any().as_str(),
+        &name,
         namespace,
         owner_ref.clone(),
-        &ctx.state.as_ref().unwrap().config
+        &app_config
     );
 
-    let config_map = config_map_api.get(bot.name_any().as_str()).await.ok();
-    let pvc = pvc_api.get(bot.name_any().as_str()).await.ok();
-    let mut deployment = deployment_api.get(bot.name_any().as_str()).await.ok();
-    let service = service_api.get(bot.name_any().as_str()).await.ok();
+    // The current child resources are read from the watch caches, every write below is recorded
+    // so a cache that hasn't observed it yet is bypassed. Only the metadata of ConfigMaps is
+    // watched, so the ConfigMap is read with a GET once it changed.
+    let stores = &ctx.child_stores;
+    let config_map = stores.config_maps.get(&config_map_api, namespace, &name).await?;
+    let pvc = stores.pvcs.get(&pvc_api, namespace, &name).await?;
+    let mut deployment = stores.deployments.get(&deployment_api, namespace, &name).await?;
+    let mut service = stores.services.get(&service_api, namespace, &name).await?;
 
     let current_config_hash = deployment
         .as_ref()
@@ -951,190 +1686,1818 @@ where
         .and_then(|annotations| annotations.get(CONFIG_HASH_ANNOTATION))
         .cloned()
         .unwrap_or_default();
-
-    let incoming_config_hash = compute_object_hash(&config_map_object.data)
-        .map_err(|e| { ControllerError::UnknownError(e.to_string())})
+    let current_restarted_at = deployment
+        .as_ref()
+        .and_then(|d| d.metadata.annotations.as_ref())
+        .and_then(|annotations| annotations.get(RESTARTED_AT_ANNOTATION))
+        .cloned()
         .unwrap_or_default();
 
-    if hub.status.is_none() {
-        info!(
-            event = "UpdatingBotStatus",
-            bot = bot.name_any().as_str()
-        );
-        update_status(bot, ctx, namespace, &BotPhase::Pending).await?;
+    // Inputs besides the ConfigMap whose changes must roll the bot, they are only
+    // hashed when present so the hash of existing bots stays the same
+    let mut config_hash_inputs: Vec<Value> = vec![];
+
+    config_hash_inputs.extend(webhook_notification_hash_input(&hub));
+
+    // The strategy source is inline or resolved from a Strategy at this point, unless it is
+    // mounted from a user managed ConfigMap
+    let mut strategy_source = hub.spec.strategy.source.clone();
+
+    // User managed strategy and model ConfigMaps are mounted as is, so the source they hold must be
+    // part of the config hash to roll the bot when they are edited
+    for (field, config_map_name, key) in referenced_config_maps(&hub) {
+        match ctx.child_stores.config_maps.get(&config_map_api, namespace, &config_map_name).await? {
+            Some(config_map) => match config_map.data.as_ref().and_then(|data| data.get(key)) {
+                Some(source) => {
+                    if field == "strategy.configMapName" {
+                        strategy_source = Some(source.clone());
+                    }
+                    config_hash_inputs.push(json!({ field: source }));
+                },
+                None => {
+                    let message = format!("ConfigMap `{}` referenced by spec.{} has no `{}` key", config_map_name, field, key);
+                    info!(
+                        event = "MissingConfigMapKey",
+                        bot = bot.name_any().as_str(),
+                        config_map = config_map_name.as_str(),
+                        key = key
+                    );
+                    conditions.set(degraded_condition("MissingConfigMapKey", Some(message)));
+                    update_status(bot, ctx, namespace, &BotPhaseStatus::from(&conditions), &conditions, None).await?;
+                    return Ok(requeue(bot, ctx, Duration::from_secs(30)));
+                },
+            },
+            None => {
+                let message = format!("ConfigMap `{}` referenced by spec.{} does not exist", config_map_name, field);
+                info!(
+                    event = "MissingConfigMap",
+                    bot = bot.name_any().as_str(),
+                    config_map = config_map_name.as_str()
+                );
+                conditions.set(degraded_condition("MissingConfigMap", Some(message)));
+                update_status(bot, ctx, namespace, &BotPhaseStatus::from(&conditions), &conditions, None).await?;
+                return Ok(requeue(bot, ctx, Duration::from_secs(30)));
+            },
+        }
     }
 
-    // If the config_map is None, OR if the config_map.data is different from the config_map_object.data,
-    // apply the changes
-    if config_map.is_none() || ResourceDrift::<Bot>::has_drifted(config_map.as_ref().unwrap(), &config_map_object) {
-        info!(
-            event = "ApplyingConfigMap",
-            bot = bot.name_any().as_str()
-        );
-        apply(&config_map_api, config_map_object, bot.name_any().as_str()).await?;
+    if hub.status.is_none() {
+        update_status(bot, ctx, namespace, &BotPhaseStatus::from(&conditions), &conditions, None).await?;
     }
 
-    // If the PVC is enabled, apply the PVC if it is None or different from the PVC object
-    // If the PVC is not enabled, delete the PVC if it exists
-    if hub.spec.pvc.enabled {
-        if pvc.is_none() || ResourceDrift::<Bot>::has_drifted(pvc.as_ref().unwrap(), &pvc_object) {
+    // If PostgreSQL provisioning is enabled, ensure the database exists before the bot is deployed
+    if let Some(condition) = reconcile_database(&hub, &ctx.client, &ctx.state.diagnostics, &name, namespace, owner_ref).await? {
+        let crd_missing = condition.reason.as_deref() == Some("CRDNotFound");
+        let message = condition.message.clone();
+
+        conditions.set(condition);
+
+        if crd_missing {
             info!(
-                event = "ApplyingPVC",
+                event = "MissingDatabaseCRD",
                 bot = bot.name_any().as_str()
             );
-            apply(&pvc_api, pvc_object, bot.name_any().as_str()).await?;
+            conditions.set(degraded_condition("MissingDatabaseCRD", message));
+            update_status(bot, ctx, namespace, &BotPhaseStatus::from(&conditions), &conditions, None).await?;
+            return Ok(requeue(bot, ctx, Duration::from_secs(30)));
         }
-    } else if pvc.is_some() {
-        info!(
-            event = "DeletingPVC",
-            bot = bot.name_any().as_str()
-        );
-        delete(&pvc_api, bot.name_any().as_str()).await?;
     }
 
-    // If the Deployment is None, OR if the Deployment spec is different from the Deployment object spec,
-    // apply the changes
-    if deployment.is_none() || ResourceDrift::<Bot>::has_drifted(deployment.as_ref().unwrap(), &deployment_object) {
-        info!(
-            event = "ApplyingDeployment",
-            bot = bot.name_any().as_str()
-        );
-        deployment = Some(apply(&deployment_api, deployment_object, bot.name_any().as_str()).await?);
-    }
+    if let Some(secret_object) = exchange_secret {
+        let secret_api = Api::<Secret>::namespaced(ctx.client.clone(), namespace);
+        let secret_name = secret_object.name_any();
+        let secret = ctx.child_stores.secrets.get(&secret_api, namespace, &secret_name).await?;
 
-    // If the current and incoming config hashes differ, cause a rollout for the deployment and patch the annotation
-    if current_config_hash != incoming_config_hash {
-        patch(&deployment_api, bot.name_any().as_str(), &Patch::Merge(json!({
-            "metadata": {
-                "annotations": {
-                    CONFIG_HASH_ANNOTATION: incoming_config_hash,
-                }
-            }
-        }))).await?;
+        // The credentials are referenced via secretKeyRefs, so their changes must be part of the
+        // config hash to roll the bot
+        config_hash_inputs.push(json!({ "exchange": secret_object.data }));
 
-        if !current_config_hash.is_empty() {
+        if secret.as_ref().is_none_or(|secret| secret.data != secret_object.data) {
             info!(
-                event = "RollingOutDeployment",
+                event = "ApplyingExchangeSecret",
                 bot = bot.name_any().as_str()
             );
-            rollout(&deployment_api, bot.name_any().as_str()).await?;
+            let applied = apply(&secret_api, secret_object, &secret_name).await?;
+            ctx.child_stores.secrets.record_write(namespace, &secret_name, Some(&applied));
         }
     }
 
-    // If the bot status is different from the Deployment status (or None), update the bot status
-    if hub.status.as_ref().is_none_or(|s| {
-        BotPhase::from(
-            deployment
-                .as_ref()
-                .unwrap()
-                .status
-                .clone()
-                .unwrap()
-            )
-            .to_string() != s.phase
-    }) {
-        info!(
-            event = "UpdatingBotStatus",
-            bot = bot.name_any().as_str(),
-            status = BotPhase::from(
-                deployment
-                    .as_ref()
-                    .unwrap()
-                    .status
-                    .clone()
-                    .unwrap()
-            )
-            .to_string()
+    // If the bot consumes signals from producer bots, resolve them into the signals Secret
+    if !hub.spec.signals.producers.is_empty() {
+        let all_namespaces = ctx.controller_config().watch_namespaces.is_empty();
+        let resolved = resolve_producers::<T>(&hub, &ctx.client, namespace, all_namespaces).await?;
+
+        // A denied producer is reported before a missing one, as it won't resolve on its own
+        let unresolved = match (resolved.denied.is_empty(), resolved.missing.is_empty()) {
+            (false, _) => Some(("ProducerNotAllowed", "DeniedProducers", format!(
+                "Producer bots in other namespaces don't allow consumers from namespace `{}`, or aren't watched: {}",
+                namespace,
+                resolved.denied.join(", ")
+            ))),
+            (true, false) => Some(("ProducerNotFound", "MissingProducers", format!(
+                "Producer bots could not be resolved: {}",
+                resolved.missing.join(", ")
+            ))),
+            (true, true) => None,
+        };
+
+        if let Some((reason, event, message)) = unresolved {
+            info!(
+                event = event,
+                bot = bot.name_any().as_str()
+            );
+
+            conditions.set(Condition {
+                type_: PRODUCERS_RESOLVED_CONDITION.to_string(),
+                status: "False".to_string(),
+                reason: Some(reason.to_string()),
+                message: Some(message.clone()),
+                last_transition_time: None,
+            });
+            conditions.set(degraded_condition(event, Some(message)));
+            update_status(bot, ctx, namespace, &BotPhaseStatus::from(&conditions), &conditions, None).await?;
+            return Ok(requeue(bot, ctx, Duration::from_secs(30)));
+        }
+
+        conditions.set(Condition {
+            type_: PRODUCERS_RESOLVED_CONDITION.to_string(),
+            status: "True".to_string(),
+            reason: Some("ProducersResolved".to_string()),
+            message: None,
+            last_transition_time: None,
+        });
+
+        let secret_api = Api::<Secret>::namespaced(ctx.client.clone(), namespace);
+        let secret_name = signals_secret_name(&name);
+        let secret_object = signals_secret(&secret_name, namespace, owner_ref.clone(), &resolved.producers);
+        let secret = ctx.child_stores.secrets.get(&secret_api, namespace, &secret_name).await?;
+
+        // The producers are referenced via a secretKeyRef, so their changes must be part of the
+        // config hash to roll the bot
+        config_hash_inputs.push(json!({ "signals": secret_object.data }));
+
+        if secret.as_ref().is_none_or(|secret| secret.data != secret_object.data) {
+            info!(
+                event = "ApplyingSignalsSecret",
+                bot = bot.name_any().as_str()
+            );
+            let applied = apply(&secret_api, secret_object, &secret_name).await?;
+            ctx.child_stores.secrets.record_write(namespace, &secret_name, Some(&applied));
+        }
+    }
+
+    // A Secret or key missing from the referenced ones would leave the pod stuck creating its
+    // container, so it is reported on the bot before anything is rolled out
+    let secrets = referenced_secrets(&hub, ctx, namespace).await?;
+    let missing = missing_secret_keys(&hub, &secrets);
+    if !missing.is_empty() {
+        let message = format!("Referenced secrets do not exist: {}", missing.join(", "));
+        info!(
+            event = "MissingSecret",
+            bot = bot.name_any().as_str()
         );
-        update_status(
-            bot,
-            ctx,
-            namespace,
-            &BotPhase::from(
-                deployment
-                    .as_ref()
-                    .unwrap()
-                    .status
-                    .clone()
-                    .unwrap()
-            )
-        ).await?;
+        ctx.publish_event(bot, EventType::Warning, "MissingSecret", "Reconcile", Some(message.clone())).await;
+        conditions.set(degraded_condition("MissingSecret", Some(message)));
+        update_status(bot, ctx, namespace, &BotPhaseStatus::from(&conditions), &conditions, None).await?;
+        return Ok(requeue(bot, ctx, Duration::from_secs(30)));
+    }
+
+    // If the config_map is None, OR if the config_map.data is different from the config_map_object.data,
+    // apply the changes
+    if config_map.as_ref().is_none_or(|config_map| ResourceDrift::<Bot>::has_drifted(config_map, &config_map_object)) {
+        info!(
+            event = "ApplyingConfigMap",
+            bot = bot.name_any().as_str()
+        );
+        ctx.publish_event(bot, EventType::Normal, "ApplyingConfigMap", "Apply", Some(format!("Applying ConfigMap `{}`", name))).await;
+        match apply(&config_map_api, config_map_object.clone(), &name)
+            .instrument(info_span!("apply", resource = "ConfigMap", resource_name = name.as_str()))
+            .await
+        {
+            Ok(applied) => stores.config_maps.record_write(namespace, &name, Some(&applied)),
+            Err(e) => {
+                return child_resource_failed(bot, ctx, namespace, &mut conditions, CONFIG_MAP_READY_CONDITION, "ConfigMap", "Apply", e).await;
+            },
+        }
+    }
+    conditions.set(applied_condition(CONFIG_MAP_READY_CONDITION));
+
+    // If the PVC is enabled, apply the PVC if it is None or different from the PVC object
+    // If the PVC is not enabled, delete the PVC if it exists
+    if hub.spec.pvc.enabled {
+        let pvc = match pvc {
+            Some(pvc) if !ResourceDrift::<Bot>::has_drifted(&pvc, &pvc_object) => pvc,
+            _ => {
+                info!(
+                    event = "ApplyingPVC",
+                    bot = bot.name_any().as_str()
+                );
+                ctx.publish_event(bot, EventType::Normal, "ApplyingPVC", "Apply", Some(format!("Applying PersistentVolumeClaim `{}`", name))).await;
+                match apply(&pvc_api, pvc_object, &name)
+                    .instrument(info_span!("apply", resource = "PersistentVolumeClaim", resource_name = name.as_str()))
+                    .await
+                {
+                    Ok(applied) => {
+                        stores.pvcs.record_write(namespace, &name, Some(&applied));
+                        applied
+                    },
+                    Err(e) => {
+                        return child_resource_failed(bot, ctx, namespace, &mut conditions, PVC_BOUND_CONDITION, "PersistentVolumeClaim", "Apply", e).await;
+                    },
+                }
+            },
+        };
+        conditions.set(pvc_bound_condition(&pvc));
+    } else {
+        if pvc.is_some() {
+            info!(
+                event = "DeletingPVC",
+                bot = bot.name_any().as_str()
+            );
+            ctx.publish_event(bot, EventType::Normal, "DeletingPVC", "Delete", Some(format!("Deleting PersistentVolumeClaim `{}`", name))).await;
+            if let Err(e) = delete(&pvc_api, &name, &DeleteParams::default()).await {
+                return child_resource_failed(bot, ctx, namespace, &mut conditions, PVC_BOUND_CONDITION, "PersistentVolumeClaim", "Delete", e).await;
+            }
+            stores.pvcs.record_write(namespace, &name, None);
+        }
+        conditions.remove(PVC_BOUND_CONDITION);
+    }
+
+    // If the Deployment is None, OR if the Deployment spec is different from the Deployment object spec,
+    // apply the changes
+    if deployment.as_ref().is_none_or(|deployment| ResourceDrift::<Bot>::has_drifted(deployment, &deployment_object)) {
+        info!(
+            event = "ApplyingDeployment",
+            bot = bot.name_any().as_str()
+        );
+        ctx.publish_event(bot, EventType::Normal, "ApplyingDeployment", "Apply", Some(format!("Applying Deployment `{}`", name))).await;
+
+        // The API server rejects a Recreate strategy next to the rolling update parameters it
+        // defaulted for RollingUpdate, and an apply leaves fields it doesn't own in place
+        let strategy_type = deployment_update_strategy(&hub).to_string();
+        if deployment.as_ref().is_some_and(|deployment| deployment_strategy_type(deployment) != strategy_type) {
+            match patch(&deployment_api, &name, &Patch::Merge(json!({
+                "spec": {
+                    "strategy": {
+                        "type": strategy_type,
+                        "rollingUpdate": null,
+                    }
+                }
+            }))).await {
+                Ok(patched) => stores.deployments.record_write(namespace, &name, Some(&patched)),
+                Err(e) => {
+                    return child_resource_failed(bot, ctx, namespace, &mut conditions, DEPLOYMENT_APPLIED_CONDITION, "Deployment", "Apply", e).await;
+                },
+            }
+        }
+        match apply_with_conflict_policy(&deployment_api, deployment_object, &name, &conflict_policy)
+            .instrument(info_span!("apply", resource = "Deployment", resource_name = name.as_str()))
+            .await
+        {
+            Ok(applied) => {
+                stores.deployments.record_write(namespace, &name, Some(&applied));
+                deployment = Some(applied);
+            },
+            Err(ControllerError::ConflictError(message)) => {
+                return adoption_blocked(bot, ctx, namespace, &mut conditions, "Deployment", message).await;
+            },
+            Err(e) => {
+                return child_resource_failed(bot, ctx, namespace, &mut conditions, DEPLOYMENT_APPLIED_CONDITION, "Deployment", "Apply", e).await;
+            },
+        }
+    }
+    conditions.set(applied_condition(DEPLOYMENT_APPLIED_CONDITION));
+
+    // The FreqUI resources are independent of the bot's rollout
+    match reconcile_ui(
+        &hub,
+        &ctx.client,
+        stores,
+        &name,
+        namespace,
+        owner_ref,
+        &app_config
+    )
+        .instrument(info_span!("apply", resource = "FreqUI", resource_name = name.as_str()))
+        .await?
+    {
+        Some(condition) => conditions.set(condition),
+        None => conditions.remove(UI_READY_CONDITION),
+    }
+
+    // An empty hash would never roll the bot on a config change, so a failure to compute it is
+    // reported like a failure to serialize the config
+    // The boxed error isn't Send, so it's converted before awaiting anything
+    let incoming_config_hash = if config_hash_inputs.is_empty() {
+        compute_object_hash(&config_map_object.data)
+    } else {
+        compute_object_hash(&(&config_map_object.data, &config_hash_inputs))
+    }
+    .map_err(|e| match e.downcast::<serde_json::Error>() {
+        Ok(e) => ControllerError::SerializationError(*e),
+        Err(e) => ControllerError::UnknownError(e.to_string()),
+    });
+    let incoming_config_hash = match incoming_config_hash {
+        Ok(hash) => hash,
+        Err(e) => return serialization_failed(bot, ctx, namespace, &mut conditions, &e).await,
+    };
+
+    // If the current and incoming config hashes differ, cause a rollout for the deployment and patch the annotation
+    if current_config_hash != incoming_config_hash {
+        let patched = patch(&deployment_api, &name, &Patch::Merge(json!({
+            "metadata": {
+                "annotations": {
+                    CONFIG_HASH_ANNOTATION: incoming_config_hash,
+                }
+            }
+        }))).await?;
+        stores.deployments.record_write(namespace, &name, Some(&patched));
+
+        // A suspended bot has no pods to roll, it starts with the new config once resumed
+        if !current_config_hash.is_empty() && !hub.spec.suspend {
+            info!(
+                event = "RollingOutDeployment",
+                bot = bot.name_any().as_str()
+            );
+            ctx.publish_event(bot, EventType::Normal, "RollingOutDeployment", "Rollout", Some(format!("Rolling out Deployment `{}` after a configuration change", name))).await;
+            let rolled = rollout(&deployment_api, &name).await?;
+            stores.deployments.record_write(namespace, &name, Some(&rolled));
+        }
+    }
+
+    // The values of the referenced Secrets are only read when the bot opts in to rolling on their changes
+    if hub.spec.secrets.rollout_on_change {
+        let current_secrets_hash = deployment
+            .as_ref()
+            .and_then(|d| d.metadata.annotations.as_ref())
+            .and_then(|annotations| annotations.get(SECRETS_HASH_ANNOTATION))
+            .cloned()
+            .unwrap_or_default();
+        let incoming_secrets_hash = referenced_secrets_hash(&hub, &secrets)?;
+
+        if current_secrets_hash != incoming_secrets_hash {
+            let patched = patch(&deployment_api, &name, &Patch::Merge(json!({
+                "metadata": {
+                    "annotations": {
+                        SECRETS_HASH_ANNOTATION: incoming_secrets_hash,
+                    }
+                }
+            }))).await?;
+            stores.deployments.record_write(namespace, &name, Some(&patched));
+
+            if !current_secrets_hash.is_empty() && !hub.spec.suspend {
+                info!(
+                    event = "RollingOutDeployment",
+                    bot = bot.name_any().as_str()
+                );
+                ctx.publish_event(bot, EventType::Normal, "RollingOutDeployment", "Rollout", Some(format!("Rolling out Deployment `{}` after a referenced Secret change", name))).await;
+                let rolled = rollout(&deployment_api, &name).await?;
+                stores.deployments.record_write(namespace, &name, Some(&rolled));
+            }
+        }
+    }
+
+    // The bot only reads its API credentials on start, so it is rolled once they are regenerated.
+    // The Secret is only ever replaced when deleted, which gives it a new UID.
+    if let Some(incoming_credentials_uid) = api_credentials_uid {
+        let current_credentials_uid = deployment
+            .as_ref()
+            .and_then(|d| d.metadata.annotations.as_ref())
+            .and_then(|annotations| annotations.get(API_CREDENTIALS_UID_ANNOTATION))
+            .cloned()
+            .unwrap_or_default();
+
+        if current_credentials_uid != incoming_credentials_uid {
+            let patched = patch(&deployment_api, &name, &Patch::Merge(json!({
+                "metadata": {
+                    "annotations": {
+                        API_CREDENTIALS_UID_ANNOTATION: incoming_credentials_uid,
+                    }
+                }
+            }))).await?;
+            stores.deployments.record_write(namespace, &name, Some(&patched));
+
+            if !current_credentials_uid.is_empty() && !hub.spec.suspend {
+                info!(
+                    event = "RollingOutDeployment",
+                    bot = bot.name_any().as_str()
+                );
+                ctx.publish_event(bot, EventType::Normal, "RollingOutDeployment", "Rollout", Some(format!("Rolling out Deployment `{}` after its API credentials were regenerated", name))).await;
+                let rolled = rollout(&deployment_api, &name).await?;
+                stores.deployments.record_write(namespace, &name, Some(&rolled));
+            }
+        }
+    }
+
+    // A restart is requested by changing the annotation's value, which is mirrored on the
+    // Deployment so that the same value only restarts the bot once
+    let requested_restarted_at = bot.annotations().get(RESTARTED_AT_ANNOTATION).cloned().unwrap_or_default();
+    // A Deployment that was just created or is scaled to zero has no pods to restart
+    let restart = restart_request(&requested_restarted_at, &current_restarted_at, !current_config_hash.is_empty() && !hub.spec.suspend);
+    if restart != RestartRequest::Handled {
+        let patched = patch(&deployment_api, &name, &Patch::Merge(json!({
+            "metadata": {
+                "annotations": {
+                    RESTARTED_AT_ANNOTATION: requested_restarted_at,
+                }
+            }
+        }))).await?;
+        stores.deployments.record_write(namespace, &name, Some(&patched));
+
+        if restart == RestartRequest::Restart {
+            info!(
+                event = "RestartRequested",
+                bot = bot.name_any().as_str()
+            );
+            ctx.publish_event(bot, EventType::Normal, "RestartRequested", "Rollout", Some(format!("Restarting Deployment `{}` as requested by the `{}` annotation", name, RESTARTED_AT_ANNOTATION))).await;
+            let rolled = rollout(&deployment_api, &name).await?;
+            stores.deployments.record_write(namespace, &name, Some(&rolled));
+        }
+    }
+
+    // If the API is enabled, apply the Service if it is None or different from the Service object
+    // If the API is not enabled, delete the Service if it exists
+    if hub.spec.api.enabled {
+        if service.as_ref().is_none_or(|service| ResourceDrift::<Bot>::has_drifted(service, &service_object)) {
+            info!(
+                event = "ApplyingService",
+                bot = bot.name_any().as_str()
+            );
+            ctx.publish_event(bot, EventType::Normal, "ApplyingService", "Apply", Some(format!("Applying Service `{}`", name))).await;
+            match apply_with_conflict_policy(&service_api, service_object, &name, &conflict_policy)
+                .instrument(info_span!("apply", resource = "Service", resource_name = name.as_str()))
+                .await
+            {
+                Ok(applied) => {
+                    stores.services.record_write(namespace, &name, Some(&applied));
+                    service = Some(applied);
+                },
+                Err(ControllerError::ConflictError(message)) => {
+                    return adoption_blocked(bot, ctx, namespace, &mut conditions, "Service", message).await;
+                },
+                Err(e) => {
+                    return child_resource_failed(bot, ctx, namespace, &mut conditions, SERVICE_READY_CONDITION, "Service", "Apply", e).await;
+                },
+            }
+        }
+        conditions.set(applied_condition(SERVICE_READY_CONDITION));
+    } else {
+        if service.is_some() {
+            info!(
+                event = "DeletingService",
+                bot = bot.name_any().as_str()
+            );
+            ctx.publish_event(bot, EventType::Normal, "DeletingService", "Delete", Some(format!("Deleting Service `{}`", name))).await;
+            if let Err(e) = delete(&service_api, &name, &DeleteParams::default()).await {
+                return child_resource_failed(bot, ctx, namespace, &mut conditions, SERVICE_READY_CONDITION, "Service", "Delete", e).await;
+            }
+            stores.services.record_write(namespace, &name, None);
+        }
+        conditions.remove(SERVICE_READY_CONDITION);
+    }
+
+    // Both the Deployment and Service were applied, so nothing blocks the adoption anymore
+    conditions.remove(ADOPTION_BLOCKED_CONDITION);
+
+    if hub.spec.suspend {
+        conditions.set(Condition {
+            type_: SUSPENDED_CONDITION.to_string(),
+            status: "True".to_string(),
+            reason: Some("SuspendedBySpec".to_string()),
+            message: Some("The bot is scaled to zero by spec.suspend".to_string()),
+            last_transition_time: None,
+        });
+    } else {
+        conditions.remove(SUSPENDED_CONDITION);
+    }
+
+    // The phase is derived from the Deployment's conditions, and the status is only patched
+    // when it changed
+    let progress_deadline_seconds = deployment
+        .as_ref()
+        .and_then(|deployment| deployment.spec.as_ref())
+        .and_then(|spec| spec.progress_deadline_seconds);
+    let deployment_status = deployment.and_then(|deployment| deployment.status).unwrap_or_default();
+    let pods = ctx.child_stores.pods
+        .list(&Api::<Pod>::namespaced(ctx.client.clone(), namespace), namespace, &bot_pod_labels(&name))
+        .await?;
+
+    set_workload_conditions(&mut conditions, &deployment_status, progress_deadline_seconds, &pods);
+
+    // An available Deployment only means the pod is up, freqtrade may still be stuck failing to
+    // start inside it, so the bot is only running once its API answers. A failed ping is
+    // reported rather than failing the reconcile.
+    if hub.spec.api.enabled && hub.spec.api.health_check && conditions.is_true(DEPLOYMENT_AVAILABLE_CONDITION) {
+        let ping = ping_bot(&hub, &ctx.client, &ctx.http, &name, namespace, HEALTH_CHECK_TIMEOUT).await;
+        if let Err(reason) = &ping {
+            info!(
+                event = "ApiUnhealthy",
+                bot = bot.name_any().as_str(),
+                reason = reason.as_str()
+            );
+        }
+        set_api_health(&mut conditions, ping);
+    } else {
+        conditions.remove(API_HEALTHY_CONDITION);
+    }
+
+    // The version is only read again once the pod template changed, rather than on every
+    // requeue. A failure leaves the previous version and is retried on the next reconcile.
+    let (mut freqtrade_version, mut version_pod_template_hash) = hub.status
+        .as_ref()
+        .map(|status| (status.freqtrade_version.clone(), status.version_pod_template_hash.clone()))
+        .unwrap_or_default();
+    let pod_template_hash = newest_running_pod(&pods).and_then(|pod| pod.labels().get("pod-template-hash").cloned());
+    if conditions.is_true(API_HEALTHY_CONDITION) {
+        if pod_template_hash.is_some() && pod_template_hash != version_pod_template_hash {
+            match bot_version(&hub, &ctx.client, &ctx.http, &name, namespace, HEALTH_CHECK_TIMEOUT).await {
+                Ok(version) => {
+                    conditions.set(freqtrade_version_condition("True", "VersionReported", None));
+                    freqtrade_version = Some(version);
+                    version_pod_template_hash = pod_template_hash;
+                },
+                Err(reason) => {
+                    info!(
+                        event = "FreqtradeVersionUnavailable",
+                        bot = bot.name_any().as_str(),
+                        reason = reason.as_str()
+                    );
+                    conditions.set(freqtrade_version_condition("False", "VersionUnavailable", Some(reason)));
+                },
+            }
+        }
+    } else if !hub.spec.api.enabled || !hub.spec.api.health_check {
+        conditions.remove(FREQTRADE_VERSION_CONDITION);
+        freqtrade_version = None;
+        version_pod_template_hash = None;
+    }
+    // The trades are queried at most once per reporting interval, however often the bot is
+    // reconciled. A failure keeps the previous summary and is retried on the next reconcile.
+    let reporting = &hub.spec.status_reporting;
+    let reporting_interval = TimeDelta::seconds(reporting.interval_seconds.max(MIN_STATUS_REPORTING_INTERVAL_SECONDS) as i64);
+    let trades_reported_at = hub.status.as_ref().and_then(|status| status.trades_reported_at);
+    let trading_summary = if reporting.enabled && hub.spec.api.enabled {
+        let due = trades_reported_at.is_none_or(|reported_at| Utc::now() - reported_at >= reporting_interval);
+
+        match due && conditions.is_true(DEPLOYMENT_AVAILABLE_CONDITION) {
+            true => match bot_trading_summary(&hub, &ctx.client, &ctx.http, &name, namespace, HEALTH_CHECK_TIMEOUT).await {
+                Ok(summary) => Some(summary),
+                Err(reason) => {
+                    info!(
+                        event = "TradingSummaryUnavailable",
+                        bot = bot.name_any().as_str(),
+                        reason = reason.as_str()
+                    );
+                    None
+                },
+            },
+            false => None,
+        }
+    } else {
+        // A summary left from when the reporting was enabled is cleared once
+        trades_reported_at.map(|_| TradingSummary::default())
+    };
+
+    let workload = BotWorkload {
+        ready_replicas: deployment_status.ready_replicas,
+        updated_replicas: deployment_status.updated_replicas,
+        pod_name: newest_running_pod(&pods).and_then(|pod| pod.metadata.name.clone()),
+        applied_config_hash: Some(incoming_config_hash),
+        strategy_hash: strategy_source.and_then(|source| compute_object_hash(&source).ok()),
+        api_credentials_secret,
+        api_endpoint: service.as_ref().filter(|_| hub.spec.api.enabled).and_then(api_endpoint),
+        freqtrade_version,
+        version_pod_template_hash,
+        trading_summary,
+    };
+    update_status(bot, ctx, namespace, &BotPhaseStatus::from(&conditions), &conditions, Some(&workload)).await?;
+
+    Ok(requeue(bot, ctx, Duration::from_secs(30)))
+}
+
+/// Cleanup the bot resource
+/// 
+/// This function is responsible for stopping the bot through its API when
+/// `spec.lifecycle.stopOnDelete` is set, once its open trades are closed, deleting its
+/// Deployment and waiting for its pods to terminate before its Service, and cleaning up the
+/// bot's PersistentVolumeClaim according to its deletion policy before the finalizer is removed.
+/// A deleted PVC is waited on until it is gone, so a PVC stuck terminating keeps the bot around
+/// with the `Deleting` condition explaining why, instead of silently relying on garbage
+/// collection. The other resources are garbage collected through their owner reference.
+///
+/// The waits requeue the bot rather than holding a reconcile slot, the step waited on and when
+/// it started are kept in the `Deleting` condition so the timeouts hold across reconciles.
+/// 
+/// # Arguments
+/// * `bot` - The bot resource to cleanup
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the bot resource
+/// 
+/// # Returns
+/// An action to requeue the bot while a step is waited on, or None once it is cleaned up
+async fn cleanup_bot<T>(bot: &T, ctx: &Context, namespace: &str) -> Result<Option<Action>>
+where
+    T: NamespacedCustomResource,
+    Bot: From<T>,
+{
+    let pvc_api = Api::<PersistentVolumeClaim>::namespaced(ctx.client.clone(), namespace);
+    let mut hub = Bot::from(bot.clone());
+    let name = hub.child_name();
+    let mut conditions = Conditions::new(hub.status.clone().and_then(|status| status.conditions));
+
+    // The bot is stopped with its generated API credentials when it has no API secrets
+    if generates_api_credentials(&hub) {
+        hub.spec.secrets.api = Some(generated_api_secrets(&api_credentials_secret_name(&name)));
+    }
+
+    // The bot is stopped before its Deployment is deleted so it isn't torn down mid-trade. A
+    // retried cleanup has moved past this step already.
+    let stopping = conditions.get(DELETING_CONDITION).is_none_or(|condition| condition.reason.as_deref() == Some("StoppingBot"));
+    if hub.spec.lifecycle.stop_on_delete
+        && hub.spec.api.enabled
+        && stopping
+        && let Some(action) = stop_deleted_bot(bot, &hub, ctx, namespace, &name, &mut conditions).await?
+    {
+        return Ok(Some(action));
+    }
+
+    // The Deployment is deleted before the Service and its pods are waited on, so no pod keeps
+    // trading once the bot is gone. Only the resources owned by the bot are deleted.
+    let uid = bot.uid().unwrap_or_default();
+    let propagation = ctx.controller_config().deletion_propagation;
+    let deployment_api = Api::<Deployment>::namespaced(ctx.client.clone(), namespace);
+    let service_api = Api::<Service>::namespaced(ctx.client.clone(), namespace);
+    let pod_api = Api::<Pod>::namespaced(ctx.client.clone(), namespace);
+
+    let deployment = deployment_api.get_opt(&name).await?.filter(|deployment| {
+        deployment.owner_references().iter().any(|owner| owner.uid == uid)
+    });
+    if deployment.is_some_and(|deployment| deployment.metadata.deletion_timestamp.is_none()) {
+        info!(
+            event = "DeletingDeployment",
+            bot = name.as_str()
+        );
+        delete(&deployment_api, &name, &delete_params(&propagation.deployment)).await?;
+    }
+
+    // The pods are waited on by requeuing the bot, up to the cleanup timeout from when the wait
+    // started
+    let timeout = ctx.controller_config().cleanup_timeout_seconds;
+    let pods = pod_api.list(&ListParams::default().labels(&bot_pod_selector(&name))).await?.items;
+    if !pods.is_empty() {
+        let message = format!("Waiting for the pods of bot `{}` to terminate", name);
+        conditions.set_step(deleting_condition("WaitingForPods", &message));
+
+        if step_elapsed(&conditions) >= TimeDelta::seconds(timeout as i64) {
+            let message = format!(
+                "{} pods of bot `{}` are still running after {}s: [{}]",
+                pods.len(),
+                name,
+                timeout,
+                pods.iter().map(|pod| pod.name_any()).collect::<Vec<_>>().join(", "),
+            );
+            conditions.set(deleting_condition("PodTerminationTimeout", &message));
+            update_status(bot, ctx, namespace, &BotPhaseStatus::from(&conditions), &conditions, None).await?;
+            ctx.publish_event(bot, EventType::Warning, "PodTerminationTimeout", "Delete", Some(message.clone())).await;
+
+            return Err(ControllerError::CleanupTimeoutError(message));
+        }
+
+        update_status(bot, ctx, namespace, &BotPhaseStatus::from(&conditions), &conditions, None).await?;
+        return Ok(Some(requeue(bot, ctx, Duration::from_secs(2))));
+    }
+
+    let service = service_api.get_opt(&name).await?.filter(|service| {
+        service.owner_references().iter().any(|owner| owner.uid == uid)
+    });
+    if service.is_some_and(|service| service.metadata.deletion_timestamp.is_none()) {
+        info!(
+            event = "DeletingService",
+            bot = name.as_str()
+        );
+        delete(&service_api, &name, &delete_params(&propagation.service)).await?;
+    }
+
+    // Only the PVC owned by the bot is cleaned up, one it doesn't own was never its to delete. A
+    // PVC gone since the last reconcile was waited on.
+    let waited_for_pvc = conditions.get(DELETING_CONDITION).is_some_and(|condition| condition.reason.as_deref() == Some("WaitingForPVC"));
+    let pvc = pvc_api.get_opt(&name).await?.filter(|pvc| {
+        pvc.owner_references().iter().any(|owner| owner.uid == uid)
+    });
+
+    if let Some(pvc) = pvc {
+        match hub.spec.pvc.deletion_policy {
+            PvcDeletionPolicy::Retain => {
+                info!(
+                    event = "RetainingPVC",
+                    bot = name.as_str()
+                );
+                ctx.publish_event(bot, EventType::Normal, "RetainingPVC", "Orphan", Some(format!("Retaining PersistentVolumeClaim `{}`", name))).await;
+
+                // Dropping the owner reference keeps the garbage collector from deleting the PVC
+                let owner_references: Vec<OwnerReference> = pvc.owner_references()
+                    .iter()
+                    .filter(|owner| owner.uid != uid)
+                    .cloned()
+                    .collect();
+                patch(&pvc_api, &name, &Patch::Merge(json!({
+                    "metadata": {
+                        "ownerReferences": owner_references
+                    }
+                }))).await?;
+            },
+            PvcDeletionPolicy::Delete => {
+                if pvc.metadata.deletion_timestamp.is_none() {
+                    info!(
+                        event = "DeletingPVC",
+                        bot = name.as_str()
+                    );
+                    ctx.publish_event(bot, EventType::Normal, "DeletingPVC", "Delete", Some(format!("Deleting PersistentVolumeClaim `{}`", name))).await;
+                    delete(&pvc_api, &name, &DeleteParams::default()).await?;
+                }
+
+                // The PVC is waited on like the pods
+                let message = format!("Waiting for PersistentVolumeClaim `{}` to be deleted", name);
+                conditions.set_step(deleting_condition("WaitingForPVC", &message));
+
+                if step_elapsed(&conditions) >= TimeDelta::seconds(timeout as i64) {
+                    let message = format!(
+                        "PersistentVolumeClaim `{}` is still terminating after {}s, its finalizers are [{}]",
+                        name,
+                        timeout,
+                        pvc.finalizers().join(", "),
+                    );
+                    conditions.set(deleting_condition("PVCDeletionTimeout", &message));
+                    update_status(bot, ctx, namespace, &BotPhaseStatus::from(&conditions), &conditions, None).await?;
+                    ctx.publish_event(bot, EventType::Warning, "PVCDeletionTimeout", "Delete", Some(message.clone())).await;
+
+                    // Failing the cleanup keeps the finalizer, the cleanup is retried with a backoff
+                    return Err(ControllerError::CleanupTimeoutError(message));
+                }
+
+                update_status(bot, ctx, namespace, &BotPhaseStatus::from(&conditions), &conditions, None).await?;
+                return Ok(Some(requeue(bot, ctx, Duration::from_secs(2))));
+            },
+        }
+    } else if waited_for_pvc {
+        info!(
+            event = "DeletedPVC",
+            bot = name.as_str()
+        );
+        ctx.publish_event(bot, EventType::Normal, "DeletedPVC", "Delete", Some(format!("Deleted PersistentVolumeClaim `{}`", name))).await;
+    }
+
+    Ok(None)
+}
+
+/// Report the bot's resources as cleaned up, before its finalizer is removed
+///
+/// # Arguments
+/// * `bot` - The bot resource cleaned up
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the bot resource
+///
+/// # Returns
+/// An action to await the bot's deletion
+async fn cleaned_up<T>(bot: &T, ctx: &Context, namespace: &str) -> Result<Action>
+where
+    T: NamespacedCustomResource,
+    Bot: From<T>,
+{
+    let hub = Bot::from(bot.clone());
+    let mut conditions = Conditions::new(hub.status.and_then(|status| status.conditions));
+    conditions.set(deleting_condition("CleanedUp", "The bot's resources are cleaned up"));
+    update_status(bot, ctx, namespace, &BotPhaseStatus::from(&conditions), &conditions, None).await?;
+
+    // The series of a deleted bot would otherwise be exposed until the controller restarts
+    ctx.remove_bot_metrics(namespace, &bot.name_any());
+
+    Ok(Action::await_change())
+}
+
+/// How long the current step of the bot's cleanup has been running
+///
+/// # Arguments
+/// * `conditions` - The conditions of the bot, with the `Deleting` condition set with `set_step`
+fn step_elapsed(conditions: &Conditions) -> TimeDelta {
+    conditions.step_started(DELETING_CONDITION).map_or(TimeDelta::zero(), |started| Utc::now() - started)
+}
+
+/// Stop a bot being deleted through its API
+///
+/// Its entries are stopped first, then its open trades are waited on for up to
+/// `spec.lifecycle.stopTimeoutSeconds` by requeuing the bot, and the bot is stopped once they
+/// are closed. The time the entries were stopped is kept in the `Deleting` condition, so the
+/// timeout holds across reconciles. An unreachable API or trades still open after the timeout
+/// don't block the deletion, they are reported instead.
+///
+/// # Arguments
+/// * `bot` - The bot resource
+/// * `hub` - The Bot Hub, with the API secrets to stop the bot with
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the bot resource
+/// * `name` - The name of the bot's resources
+/// * `conditions` - The conditions of the bot resource
+///
+/// # Returns
+/// An action to requeue the bot while its trades are open, or None once it is stopped
+async fn stop_deleted_bot<T>(
+    bot: &T,
+    hub: &Bot,
+    ctx: &Context,
+    namespace: &str,
+    name: &str,
+    conditions: &mut Conditions,
+) -> Result<Option<Action>>
+where
+    T: NamespacedCustomResource,
+    Bot: From<T>,
+{
+    let timeout = hub.spec.lifecycle.stop_timeout_seconds;
+
+    // The entries are stopped once, the open trades are then waited on
+    let mut stopped = match conditions.get(DELETING_CONDITION) {
+        Some(_) => Ok(()),
+        None => stop_entries(hub, &ctx.client, &ctx.http, name, namespace).await,
+    };
+    if stopped.is_ok() && conditions.get(DELETING_CONDITION).is_none() {
+        info!(
+            event = "StoppedEntries",
+            bot = name
+        );
+        conditions.set_step(deleting_condition("StoppingBot", &format!("Stopped the entries of bot `{}`, waiting for its open trades to close", name)));
+        update_status(bot, ctx, namespace, &BotPhaseStatus::from(&*conditions), conditions, None).await?;
+    }
+
+    if stopped.is_ok() {
+        let elapsed = step_elapsed(conditions);
+
+        stopped = match open_trades(hub, &ctx.client, &ctx.http, name, namespace).await {
+            Ok(0) => stop_bot(hub, &ctx.client, &ctx.http, name, namespace).await,
+            Ok(open) if elapsed < TimeDelta::seconds(timeout as i64) => {
+                conditions.set_step(deleting_condition("StoppingBot", &format!("Waiting for the {} open trades of bot `{}` to close", open, name)));
+                update_status(bot, ctx, namespace, &BotPhaseStatus::from(&*conditions), conditions, None).await?;
+                return Ok(Some(requeue(bot, ctx, Duration::from_secs(5))));
+            },
+            Ok(open) => Err(format!("{} trades are still open after {}s", open, timeout)),
+            Err(reason) => Err(reason),
+        };
+    }
+
+    match stopped {
+        Ok(()) => {
+            info!(
+                event = "StoppedBot",
+                bot = name
+            );
+            ctx.publish_event(bot, EventType::Normal, "StoppedBot", "Stop", Some(format!("Stopped bot `{}` with no open trades", name))).await;
+        },
+        Err(reason) => {
+            info!(
+                event = "StopBotFailed",
+                bot = name,
+                reason = reason.as_str()
+            );
+            ctx.publish_event(bot, EventType::Warning, "StopBotFailed", "Stop", Some(format!("Could not stop bot `{}` cleanly: {}", name, reason))).await;
+        },
+    }
+
+    Ok(None)
+}
+
+/// Report that a resource of the bot couldn't be applied over an existing object
+///
+/// The conflict is surfaced in the AdoptionBlocked condition, the status message and a Warning
+/// Event, and the bot is requeued so it recovers once the existing object is removed or released.
+///
+/// # Arguments
+/// * `bot` - The bot resource
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the bot resource
+/// * `conditions` - The conditions of the bot resource
+/// * `kind` - The kind of the conflicting resource
+/// * `message` - The details of the conflict
+///
+/// # Returns
+/// An action to requeue the bot resource
+async fn adoption_blocked<T>(
+    bot: &T,
+    ctx: &Context,
+    namespace: &str,
+    conditions: &mut Conditions,
+    kind: &str,
+    message: String,
+) -> Result<Action>
+where
+    T: NamespacedCustomResource,
+    Bot: From<T>,
+{
+    let message = format!("{} {}", kind, message);
+
+    info!(
+        event = "AdoptionBlocked",
+        bot = bot.name_any().as_str(),
+        message = message.as_str()
+    );
+    ctx.publish_event(bot, EventType::Warning, "AdoptionBlocked", "Apply", Some(message.clone())).await;
+
+    conditions.set(Condition {
+        type_: ADOPTION_BLOCKED_CONDITION.to_string(),
+        status: "True".to_string(),
+        reason: Some(format!("{}Conflict", kind)),
+        message: Some(message.clone()),
+        last_transition_time: None,
+    });
+    conditions.set(degraded_condition("AdoptionBlocked", Some(message)));
+    update_status(bot, ctx, namespace, &BotPhaseStatus::from(&*conditions), conditions, None).await?;
+
+    Ok(requeue(bot, ctx, Duration::from_secs(30)))
+}
+
+/// Report that a child resource of the bot couldn't be applied or deleted
+///
+/// The API server's message is surfaced in the resource's condition, the status message and a
+/// Warning Event, and the error is returned so the bot is requeued with a backoff. The next
+/// successful apply or delete clears the condition.
+///
+/// # Arguments
+/// * `bot` - The bot resource
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the bot resource
+/// * `conditions` - The conditions of the bot resource
+/// * `condition_type` - The condition reporting the resource
+/// * `kind` - The kind of the resource
+/// * `action` - Whether the resource was being applied or deleted, `Apply` or `Delete`
+/// * `error` - The error the API server responded with
+///
+/// # Returns
+/// The error, once it is reported
+#[allow(clippy::too_many_arguments)]
+async fn child_resource_failed<T>(
+    bot: &T,
+    ctx: &Context,
+    namespace: &str,
+    conditions: &mut Conditions,
+    condition_type: &str,
+    kind: &str,
+    action: &str,
+    error: ControllerError,
+) -> Result<Action>
+where
+    T: NamespacedCustomResource,
+    Bot: From<T>,
+{
+    // The API server's message says what is wrong without the client's wrapping of it
+    let message = match &error {
+        ControllerError::KubeError(kube::Error::Api(response)) => response.message.clone(),
+        error => error.to_string(),
+    };
+    let message = format!("{} of {} failed: {}", action, kind, message);
+    let reason = format!("{}Failed", action);
+
+    info!(
+        event = "ChildResourceFailed",
+        bot = bot.name_any().as_str(),
+        resource = kind,
+        message = message.as_str()
+    );
+    ctx.publish_event(bot, EventType::Warning, &format!("{}{}", kind, reason), action, Some(message.clone())).await;
+
+    conditions.set(Condition {
+        type_: condition_type.to_string(),
+        status: "False".to_string(),
+        reason: Some(reason.clone()),
+        message: Some(message.clone()),
+        last_transition_time: None,
+    });
+    conditions.set(degraded_condition(&reason, Some(message)));
+    update_status(bot, ctx, namespace, &BotPhaseStatus::from(&*conditions), conditions, None).await?;
+
+    Err(error)
+}
+
+/// Report that the config of the bot couldn't be serialized
+///
+/// The error is surfaced in the status message and a Warning Event instead of rolling out a
+/// broken ConfigMap or config hash, and the bot is requeued.
+///
+/// # Arguments
+/// * `bot` - The bot resource
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the bot resource
+/// * `conditions` - The conditions of the bot resource
+/// * `error` - The serialization error
+///
+/// # Returns
+/// An action to requeue the bot resource
+async fn serialization_failed<T>(
+    bot: &T,
+    ctx: &Context,
+    namespace: &str,
+    conditions: &mut Conditions,
+    error: &ControllerError,
+) -> Result<Action>
+where
+    T: NamespacedCustomResource,
+    Bot: From<T>,
+{
+    let message = format!("The bot's config could not be rendered: {}", error);
+
+    info!(
+        event = "SerializationFailed",
+        bot = bot.name_any().as_str(),
+        error = %error
+    );
+    ctx.publish_event(bot, EventType::Warning, "SerializationFailed", "Reconcile", Some(message.clone())).await;
+
+    conditions.set(degraded_condition("SerializationFailed", Some(message)));
+    update_status(bot, ctx, namespace, &BotPhaseStatus::from(&*conditions), conditions, None).await?;
+
+    Ok(requeue(bot, ctx, Duration::from_secs(30)))
+}
+
+/// Update the status of the bot resource
+/// 
+/// This function is responsible for updating the phase, conditions and workload of the bot
+/// resource. Nothing is patched when they are unchanged to avoid triggering the watch.
+/// 
+/// # Arguments
+/// * `bot` - The bot resource to update
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the bot resource
+/// * `phase` - The phase to set the bot resource to, along with its reason and message
+/// * `conditions` - The conditions of the bot resource
+/// * `workload` - The observed workload of the bot, left as is when None
+///
+/// # Returns
+/// A result indicating success or failure
+async fn update_status<T>(
+    bot: &T,
+    ctx: &Context,
+    namespace: &str,
+    phase: &BotPhaseStatus,
+    conditions: &Conditions,
+    workload: Option<&BotWorkload>,
+) -> Result<()>
+where
+    T: NamespacedCustomResource,
+    Bot: From<T>,
+{
+    let status = Bot::from(bot.clone()).status;
+    ctx.record_bot_phase(namespace, &bot.name_any(), &phase.phase);
+
+    if status.is_some_and(|status| {
+        status.phase == phase.phase.to_string()
+            && status.reason == phase.reason
+            && status.message == phase.message
+            && workload.is_none_or(|workload| workload.trading_summary.is_none() && *workload == BotWorkload::from(&status))
+    }) && !conditions.changed() {
+        return Ok(());
+    }
+
+    info!(
+        event = "UpdatingBotStatus",
+        bot = bot.name_any().as_str(),
+        status = phase.phase.to_string()
+    );
+
+    let mut status = json!({
+        "phase": phase.phase.to_string(),
+        "reason": phase.reason,
+        "message": phase.message,
+        "lastUpdated": Utc::now(),
+        "conditions": conditions.as_slice(),
+    });
+
+    // Unset fields are sent as null so the merge patch clears them
+    if let Some(workload) = workload {
+        status["readyReplicas"] = json!(workload.ready_replicas);
+        status["updatedReplicas"] = json!(workload.updated_replicas);
+        status["podName"] = json!(workload.pod_name);
+        status["appliedConfigHash"] = json!(workload.applied_config_hash);
+        status["strategyHash"] = json!(workload.strategy_hash);
+        status["apiCredentialsSecret"] = json!(workload.api_credentials_secret);
+        status["apiEndpoint"] = json!(workload.api_endpoint);
+        status["freqtradeVersion"] = json!(workload.freqtrade_version);
+        status["versionPodTemplateHash"] = json!(workload.version_pod_template_hash);
+
+        if let Some(summary) = workload.trading_summary.as_ref() {
+            status["openTrades"] = json!(summary.open_trades);
+            status["closedTrades"] = json!(summary.closed_trades);
+            status["profitAbs"] = json!(summary.profit_abs);
+            status["profitRatio"] = json!(summary.profit_ratio);
+            status["tradesReportedAt"] = json!(summary.reported_at);
+        }
+    }
+
+    patch_status(&Api::<T>::namespaced(ctx.client.clone(), namespace), &bot.name_any(), status).await?;
+
+    Ok(())
+}
+
+/// The settings of a bot's webhook notification whose changes must roll the bot
+///
+/// The URL is referenced from a Secret, only the plain settings are hashed.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+///
+/// # Returns
+/// The input of the config hash, None when the webhook notification isn't set
+fn webhook_notification_hash_input(bot: &Bot) -> Option<Value> {
+    bot.spec.notifications.webhook.as_ref().map(|webhook| json!({
+        "webhook": {
+            "enabled": webhook.enabled,
+            "format": webhook.format,
+            "retries": webhook.retries,
+        }
+    }))
+}
+
+/// The user managed ConfigMaps a bot mounts, along with the spec field referencing them
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+///
+/// # Returns
+/// The spec field, name and source key of each referenced ConfigMap
+pub fn referenced_config_maps(bot: &Bot) -> Vec<(&'static str, String, &'static str)> {
+    bot.spec.strategy.config_map_name
+        .clone()
+        .map(|name| ("strategy.configMapName", name, "strategy.py"))
+        .into_iter()
+        .chain(
+            bot.spec.model
+                .as_ref()
+                .and_then(|model| model.config_map_name.clone())
+                .map(|name| ("model.configMapName", name, "model.py"))
+        )
+        .collect()
+}
+
+/// Whether a bot mounts the given user managed ConfigMap
+///
+/// ConfigMaps can only be referenced from the same namespace.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+/// * `bot_namespace` - The namespace of the bot
+/// * `config_map_name` - The name of the ConfigMap
+/// * `config_map_namespace` - The namespace of the ConfigMap
+pub fn references_config_map(bot: &Bot, bot_namespace: &str, config_map_name: &str, config_map_namespace: &str) -> bool {
+    bot_namespace == config_map_namespace
+        && referenced_config_maps(bot).iter().any(|(_, name, _)| name == config_map_name)
+}
+
+/// The Secret keys a bot references from its secrets and notifications
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+pub fn referenced_secret_keys(bot: &Bot) -> Vec<&SecretKeyRef> {
+    let secrets = &bot.spec.secrets;
+    let exchange = secrets.exchange.iter().flat_map(|exchange| [&exchange.key, &exchange.secret, &exchange.password, &exchange.uid]);
+    let api = secrets.api.iter().flat_map(|api| [&api.username, &api.password, &api.ws_token, &api.jwt_secret_key]);
+    let telegram = secrets.telegram.iter().flat_map(|telegram| [&telegram.token, &telegram.chat_id]);
+    let webhook_urls = [&secrets.discord, &secrets.slack]
+        .into_iter()
+        .flatten()
+        .map(|webhook| &webhook.webhook_url)
+        .chain(bot.spec.notifications.webhook.as_ref().map(|webhook| &webhook.url));
+
+    exchange
+        .chain(api)
+        .chain(telegram)
+        .flatten()
+        .chain(webhook_urls)
+        .filter_map(|item| match item {
+            SecretItem::SecretKeyRef { secret_key_ref } => Some(secret_key_ref),
+            SecretItem::Value { .. } => None,
+        })
+        .collect()
+}
+
+/// Whether a bot references the given Secret
+///
+/// Secrets can only be referenced from the same namespace.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+/// * `bot_namespace` - The namespace of the bot
+/// * `secret_name` - The name of the Secret
+/// * `secret_namespace` - The namespace of the Secret
+pub fn references_secret(bot: &Bot, bot_namespace: &str, secret_name: &str, secret_namespace: &str) -> bool {
+    bot_namespace == secret_namespace
+        && referenced_secret_keys(bot).iter().any(|secret_key_ref| secret_key_ref.name == secret_name)
+}
+
+/// Read the Secrets a bot references, once per Secret however many of its keys are referenced
+///
+/// The Secrets are read through the Secret store, so an unchanged Secret isn't read again.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the bot
+///
+/// # Returns
+/// A Result containing each referenced Secret by name, or None when it doesn't exist
+async fn referenced_secrets(bot: &Bot, ctx: &Context, namespace: &str) -> Result<BTreeMap<String, Option<Secret>>> {
+    let secret_api = Api::<Secret>::namespaced(ctx.client.clone(), namespace);
+    let mut secrets = BTreeMap::new();
+
+    for secret_key_ref in referenced_secret_keys(bot) {
+        if !secrets.contains_key(&secret_key_ref.name) {
+            let secret = ctx.child_stores.secrets.get(&secret_api, namespace, &secret_key_ref.name).await?;
+            secrets.insert(secret_key_ref.name.clone(), secret);
+        }
+    }
+
+    Ok(secrets)
+}
+
+/// The value of a Secret key a bot references, if the Secret and key exist
+///
+/// # Arguments
+/// * `secrets` - The referenced Secrets, as read by `referenced_secrets`
+/// * `secret_key_ref` - The reference to the key
+fn referenced_secret_value<'a>(secrets: &'a BTreeMap<String, Option<Secret>>, secret_key_ref: &SecretKeyRef) -> Option<&'a ByteString> {
+    secrets
+        .get(&secret_key_ref.name)
+        .and_then(|secret| secret.as_ref())
+        .and_then(|secret| secret.data.as_ref())
+        .and_then(|data| data.get(&secret_key_ref.key))
+}
+
+/// The Secrets and keys a bot references that don't exist
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+/// * `secrets` - The referenced Secrets, as read by `referenced_secrets`
+///
+/// # Returns
+/// A description of each missing Secret or key
+fn missing_secret_keys(bot: &Bot, secrets: &BTreeMap<String, Option<Secret>>) -> Vec<String> {
+    let mut missing: Vec<String> = referenced_secret_keys(bot)
+        .into_iter()
+        .filter(|secret_key_ref| referenced_secret_value(secrets, secret_key_ref).is_none())
+        .map(|secret_key_ref| match secrets.get(&secret_key_ref.name) {
+            Some(Some(_)) => format!("key `{}` of Secret `{}`", secret_key_ref.key, secret_key_ref.name),
+            _ => format!("Secret `{}`", secret_key_ref.name),
+        })
+        .collect();
+
+    missing.sort();
+    missing.dedup();
+    missing
+}
+
+/// Hash the values of the Secret keys a bot references
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+/// * `secrets` - The referenced Secrets, as read by `referenced_secrets`
+///
+/// # Returns
+/// A Result containing the hash of the referenced values
+fn referenced_secrets_hash(bot: &Bot, secrets: &BTreeMap<String, Option<Secret>>) -> Result<String> {
+    let values: BTreeMap<String, Option<&ByteString>> = referenced_secret_keys(bot)
+        .into_iter()
+        .map(|secret_key_ref| (
+            format!("{}/{}", secret_key_ref.name, secret_key_ref.key),
+            referenced_secret_value(secrets, secret_key_ref),
+        ))
+        .collect();
+
+    compute_object_hash(&values).map_err(|e| ControllerError::UnknownError(e.to_string()))
+}
+
+/// What to do about the restart requested with the `restartedAt` annotation of a Bot
+#[derive(Debug, PartialEq)]
+enum RestartRequest {
+    // No restart was requested, or the requested one was already handled
+    Handled,
+    // The value is recorded without a restart
+    Record,
+    // The value is recorded and the bot is restarted
+    Restart,
+}
+
+/// Decide what to do about the restart requested by a Bot
+///
+/// # Arguments
+/// * `requested` - The `restartedAt` annotation of the Bot, empty if not set
+/// * `handled` - The value last handled, mirrored on the Deployment
+/// * `has_pods` - Whether the Deployment existed and isn't scaled to zero
+fn restart_request(requested: &str, handled: &str, has_pods: bool) -> RestartRequest {
+    match (requested.is_empty() || requested == handled, has_pods) {
+        (true, _) => RestartRequest::Handled,
+        (false, true) => RestartRequest::Restart,
+        (false, false) => RestartRequest::Record,
+    }
+}
+
+/// The update strategy of the bot's Deployment
+///
+/// Defaults to `Recreate` when the PVC is enabled, since the ReadWriteOnce PVC can't be mounted by
+/// the new pod while the old one holds it, and to `RollingUpdate` otherwise.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+pub fn deployment_update_strategy(bot: &Bot) -> DeploymentUpdateStrategy {
+    bot.spec.deployment.update_strategy.clone().unwrap_or(match bot.spec.pvc.enabled {
+        true => DeploymentUpdateStrategy::Recreate,
+        false => DeploymentUpdateStrategy::RollingUpdate,
+    })
+}
+
+/// The strategy type of a Deployment, defaulting to `RollingUpdate` like the API server
+///
+/// # Arguments
+/// * `deployment` - The Deployment
+fn deployment_strategy_type(deployment: &Deployment) -> String {
+    deployment.spec
+        .as_ref()
+        .and_then(|spec| spec.strategy.as_ref())
+        .and_then(|strategy| strategy.type_.clone())
+        .unwrap_or_else(|| DeploymentUpdateStrategy::RollingUpdate.to_string())
+}
+
+/// The identifying labels of the bot's pods, the selector of its Deployment
+///
+/// # Arguments
+/// * `name` - The name of the bot
+pub fn bot_pod_labels(name: &str) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("freqtrade.io/bot-name".to_string(), name.to_string()),
+        ("app.kubernetes.io/name".to_string(), name.to_string()),
+        ("app.kubernetes.io/instance".to_string(), name.to_string()),
+    ])
+}
+
+/// The label selector of the bot's pods, matching the identifying labels of its Deployment
+///
+/// # Arguments
+/// * `name` - The name of the bot
+pub fn bot_pod_selector(name: &str) -> String {
+    label_selector(&bot_pod_labels(name))
+}
+
+/// The URL the API of a bot is reachable at through its Service
+///
+/// The LoadBalancer ingress is preferred once assigned, otherwise the Service's cluster DNS name
+/// is used.
+///
+/// # Arguments
+/// * `service` - The bot's Service
+///
+/// # Returns
+/// The URL, or None if the Service has no `api` port
+fn api_endpoint(service: &Service) -> Option<String> {
+    let port = service.spec
+        .as_ref()
+        .and_then(|spec| spec.ports.as_ref())
+        .and_then(|ports| ports.iter().find(|port| port.name.as_deref() == Some("api")))?
+        .port;
+    let ingress = service.status
+        .as_ref()
+        .and_then(|status| status.load_balancer.as_ref())
+        .and_then(|load_balancer| load_balancer.ingress.as_ref())
+        .and_then(|ingress| ingress.first())
+        .and_then(|ingress| ingress.hostname.clone().or_else(|| ingress.ip.clone()));
+    let host = match ingress {
+        Some(host) => host,
+        None => format!("{}.{}.svc", service.name_any(), service.namespace().unwrap_or_default()),
+    };
+
+    Some(format!("http://{}:{}", host, port))
+}
+
+/// The newest running pod, if any
+///
+/// Several pods can exist during a rollout, the newest one is the one running the latest spec.
+///
+/// # Arguments
+/// * `pods` - The pods of the bot
+fn newest_running_pod(pods: &[Pod]) -> Option<&Pod> {
+    pods.iter()
+        .filter(|pod| pod.status.as_ref().and_then(|status| status.phase.as_deref()) == Some("Running"))
+        .max_by_key(|pod| pod.metadata.creation_timestamp.clone())
+}
+
+/// Create the PVCBound condition from the bot's PersistentVolumeClaim
+///
+/// # Arguments
+/// * `pvc` - The PersistentVolumeClaim of the bot
+///
+/// # Returns
+/// The PVCBound condition
+fn pvc_bound_condition(pvc: &PersistentVolumeClaim) -> Condition {
+    let phase = pvc.status
+        .as_ref()
+        .and_then(|status| status.phase.clone())
+        .unwrap_or_else(|| "Pending".to_string());
+    let bound = phase == "Bound";
+
+    Condition {
+        type_: PVC_BOUND_CONDITION.to_string(),
+        status: if bound { "True" } else { "False" }.to_string(),
+        message: (!bound).then(|| format!("PersistentVolumeClaim is {}", phase)),
+        reason: Some(phase),
+        last_transition_time: None,
+    }
+}
+
+/// Create the DeploymentAvailable condition from the status of the bot's Deployment
+///
+/// # Arguments
+/// * `status` - The status of the bot's Deployment
+///
+/// # Returns
+/// The DeploymentAvailable condition, mirroring the Deployment's `Available` condition
+fn deployment_available_condition(status: &DeploymentStatus) -> Condition {
+    match status.conditions.iter().flatten().find(|c| c.type_ == "Available") {
+        Some(available) => Condition {
+            type_: DEPLOYMENT_AVAILABLE_CONDITION.to_string(),
+            status: available.status.clone(),
+            reason: available.reason.clone(),
+            message: available.message.clone(),
+            last_transition_time: None,
+        },
+        None => Condition {
+            type_: DEPLOYMENT_AVAILABLE_CONDITION.to_string(),
+            status: "Unknown".to_string(),
+            reason: Some("DeploymentPending".to_string()),
+            message: None,
+            last_transition_time: None,
+        },
+    }
+}
+
+/// Create the Degraded condition from the status of the bot's Deployment
+///
+/// A Deployment exceeding its progress deadline is told apart from the other failures to
+/// progress, since a bot that is slow to start only needs a longer deadline.
+///
+/// # Arguments
+/// * `status` - The status of the bot's Deployment
+/// * `progress_deadline_seconds` - The progress deadline of the bot's Deployment
+///
+/// # Returns
+/// The Degraded condition, `True` when the Deployment failed to progress
+fn deployment_degraded_condition(status: &DeploymentStatus, progress_deadline_seconds: Option<i32>) -> Condition {
+    match status.conditions.iter().flatten().find(|c| c.type_ == "Progressing" && c.status == "False") {
+        Some(progressing) if progressing.reason.as_deref() == Some("ProgressDeadlineExceeded") => degraded_condition(
+            "ProgressDeadlineExceeded",
+            Some(format!(
+                "The bot didn't become available within its progress deadline of {}s, raise spec.deployment.progressDeadlineSeconds if it is still starting",
+                progress_deadline_seconds.unwrap_or(DEFAULT_PROGRESS_DEADLINE_SECONDS),
+            )),
+        ),
+        Some(progressing) => degraded_condition(
+            progressing.reason.as_deref().unwrap_or("DeploymentNotProgressing"),
+            progressing.message.clone(),
+        ),
+        None => Condition {
+            type_: DEGRADED_CONDITION.to_string(),
+            status: "False".to_string(),
+            reason: Some("AsExpected".to_string()),
+            message: None,
+            last_transition_time: None,
+        },
+    }
+}
+
+/// Set the conditions derived from the bot's Deployment and pods
+///
+/// A stuck container explains a failure better than the Deployment's progress deadline, so it
+/// takes precedence in the Degraded condition.
+///
+/// # Arguments
+/// * `conditions` - The conditions of the bot
+/// * `status` - The status of the bot's Deployment
+/// * `progress_deadline_seconds` - The progress deadline of the bot's Deployment
+/// * `pods` - The pods of the bot
+fn set_workload_conditions(conditions: &mut Conditions, status: &DeploymentStatus, progress_deadline_seconds: Option<i32>, pods: &[Pod]) {
+    conditions.set(deployment_available_condition(status));
+    conditions.set(
+        pod_degraded_condition(pods)
+            .unwrap_or_else(|| deployment_degraded_condition(status, progress_deadline_seconds))
+    );
+}
+
+/// Set the ApiHealthy condition from the outcome of the health check of the bot's API
+///
+/// An unhealthy API degrades the bot, unless it is already degraded for a reason found on its
+/// Deployment or pods.
+///
+/// # Arguments
+/// * `conditions` - The conditions of the bot
+/// * `ping` - The outcome of the health check, with the reason it failed
+fn set_api_health(conditions: &mut Conditions, ping: std::result::Result<(), String>) {
+    match ping {
+        Ok(()) => conditions.set(api_healthy_condition("True", "PingSucceeded", None)),
+        Err(reason) => {
+            conditions.set(api_healthy_condition("False", "PingFailed", Some(reason.clone())));
+            if !conditions.is_true(DEGRADED_CONDITION) {
+                conditions.set(degraded_condition("ApiUnhealthy", Some(format!("The bot's API is not healthy: {}", reason))));
+            }
+        },
+    }
+}
+
+/// Create a Degraded condition from the bot's pods, when one of their containers is stuck waiting
+///
+/// # Arguments
+/// * `pods` - The pods of the bot
+///
+/// # Returns
+/// The Degraded condition with the waiting reason of the container, e.g. `CrashLoopBackOff`
+fn pod_degraded_condition(pods: &[Pod]) -> Option<Condition> {
+    pods.iter()
+        .filter_map(|pod| pod.status.as_ref())
+        .flat_map(|status| status.init_container_statuses
+            .iter()
+            .flatten()
+            .chain(status.container_statuses.iter().flatten())
+        )
+        .filter_map(|container| container.state.as_ref()?.waiting.as_ref())
+        .find(|waiting| waiting.reason.as_deref().is_some_and(|reason| FAILED_WAITING_REASONS.contains(&reason)))
+        .map(|waiting| degraded_condition(waiting.reason.as_deref().unwrap_or_default(), waiting.message.clone()))
+}
+
+/// The condition reporting whether the bot's API answers its health check
+///
+/// # Arguments
+/// * `status` - The status of the condition
+/// * `reason` - The reason of the condition
+/// * `message` - Why the API isn't healthy
+///
+/// # Returns
+/// The ApiHealthy condition
+fn api_healthy_condition(status: &str, reason: &str, message: Option<String>) -> Condition {
+    Condition {
+        type_: API_HEALTHY_CONDITION.to_string(),
+        status: status.to_string(),
+        reason: Some(reason.to_string()),
+        message,
+        last_transition_time: None,
+    }
+}
+
+/// The condition reporting whether the freqtrade version of the bot could be read
+///
+/// # Arguments
+/// * `status` - The status of the condition
+/// * `reason` - The reason of the condition
+/// * `message` - Why the version couldn't be read
+///
+/// # Returns
+/// The FreqtradeVersionReported condition
+fn freqtrade_version_condition(status: &str, reason: &str, message: Option<String>) -> Condition {
+    Condition {
+        type_: FREQTRADE_VERSION_CONDITION.to_string(),
+        status: status.to_string(),
+        reason: Some(reason.to_string()),
+        message,
+        last_transition_time: None,
+    }
+}
+
+/// Create the Deleting condition reporting the progress of the bot's cleanup
+///
+/// # Arguments
+/// * `reason` - The reason of the condition
+/// * `message` - The progress of the cleanup
+///
+/// # Returns
+/// The Deleting condition
+fn deleting_condition(reason: &str, message: &str) -> Condition {
+    Condition {
+        type_: DELETING_CONDITION.to_string(),
+        status: "True".to_string(),
+        reason: Some(reason.to_string()),
+        message: Some(message.to_string()),
+        last_transition_time: None,
+    }
+}
+
+/// Create the `True` condition of a child resource that was applied, clearing a failed apply
+///
+/// # Arguments
+/// * `type_` - The condition reporting the resource
+fn applied_condition(type_: &str) -> Condition {
+    Condition {
+        type_: type_.to_string(),
+        status: "True".to_string(),
+        reason: Some("Applied".to_string()),
+        message: None,
+        last_transition_time: None,
+    }
+}
+
+/// Create a `True` Degraded condition
+///
+/// # Arguments
+/// * `reason` - Why the bot is degraded
+/// * `message` - Details about why the bot is degraded
+///
+/// # Returns
+/// The Degraded condition
+fn degraded_condition(reason: &str, message: Option<String>) -> Condition {
+    Condition {
+        type_: DEGRADED_CONDITION.to_string(),
+        status: "True".to_string(),
+        reason: Some(reason.to_string()),
+        message,
+        last_transition_time: None,
+    }
+}
+
+/// The name of the Secret holding the generated API credentials of a bot
+///
+/// # Arguments
+/// * `name` - The name of the bot's resources
+pub fn api_credentials_secret_name(name: &str) -> String {
+    format!("{}-api-credentials", name)
+}
+
+/// The key of the bot's ConfigMap holding the strategy parameters, mounted next to `strategy.py`
+///
+/// Freqtrade loads the parameters of a strategy from the `<class name>.json` file next to it.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+///
+/// # Returns
+/// The key, `None` without parameters or when the strategy comes from `strategy.configMapName`
+pub fn strategy_parameters_key(bot: &Bot) -> Option<String> {
+    let strategy = &bot.spec.strategy;
+    (strategy.parameters.is_some() && strategy.config_map_name.is_none())
+        .then(|| format!("{}.json", strategy.name))
+}
+
+/// Whether a bot's API credentials are generated by the operator
+///
+/// They are when the API is enabled without any `secrets.api`.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+pub fn generates_api_credentials(bot: &Bot) -> bool {
+    bot.spec.api.enabled && bot.spec.secrets.api.is_none()
+}
+
+/// The API secrets referencing the generated API credentials of a bot
+///
+/// # Arguments
+/// * `secret_name` - The name of the Secret holding the credentials
+pub fn generated_api_secrets(secret_name: &str) -> ApiSecrets {
+    let secret_item = |key: &str| Some(SecretItem::SecretKeyRef {
+        secret_key_ref: SecretKeyRef {
+            name: secret_name.to_string(),
+            key: key.to_string(),
+        },
+    });
+
+    ApiSecrets {
+        username: secret_item("username"),
+        password: secret_item("password"),
+        jwt_secret_key: secret_item("jwt_secret_key"),
+        ws_token: secret_item("ws_token"),
+    }
+}
+
+/// Create the environment variables of a bot's main container
+///
+/// The variables are collected by name and emitted sorted, so the pod spec stays the same
+/// regardless of the order they are built in. The Bot's `deployment.env` overrides the
+/// operator's variables, except the reserved ones unless the Bot was admitted with the
+/// reserved keys validation skipped, as well as the operator's proxy settings.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+/// * `name` - The name of the bot's resources
+/// * `config` - The application configuration holding the proxy and bypass settings
+///
+/// # Returns
+/// The environment variables, sorted by name
+pub fn bot_env_vars(bot: &Bot, name: &str, config: &AppConfig) -> Vec<EnvVar> {
+    let api = &bot.spec.api;
+    let secrets = &bot.spec.secrets;
+    let mut env_vars = BTreeMap::new();
+    let mut set = |env_var: EnvVar| {
+        env_vars.insert(env_var.name.clone(), env_var);
+    };
+
+    set(create_env_var("FREQTRADE__STRATEGY", Some(bot.spec.strategy.name.clone())));
+    set(create_env_var("FREQTRADE__STRATEGY_PATH", Some("/etc/freqtrade".to_string())));
+    set(create_env_var("FREQTRADE__FREQAIMODEL_PATH", Some("/etc/freqtrade".to_string())));
+    // When PostgreSQL is provisioned through CloudNativePG, the connection URI
+    // comes from the `<cluster>-app` Secret generated by CloudNativePG
+    set(match bot.spec.postgres.as_ref().and_then(|p| p.cluster_name(name)) {
+        Some(cluster_name) => create_secret_env_var("FREQTRADE__DB_URL", &Some(SecretItem::SecretKeyRef {
+            secret_key_ref: SecretKeyRef {
+                name: format!("{}-app", cluster_name),
+                key: "uri".to_string(),
+            },
+        })),
+        None => create_env_var("FREQTRADE__DB_URL", Some(bot.spec.database.to_string())),
+    });
+    set(create_env_var("FREQTRADE__BOT_NAME", Some(name.to_string())));
+    set(create_env_var("FREQTRADE__API_SERVER__ENABLED", Some(api.enabled.to_string())));
+    set(create_env_var("FREQTRADE__API_SERVER__LISTEN_IP_ADDRESS", Some(api.host.to_string())));
+    set(create_env_var("FREQTRADE__API_SERVER__LISTEN_PORT", Some(api.port.to_string())));
+    set(create_env_var("FREQTRADE__EXCHANGE__NAME", Some(bot.spec.exchange.to_string())));
+
+    // Secret-based environment variables, left empty when the secret isn't set
+    let api_secrets = secrets.api.as_ref();
+    let telegram = secrets.telegram.as_ref();
+    let exchange = secrets.exchange.as_ref();
+    for (env_name, secret_item) in [
+        ("FREQTRADE__API_SERVER__USERNAME", api_secrets.and_then(|a| a.username.clone())),
+        ("FREQTRADE__API_SERVER__PASSWORD", api_secrets.and_then(|a| a.password.clone())),
+        ("FREQTRADE__API_SERVER__WS_TOKEN", api_secrets.and_then(|a| a.ws_token.clone())),
+        ("FREQTRADE__API_SERVER__JWT_SECRET_KEY", api_secrets.and_then(|a| a.jwt_secret_key.clone())),
+        ("FREQTRADE__TELEGRAM__TOKEN", telegram.and_then(|t| t.token.clone())),
+        ("FREQTRADE__TELEGRAM__CHAT_ID", telegram.and_then(|t| t.chat_id.clone())),
+        ("FREQTRADE__DISCORD__WEBHOOK_URL", secrets.discord.as_ref().map(|d| d.webhook_url.clone())),
+        ("FREQTRADE__SLACK__WEBHOOK_URL", secrets.slack.as_ref().map(|s| s.webhook_url.clone())),
+        ("FREQTRADE__EXCHANGE__KEY", exchange.and_then(|e| e.key.clone())),
+        ("FREQTRADE__EXCHANGE__SECRET", exchange.and_then(|e| e.secret.clone())),
+        ("FREQTRADE__EXCHANGE__PASSWORD", exchange.and_then(|e| e.password.clone())),
+        ("FREQTRADE__EXCHANGE__UID", exchange.and_then(|e| e.uid.clone())),
+    ] {
+        set(create_secret_env_var(env_name, &secret_item));
+    }
+
+    if bot.spec.model.is_some() {
+        set(create_env_var("FREQTRADE__FREQAI__ENABLED", Some("true".to_string())));
+    }
+
+    // Freqtrade parses list settings from the environment as JSON
+    if !api.cors_origins.is_empty() {
+        set(create_env_var("FREQTRADE__API_SERVER__CORS_ORIGINS", Some(Value::from(api.cors_origins.clone()).to_string())));
     }
 
-    // If the API is enabled, apply the Service if it is None or different from the Service object
-    // If the API is not enabled, delete the Service if it exists
-    if hub.spec.api.enabled {
-        if service.is_none() || ResourceDrift::<Bot>::has_drifted(service.as_ref().unwrap(), &service_object) {
-            info!(
-                event = "ApplyingService",
-                bot = bot.name_any().as_str()
-            );
-            apply(&service_api, service_object, bot.name_any().as_str()).await?;
+    if let Some(webhook) = bot.spec.notifications.webhook.as_ref() {
+        set(create_env_var("FREQTRADE__WEBHOOK__ENABLED", Some(webhook.enabled.to_string())));
+        set(create_secret_env_var("FREQTRADE__WEBHOOK__URL", &Some(webhook.url.clone())));
+        set(create_env_var("FREQTRADE__WEBHOOK__FORMAT", Some(webhook.format.to_string())));
+        if let Some(retries) = webhook.retries {
+            set(create_env_var("FREQTRADE__WEBHOOK__RETRIES", Some(retries.to_string())));
         }
-    } else if service.is_some() {
-        info!(
-            event = "DeletingService",
-            bot = bot.name_any().as_str()
-        );
-        delete(&service_api, bot.name_any().as_str()).await?;
     }
 
-    Ok(Action::requeue(Duration::from_secs(30)))
-}
+    // The resolved producers (including their ws tokens) live in a Secret maintained by the controller
+    if !bot.spec.signals.producers.is_empty() {
+        set(create_env_var("FREQTRADE__EXTERNAL_MESSAGE_CONSUMER__ENABLED", Some("true".to_string())));
+        set(create_secret_env_var("FREQTRADE__EXTERNAL_MESSAGE_CONSUMER__PRODUCERS", &Some(SecretItem::SecretKeyRef {
+            secret_key_ref: SecretKeyRef {
+                name: signals_secret_name(name),
+                key: SIGNALS_SECRET_KEY.to_string(),
+            },
+        })));
+    }
 
-/// Cleanup the bot resource
-/// 
-/// This function is responsible for cleaning up the bot resource.
-/// 
-/// # Arguments
-/// * `bot` - The bot resource to cleanup
-/// * `ctx` - The controller context
-/// * `namespace` - The namespace of the bot resource
-/// 
-/// # Returns
-/// An action to take after cleaning up the bot resource
-async fn cleanup_bot<T>(bot: &T, ctx: &Context, namespace: &str) -> Result<Action>
-where
-    T: NamespacedCustomResource,
-    Bot: From<T>,
-{
-    update_status(bot, ctx, namespace, &BotPhase::Deleting).await?;
+    // The operator's proxy settings apply to every bot unless it sets its own
+    for (env_name, value) in config.controller.proxy.env_vars() {
+        set(create_env_var(env_name, Some(value)));
+    }
 
-    Ok(Action::await_change())
+    // The webhook only admits reserved env vars when the bypass is allowed, the annotation alone
+    // doesn't unlock them for a Bot admitted without the webhook or before the bypass was disabled
+    let override_reserved = config.webhook.allow_validation_bypass && bot.metadata.annotations
+        .as_ref()
+        .and_then(|annotations| annotations.get(SKIP_VALIDATION_ANNOTATION))
+        .is_some_and(|scopes| scopes.split(',').any(|scope| scope.trim() == RESERVED_KEYS_SCOPE));
+    for env_var in &bot.spec.deployment.env {
+        if override_reserved || !RESERVED_ENV_VARS.contains(&env_var.name.as_str()) {
+            set(env_var.clone());
+        }
+    }
+
+    env_vars.into_values().collect()
 }
 
-/// Update the status of the bot resource
-/// 
-/// This function is responsible for updating the status of the bot resource.
-/// 
+/// Add the operator's proxy env vars to a container, unless it already sets them
+///
 /// # Arguments
-/// * `bot` - The bot resource to update
-/// * `ctx` - The controller context
-/// * `namespace` - The namespace of the bot resource
-/// * `phase` - The phase to set the bot resource to
+/// * `container` - The container
+/// * `proxy` - The operator's proxy settings
 ///
 /// # Returns
-/// A result indicating success or failure
-async fn update_status<T>(bot: &T, ctx: &Context, namespace: &str, phase: &BotPhase) -> Result<()>
-where
-    T: NamespacedCustomResource,
-    Bot: From<T>,
-{
-    let client = ctx.client.clone();
-    let api = Api::<T>::namespaced(client.clone(), namespace);
-
-    api.patch_status(
-        &bot.name_any(),
-        &PatchParams::apply(FIELD_MANAGER),
-        &Patch::Merge(json!({
-            "status": BotStatus {
-                phase: phase.to_string(),
-                last_updated: Some(Utc::now()),
-            }
-        })),
-    ).await?;
+/// The container with the proxy env vars
+fn with_proxy_env(mut container: Container, proxy: &ProxyConfig) -> Container {
+    let missing: Vec<EnvVar> = proxy.env_vars()
+        .into_iter()
+        .filter(|(name, _)| !container.env.iter().flatten().any(|env_var| env_var.name == *name))
+        .map(|(name, value)| create_env_var(name, Some(value)))
+        .collect();
 
-    Ok(())
+    if !missing.is_empty() {
+        container.env.get_or_insert_with(Vec::new).extend(missing);
+    }
+
+    container
 }
 
 /// Create an environment variable from a secret item
@@ -1144,7 +3507,7 @@ where
 /// # Arguments
 /// * `name` - The name of the environment variable
 /// * `secret_item` - The secret item to create the environment variable from
-fn create_secret_env_var(name: &str, secret_item: &Option<SecretItem>) -> EnvVar {
+pub fn create_secret_env_var(name: &str, secret_item: &Option<SecretItem>) -> EnvVar {
     EnvVar {
         name: name.to_string(),
         value: match secret_item {
@@ -1175,7 +3538,7 @@ fn create_secret_env_var(name: &str, secret_item: &Option<SecretItem>) -> EnvVar
 /// 
 /// # Returns
 /// The environment variable
-fn create_env_var(name: &str, value: Option<String>) -> EnvVar {
+pub fn create_env_var(name: &str, value: Option<String>) -> EnvVar {
     EnvVar {
         name: name.to_string(),
         value: value.map(|value| value.to_string()),
@@ -1219,6 +3582,43 @@ fn compare_container_ports(self_ports: Option<&Vec<ContainerPort>>, other_ports:
 }
 
 
+/// Compare container resources
+/// 
+/// This function is responsible for comparing the resource requirements of two containers.
+/// Quantities are compared by value since the API server canonicalizes them (e.g. `1000m` to `1`).
+/// 
+/// # Arguments
+/// * `self_resources` - The resources of the first container
+/// * `other_resources` - The resources of the second container
+/// 
+/// # Returns
+/// Whether the resources are different
+fn compare_resources(self_resources: Option<&ResourceRequirements>, other_resources: Option<&ResourceRequirements>) -> bool {
+    let quantities_differ = |left: &Option<BTreeMap<String, Quantity>>, right: &Option<BTreeMap<String, Quantity>>| {
+        let empty = BTreeMap::new();
+        let left = left.as_ref().unwrap_or(&empty);
+        let right = right.as_ref().unwrap_or(&empty);
+
+        left.len() != right.len()
+            || left.iter().any(|(name, quantity)| {
+                right.get(name).is_none_or(|other| {
+                    match (parse_quantity(&quantity.0), parse_quantity(&other.0)) {
+                        (Some(left), Some(right)) => (left - right).abs() > f64::EPSILON * left.abs().max(1.0),
+                        _ => quantity != other,
+                    }
+                })
+            })
+    };
+
+    match (self_resources, other_resources) {
+        (Some(self_resources), Some(other_resources)) => {
+            quantities_differ(&self_resources.limits, &other_resources.limits)
+                || quantities_differ(&self_resources.requests, &other_resources.requests)
+        },
+        _ => false,
+    }
+}
+
 /// Compare environment variables
 /// 
 /// This function is responsible for comparing environment variables.
@@ -1236,18 +3636,12 @@ fn compare_env_vars(self_vars: Option<&Vec<EnvVar>>, other_vars: Option<&Vec<Env
                 return true;
             }
 
-            let mut self_vars = self_vars.clone();
-            let mut other_vars = other_vars.clone();
-
-            // Sort the environment variables by name (or any other field you want to compare)
-            self_vars.sort_by(|a, b| a.name.cmp(&b.name));
-            other_vars.sort_by(|a, b| a.name.cmp(&b.name));
-
-            if self_vars != other_vars {
-                return true;
+            // The variables are keyed by name as they are built, so their order doesn't matter
+            fn by_name(vars: &[EnvVar]) -> BTreeMap<&str, &EnvVar> {
+                vars.iter().map(|var| (var.name.as_str(), var)).collect()
             }
 
-            false
+            by_name(self_vars) != by_name(other_vars)
         },
         (None, None) => false,
         _ => true,
@@ -1327,3 +3721,710 @@ fn volumes_are_equal(self_vol: &Volume, other_vol: &Volume) -> bool {
         _ => false, // One is Some, the other is None, not equal
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::StatusCode;
+    use ft_operator_common::state::State;
+    use crate::controller::mock::mock_client;
+    use crate::crd::v1alpha1::bot::Bot as V1Alpha1Bot;
+
+    // The resources watched by the Bot controller, and whether every watch was scoped to the namespace
+    async fn watched_resources(namespace: Option<&str>) -> (BTreeMap<String, bool>, Vec<String>) {
+        let (client, mut requests) = mock_client(|_| (StatusCode::OK, json!({ "metadata": { "resourceVersion": "1" }, "items": [] })));
+        let ctx = Arc::new(Context::new(client, Arc::new(State::new(AppConfig::default()))));
+        let controller = BotController::create_controller::<V1Alpha1Bot>(ctx, namespace).await;
+        let running = tokio::spawn(
+            controller
+                .run(|_, _| async { Ok::<_, ControllerError>(Action::await_change()) }, |_, _, _| Action::await_change(), Arc::new(()))
+                .for_each(|_| future::ready(()))
+        );
+
+        let expected = ["bots", "strategies", "deployments", "services", "configmaps", "persistentvolumeclaims", "secrets", "ingresses"];
+        let mut paths = vec![];
+        let mut resources = BTreeMap::new();
+        while !expected.iter().all(|resource| resources.contains_key(*resource)) {
+            let Ok(Some(request)) = tokio::time::timeout(Duration::from_secs(5), requests.recv()).await else {
+                break;
+            };
+            let resource = request.path.rsplit('/').next().unwrap_or_default().to_string();
+            let scoped = namespace.is_some_and(|namespace| request.path.contains(&format!("/namespaces/{}/", namespace)));
+            *resources.entry(resource).or_insert(true) &= scoped;
+            paths.push(request.path);
+        }
+        running.abort();
+
+        (resources, paths)
+    }
+
+    #[tokio::test]
+    async fn controller_watches_only_its_namespace() {
+        let (resources, paths) = watched_resources(Some("team-a")).await;
+
+        for resource in ["bots", "strategies", "deployments", "services", "configmaps", "persistentvolumeclaims", "secrets", "ingresses"] {
+            assert_eq!(resources.get(resource), Some(&true), "{} in {:?}", resource, paths);
+        }
+    }
+
+    #[tokio::test]
+    async fn controller_watches_all_namespaces_by_default() {
+        let (resources, paths) = watched_resources(None).await;
+
+        assert!(resources.contains_key("bots"), "{:?}", paths);
+        assert!(paths.iter().all(|path| !path.contains("/namespaces/")), "{:?}", paths);
+    }
+
+    fn secret(name: &str, keys: &[&str]) -> Secret {
+        Secret {
+            metadata: ObjectMeta { name: Some(name.to_string()), ..Default::default() },
+            data: Some(keys.iter().map(|key| (key.to_string(), ByteString(b"value".to_vec()))).collect()),
+            ..Default::default()
+        }
+    }
+
+    fn secret_referencing_bot() -> Bot {
+        bot(json!({
+            "secrets": {
+                "exchange": {
+                    "key": { "secretKeyRef": { "name": "exchange", "key": "key" } },
+                    "secret": { "secretKeyRef": { "name": "exchange", "key": "secret" } },
+                },
+                "telegram": {
+                    "token": { "secretKeyRef": { "name": "telegram", "key": "token" } },
+                    "chatId": { "value": "1234" },
+                },
+            },
+        }))
+    }
+
+    #[test]
+    fn missing_secrets_are_reported_once() {
+        let secrets = BTreeMap::from([
+            ("exchange".to_string(), None),
+            ("telegram".to_string(), Some(secret("telegram", &["token"]))),
+        ]);
+
+        assert_eq!(missing_secret_keys(&secret_referencing_bot(), &secrets), vec!["Secret `exchange`"]);
+    }
+
+    #[test]
+    fn missing_secret_keys_are_reported() {
+        let secrets = BTreeMap::from([
+            ("exchange".to_string(), Some(secret("exchange", &["key"]))),
+            ("telegram".to_string(), Some(secret("telegram", &[]))),
+        ]);
+
+        assert_eq!(
+            missing_secret_keys(&secret_referencing_bot(), &secrets),
+            vec!["key `secret` of Secret `exchange`", "key `token` of Secret `telegram`"],
+        );
+    }
+
+    #[test]
+    fn created_secrets_are_no_longer_missing() {
+        let bot = secret_referencing_bot();
+        let mut secrets = BTreeMap::from([
+            ("exchange".to_string(), None),
+            ("telegram".to_string(), None),
+        ]);
+        assert_eq!(missing_secret_keys(&bot, &secrets).len(), 2);
+
+        secrets.insert("exchange".to_string(), Some(secret("exchange", &["key", "secret"])));
+        secrets.insert("telegram".to_string(), Some(secret("telegram", &["token"])));
+        assert!(missing_secret_keys(&bot, &secrets).is_empty());
+    }
+
+    #[test]
+    fn label_selector_filters_the_bot_watches() {
+        let mut config = ControllerConfig::default();
+        assert_eq!(bot_watcher_config(&config).label_selector, None);
+
+        config.watch_label_selector = Some("freqtrade.io/operator=canary".to_string());
+        assert_eq!(bot_watcher_config(&config).label_selector.as_deref(), Some("freqtrade.io/operator=canary"));
+    }
+
+    fn bot(spec: Value) -> Bot {
+        let mut bot_spec = json!({ "exchange": "binance", "strategy": { "name": "SampleStrategy" } });
+        bot_spec.as_object_mut().unwrap().extend(spec.as_object().cloned().unwrap_or_default());
+
+        Bot::from(serde_json::from_value::<V1Alpha1Bot>(json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "Bot",
+            "metadata": { "name": "bot", "namespace": "default", "uid": "bot-uid" },
+            "spec": bot_spec,
+        })).unwrap())
+    }
+
+    // The freqtrade container is named after the bot
+    fn freqtrade_image(deployment: &Deployment) -> Option<String> {
+        deployment.spec.as_ref()?.template.spec.as_ref()?.containers
+            .iter()
+            .find(|container| container.name == "bot")?
+            .image
+            .clone()
+    }
+
+    #[test]
+    fn default_image_change_applies_without_restart() {
+        let state = State::new(AppConfig::default());
+        let bot = bot(json!({}));
+        let deployment = Deployment::from_hub(&bot, "bot", "default", OwnerReference::default(), &state.config());
+        assert_eq!(freqtrade_image(&deployment).as_deref(), Some("freqtradeorg/freqtrade:stable"));
+
+        // The reconciles take a new snapshot of the reloaded config
+        let mut config = AppConfig::clone(&state.config());
+        config.controller.default_image_tag = "develop".to_string();
+        state.set_config(config);
+
+        let deployment = Deployment::from_hub(&bot, "bot", "default", OwnerReference::default(), &state.config());
+        assert_eq!(freqtrade_image(&deployment).as_deref(), Some("freqtradeorg/freqtrade:develop"));
+    }
+
+    fn bot_env(env: Value) -> Bot {
+        let mut bot = bot(json!({ "deployment": { "env": env } }));
+        bot.metadata.annotations = Some([(SKIP_VALIDATION_ANNOTATION.to_string(), RESERVED_KEYS_SCOPE.to_string())].into());
+        bot
+    }
+
+    #[test]
+    fn builds_of_the_same_bot_are_byte_identical() {
+        let config = AppConfig::default();
+        let env = json!([{ "name": "B", "value": "b" }, { "name": "A", "value": "a" }]);
+        let mut reversed = env.clone();
+        reversed.as_array_mut().unwrap().reverse();
+
+        let build = |bot: &Bot| serde_json::to_vec(&Deployment::from_hub(bot, "bot", "default", OwnerReference::default(), &config)).unwrap();
+        assert_eq!(build(&bot_env(env.clone())), build(&bot_env(env.clone())));
+        // The env vars are emitted sorted, whatever order the Bot lists them in
+        assert_eq!(build(&bot_env(env)), build(&bot_env(reversed)));
+    }
+
+    #[test]
+    fn reserved_env_vars_are_only_overridden_when_the_bypass_is_allowed() {
+        let bot = bot_env(json!([{ "name": "FREQTRADE__STRATEGY", "value": "Override" }]));
+        let strategy = |config: &AppConfig| bot_env_vars(&bot, "bot", config)
+            .into_iter()
+            .find(|env_var| env_var.name == "FREQTRADE__STRATEGY")
+            .and_then(|env_var| env_var.value);
+
+        let mut config = AppConfig::default();
+        assert_eq!(strategy(&config).as_deref(), Some("SampleStrategy"));
+
+        config.webhook.allow_validation_bypass = true;
+        assert_eq!(strategy(&config).as_deref(), Some("Override"));
+    }
+
+    fn proxy_config() -> AppConfig {
+        let mut config = AppConfig::default();
+        config.controller.proxy = ProxyConfig {
+            http_proxy: Some("http://proxy:3128".to_string()),
+            https_proxy: Some("http://proxy:3128".to_string()),
+            no_proxy: Some(".svc,.cluster.local".to_string()),
+        };
+        config
+    }
+
+    fn env_value(env: &[EnvVar], name: &str) -> Option<String> {
+        env.iter().find(|env_var| env_var.name == name).and_then(|env_var| env_var.value.clone())
+    }
+
+    #[test]
+    fn proxy_env_vars_are_injected_once_configured() {
+        let bot = bot(json!({}));
+
+        let env = bot_env_vars(&bot, "bot", &AppConfig::default());
+        assert!(env.iter().all(|env_var| !env_var.name.ends_with("_PROXY")));
+
+        let env = bot_env_vars(&bot, "bot", &proxy_config());
+        assert_eq!(env_value(&env, "HTTP_PROXY").as_deref(), Some("http://proxy:3128"));
+        assert_eq!(env_value(&env, "HTTPS_PROXY").as_deref(), Some("http://proxy:3128"));
+        assert_eq!(env_value(&env, "NO_PROXY").as_deref(), Some(".svc,.cluster.local"));
+    }
+
+    #[test]
+    fn bots_override_the_proxy_env_vars() {
+        let bot = bot(json!({ "deployment": { "env": [{ "name": "HTTPS_PROXY", "value": "http://bot-proxy:8080" }] } }));
+        let env = bot_env_vars(&bot, "bot", &proxy_config());
+
+        assert_eq!(env_value(&env, "HTTPS_PROXY").as_deref(), Some("http://bot-proxy:8080"));
+        assert_eq!(env_value(&env, "HTTP_PROXY").as_deref(), Some("http://proxy:3128"));
+        assert_eq!(env.iter().filter(|env_var| env_var.name == "HTTPS_PROXY").count(), 1);
+    }
+
+    #[test]
+    fn proxy_env_vars_are_injected_into_init_containers() {
+        let bot = bot(json!({ "deployment": { "initContainers": [
+            { "name": "fetch", "image": "busybox" },
+            { "name": "local", "image": "busybox", "env": [{ "name": "NO_PROXY", "value": "*" }] },
+        ] } }));
+        let deployment = Deployment::from_hub(&bot, "bot", "default", OwnerReference::default(), &proxy_config());
+        let init_containers = deployment.spec.unwrap().template.spec.unwrap().init_containers.unwrap();
+        let init_env = |name: &str| init_containers.iter().find(|container| container.name == name).unwrap().env.clone().unwrap();
+
+        assert_eq!(env_value(&init_env("fetch"), "NO_PROXY").as_deref(), Some(".svc,.cluster.local"));
+        assert_eq!(env_value(&init_env("local"), "NO_PROXY").as_deref(), Some("*"));
+        assert_eq!(env_value(&init_env("local"), "HTTP_PROXY").as_deref(), Some("http://proxy:3128"));
+        assert_eq!(init_env("local").iter().filter(|env_var| env_var.name == "NO_PROXY").count(), 1);
+    }
+
+    #[test]
+    fn proxy_changes_roll_the_bot() {
+        let bot = bot(json!({}));
+        let without_proxy = Deployment::from_hub(&bot, "bot", "default", OwnerReference::default(), &AppConfig::default());
+        let with_proxy = Deployment::from_hub(&bot, "bot", "default", OwnerReference::default(), &proxy_config());
+
+        assert!(ResourceDrift::<Bot>::has_drifted(&without_proxy, &with_proxy));
+        assert!(!ResourceDrift::<Bot>::has_drifted(&with_proxy, &Deployment::from_hub(&bot, "bot", "default", OwnerReference::default(), &proxy_config())));
+    }
+
+    #[test]
+    fn default_pull_secrets_are_merged_into_the_pod() {
+        let mut config = AppConfig::default();
+        config.controller.default_image_pull_secrets = vec!["mirror".to_string(), "shared".to_string()];
+        let pull_secrets = |deployment: &Deployment| deployment.spec.as_ref().unwrap().template.spec.as_ref().unwrap()
+            .image_pull_secrets
+            .iter()
+            .flatten()
+            .map(|secret| secret.name.clone())
+            .collect::<Vec<_>>();
+
+        let bot = bot(json!({ "image": { "pullSecrets": ["shared", "bot"] } }));
+        let deployment = Deployment::from_hub(&bot, "bot", "default", OwnerReference::default(), &config);
+        assert_eq!(pull_secrets(&deployment), ["shared", "bot", "mirror"]);
+
+        // The merge is the same on every reconcile
+        assert!(!ResourceDrift::<Bot>::has_drifted(&deployment, &Deployment::from_hub(&bot, "bot", "default", OwnerReference::default(), &config)));
+
+        let deployment = Deployment::from_hub(&bot, "bot", "default", OwnerReference::default(), &AppConfig::default());
+        assert_eq!(pull_secrets(&deployment), ["shared", "bot"]);
+    }
+
+    #[test]
+    fn a_new_restart_request_restarts_the_bot_once() {
+        assert_eq!(restart_request("2026-01-01T00:00:00Z", "", true), RestartRequest::Restart);
+        // Once mirrored on the Deployment, reconciling the same value doesn't restart the bot again
+        assert_eq!(restart_request("2026-01-01T00:00:00Z", "2026-01-01T00:00:00Z", true), RestartRequest::Handled);
+        // Annotating the bot again restarts it again
+        assert_eq!(restart_request("2026-01-02T00:00:00Z", "2026-01-01T00:00:00Z", true), RestartRequest::Restart);
+    }
+
+    #[test]
+    fn restart_requests_without_pods_are_only_recorded() {
+        // A stale value left on a Bot whose Deployment is recreated, or a suspended bot
+        assert_eq!(restart_request("2026-01-01T00:00:00Z", "", false), RestartRequest::Record);
+        assert_eq!(restart_request("2026-01-02T00:00:00Z", "2026-01-01T00:00:00Z", false), RestartRequest::Record);
+        assert_eq!(restart_request("2026-01-01T00:00:00Z", "2026-01-01T00:00:00Z", false), RestartRequest::Handled);
+    }
+
+    #[test]
+    fn removing_the_restart_annotation_is_not_a_request() {
+        assert_eq!(restart_request("", "2026-01-01T00:00:00Z", true), RestartRequest::Handled);
+        assert_eq!(restart_request("", "", true), RestartRequest::Handled);
+    }
+
+    #[tokio::test]
+    async fn rejected_applies_are_reported_until_the_next_apply() {
+        let invalid = "Deployment.apps \"bot\" is invalid: spec.replicas: Invalid value: -1: must be greater than or equal to 0";
+        let bot_object = json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "Bot",
+            "metadata": { "name": "bot", "namespace": "default", "uid": "bot-uid" },
+            "spec": { "exchange": "binance", "strategy": { "name": "SampleStrategy" } },
+        });
+        let bot: V1Alpha1Bot = serde_json::from_value(bot_object.clone()).unwrap();
+        let (client, mut requests) = mock_client(move |request| match (request.method.as_str(), request.path.as_str()) {
+            ("PATCH", "/apis/apps/v1/namespaces/default/deployments/bot") => (StatusCode::UNPROCESSABLE_ENTITY, json!({
+                "kind": "Status",
+                "apiVersion": "v1",
+                "status": "Failure",
+                "message": invalid,
+                "reason": "Invalid",
+                "code": 422,
+            })),
+            ("PATCH", _) => (StatusCode::OK, bot_object.clone()),
+            _ => (StatusCode::OK, request.body.clone()),
+        });
+        let ctx = Context::new(client.clone(), Arc::new(State::new(AppConfig::default())));
+        let deployment = Deployment::from_hub(&Bot::from(bot.clone()), "bot", "default", OwnerReference::default(), &AppConfig::default());
+
+        let error = apply(&Api::<Deployment>::namespaced(client, "default"), deployment, "bot").await.unwrap_err();
+        let mut conditions = Conditions::new(None);
+        let result = child_resource_failed(&bot, &ctx, "default", &mut conditions, DEPLOYMENT_APPLIED_CONDITION, "Deployment", "Apply", error).await;
+
+        // The error is returned for the bot to be retried with a backoff
+        assert!(matches!(result, Err(ControllerError::KubeError(kube::Error::Api(ref response))) if response.code == 422), "{:?}", result);
+
+        let mut sent = vec![];
+        while let Ok(request) = requests.try_recv() {
+            sent.push(request);
+        }
+        let message = format!("Apply of Deployment failed: {}", invalid);
+        let event = sent.iter().find(|request| request.path.ends_with("/events")).unwrap();
+        assert_eq!(event.body["type"], "Warning");
+        assert_eq!(event.body["reason"], "DeploymentApplyFailed");
+        assert_eq!(event.body["note"], message.as_str());
+
+        let status = &sent.iter().find(|request| request.path.ends_with("/bots/bot/status")).unwrap().body["status"];
+        assert_eq!(status["phase"], BotPhase::Error.to_string());
+        assert_eq!(status["message"], message.as_str());
+        let applied = status["conditions"].as_array().unwrap().iter().find(|condition| condition["type"] == DEPLOYMENT_APPLIED_CONDITION).unwrap();
+        assert_eq!(applied["status"], "False");
+        assert_eq!(applied["reason"], "ApplyFailed");
+        assert_eq!(applied["message"], message.as_str());
+
+        // The next successful apply clears the condition
+        conditions.set(applied_condition(DEPLOYMENT_APPLIED_CONDITION));
+        let applied = conditions.get(DEPLOYMENT_APPLIED_CONDITION).unwrap();
+        assert_eq!((applied.status.as_str(), applied.reason.as_deref(), applied.message.as_deref()), ("True", Some("Applied"), None));
+    }
+
+    fn webhook_env(bot: &Bot) -> BTreeMap<String, EnvVar> {
+        bot_env_vars(bot, "bot", &AppConfig::default())
+            .into_iter()
+            .filter(|env_var| env_var.name.starts_with("FREQTRADE__WEBHOOK__"))
+            .map(|env_var| (env_var.name.clone(), env_var))
+            .collect()
+    }
+
+    #[test]
+    fn webhook_notification_is_rendered_once_enabled() {
+        let bot = bot(json!({ "notifications": { "webhook": {
+            "url": { "secretKeyRef": { "name": "webhook", "key": "url" } },
+        } } }));
+        let env = webhook_env(&bot);
+
+        assert_eq!(env.keys().collect::<Vec<_>>(), ["FREQTRADE__WEBHOOK__ENABLED", "FREQTRADE__WEBHOOK__FORMAT", "FREQTRADE__WEBHOOK__URL"]);
+        assert_eq!(env["FREQTRADE__WEBHOOK__ENABLED"].value.as_deref(), Some("true"));
+        assert_eq!(env["FREQTRADE__WEBHOOK__FORMAT"].value.as_deref(), Some("form"));
+        // The URL holds a token, so it is only ever referenced from its Secret
+        let url = &env["FREQTRADE__WEBHOOK__URL"];
+        assert_eq!(url.value, None);
+        let secret_key_ref = url.value_from.as_ref().and_then(|value_from| value_from.secret_key_ref.as_ref()).unwrap();
+        assert_eq!((secret_key_ref.name.as_str(), secret_key_ref.key.as_str()), ("webhook", "url"));
+    }
+
+    #[test]
+    fn webhook_notification_edits_roll_the_bot_unless_only_the_url_changed() {
+        let webhook = |webhook: Value| bot(json!({ "notifications": { "webhook": webhook } }));
+        let enabled = webhook(json!({ "url": { "secretKeyRef": { "name": "webhook", "key": "url" } } }));
+        let edited = webhook(json!({ "url": { "secretKeyRef": { "name": "webhook", "key": "url" } }, "format": "json", "retries": 3 }));
+        let moved = webhook(json!({ "url": { "secretKeyRef": { "name": "other", "key": "url" } } }));
+
+        let env = webhook_env(&edited);
+        assert_eq!(env["FREQTRADE__WEBHOOK__FORMAT"].value.as_deref(), Some("json"));
+        assert_eq!(env["FREQTRADE__WEBHOOK__RETRIES"].value.as_deref(), Some("3"));
+
+        assert_ne!(webhook_notification_hash_input(&enabled), webhook_notification_hash_input(&edited));
+        // Moving the URL to another Secret changes the Deployment itself, not the config
+        assert_eq!(webhook_notification_hash_input(&enabled), webhook_notification_hash_input(&moved));
+    }
+
+    #[test]
+    fn webhook_notification_is_turned_off_once_disabled() {
+        let url = json!({ "secretKeyRef": { "name": "webhook", "key": "url" } });
+        let enabled = bot(json!({ "notifications": { "webhook": { "url": url } } }));
+        let disabled = bot(json!({ "notifications": { "webhook": { "enabled": false, "url": url } } }));
+
+        assert_eq!(webhook_env(&disabled)["FREQTRADE__WEBHOOK__ENABLED"].value.as_deref(), Some("false"));
+        assert_ne!(webhook_notification_hash_input(&enabled), webhook_notification_hash_input(&disabled));
+    }
+
+    #[test]
+    fn webhook_notification_is_removed_once_unset() {
+        let bot = bot(json!({ "notifications": {} }));
+
+        assert!(webhook_env(&bot).is_empty());
+        // Bots without a webhook notification keep the config hash they had before it existed
+        assert_eq!(webhook_notification_hash_input(&bot), None);
+    }
+
+    #[test]
+    fn deployment_without_a_status_is_pending() {
+        // A Deployment the API server hasn't reported on yet
+        let deployment = Deployment { status: None, ..Default::default() };
+        let deployment_status = deployment.status.unwrap_or_default();
+
+        let mut conditions = Conditions::new(None);
+        conditions.set(deployment_available_condition(&deployment_status));
+        conditions.set(deployment_degraded_condition(&deployment_status, None));
+
+        let phase_status = BotPhaseStatus::from(&conditions);
+        assert_eq!(phase_status.phase.to_string(), BotPhase::Pending.to_string());
+        assert_eq!(phase_status.reason.as_deref(), Some("DeploymentPending"));
+    }
+
+    fn waiting_pod(container_status: &str, reason: &str, message: &str) -> Pod {
+        serde_json::from_value(json!({
+            "metadata": { "name": "bot-0" },
+            "status": {
+                container_status: [{
+                    "name": "bot",
+                    "image": "freqtradeorg/freqtrade:stable",
+                    "imageID": "",
+                    "ready": false,
+                    "restartCount": 3,
+                    "state": { "waiting": { "reason": reason, "message": message } },
+                }],
+            },
+        }))
+        .unwrap()
+    }
+
+    fn deployment_status(conditions: Value) -> DeploymentStatus {
+        serde_json::from_value(json!({ "conditions": conditions })).unwrap()
+    }
+
+    fn unavailable_status() -> DeploymentStatus {
+        deployment_status(json!([
+            { "type": "Available", "status": "False", "reason": "MinimumReplicasUnavailable" },
+            { "type": "Progressing", "status": "True", "reason": "ReplicaSetUpdated" },
+        ]))
+    }
+
+    fn workload_phase_status(status: &DeploymentStatus, pods: &[Pod]) -> BotPhaseStatus {
+        let mut conditions = Conditions::new(None);
+        set_workload_conditions(&mut conditions, status, None, pods);
+        BotPhaseStatus::from(&conditions)
+    }
+
+    #[test]
+    fn crash_looping_container_is_an_error() {
+        let pods = [waiting_pod("containerStatuses", "CrashLoopBackOff", "back-off 5m0s restarting failed container=bot")];
+
+        let phase_status = workload_phase_status(&unavailable_status(), &pods);
+        assert_eq!(phase_status.phase.to_string(), BotPhase::Error.to_string());
+        assert_eq!(phase_status.reason.as_deref(), Some("CrashLoopBackOff"));
+        assert_eq!(phase_status.message.as_deref(), Some("back-off 5m0s restarting failed container=bot"));
+    }
+
+    #[test]
+    fn image_pull_backoff_is_an_error() {
+        let pods = [waiting_pod("containerStatuses", "ImagePullBackOff", "Back-off pulling image \"freqtradeorg/freqtrade:typo\"")];
+
+        let phase_status = workload_phase_status(&unavailable_status(), &pods);
+        assert_eq!(phase_status.phase.to_string(), BotPhase::Error.to_string());
+        assert_eq!(phase_status.reason.as_deref(), Some("ImagePullBackOff"));
+        assert_eq!(phase_status.message.as_deref(), Some("Back-off pulling image \"freqtradeorg/freqtrade:typo\""));
+    }
+
+    #[test]
+    fn every_failed_waiting_reason_is_an_error() {
+        for container_status in ["containerStatuses", "initContainerStatuses"] {
+            for reason in FAILED_WAITING_REASONS {
+                let phase_status = workload_phase_status(&unavailable_status(), &[waiting_pod(container_status, reason, "failed")]);
+                assert_eq!(phase_status.phase.to_string(), BotPhase::Error.to_string(), "{} in {}", reason, container_status);
+                assert_eq!(phase_status.reason.as_deref(), Some(*reason), "{} in {}", reason, container_status);
+            }
+        }
+    }
+
+    #[test]
+    fn creating_container_is_pending() {
+        let phase_status = workload_phase_status(&unavailable_status(), &[waiting_pod("containerStatuses", "ContainerCreating", "")]);
+        assert_eq!(phase_status.phase.to_string(), BotPhase::Pending.to_string());
+        assert_eq!(phase_status.reason.as_deref(), Some("MinimumReplicasUnavailable"));
+    }
+
+    #[test]
+    fn exceeded_progress_deadline_is_an_error() {
+        let status = deployment_status(json!([
+            { "type": "Available", "status": "False", "reason": "MinimumReplicasUnavailable" },
+            { "type": "Progressing", "status": "False", "reason": "ProgressDeadlineExceeded", "message": "ReplicaSet \"bot-1\" has timed out progressing." },
+        ]));
+
+        let mut conditions = Conditions::new(None);
+        set_workload_conditions(&mut conditions, &status, Some(120), &[]);
+        let phase_status = BotPhaseStatus::from(&conditions);
+        assert_eq!(phase_status.phase.to_string(), BotPhase::Error.to_string());
+        assert_eq!(phase_status.reason.as_deref(), Some("ProgressDeadlineExceeded"));
+        assert!(phase_status.message.as_deref().is_some_and(|message| message.contains("deadline of 120s")));
+
+        // The container stuck behind the deadline explains it better
+        let pods = [waiting_pod("containerStatuses", "CrashLoopBackOff", "back-off")];
+        let phase_status = workload_phase_status(&status, &pods);
+        assert_eq!(phase_status.reason.as_deref(), Some("CrashLoopBackOff"));
+    }
+
+    #[test]
+    fn unhealthy_api_is_an_error() {
+        let available = deployment_status(json!([{ "type": "Available", "status": "True", "reason": "MinimumReplicasAvailable" }]));
+
+        let mut conditions = Conditions::new(None);
+        set_workload_conditions(&mut conditions, &available, None, &[]);
+        set_api_health(&mut conditions, Ok(()));
+        assert_eq!(BotPhaseStatus::from(&conditions).phase.to_string(), BotPhase::Running.to_string());
+
+        set_workload_conditions(&mut conditions, &available, None, &[]);
+        set_api_health(&mut conditions, Err("connection refused".to_string()));
+        let phase_status = BotPhaseStatus::from(&conditions);
+        assert_eq!(phase_status.phase.to_string(), BotPhase::Error.to_string());
+        assert_eq!(phase_status.reason.as_deref(), Some("ApiUnhealthy"));
+        assert_eq!(phase_status.message.as_deref(), Some("The bot's API is not healthy: connection refused"));
+        assert_eq!(conditions.get(API_HEALTHY_CONDITION).and_then(|c| c.reason.as_deref()), Some("PingFailed"));
+    }
+
+    fn bot_resources(deployment: &Deployment) -> ResourceRequirements {
+        deployment.spec.as_ref().unwrap().template.spec.as_ref().unwrap().containers
+            .iter()
+            .find(|container| container.name == "bot")
+            .and_then(|container| container.resources.clone())
+            .unwrap()
+    }
+
+    fn with_bot_resources(deployment: &Deployment, resources: &ResourceRequirements) -> Deployment {
+        let mut deployment = deployment.clone();
+        for container in &mut deployment.spec.as_mut().unwrap().template.spec.as_mut().unwrap().containers {
+            if container.name == "bot" {
+                container.resources = Some(resources.clone());
+            }
+        }
+        deployment
+    }
+
+    #[test]
+    fn gpu_request_does_not_drift() {
+        let config = AppConfig::default();
+        let bot = bot(json!({
+            "model": {
+                "name": "ReinforcementLearner",
+                "resources": {
+                    "limits": { "nvidia.com/gpu": "1", "memory": "1024Mi" },
+                    "requests": { "nvidia.com/gpu": "1", "cpu": "1000m" },
+                },
+            },
+            "deployment": { "resources": { "limits": { "memory": "512Mi" } } },
+        }));
+        let deployment = Deployment::from_hub(&bot, "bot", "default", OwnerReference::default(), &config);
+
+        // The model's resources take precedence over deployment.resources
+        let mut resources = bot_resources(&deployment);
+        let limits = resources.limits.as_mut().unwrap();
+        assert_eq!(limits.get("nvidia.com/gpu"), Some(&Quantity("1".to_string())));
+        assert_eq!(limits.get("memory"), Some(&Quantity("1024Mi".to_string())));
+
+        // The API server returns the quantities in their canonical form
+        limits.insert("memory".to_string(), Quantity("1Gi".to_string()));
+        resources.requests.as_mut().unwrap().insert("cpu".to_string(), Quantity("1".to_string()));
+        let live = with_bot_resources(&deployment, &resources);
+        assert!(!ResourceDrift::<Bot>::has_drifted(&live, &deployment));
+        assert!(!ResourceDrift::<Bot>::has_drifted(&live, &Deployment::from_hub(&bot, "bot", "default", OwnerReference::default(), &config)));
+
+        resources.limits.as_mut().unwrap().insert("nvidia.com/gpu".to_string(), Quantity("2".to_string()));
+        assert!(ResourceDrift::<Bot>::has_drifted(&with_bot_resources(&deployment, &resources), &deployment));
+    }
+
+    #[test]
+    fn tag_to_digest_rolls_the_bot_once() {
+        let config = AppConfig::default();
+        let digest = format!("sha256:{}", "a".repeat(64));
+        let tagged = Deployment::from_hub(&bot(json!({ "image": { "tag": "2024.1" } })), "bot", "default", OwnerReference::default(), &config);
+        // The tag is left in the spec, the digest takes precedence over it
+        let pinned_bot = bot(json!({ "image": { "tag": "2024.1", "digest": digest } }));
+        let pinned = Deployment::from_hub(&pinned_bot, "bot", "default", OwnerReference::default(), &config);
+
+        assert_eq!(freqtrade_image(&pinned), Some(format!("freqtradeorg/freqtrade@{}", digest)));
+        assert!(ResourceDrift::<Bot>::has_drifted(&tagged, &pinned));
+
+        // Once applied, the requeues render the same Deployment
+        let requeued = Deployment::from_hub(&pinned_bot, "bot", "default", OwnerReference::default(), &config);
+        assert!(!ResourceDrift::<Bot>::has_drifted(&pinned, &requeued));
+    }
+
+    fn default_metadata_config() -> AppConfig {
+        let mut config = AppConfig::default();
+        config.controller.default_labels = BTreeMap::from([
+            ("team".to_string(), "platform".to_string()),
+            ("cost-center".to_string(), "cc-1".to_string()),
+            ("app.kubernetes.io/name".to_string(), "other".to_string()),
+        ]);
+        config.controller.default_annotations = BTreeMap::from([("owner".to_string(), "platform".to_string())]);
+        config
+    }
+
+    #[test]
+    fn default_metadata_is_merged_under_the_bots_own() {
+        let config = default_metadata_config();
+        let bot = bot(json!({
+            "deployment": { "labels": { "team": "trading" } },
+            "service": { "serviceType": "ClusterIP", "labels": { "team": "api" }, "annotations": { "owner": "trading" } },
+        }));
+        let label = |metadata: &ObjectMeta, key: &str| metadata.labels.as_ref().and_then(|labels| labels.get(key)).cloned();
+        let annotation = |metadata: &ObjectMeta, key: &str| metadata.annotations.as_ref().and_then(|annotations| annotations.get(key)).cloned();
+
+        let deployment = Deployment::from_hub(&bot, "bot", "default", OwnerReference::default(), &config);
+        let pod_metadata = deployment.spec.as_ref().unwrap().template.metadata.clone().unwrap();
+        for metadata in [&deployment.metadata, &pod_metadata] {
+            assert_eq!(label(metadata, "team").as_deref(), Some("trading"));
+            assert_eq!(label(metadata, "cost-center").as_deref(), Some("cc-1"));
+            // The identifying labels the selectors rely on win over the defaults
+            assert_eq!(label(metadata, "app.kubernetes.io/name").as_deref(), Some("bot"));
+        }
+        assert_eq!(annotation(&deployment.metadata, "owner").as_deref(), Some("platform"));
+
+        let service = Service::from_hub(&bot, "bot", "default", OwnerReference::default(), &config);
+        assert_eq!(label(&service.metadata, "team").as_deref(), Some("api"));
+        assert_eq!(label(&service.metadata, "cost-center").as_deref(), Some("cc-1"));
+        assert_eq!(annotation(&service.metadata, "owner").as_deref(), Some("trading"));
+
+        let config_map = ConfigMap::try_from_hub(&bot, "bot", "default", OwnerReference::default(), &config).unwrap();
+        assert_eq!(config_map.metadata.labels, Some(config.controller.default_labels.clone()));
+        assert_eq!(config_map.metadata.annotations, Some(config.controller.default_annotations.clone()));
+    }
+
+    #[test]
+    fn default_metadata_is_part_of_the_desired_state() {
+        let bot = bot(json!({}));
+        let without_defaults = Deployment::from_hub(&bot, "bot", "default", OwnerReference::default(), &AppConfig::default());
+        let with_defaults = Deployment::from_hub(&bot, "bot", "default", OwnerReference::default(), &default_metadata_config());
+
+        // Adding a default is applied once
+        assert!(ResourceDrift::<Bot>::has_drifted(&without_defaults, &with_defaults));
+
+        // Once applied, the labels and annotations added by others don't loop
+        let mut live = with_defaults.clone();
+        live.metadata.labels.as_mut().unwrap().insert("added-by".to_string(), "others".to_string());
+        live.metadata.annotations.as_mut().unwrap().insert("deployment.kubernetes.io/revision".to_string(), "2".to_string());
+        assert!(!ResourceDrift::<Bot>::has_drifted(&live, &with_defaults));
+
+        let config_map = ConfigMap::try_from_hub(&bot, "bot", "default", OwnerReference::default(), &AppConfig::default()).unwrap();
+        let desired_config_map = ConfigMap::try_from_hub(&bot, "bot", "default", OwnerReference::default(), &default_metadata_config()).unwrap();
+        assert!(ResourceDrift::<Bot>::has_drifted(&config_map, &desired_config_map));
+        assert!(!ResourceDrift::<Bot>::has_drifted(&desired_config_map, &desired_config_map));
+    }
+
+    #[test]
+    fn pod_annotations_change_drifts() {
+        let config = AppConfig::default();
+        // Only v1alpha2 has deployment.podAnnotations
+        let deployment = |pod_annotations: Option<BTreeMap<String, String>>| {
+            let mut bot = bot(json!({}));
+            bot.spec.deployment.pod_annotations = pod_annotations;
+            Deployment::from_hub(&bot, "bot", "default", OwnerReference::default(), &config)
+        };
+        let scrape = |value: &str| Some(BTreeMap::from([("prometheus.io/scrape".to_string(), value.to_string())]));
+        let live = deployment(scrape("true"));
+
+        assert!(ResourceDrift::<Bot>::has_drifted(&live, &deployment(scrape("false"))));
+        assert!(ResourceDrift::<Bot>::has_drifted(&deployment(None), &live));
+        assert!(!ResourceDrift::<Bot>::has_drifted(&live, &deployment(scrape("true"))));
+
+        // The restartedAt annotation of a rollout isn't part of the desired state
+        let mut restarted = live.clone();
+        restarted.spec.as_mut().unwrap().template.metadata.as_mut().unwrap().annotations.as_mut().unwrap()
+            .insert("kubectl.kubernetes.io/restartedAt".to_string(), "2026-01-01T00:00:00Z".to_string());
+        assert!(!ResourceDrift::<Bot>::has_drifted(&restarted, &live));
+    }
+
+    #[test]
+    fn literal_chat_id_is_rendered_as_a_value() {
+        let bot = bot(json!({ "secrets": { "telegram": { "chatId": "-1001234" } } }));
+        let chat_id = bot_env_vars(&bot, "bot", &AppConfig::default())
+            .into_iter()
+            .find(|env_var| env_var.name == "FREQTRADE__TELEGRAM__CHAT_ID")
+            .unwrap();
+
+        assert_eq!(chat_id.value.as_deref(), Some("-1001234"));
+        assert_eq!(chat_id.value_from, None);
+    }
+}
@@ -0,0 +1,204 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use http_body_util::{BodyExt, Full};
+use hyper::{header::AUTHORIZATION, Method, Request};
+use hyper_util::{client::legacy::{connect::HttpConnector, Client as HttpClient}, rt::TokioExecutor};
+use kube::Client;
+use serde_json::Value;
+use tokio::time::{timeout, Duration};
+
+use crate::controller::utils::resolve_secret_item;
+use crate::crd::hub::bot::Bot;
+
+/// The HTTP client the controller calls the bots' REST API with
+pub type ApiClient = HttpClient<HttpConnector, Full<Bytes>>;
+
+/// The trades and profit of a bot, as reported by its API
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TradingSummary {
+    pub open_trades: Option<i64>,
+    pub closed_trades: Option<i64>,
+    pub profit_abs: Option<f64>,
+    pub profit_ratio: Option<f64>,
+    pub reported_at: Option<DateTime<Utc>>,
+}
+
+
+/// Create the HTTP client to call the bots' REST API with
+pub fn api_client() -> ApiClient {
+    HttpClient::builder(TokioExecutor::new()).build_http::<Full<Bytes>>()
+}
+
+/// The base URL of a bot's REST API, reached through its Service
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+/// * `name` - The name of the bot's Service
+/// * `namespace` - The namespace of the bot
+pub fn api_base_url(bot: &Bot, name: &str, namespace: &str) -> String {
+    let port = bot.spec.service.ports
+        .iter()
+        .find(|port| port.name == "api")
+        .map_or(bot.spec.api.port, |port| port.port);
+
+    format!("http://{}.{}.svc:{}/api/v1", name, namespace, port)
+}
+
+/// The Authorization header for a bot's REST API, from its API username and password
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+/// * `client` - The kube client, to read the credentials referenced through secretKeyRefs
+/// * `namespace` - The namespace of the bot
+///
+/// # Returns
+/// A Result containing the header, or why it couldn't be built
+pub async fn api_authorization(bot: &Bot, client: &Client, namespace: &str) -> Result<String, String> {
+    let api = bot.spec.secrets.api.as_ref();
+    let username = match api.and_then(|api| api.username.as_ref()) {
+        Some(username) => resolve_secret_item(client, namespace, username).await.map_err(|e| e.to_string())?,
+        None => None,
+    };
+    let password = match api.and_then(|api| api.password.as_ref()) {
+        Some(password) => resolve_secret_item(client, namespace, password).await.map_err(|e| e.to_string())?,
+        None => None,
+    };
+    let (Some(username), Some(password)) = (username, password) else {
+        return Err("the bot's API username and password are not set".to_string());
+    };
+
+    Ok(format!("Basic {}", STANDARD.encode(format!("{}:{}", username, password))))
+}
+
+/// Call an endpoint of a bot's REST API
+///
+/// # Arguments
+/// * `http` - The HTTP client
+/// * `url` - The URL of the endpoint
+/// * `method` - The HTTP method
+/// * `authorization` - The Authorization header
+/// * `request_timeout` - How long the call may take
+///
+/// # Returns
+/// A Result containing the JSON response, or why the call failed
+pub async fn api_request(
+    http: &ApiClient,
+    url: &str,
+    method: Method,
+    authorization: &str,
+    request_timeout: Duration,
+) -> Result<Value, String> {
+    let request = Request::builder()
+        .method(method)
+        .uri(url)
+        .header(AUTHORIZATION, authorization)
+        .body(Full::new(Bytes::new()))
+        .map_err(|e| e.to_string())?;
+
+    let response = timeout(request_timeout, http.request(request))
+        .await
+        .map_err(|_| format!("{} timed out", url))?
+        .map_err(|e| format!("{} is unreachable: {}", url, e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("{} responded with {}", url, response.status()));
+    }
+
+    let body = response.into_body().collect().await.map_err(|e| e.to_string())?.to_bytes();
+    serde_json::from_slice(&body).map_err(|e| e.to_string())
+}
+
+/// Check a bot's REST API responds to `/ping`
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+/// * `client` - The kube client, to read the API credentials
+/// * `http` - The HTTP client
+/// * `name` - The name of the bot's Service
+/// * `namespace` - The namespace of the bot
+/// * `request_timeout` - How long the call may take
+///
+/// # Returns
+/// A Result with why the API isn't healthy as the error
+pub async fn ping_bot(
+    bot: &Bot,
+    client: &Client,
+    http: &ApiClient,
+    name: &str,
+    namespace: &str,
+    request_timeout: Duration,
+) -> Result<(), String> {
+    // `/ping` doesn't require authentication, the credentials are only sent when set
+    let authorization = api_authorization(bot, client, namespace).await.unwrap_or_default();
+    let url = format!("{}/ping", api_base_url(bot, name, namespace));
+
+    match api_request(http, &url, Method::GET, &authorization, request_timeout).await?.get("status") {
+        Some(Value::String(status)) if status == "pong" => Ok(()),
+        _ => Err(format!("{} didn't respond with pong", url)),
+    }
+}
+
+/// The version of freqtrade a bot runs, from its REST API's `/version`
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+/// * `client` - The kube client, to read the API credentials
+/// * `http` - The HTTP client
+/// * `name` - The name of the bot's Service
+/// * `namespace` - The namespace of the bot
+/// * `request_timeout` - How long the call may take
+///
+/// # Returns
+/// A Result containing the version, or why it couldn't be read
+pub async fn bot_version(
+    bot: &Bot,
+    client: &Client,
+    http: &ApiClient,
+    name: &str,
+    namespace: &str,
+    request_timeout: Duration,
+) -> Result<String, String> {
+    let authorization = api_authorization(bot, client, namespace).await?;
+    let url = format!("{}/version", api_base_url(bot, name, namespace));
+
+    match api_request(http, &url, Method::GET, &authorization, request_timeout).await?.get("version") {
+        Some(Value::String(version)) => Ok(version.clone()),
+        _ => Err(format!("{} didn't respond with a version", url)),
+    }
+}
+
+/// The trades and profit of a bot, from its REST API's `/count` and `/profit`
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+/// * `client` - The kube client, to read the API credentials
+/// * `http` - The HTTP client
+/// * `name` - The name of the bot's Service
+/// * `namespace` - The namespace of the bot
+/// * `request_timeout` - How long each call may take
+///
+/// # Returns
+/// A Result containing the summary, or why it couldn't be read
+pub async fn bot_trading_summary(
+    bot: &Bot,
+    client: &Client,
+    http: &ApiClient,
+    name: &str,
+    namespace: &str,
+    request_timeout: Duration,
+) -> Result<TradingSummary, String> {
+    let authorization = api_authorization(bot, client, namespace).await?;
+    let base_url = api_base_url(bot, name, namespace);
+
+    let count = api_request(http, &format!("{}/count", base_url), Method::GET, &authorization, request_timeout).await?;
+    let profit = api_request(http, &format!("{}/profit", base_url), Method::GET, &authorization, request_timeout).await?;
+
+    Ok(TradingSummary {
+        open_trades: count.get("current").and_then(Value::as_i64),
+        closed_trades: profit.get("closed_trade_count").and_then(Value::as_i64),
+        profit_abs: profit.get("profit_closed_coin").and_then(Value::as_f64),
+        profit_ratio: profit.get("profit_closed_ratio").and_then(Value::as_f64),
+        reported_at: Some(Utc::now()),
+    })
+}
@@ -0,0 +1,390 @@
+use kube::{
+    api::{Api, DeleteParams, ListParams, ObjectMeta, ResourceExt},
+    runtime::{
+        controller::{Action, Controller},
+        watcher,
+    },
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use ft_operator_common::telemetry::info;
+
+use crate::controller::{context::Context, utils::{apply, delete, metadata_drifted, patch_status, runtime_config, scoped_api}};
+use crate::crd::{
+    NamespacedCustomResource,
+    hub::{bot::{BotPhase, BotSpec}, bot_fleet::{BotFleet, BotFleetInstance}},
+    v1alpha1::bot::{Bot as V1Alpha1Bot, BotSpec as V1Alpha1BotSpec},
+};
+use crate::error::{ControllerError, Result};
+
+pub static FLEET_NAME_LABEL: &str = "freqtrade.io/fleet-name";
+
+
+/// The name of the Bot created for a fleet instance
+///
+/// # Arguments
+/// * `fleet_name` - The name of the fleet
+/// * `instance` - The fleet instance
+pub fn fleet_bot_name(fleet_name: &str, instance: &BotFleetInstance) -> String {
+    format!("{}-{}", fleet_name, instance.name_suffix)
+}
+
+/// Create the Bot of a fleet instance from the fleet's template
+///
+/// The instance's exchange and exchange credentials replace the template's, and its config is
+/// applied to the template's config as a JSON merge patch. Bots are created at their storage version.
+///
+/// # Arguments
+/// * `fleet` - The BotFleet Hub
+/// * `instance` - The fleet instance to create the Bot for
+/// * `namespace` - The namespace of the fleet
+/// * `owner_ref` - The owner reference for the Bot
+///
+/// # Returns
+/// The Bot resource
+pub fn fleet_bot(fleet: &BotFleet, instance: &BotFleetInstance, namespace: &str, owner_ref: OwnerReference) -> V1Alpha1Bot {
+    let fleet_name = fleet.metadata.name.clone().unwrap_or_default();
+    let template = &fleet.spec.template;
+    let mut spec: BotSpec = template.spec.clone();
+
+    if let Some(exchange) = &instance.exchange {
+        spec.exchange = exchange.clone();
+    }
+    if let Some(exchange_ref) = &instance.exchange_ref {
+        spec.secrets.exchange = None;
+        spec.secrets.exchange_ref = Some(exchange_ref.clone());
+    }
+    if let Some(config_patch) = &instance.config {
+        let mut config = json!(spec.config.clone().unwrap_or_default());
+        merge_patch(&mut config, &json!(config_patch));
+        spec.config = serde_json::from_value(config).ok();
+    }
+
+    let mut bot = V1Alpha1Bot::new(&fleet_bot_name(&fleet_name, instance), V1Alpha1BotSpec::from(spec));
+    bot.metadata = ObjectMeta {
+        name: bot.metadata.name,
+        namespace: Some(namespace.to_string()),
+        owner_references: Some(vec![owner_ref]),
+        labels: Some(
+            template.metadata.labels
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .chain([(FLEET_NAME_LABEL.to_string(), fleet_name)])
+                .collect()
+        ),
+        annotations: template.metadata.annotations.clone(),
+        ..Default::default()
+    };
+
+    bot
+}
+
+/// Apply a JSON merge patch (RFC 7386) to a value
+///
+/// # Arguments
+/// * `target` - The value to patch
+/// * `patch` - The patch to apply, `null` values remove the key
+fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+
+    if !target.is_object() {
+        *target = json!({});
+    }
+
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+/// Whether a Bot is owned by the object with the given UID
+///
+/// # Arguments
+/// * `bot` - The Bot
+/// * `uid` - The UID of the owner
+fn owned_by(bot: &V1Alpha1Bot, uid: &str) -> bool {
+    bot.owner_references().iter().any(|owner_ref| owner_ref.uid == uid)
+}
+
+pub struct BotFleetController;
+
+impl BotFleetController {
+    /// Create a new controller for the BotFleet resource
+    ///
+    /// The controller owns the fleet's Bots so their phases are aggregated as they change.
+    ///
+    /// # Arguments
+    /// * `ctx` - The controller context
+    /// * `namespace` - The namespace to watch, or `None` to watch all namespaces
+    ///
+    /// # Returns
+    /// The controller for the BotFleet resource
+    pub async fn create_controller<T>(ctx: Arc<Context>, namespace: Option<&str>) -> Controller<T>
+    where
+        T: NamespacedCustomResource,
+        BotFleet: From<T>,
+    {
+        let client = ctx.client.clone();
+        let bot_fleet = scoped_api::<T>(client.clone(), namespace);
+        let bot = scoped_api::<V1Alpha1Bot>(client.clone(), namespace);
+
+        Controller::new(bot_fleet, watcher::Config::default())
+            .with_config(runtime_config(&ctx))
+            .owns(bot, watcher::Config::default())
+    }
+
+    /// Reconcile the BotFleet resource
+    ///
+    /// This function is responsible for creating, updating and deleting the fleet's Bots and
+    /// aggregating their phases into the fleet status. The Bots are garbage collected through
+    /// their owner reference when the fleet is deleted.
+    ///
+    /// # Arguments
+    /// * `bot_fleet` - The BotFleet resource to reconcile
+    /// * `ctx` - The controller context
+    ///
+    /// # Returns
+    /// An action to take after reconciling the BotFleet resource
+    pub async fn reconcile<T>(bot_fleet: Arc<T>, ctx: Arc<Context>) -> Result<Action>
+    where
+        T: NamespacedCustomResource,
+        BotFleet: From<T>,
+    {
+        let _in_flight = ctx.track_reconcile();
+        let namespace = bot_fleet.namespace().ok_or(
+            ControllerError::MissingObjectKeyError(
+                "Expected BotFleet to be namespaced via metadata.namespace"
+            )
+        )?;
+        let owner_ref = bot_fleet.controller_owner_ref(&()).ok_or_else(|| {
+            ControllerError::MissingObjectKeyError(
+                "Expected BotFleet to have an owner reference"
+            )
+        })?;
+        let name = bot_fleet.name_any();
+        let hub = BotFleet::from(bot_fleet.as_ref().clone());
+        let bot_api = Api::<V1Alpha1Bot>::namespaced(ctx.client.clone(), &namespace);
+
+        let mut bots: BTreeMap<String, V1Alpha1Bot> = bot_api
+            .list(&ListParams::default().labels(&format!("{}={}", FLEET_NAME_LABEL, name)))
+            .await?
+            .items
+            .into_iter()
+            .map(|bot| (bot.name_any(), bot))
+            .collect();
+
+        let mut ready_bots = 0;
+        for instance in &hub.spec.instances {
+            let bot_name = fleet_bot_name(&name, instance);
+            let bot_object = fleet_bot(&hub, instance, &namespace, owner_ref.clone());
+
+            let bot = match bots.remove(&bot_name) {
+                // The labels and annotations added by others, such as the mutating webhook's, are left alone
+                Some(bot) if bot.spec == bot_object.spec && !metadata_drifted(&bot.metadata, &bot_object.metadata) => bot,
+                _ => {
+                    info!(
+                        event = "ApplyingFleetBot",
+                        bot_fleet = name.as_str(),
+                        bot = bot_name.as_str()
+                    );
+                    apply(&bot_api, bot_object, &bot_name).await?
+                },
+            };
+
+            if bot.status.is_some_and(|status| status.phase == BotPhase::Running.to_string()) {
+                ready_bots += 1;
+            }
+        }
+
+        // The Bots left over belong to removed instances, unless the fleet doesn't own them. Any Bot
+        // can carry the fleet's label.
+        for bot_name in bots.values().filter(|bot| owned_by(bot, &owner_ref.uid)).map(ResourceExt::name_any) {
+            info!(
+                event = "DeletingFleetBot",
+                bot_fleet = name.as_str(),
+                bot = bot_name.as_str()
+            );
+            delete(&bot_api, &bot_name, &DeleteParams::default()).await?;
+        }
+
+        let total_bots = hub.spec.instances.len() as u32;
+        let unchanged = hub.status.as_ref().is_some_and(|status| {
+            status.ready_bots == ready_bots && status.total_bots == total_bots
+        });
+
+        if !unchanged {
+            patch_status(&Api::<T>::namespaced(ctx.client.clone(), &namespace), &name, json!({
+                "readyBots": ready_bots,
+                "totalBots": total_bots,
+                "lastUpdated": Utc::now(),
+            })).await?;
+        }
+
+        Ok(Action::await_change())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::StatusCode;
+    use kube::Resource;
+    use ft_operator_common::{config::AppConfig, state::State};
+    use crate::controller::mock::mock_client;
+    use crate::crd::v1alpha1::bot_fleet::BotFleet as V1Alpha1BotFleet;
+
+    fn fleet(instances: Value) -> V1Alpha1BotFleet {
+        serde_json::from_value(json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "BotFleet",
+            "metadata": { "name": "fleet", "namespace": "default", "uid": "fleet-uid" },
+            "spec": {
+                "template": {
+                    "metadata": { "labels": { "team": "trading" } },
+                    "spec": {
+                        "exchange": "binance",
+                        "strategy": { "name": "SampleStrategy" },
+                        "config": { "stake_currency": "USDT", "max_open_trades": 3, "unfilledtimeout": { "entry": 10, "exit": 10 } },
+                        "secrets": { "exchange": { "key": { "value": "key" }, "secret": { "value": "secret" } } },
+                    },
+                },
+                "instances": instances,
+            },
+        })).unwrap()
+    }
+
+    fn owner_ref(fleet: &V1Alpha1BotFleet) -> OwnerReference {
+        fleet.controller_owner_ref(&()).unwrap()
+    }
+
+    #[test]
+    fn fleet_bot_applies_the_instance_to_the_template() {
+        let fleet = fleet(json!([{
+            "nameSuffix": "kraken",
+            "exchange": "kraken",
+            "exchangeRef": { "name": "kraken-credentials" },
+            "config": { "max_open_trades": null, "unfilledtimeout": { "exit": 30 }, "dry_run": true },
+        }]));
+        let hub = BotFleet::from(fleet.clone());
+
+        let bot = fleet_bot(&hub, &hub.spec.instances[0], "default", owner_ref(&fleet));
+
+        assert_eq!(bot.metadata.name.as_deref(), Some("fleet-kraken"));
+        assert_eq!(bot.metadata.namespace.as_deref(), Some("default"));
+        assert_eq!(bot.metadata.owner_references, Some(vec![owner_ref(&fleet)]));
+        assert_eq!(bot.metadata.labels, Some(BTreeMap::from([
+            (FLEET_NAME_LABEL.to_string(), "fleet".to_string()),
+            ("team".to_string(), "trading".to_string()),
+        ])));
+        assert_eq!(bot.spec.exchange, "kraken");
+        // The instance's credentials replace the template's
+        assert_eq!(bot.spec.secrets.exchange, None);
+        assert_eq!(bot.spec.secrets.exchange_ref.as_ref().map(|exchange_ref| exchange_ref.name.as_str()), Some("kraken-credentials"));
+        assert_eq!(json!(bot.spec.config), json!({
+            "stake_currency": "USDT",
+            "unfilledtimeout": { "entry": 10, "exit": 30 },
+            "dry_run": true,
+        }));
+    }
+
+    #[test]
+    fn fleet_bot_without_overrides_is_the_template() {
+        let fleet = fleet(json!([{ "nameSuffix": "a" }]));
+        let hub = BotFleet::from(fleet.clone());
+
+        let bot = fleet_bot(&hub, &hub.spec.instances[0], "default", owner_ref(&fleet));
+
+        assert_eq!(bot.spec, V1Alpha1BotSpec::from(hub.spec.template.spec.clone()));
+    }
+
+    #[test]
+    fn merge_patch_follows_rfc_7386() {
+        let cases = [
+            (json!({ "a": "b" }), json!({ "a": "c" }), json!({ "a": "c" })),
+            (json!({ "a": "b" }), json!({ "b": "c" }), json!({ "a": "b", "b": "c" })),
+            (json!({ "a": "b", "b": "c" }), json!({ "a": null }), json!({ "b": "c" })),
+            (json!({ "a": { "b": "c", "d": "e" } }), json!({ "a": { "d": null, "f": "g" } }), json!({ "a": { "b": "c", "f": "g" } })),
+            (json!({ "a": ["b"] }), json!({ "a": ["c", "d"] }), json!({ "a": ["c", "d"] })),
+            (json!({ "a": "b" }), json!(["c"]), json!(["c"])),
+            (json!({ "a": "foo" }), json!("bar"), json!("bar")),
+            (json!(["a"]), json!({ "a": "b" }), json!({ "a": "b" })),
+            (json!({ "e": null }), json!({ "a": 1 }), json!({ "e": null, "a": 1 })),
+            (json!({}), json!({ "a": { "bb": { "ccc": null } } }), json!({ "a": { "bb": {} } })),
+        ];
+
+        for (target, patch, expected) in cases {
+            let mut patched = target.clone();
+            merge_patch(&mut patched, &patch);
+            assert_eq!(patched, expected, "{} patched with {}", target, patch);
+        }
+    }
+
+    fn listed_bot(fleet: &V1Alpha1BotFleet, name_suffix: &str, owned: bool, phase: BotPhase) -> Value {
+        let hub = BotFleet::from(fleet.clone());
+        let instance = BotFleetInstance { name_suffix: name_suffix.to_string(), exchange: None, exchange_ref: None, config: None };
+        let mut bot = serde_json::to_value(fleet_bot(&hub, &instance, "default", owner_ref(fleet))).unwrap();
+        if !owned {
+            bot["metadata"]["ownerReferences"] = json!([]);
+        }
+        // Added by the mutating webhook
+        bot["metadata"]["labels"]["app.kubernetes.io/managed-by"] = json!("freqtrade-operator");
+        bot["status"] = json!({ "phase": phase.to_string() });
+        bot
+    }
+
+    #[tokio::test]
+    async fn reconcile_syncs_the_fleet_bots_and_counts_the_running_ones() {
+        let fleet = fleet(json!([{ "nameSuffix": "a" }, { "nameSuffix": "b" }, { "nameSuffix": "c" }]));
+        let bots = json!([
+            // Unchanged, only labelled by the mutating webhook
+            listed_bot(&fleet, "a", true, BotPhase::Running),
+            listed_bot(&fleet, "b", true, BotPhase::Pending),
+            // A removed instance
+            listed_bot(&fleet, "removed", true, BotPhase::Running),
+            // Labelled by a user, not owned by the fleet
+            listed_bot(&fleet, "user", false, BotPhase::Running),
+        ]);
+        let fleet_object = serde_json::to_value(&fleet).unwrap();
+        let (client, mut requests) = mock_client(move |request| match request.method.as_str() {
+            "GET" => (StatusCode::OK, json!({ "metadata": { "resourceVersion": "1" }, "items": bots })),
+            "DELETE" => (StatusCode::OK, json!({ "kind": "Status", "apiVersion": "v1", "status": "Success" })),
+            _ if request.path.ends_with("/status") => (StatusCode::OK, fleet_object.clone()),
+            _ => (StatusCode::OK, request.body.clone()),
+        });
+        let ctx = Arc::new(Context::new(client, Arc::new(State::new(AppConfig::default()))));
+
+        BotFleetController::reconcile(Arc::new(fleet), ctx).await.unwrap();
+
+        let mut sent = vec![];
+        while let Ok(request) = requests.try_recv() {
+            sent.push((request.method.to_string(), request.path, request.body));
+        }
+        let bots_path = "/apis/freqtrade.io/v1alpha1/namespaces/default/bots";
+        let changes: Vec<(&str, &str)> = sent.iter()
+            .filter(|(method, _, _)| method != "GET")
+            .map(|(method, path, _)| (method.as_str(), path.as_str()))
+            .collect();
+        assert_eq!(changes, vec![
+            ("PATCH", format!("{}/fleet-c", bots_path).as_str()),
+            ("DELETE", format!("{}/fleet-removed", bots_path).as_str()),
+            ("PATCH", "/apis/freqtrade.io/v1alpha1/namespaces/default/botfleets/fleet/status"),
+        ]);
+
+        // The created Bot has no status yet
+        let status = &sent.last().unwrap().2["status"];
+        assert_eq!((&status["readyBots"], &status["totalBots"]), (&json!(1), &json!(3)));
+    }
+}
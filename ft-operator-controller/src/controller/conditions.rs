@@ -0,0 +1,166 @@
+use chrono::{DateTime, Utc};
+
+use crate::crd::hub::common::Condition;
+
+
+/// The status conditions of a resource being reconciled
+///
+/// Conditions are collected through a reconcile and patched into the status at once, since a
+/// merge patch replaces the whole array and patching them one at a time from the same snapshot
+/// of the resource would drop the ones set earlier.
+#[derive(Clone, Debug, Default)]
+pub struct Conditions {
+    conditions: Vec<Condition>,
+    changed: bool,
+}
+
+impl Conditions {
+    /// Create the conditions from the ones currently in the status
+    ///
+    /// # Arguments
+    /// * `conditions` - The conditions of the resource's status
+    pub fn new(conditions: Option<Vec<Condition>>) -> Self {
+        Self { conditions: conditions.unwrap_or_default(), changed: false }
+    }
+
+    /// Set a condition, replacing any existing condition of the same type
+    ///
+    /// The last transition time is only bumped when the condition status changes.
+    ///
+    /// # Arguments
+    /// * `condition` - The condition to set
+    pub fn set(&mut self, condition: Condition) {
+        match self.conditions.iter_mut().find(|c| c.type_ == condition.type_) {
+            Some(existing) if existing.status == condition.status
+                && existing.reason == condition.reason
+                && existing.message == condition.message => (),
+            Some(existing) => {
+                let last_transition_time = match existing.status == condition.status {
+                    true => existing.last_transition_time,
+                    false => Some(Utc::now()),
+                };
+                *existing = Condition { last_transition_time, ..condition };
+                self.changed = true;
+            },
+            None => {
+                self.conditions.push(Condition { last_transition_time: Some(Utc::now()), ..condition });
+                self.changed = true;
+            },
+        }
+    }
+
+    /// Set a condition tracking the steps of a process, e.g. the cleanup of a bot
+    ///
+    /// The last transition time is also bumped when the reason changes, so it is the time the
+    /// current step started.
+    ///
+    /// # Arguments
+    /// * `condition` - The condition to set, its reason naming the step
+    pub fn set_step(&mut self, condition: Condition) {
+        match self.conditions.iter_mut().find(|c| c.type_ == condition.type_) {
+            Some(existing) if existing.reason != condition.reason => {
+                *existing = Condition { last_transition_time: Some(Utc::now()), ..condition };
+                self.changed = true;
+            },
+            _ => self.set(condition),
+        }
+    }
+
+    /// The time the current step of a condition set with `set_step` started
+    ///
+    /// # Arguments
+    /// * `type_` - The type of the condition
+    pub fn step_started(&self, type_: &str) -> Option<DateTime<Utc>> {
+        self.get(type_).and_then(|condition| condition.last_transition_time)
+    }
+
+    /// Remove the condition of a type, if any
+    ///
+    /// # Arguments
+    /// * `type_` - The type of the condition to remove
+    pub fn remove(&mut self, type_: &str) {
+        let len = self.conditions.len();
+        self.conditions.retain(|c| c.type_ != type_);
+        self.changed |= self.conditions.len() != len;
+    }
+
+    /// The condition of a type, if any
+    ///
+    /// # Arguments
+    /// * `type_` - The type of the condition
+    pub fn get(&self, type_: &str) -> Option<&Condition> {
+        self.conditions.iter().find(|c| c.type_ == type_)
+    }
+
+    /// Whether the condition of a type is `True`
+    ///
+    /// # Arguments
+    /// * `type_` - The type of the condition
+    pub fn is_true(&self, type_: &str) -> bool {
+        self.conditions.iter().any(|c| c.type_ == type_ && c.status == "True")
+    }
+
+    /// Whether any condition was set or removed since the conditions were created
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+
+    /// The conditions to write to the status
+    pub fn as_slice(&self) -> &[Condition] {
+        &self.conditions
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(reason: &str, message: &str) -> Condition {
+        Condition {
+            type_: "Deleting".to_string(),
+            status: "True".to_string(),
+            reason: Some(reason.to_string()),
+            message: Some(message.to_string()),
+            last_transition_time: None,
+        }
+    }
+
+    #[test]
+    fn set_step_keeps_the_start_of_the_current_step() {
+        let started = Utc::now() - chrono::TimeDelta::seconds(60);
+        let mut conditions = Conditions::new(Some(vec![Condition {
+            last_transition_time: Some(started),
+            ..condition("WaitingForPods", "Waiting for 2 pods")
+        }]));
+
+        conditions.set_step(condition("WaitingForPods", "Waiting for 1 pod"));
+
+        assert_eq!(conditions.step_started("Deleting"), Some(started));
+        assert!(conditions.changed());
+    }
+
+    #[test]
+    fn set_step_restarts_on_a_new_step() {
+        let started = Utc::now() - chrono::TimeDelta::seconds(60);
+        let mut conditions = Conditions::new(Some(vec![Condition {
+            last_transition_time: Some(started),
+            ..condition("StoppingBot", "Waiting for trades")
+        }]));
+
+        conditions.set_step(condition("WaitingForPods", "Waiting for pods"));
+
+        assert!(conditions.step_started("Deleting").is_some_and(|time| time > started));
+    }
+
+    #[test]
+    fn set_keeps_the_transition_time_on_a_new_reason() {
+        let started = Utc::now() - chrono::TimeDelta::seconds(60);
+        let mut conditions = Conditions::new(Some(vec![Condition {
+            last_transition_time: Some(started),
+            ..condition("StoppingBot", "Waiting for trades")
+        }]));
+
+        conditions.set(condition("WaitingForPods", "Waiting for pods"));
+
+        assert_eq!(conditions.step_started("Deleting"), Some(started));
+    }
+}
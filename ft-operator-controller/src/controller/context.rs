@@ -1,22 +1,308 @@
-use kube::Client;
-use std::sync::Arc;
+use kube::{
+    api::DynamicObject,
+    runtime::{controller::{Action, Error as RunError}, events::{Event, EventType, Recorder, Reporter}, reflector::ObjectRef},
+    Client, Resource,
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError, atomic::{AtomicI64, AtomicUsize, Ordering}};
+use tokio::time::Duration;
 
+use ft_operator_common::config::ControllerConfig;
+use ft_operator_common::constant::APP_NAME;
 use ft_operator_common::state::State;
+use ft_operator_common::telemetry::error;
 
-// Context struct to hold the kube client and the state
+use crate::controller::{bot_api::{api_client, ApiClient}, metrics::BotMetrics, store::BotChildStores};
+use crate::crd::hub::bot::BotPhase;
+use crate::error::ControllerError;
+
+// Context struct to hold the kube client, the HTTP client for the bots' API, the event
+// recorder, the cached child resources of the bots, the error backoffs, the controller
+// activity, the per-bot metrics and the state
 #[derive(Clone)]
 pub struct Context {
     pub client: Client,
-    pub state: Option<Arc<State>>,
+    pub http: ApiClient,
+    pub recorder: Recorder,
+    pub state: Arc<State>,
+    // The PVCs, Deployments, Services and referenced Secrets watched by the Bot controllers
+    pub child_stores: Arc<BotChildStores>,
+    // Consecutive failed reconciles per object, shared by all controllers
+    backoffs: Arc<Mutex<HashMap<ObjectRef<DynamicObject>, u32>>>,
+    // Unix timestamp of the last reconcile result of any controller, for the readiness probe
+    last_activity: Arc<AtomicI64>,
+    // Number of reconciles currently running across all controllers, exposed as a metric
+    in_flight: Arc<AtomicUsize>,
+    // The phase and reconciles of every bot, exposed as metrics
+    bot_metrics: Arc<BotMetrics>,
 }
 
 impl Context {
-    pub fn new(client: Client) -> Self {
-        Self { client, state: None }
+    pub fn new(client: Client, state: Arc<State>) -> Self {
+        let recorder = Recorder::new(client.clone(), Reporter::from(APP_NAME));
+        let last_activity = Arc::new(AtomicI64::new(Utc::now().timestamp()));
+        Self {
+            client,
+            http: api_client(),
+            recorder,
+            state,
+            child_stores: Arc::default(),
+            backoffs: Arc::default(),
+            last_activity,
+            in_flight: Arc::default(),
+            bot_metrics: Arc::default(),
+        }
+    }
+
+    /// Publish a Kubernetes Event on an object
+    ///
+    /// Events are best effort, a failure to publish is logged rather than failing the reconcile.
+    ///
+    /// # Arguments
+    /// * `object` - The object the Event is about
+    /// * `type_` - Whether the Event is Normal or a Warning
+    /// * `reason` - The reason of the Event, e.g. `ApplyingDeployment`
+    /// * `action` - The action taken, e.g. `Apply`
+    /// * `note` - A human readable description of the Event
+    pub async fn publish_event<T>(&self, object: &T, type_: EventType, reason: &str, action: &str, note: Option<String>)
+    where
+        T: Resource<DynamicType = ()>,
+    {
+        let event = Event {
+            type_,
+            reason: reason.to_string(),
+            note,
+            action: action.to_string(),
+            secondary: None,
+        };
+
+        if let Err(e) = self.recorder.publish(&event, &object.object_ref(&())).await {
+            error!(
+                event = "PublishEventFailed",
+                reason = reason,
+                error = %e,
+            );
+        }
+    }
+
+    /// Record a failed reconcile of an object and get how long to wait before retrying it
+    ///
+    /// The delay starts at `error_backoff_base_seconds` and doubles with each consecutive
+    /// failure, up to `error_backoff_max_seconds`.
+    ///
+    /// # Arguments
+    /// * `object` - The reference of the object that failed to reconcile
+    ///
+    /// # Returns
+    /// The delay before the object is reconciled again
+    pub fn next_backoff<K>(&self, object: &ObjectRef<K>) -> Duration
+    where
+        K: Resource<DynamicType = ()>,
+    {
+        let mut backoffs = self.backoffs.lock().unwrap_or_else(PoisonError::into_inner);
+        let failures = backoffs.entry(object.clone().erase()).or_insert(0);
+        *failures = failures.saturating_add(1);
+
+        backoff_delay(&self.controller_config(), *failures)
+    }
+
+    /// Reset the backoff of an object after it reconciled successfully
+    ///
+    /// # Arguments
+    /// * `object` - The reference of the object that reconciled
+    pub fn reset_backoff<K>(&self, object: &ObjectRef<K>)
+    where
+        K: Resource<DynamicType = ()>,
+    {
+        self.backoffs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&object.clone().erase());
+    }
+
+    /// Record that a controller emitted a reconcile result
+    pub fn record_activity(&self) {
+        self.last_activity.store(Utc::now().timestamp(), Ordering::Relaxed);
+    }
+
+    /// The time of the last reconcile result of any controller, or of the context's
+    /// creation when nothing was reconciled yet
+    pub fn last_activity(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp(self.last_activity.load(Ordering::Relaxed), 0).unwrap_or_default()
+    }
+
+    /// Count a reconcile as in flight until the returned guard is dropped
+    pub fn track_reconcile(&self) -> InFlightReconcile {
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        InFlightReconcile { in_flight: self.in_flight.clone() }
+    }
+
+    /// The number of reconciles currently running
+    pub fn reconciles_in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Record the phase of a bot in the per-bot metrics, unless they are disabled
+    ///
+    /// # Arguments
+    /// * `namespace` - The namespace of the bot
+    /// * `name` - The name of the bot
+    /// * `phase` - The phase of the bot
+    pub fn record_bot_phase(&self, namespace: &str, name: &str, phase: &BotPhase) {
+        if self.controller_config().per_bot_metrics {
+            self.bot_metrics.record_phase(namespace, name, phase);
+        }
+    }
+
+    /// Record a reconcile result of the Bot controller in the per-bot metrics, unless they are disabled
+    ///
+    /// # Arguments
+    /// * `result` - The reconcile result emitted by the controller
+    pub fn record_bot_reconcile<K, QueueErr>(&self, result: &std::result::Result<(ObjectRef<K>, Action), RunError<ControllerError, QueueErr>>)
+    where
+        K: Resource<DynamicType = ()>,
+        QueueErr: std::error::Error + 'static,
+    {
+        if !self.controller_config().per_bot_metrics {
+            return;
+        }
+
+        match result {
+            Ok((object, _)) => self.bot_metrics.record_reconcile(object.namespace.as_deref().unwrap_or_default(), &object.name, true),
+            Err(RunError::ReconcilerFailed(_, object)) => {
+                self.bot_metrics.record_reconcile(object.namespace.as_deref().unwrap_or_default(), &object.name, false)
+            },
+            // The errors of the controller's queue aren't about a bot
+            Err(_) => (),
+        }
+    }
+
+    /// Remove the per-bot metrics of a deleted bot
+    ///
+    /// # Arguments
+    /// * `namespace` - The namespace of the bot
+    /// * `name` - The name of the bot
+    pub fn remove_bot_metrics(&self, namespace: &str, name: &str) {
+        self.bot_metrics.remove(namespace, name);
     }
 
-    pub fn with_state(mut self, state: Arc<State>) -> Self {
-        self.state = Some(state);
-        self
+    /// Append the per-bot metrics in the Prometheus format, unless they are disabled
+    ///
+    /// # Arguments
+    /// * `metrics` - The metrics to append to
+    pub fn render_bot_metrics(&self, metrics: &mut String) {
+        if self.controller_config().per_bot_metrics {
+            self.bot_metrics.render(metrics);
+        }
     }
-}
\ No newline at end of file
+
+    pub fn controller_config(&self) -> ControllerConfig {
+        self.state.config().controller.clone()
+    }
+}
+
+/// A reconcile counted as in flight, until it is dropped
+pub struct InFlightReconcile {
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Drop for InFlightReconcile {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// The delay before retrying an object after consecutive failed reconciles
+///
+/// # Arguments
+/// * `config` - The controller configuration holding the backoff bounds
+/// * `failures` - The number of consecutive failures, starting at 1
+///
+/// # Returns
+/// The base delay doubled for every failure after the first, capped at the max delay
+pub fn backoff_delay(config: &ControllerConfig, failures: u32) -> Duration {
+    let factor = 2u64.checked_pow(failures.saturating_sub(1)).unwrap_or(u64::MAX);
+    let seconds = config.error_backoff_base_seconds
+        .saturating_mul(factor)
+        .min(config.error_backoff_max_seconds);
+
+    Duration::from_secs(seconds)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::StatusCode;
+    use serde_json::json;
+    use tokio::sync::mpsc::UnboundedReceiver;
+
+    use ft_operator_common::config::AppConfig;
+
+    use crate::controller::mock::{mock_client, MockRequest};
+    use crate::crd::v1alpha1::bot::Bot;
+
+    fn context(status: StatusCode) -> (Context, UnboundedReceiver<MockRequest>) {
+        // The API server echoes the Events it creates
+        let (client, requests) = mock_client(move |request| (status, request.body.clone()));
+        (Context::new(client, Arc::new(State::new(AppConfig::default()))), requests)
+    }
+
+    fn bot() -> Bot {
+        serde_json::from_value(json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "Bot",
+            "metadata": { "name": "bot", "namespace": "default", "uid": "bot-uid" },
+            "spec": { "exchange": "binance", "strategy": { "name": "SampleStrategy" } },
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn events_are_published_on_the_bot() {
+        let (ctx, mut requests) = context(StatusCode::CREATED);
+
+        ctx.publish_event(&bot(), EventType::Normal, "ApplyingDeployment", "Apply", Some("Applying Deployment `bot`".to_string())).await;
+        ctx.publish_event(&bot(), EventType::Normal, "DeletingService", "Delete", Some("Deleting Service `bot`".to_string())).await;
+
+        for (reason, action, note) in [
+            ("ApplyingDeployment", "Apply", "Applying Deployment `bot`"),
+            ("DeletingService", "Delete", "Deleting Service `bot`"),
+        ] {
+            let request = requests.recv().await.unwrap();
+            assert_eq!(request.method, "POST");
+            assert_eq!(request.path, "/apis/events.k8s.io/v1/namespaces/default/events");
+            assert_eq!(request.body["type"], json!("Normal"));
+            assert_eq!(request.body["reason"], json!(reason));
+            assert_eq!(request.body["action"], json!(action));
+            assert_eq!(request.body["note"], json!(note));
+            assert_eq!(request.body["regarding"]["kind"], json!("Bot"));
+            assert_eq!(request.body["regarding"]["name"], json!("bot"));
+            assert_eq!(request.body["regarding"]["uid"], json!("bot-uid"));
+        }
+    }
+
+    #[tokio::test]
+    async fn failing_to_publish_an_event_is_not_an_error() {
+        let (ctx, mut requests) = context(StatusCode::FORBIDDEN);
+
+        ctx.publish_event(&bot(), EventType::Warning, "ReconcileError", "Reconcile", None).await;
+        assert_eq!(requests.recv().await.unwrap().body["reason"], json!("ReconcileError"));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_max_delay() {
+        let config = ControllerConfig::default();
+        let delays: Vec<u64> = (1..=9).map(|failures| backoff_delay(&config, failures).as_secs()).collect();
+
+        assert_eq!(delays, vec![5, 10, 20, 40, 80, 160, 320, 600, 600]);
+    }
+
+    #[test]
+    fn backoff_saturates_after_many_failures() {
+        let config = ControllerConfig::default();
+
+        assert_eq!(backoff_delay(&config, 0), Duration::from_secs(5));
+        assert_eq!(backoff_delay(&config, 64), Duration::from_secs(600));
+        assert_eq!(backoff_delay(&config, u32::MAX), Duration::from_secs(600));
+    }
+}
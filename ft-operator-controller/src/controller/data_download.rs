@@ -0,0 +1,428 @@
+use kube::{
+    api::{Api, ObjectMeta, ResourceExt},
+    runtime::{
+        controller::{Action, Controller},
+        watcher,
+    },
+};
+use k8s_openapi::api::batch::v1::{CronJob, CronJobSpec, JobSpec, JobTemplateSpec};
+use k8s_openapi::api::core::v1::{
+    Container, LocalObjectReference, PersistentVolumeClaimVolumeSource, PodSpec, PodTemplateSpec,
+    Volume, VolumeMount,
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+use chrono::Utc;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use ft_operator_common::config::AppConfig;
+use ft_operator_common::telemetry::info;
+
+use crate::controller::{
+    bot::{create_env_var, create_secret_env_var, USER_DATA_PATH},
+    context::Context,
+    traits::{FromHub, ResourceDrift},
+    utils::{apply, merge_pull_secrets, metadata_drifted, patch_status, runtime_config, scoped_api, with_defaults},
+};
+use crate::crd::{NamespacedCustomResource, hub::{bot::BotImageSpec, data_download::DataDownload}};
+use crate::error::{ControllerError, Result};
+
+
+impl FromHub<DataDownload> for CronJob {
+    /// Create a CronJob resource from a DataDownload Hub
+    ///
+    /// The CronJob runs `freqtrade download-data` on the schedule, writing the data onto the
+    /// referenced PVC. Runs never overlap as downloads of the same data would conflict.
+    ///
+    /// # Arguments
+    /// * `data_download` - The DataDownload Hub
+    /// * `name` - The name of the CronJob
+    /// * `namespace` - The namespace of the CronJob
+    /// * `owner_ref` - The owner reference for the CronJob
+    /// * `config` - The application configuration
+    ///
+    /// # Returns
+    /// The CronJob resource
+    fn from_hub(data_download: &DataDownload, name: &str, namespace: &str, owner_ref: OwnerReference, config: &AppConfig) -> Self {
+        let spec = &data_download.spec;
+        // Without an image the controller's default image is used
+        let image = spec.image.clone().unwrap_or(BotImageSpec {
+            repository: None,
+            tag: None,
+            digest: None,
+            pull_policy: None,
+            pull_secrets: None,
+        });
+        let image_pull_secrets = merge_pull_secrets(&config.controller.default_image_pull_secrets, image.pull_secrets.clone());
+        let labels = BTreeMap::from([
+            ("freqtrade.io/data-download-name".to_string(), name.to_string()),
+            ("app.kubernetes.io/component".to_string(), "data-download".to_string()),
+            ("app.kubernetes.io/part-of".to_string(), "freqtrade".to_string()),
+            ("app.kubernetes.io/managed-by".to_string(), "freqtrade-operator".to_string()),
+        ]);
+
+        CronJob {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some(namespace.to_string()),
+                owner_references: Some(vec![owner_ref]),
+                annotations: with_defaults(&config.controller.default_annotations, None),
+                labels: with_defaults(&config.controller.default_labels, Some(labels.clone())),
+                ..Default::default()
+            },
+            spec: Some(CronJobSpec {
+                schedule: spec.schedule.clone(),
+                concurrency_policy: Some("Forbid".to_string()),
+                job_template: JobTemplateSpec {
+                    metadata: Some(ObjectMeta {
+                        labels: Some(labels),
+                        ..Default::default()
+                    }),
+                    spec: Some(JobSpec {
+                        backoff_limit: Some(0),
+                        template: PodTemplateSpec {
+                            spec: Some(PodSpec {
+                                restart_policy: Some("Never".to_string()),
+                                image_pull_secrets: image_pull_secrets.map(|secrets| secrets
+                                    .into_iter()
+                                    .map(|secret| LocalObjectReference { name: secret })
+                                    .collect()
+                                ),
+                                containers: vec![Container {
+                                    name: "download-data".to_string(),
+                                    image: Some(image.image_name(&config.controller.default_image_repo, &config.controller.default_image_tag)),
+                                    image_pull_policy: image.pull_policy.clone().or(config.controller.default_image_pull_policy.clone()),
+                                    command: Some(
+                                        vec![
+                                            "freqtrade".to_string(),
+                                            "download-data".to_string(),
+                                            "--userdir".to_string(),
+                                            USER_DATA_PATH.to_string(),
+                                            "--exchange".to_string(),
+                                            spec.exchange.clone(),
+                                            "--pairs".to_string(),
+                                        ]
+                                        .into_iter()
+                                        .chain(spec.pairs.clone())
+                                        .chain(
+                                            (!spec.timeframes.is_empty())
+                                                .then(|| std::iter::once("--timeframes".to_string()).chain(spec.timeframes.clone()))
+                                                .into_iter()
+                                                .flatten()
+                                        )
+                                        .chain(
+                                            spec.timerange
+                                                .as_ref()
+                                                .map(|timerange| vec!["--timerange".to_string(), timerange.clone()])
+                                                .into_iter()
+                                                .flatten()
+                                        )
+                                        .collect()
+                                    ),
+                                    env: Some(
+                                        vec![create_env_var("FREQTRADE__EXCHANGE__NAME", Some(spec.exchange.clone()))]
+                                            .into_iter()
+                                            .chain(
+                                                spec.exchange_secrets
+                                                    .iter()
+                                                    .flat_map(|exchange_secrets| [
+                                                        ("FREQTRADE__EXCHANGE__KEY", &exchange_secrets.key),
+                                                        ("FREQTRADE__EXCHANGE__SECRET", &exchange_secrets.secret),
+                                                        ("FREQTRADE__EXCHANGE__PASSWORD", &exchange_secrets.password),
+                                                        ("FREQTRADE__EXCHANGE__UID", &exchange_secrets.uid),
+                                                    ])
+                                                    .filter(|(_, secret_item)| secret_item.is_some())
+                                                    .map(|(env_name, secret_item)| create_secret_env_var(env_name, secret_item))
+                                            )
+                                            .collect()
+                                    ),
+                                    volume_mounts: Some(vec![VolumeMount {
+                                        name: "user-data".to_string(),
+                                        mount_path: USER_DATA_PATH.to_string(),
+                                        ..Default::default()
+                                    }]),
+                                    ..Default::default()
+                                }],
+                                volumes: Some(vec![Volume {
+                                    name: "user-data".to_string(),
+                                    persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+                                        claim_name: spec.pvc_name().unwrap_or_default(),
+                                        ..Default::default()
+                                    }),
+                                    ..Default::default()
+                                }]),
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    }),
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+impl ResourceDrift<DataDownload> for CronJob {
+    /// Determine if the CronJob resource has drifted from another CronJob resource
+    /// derived from the DataDownload CRD
+    ///
+    /// Only the fields set by the operator are compared, as the API server defaults the rest.
+    ///
+    /// # Arguments
+    /// * `other` - The other CronJob resource to compare against
+    ///
+    /// # Returns
+    /// Whether the CronJob resource has drifted from the other CronJob resource
+    fn has_drifted(&self, other: &Self) -> bool {
+        let schedule = |cron_job: &CronJob| cron_job.spec.as_ref().map(|spec| spec.schedule.clone());
+        let pod_spec = |cron_job: &CronJob| cron_job.spec
+            .as_ref()
+            .and_then(|spec| spec.job_template.spec.as_ref())
+            .and_then(|spec| spec.template.spec.clone());
+
+        if schedule(self) != schedule(other) || metadata_drifted(&self.metadata, &other.metadata) {
+            return true;
+        }
+
+        let (Some(self_pod_spec), Some(other_pod_spec)) = (pod_spec(self), pod_spec(other)) else {
+            return true;
+        };
+
+        if self_pod_spec.image_pull_secrets != other_pod_spec.image_pull_secrets {
+            return true;
+        }
+
+        let claim_names = |pod_spec: &PodSpec| pod_spec.volumes
+            .iter()
+            .flatten()
+            .filter_map(|volume| volume.persistent_volume_claim.as_ref().map(|pvc| pvc.claim_name.clone()))
+            .collect::<Vec<_>>();
+        if claim_names(&self_pod_spec) != claim_names(&other_pod_spec) {
+            return true;
+        }
+
+        let (Some(self_container), Some(other_container)) = (self_pod_spec.containers.first(), other_pod_spec.containers.first()) else {
+            return true;
+        };
+
+        self_container.image != other_container.image
+            || self_container.command != other_container.command
+            || self_container.env != other_container.env
+            || (other_container.image_pull_policy.is_some() && self_container.image_pull_policy != other_container.image_pull_policy)
+    }
+}
+
+pub struct DataDownloadController;
+
+impl DataDownloadController {
+    /// Create a new controller for the DataDownload resource
+    ///
+    /// # Arguments
+    /// * `ctx` - The controller context
+    /// * `namespace` - The namespace to watch, or `None` to watch all namespaces
+    ///
+    /// # Returns
+    /// The controller for the DataDownload resource
+    pub async fn create_controller<T>(ctx: Arc<Context>, namespace: Option<&str>) -> Controller<T>
+    where
+        T: NamespacedCustomResource,
+        DataDownload: From<T>,
+    {
+        let client = ctx.client.clone();
+        let data_download = scoped_api::<T>(client.clone(), namespace);
+        let cron_job = scoped_api::<CronJob>(client.clone(), namespace);
+
+        Controller::new(data_download, watcher::Config::default())
+            .with_config(runtime_config(&ctx))
+            .owns(cron_job, watcher::Config::default())
+    }
+
+    /// Reconcile the DataDownload resource
+    ///
+    /// This function is responsible for keeping the CronJob in sync with the DataDownload and
+    /// reporting the last runs of the CronJob in the status. The CronJob is garbage collected
+    /// through its owner reference when the DataDownload is deleted.
+    ///
+    /// # Arguments
+    /// * `data_download` - The DataDownload resource to reconcile
+    /// * `ctx` - The controller context
+    ///
+    /// # Returns
+    /// An action to take after reconciling the DataDownload resource
+    pub async fn reconcile<T>(data_download: Arc<T>, ctx: Arc<Context>) -> Result<Action>
+    where
+        T: NamespacedCustomResource,
+        DataDownload: From<T>,
+    {
+        let _in_flight = ctx.track_reconcile();
+        let namespace = data_download.namespace().ok_or(
+            ControllerError::MissingObjectKeyError(
+                "Expected DataDownload to be namespaced via metadata.namespace"
+            )
+        )?;
+        let owner_ref = data_download.controller_owner_ref(&()).ok_or_else(|| {
+            ControllerError::MissingObjectKeyError(
+                "Expected DataDownload to have an owner reference"
+            )
+        })?;
+        let name = data_download.name_any();
+        let hub = DataDownload::from(data_download.as_ref().clone());
+        let cron_job_api = Api::<CronJob>::namespaced(ctx.client.clone(), &namespace);
+
+        if hub.spec.pvc_name().is_none() {
+            update_status(data_download.as_ref(), &ctx, &namespace, None, Some("One of pvcRef or botRef must be set".to_string())).await?;
+            return Ok(Action::await_change());
+        }
+
+        let cron_job = cron_job_api.get_opt(&name).await?;
+        let cron_job_object = CronJob::from_hub(&hub, &name, &namespace, owner_ref, &ctx.state.config());
+
+        let cron_job = if cron_job.as_ref().is_none_or(|cron_job| ResourceDrift::<DataDownload>::has_drifted(cron_job, &cron_job_object)) {
+            info!(
+                event = "ApplyingCronJob",
+                data_download = name.as_str()
+            );
+            apply(&cron_job_api, cron_job_object, &name).await?
+        } else {
+            cron_job.unwrap()
+        };
+
+        update_status(data_download.as_ref(), &ctx, &namespace, Some(&cron_job), None).await?;
+
+        Ok(Action::await_change())
+    }
+}
+
+/// Update the status of the DataDownload resource
+///
+/// The last schedule and success times are taken from the CronJob status, and nothing is
+/// patched when they are unchanged to avoid triggering the watch.
+///
+/// # Arguments
+/// * `data_download` - The DataDownload resource to update
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the DataDownload resource
+/// * `cron_job` - The CronJob of the DataDownload, if it exists
+/// * `message` - Details about why the CronJob could not be created
+///
+/// # Returns
+/// A result indicating success or failure
+async fn update_status<T>(data_download: &T, ctx: &Context, namespace: &str, cron_job: Option<&CronJob>, message: Option<String>) -> Result<()>
+where
+    T: NamespacedCustomResource,
+    DataDownload: From<T>,
+{
+    let name = data_download.name_any();
+    let cron_job_status = cron_job.and_then(|cron_job| cron_job.status.as_ref());
+    let last_schedule_time = cron_job_status.and_then(|status| status.last_schedule_time.as_ref()).map(|time| time.0);
+    let last_successful_time = cron_job_status.and_then(|status| status.last_successful_time.as_ref()).map(|time| time.0);
+
+    let unchanged = DataDownload::from(data_download.clone()).status.is_some_and(|status| {
+        status.message == message
+            && status.last_schedule_time == last_schedule_time
+            && status.last_successful_time == last_successful_time
+    });
+    if unchanged {
+        return Ok(());
+    }
+
+    patch_status(&Api::<T>::namespaced(ctx.client.clone(), namespace), &name, json!({
+        "message": message,
+        "lastScheduleTime": last_schedule_time,
+        "lastSuccessfulTime": last_successful_time,
+        "lastUpdated": Utc::now(),
+    })).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use crate::crd::v1alpha1::data_download::DataDownload as V1Alpha1DataDownload;
+
+    fn data_download(spec: Value) -> DataDownload {
+        let mut data_download = json!({
+            "schedule": "0 3 * * *",
+            "exchange": "binance",
+            "pairs": ["BTC/USDT", "ETH/USDT"],
+            "botRef": { "name": "bot" },
+        });
+        data_download.as_object_mut().unwrap().extend(spec.as_object().cloned().unwrap());
+
+        DataDownload::from(serde_json::from_value::<V1Alpha1DataDownload>(json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "DataDownload",
+            "metadata": { "name": "download", "namespace": "default" },
+            "spec": data_download,
+        })).unwrap())
+    }
+
+    fn cron_job(spec: Value) -> CronJob {
+        CronJob::from_hub(&data_download(spec), "download", "default", OwnerReference::default(), &AppConfig::default())
+    }
+
+    fn container(cron_job: &CronJob) -> Container {
+        cron_job.spec.clone().unwrap().job_template.spec.unwrap().template.spec.unwrap().containers[0].clone()
+    }
+
+    #[test]
+    fn download_runs_on_the_schedule_without_overlapping() {
+        let cron_job = cron_job(json!({}));
+        let spec = cron_job.spec.clone().unwrap();
+        let pod_spec = spec.job_template.spec.clone().unwrap().template.spec.unwrap();
+
+        assert_eq!(spec.schedule, "0 3 * * *");
+        assert_eq!(spec.concurrency_policy.as_deref(), Some("Forbid"));
+        assert_eq!(pod_spec.restart_policy.as_deref(), Some("Never"));
+        assert_eq!(pod_spec.volumes.unwrap()[0].persistent_volume_claim.as_ref().unwrap().claim_name, "bot");
+        assert_eq!(container(&cron_job).image.as_deref(), Some("freqtradeorg/freqtrade:stable"));
+        assert_eq!(container(&cron_job).command.unwrap(), vec![
+            "freqtrade", "download-data", "--userdir", USER_DATA_PATH, "--exchange", "binance", "--pairs", "BTC/USDT", "ETH/USDT",
+        ]);
+    }
+
+    #[test]
+    fn download_options_are_passed_on() {
+        let cron_job = cron_job(json!({ "timeframes": ["5m", "1h"], "timerange": "20240101-", "pvcRef": { "name": "data" } }));
+        let pod_spec = cron_job.spec.clone().unwrap().job_template.spec.unwrap().template.spec.unwrap();
+
+        assert_eq!(container(&cron_job).command.unwrap()[9..], ["--timeframes", "5m", "1h", "--timerange", "20240101-"]);
+        // The pvcRef takes precedence over the bot's PVC
+        assert_eq!(pod_spec.volumes.unwrap()[0].persistent_volume_claim.as_ref().unwrap().claim_name, "data");
+    }
+
+    #[test]
+    fn unchanged_cron_job_does_not_drift() {
+        let mut existing = cron_job(json!({}));
+        // Fields defaulted by the API server are ignored
+        existing.metadata.resource_version = Some("1".to_string());
+        existing.spec.as_mut().unwrap().successful_jobs_history_limit = Some(3);
+        existing.spec.as_mut().unwrap().job_template.spec.as_mut().unwrap().template.spec.as_mut().unwrap().dns_policy = Some("ClusterFirst".to_string());
+
+        assert!(!ResourceDrift::<DataDownload>::has_drifted(&existing, &cron_job(json!({}))));
+    }
+
+    #[test]
+    fn changed_spec_drifts() {
+        let existing = cron_job(json!({}));
+
+        for spec in [
+            json!({ "schedule": "0 4 * * *" }),
+            json!({ "pairs": ["BTC/USDT"] }),
+            json!({ "exchange": "kraken" }),
+            json!({ "pvcRef": { "name": "data" } }),
+            json!({ "image": { "tag": "develop" } }),
+            json!({ "image": { "pullSecrets": ["registry"] } }),
+            json!({ "image": { "pullPolicy": "Always" } }),
+            json!({ "exchangeSecrets": { "key": { "secretKeyRef": { "name": "exchange", "key": "key" } } } }),
+        ] {
+            assert!(ResourceDrift::<DataDownload>::has_drifted(&existing, &cron_job(spec.clone())), "{}", spec);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use kube::{
+    api::{Api, ApiResource, DynamicObject, GroupVersionKind, Patch, PatchParams},
+    Client,
+};
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+use serde_json::json;
+
+use ft_operator_common::state::Diagnostics;
+
+use crate::controller::utils::FIELD_MANAGER;
+use crate::crd::hub::{bot::Bot, common::Condition};
+use crate::error::{ControllerError, Result};
+
+pub static CNPG_GROUP: &str = "postgresql.cnpg.io";
+pub static CNPG_VERSION: &str = "v1";
+pub static CNPG_KIND: &str = "Cluster";
+pub static CNPG_PLURAL: &str = "clusters";
+pub static CNPG_CRD_NAME: &str = "clusters.postgresql.cnpg.io";
+pub static DATABASE_READY_CONDITION: &str = "DatabaseReady";
+
+
+/// The ApiResource describing a CloudNativePG Cluster
+///
+/// # Returns
+/// The ApiResource for the `postgresql.cnpg.io/v1` Cluster kind
+pub fn cnpg_cluster_resource() -> ApiResource {
+    ApiResource::from_gvk_with_plural(
+        &GroupVersionKind::gvk(CNPG_GROUP, CNPG_VERSION, CNPG_KIND),
+        CNPG_PLURAL,
+    )
+}
+
+/// Check whether the CloudNativePG Cluster CRD is installed in the cluster
+///
+/// # Arguments
+/// * `client` - The kube client
+/// * `diagnostics` - The diagnostics to record the result in
+///
+/// # Returns
+/// A Result containing whether the CRD exists or an error
+pub async fn cnpg_installed(client: &Client, diagnostics: &Diagnostics) -> Result<bool> {
+    let installed = Api::<CustomResourceDefinition>::all(client.clone())
+        .get_opt(CNPG_CRD_NAME)
+        .await
+        .map(|crd| crd.is_some())
+        .map_err(ControllerError::from)?;
+
+    diagnostics.record_crd(CNPG_CRD_NAME, installed);
+    Ok(installed)
+}
+
+/// Create a CloudNativePG Cluster from a Bot Hub
+///
+/// The Cluster is a small cluster with a single `freqtrade` database owned by the Bot,
+/// CloudNativePG generates the `<name>-app` Secret holding the connection URI.
+///
+/// # Arguments
+/// * `bot` - The Bot CRD to create the Cluster from
+/// * `name` - The name of the Cluster
+/// * `namespace` - The namespace of the Cluster
+/// * `owner_ref` - The owner reference for the Cluster
+///
+/// # Returns
+/// The Cluster as a DynamicObject
+pub fn cnpg_cluster_from_hub(bot: &Bot, name: &str, namespace: &str, owner_ref: OwnerReference) -> DynamicObject {
+    let managed = bot.spec.postgres
+        .as_ref()
+        .and_then(|postgres| postgres.managed.clone())
+        .unwrap_or_default();
+
+    let mut cluster = DynamicObject::new(name, &cnpg_cluster_resource()).within(namespace);
+    cluster.metadata.owner_references = Some(vec![owner_ref]);
+    cluster.data = json!({
+        "spec": {
+            "instances": managed.instances,
+            "storage": {
+                "size": managed.storage,
+            },
+            "bootstrap": {
+                "initdb": {
+                    "database": "freqtrade",
+                    "owner": "freqtrade",
+                }
+            }
+        }
+    });
+
+    cluster
+}
+
+/// Reconcile the database backing the bot
+///
+/// When PostgreSQL provisioning is enabled this ensures the managed Cluster exists and
+/// reports its readiness. A missing CloudNativePG CRD is reported via the condition rather
+/// than an error so the Bot can be moved to the Error phase with a clear message.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub to reconcile the database for
+/// * `client` - The kube client
+/// * `diagnostics` - The diagnostics to record the CRD check in
+/// * `name` - The name of the Bot
+/// * `namespace` - The namespace of the Bot
+/// * `owner_ref` - The owner reference for the Cluster
+///
+/// # Returns
+/// The `DatabaseReady` condition, or None if PostgreSQL provisioning is disabled
+pub async fn reconcile_database(bot: &Bot, client: &Client, diagnostics: &Diagnostics, name: &str, namespace: &str, owner_ref: &OwnerReference) -> Result<Option<Condition>> {
+    let postgres = match bot.spec.postgres.as_ref().filter(|postgres| postgres.enabled) {
+        Some(postgres) => postgres,
+        None => return Ok(None),
+    };
+    let cluster_name = postgres.cluster_name(name).unwrap_or_default();
+
+    if !cnpg_installed(client, diagnostics).await? {
+        return Ok(Some(database_condition(
+            "False",
+            "CRDNotFound",
+            format!("CloudNativePG is not installed, the CRD `{}` was not found", CNPG_CRD_NAME),
+        )));
+    }
+
+    let api = Api::<DynamicObject>::namespaced_with(client.clone(), namespace, &cnpg_cluster_resource());
+
+    if postgres.is_managed() {
+        api.patch(
+            &cluster_name,
+            &PatchParams::apply(FIELD_MANAGER),
+            &Patch::Apply(cnpg_cluster_from_hub(bot, &cluster_name, namespace, owner_ref.clone())),
+        ).await?;
+    }
+
+    let condition = match api.get_opt(&cluster_name).await? {
+        Some(cluster) => {
+            let instances = cluster.data["spec"]["instances"].as_i64().unwrap_or(1);
+            let ready_instances = cluster.data["status"]["readyInstances"].as_i64().unwrap_or(0);
+
+            if ready_instances >= instances {
+                database_condition(
+                    "True",
+                    "ClusterReady",
+                    format!("Cluster `{}` has {}/{} ready instances", cluster_name, ready_instances, instances),
+                )
+            } else {
+                database_condition(
+                    "False",
+                    "ClusterNotReady",
+                    format!("Cluster `{}` has {}/{} ready instances", cluster_name, ready_instances, instances),
+                )
+            }
+        },
+        None => database_condition(
+            "False",
+            "ClusterNotFound",
+            format!("Cluster `{}` was not found in namespace `{}`", cluster_name, namespace),
+        ),
+    };
+
+    Ok(Some(condition))
+}
+
+fn database_condition(status: &str, reason: &str, message: String) -> Condition {
+    Condition {
+        type_: DATABASE_READY_CONDITION.to_string(),
+        status: status.to_string(),
+        reason: Some(reason.to_string()),
+        message: Some(message),
+        last_transition_time: None,
+    }
+}
@@ -0,0 +1,487 @@
+use kube::{api::{Api, ListParams, PostParams}, Client, CustomResourceExt};
+use k8s_openapi::api::admissionregistration::v1::{ServiceReference, ValidatingWebhookConfiguration};
+use k8s_openapi::api::authorization::v1::{ResourceAttributes, SelfSubjectAccessReview, SelfSubjectAccessReviewSpec};
+use k8s_openapi::api::core::v1::Endpoints;
+use k8s_openapi::api::storage::v1::StorageClass;
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
+
+use crate::crd::{utils::generate_crds, v1alpha1::bot::Bot as V1Alpha1Bot};
+
+// The path the webhook validates Bots at
+static BOT_VALIDATE_PATH: &str = "/admission/freqtrade.io/bot/validate";
+static DEFAULT_STORAGE_CLASS_ANNOTATION: &str = "storageclass.kubernetes.io/is-default-class";
+// The verbs the controller needs per resource, as granted by the Helm chart's ClusterRole
+static REQUIRED_PERMISSIONS: &[(&str, &str, &[&str])] = &[
+    ("freqtrade.io", "bots", &["get", "list", "watch", "patch", "update", "create", "delete"]),
+    ("freqtrade.io", "bots/status", &["patch"]),
+    ("freqtrade.io", "botfleets", &["get", "list", "watch", "patch"]),
+    ("freqtrade.io", "strategies", &["get", "list", "watch"]),
+    ("freqtrade.io", "exchangecredentials", &["get", "list", "watch"]),
+    ("freqtrade.io", "backtests", &["get", "list", "watch", "patch"]),
+    ("freqtrade.io", "hyperopts", &["get", "list", "watch", "patch"]),
+    ("freqtrade.io", "datadownloads", &["get", "list", "watch", "patch"]),
+    ("events.k8s.io", "events", &["create", "patch"]),
+    ("", "configmaps", &["get", "list", "watch", "create", "patch", "delete"]),
+    ("", "secrets", &["get", "list", "watch", "create", "patch", "delete"]),
+    ("", "services", &["get", "list", "watch", "create", "patch", "delete"]),
+    ("", "persistentvolumeclaims", &["get", "list", "watch", "create", "patch", "delete"]),
+    ("", "pods", &["get", "list", "watch"]),
+    ("apps", "deployments", &["get", "list", "watch", "create", "patch", "delete"]),
+    ("batch", "jobs", &["get", "list", "watch", "create", "patch", "delete"]),
+    ("batch", "cronjobs", &["get", "list", "watch", "create", "patch", "delete"]),
+    ("networking.k8s.io", "ingresses", &["get", "list", "watch", "create", "patch", "delete"]),
+    ("apiextensions.k8s.io", "customresourcedefinitions", &["get", "list", "watch"]),
+];
+
+
+/// The result of a check of the cluster's prerequisites
+#[derive(Clone, Debug)]
+pub struct CheckResult {
+    pub name: String,
+    pub passed: bool,
+    pub message: String,
+}
+
+impl CheckResult {
+    fn pass(name: &str, message: String) -> Self {
+        Self { name: name.to_string(), passed: true, message }
+    }
+
+    fn fail(name: &str, message: String) -> Self {
+        Self { name: name.to_string(), passed: false, message }
+    }
+}
+
+/// Run every check of the cluster's prerequisites
+///
+/// # Arguments
+/// * `client` - The kube client
+///
+/// # Returns
+/// The result of every check
+pub async fn run_checks(client: &Client) -> Vec<CheckResult> {
+    let mut results = vec![
+        check_bot_crd(client).await,
+        check_validating_webhook(client).await,
+    ];
+    results.extend(check_rbac(client).await);
+    results.push(check_storage_classes(client).await);
+
+    results
+}
+
+/// Check the Bot CRD is installed, established and serves the versions of this operator
+///
+/// # Arguments
+/// * `client` - The kube client
+pub async fn check_bot_crd(client: &Client) -> CheckResult {
+    match Api::<CustomResourceDefinition>::all(client.clone()).get_opt(V1Alpha1Bot::crd_name()).await {
+        Ok(crd) => bot_crd_result(crd),
+        Err(e) => CheckResult::fail("Bot CRD", format!("Kubernetes API unavailable: {}", e)),
+    }
+}
+
+/// Check the installed Bot CRD, if any, is established and serves the versions of this operator
+fn bot_crd_result(crd: Option<CustomResourceDefinition>) -> CheckResult {
+    let name = "Bot CRD";
+    let Some(crd) = crd else {
+        return CheckResult::fail(name, format!("CRD {} is not installed", V1Alpha1Bot::crd_name()));
+    };
+
+    let expected_versions: Vec<String> = generate_crds(None)
+        .ok()
+        .and_then(|crds| crds.into_iter().find(|expected| expected.metadata.name == crd.metadata.name))
+        .map(|expected| expected.spec.versions.into_iter().map(|version| version.name).collect())
+        .unwrap_or_default();
+    let missing_versions: Vec<&str> = expected_versions
+        .iter()
+        .filter(|expected| !crd.spec.versions.iter().any(|version| version.served && version.name == **expected))
+        .map(String::as_str)
+        .collect();
+    if !missing_versions.is_empty() {
+        return CheckResult::fail(name, format!("CRD {} doesn't serve {}, upgrade the CRDs", V1Alpha1Bot::crd_name(), missing_versions.join(", ")));
+    }
+
+    let established = crd.status
+        .and_then(|status| status.conditions)
+        .unwrap_or_default()
+        .iter()
+        .any(|condition| condition.type_ == "Established" && condition.status == "True");
+    match established {
+        true => CheckResult::pass(name, format!("CRD {} serves {}", V1Alpha1Bot::crd_name(), expected_versions.join(", "))),
+        false => CheckResult::fail(name, format!("CRD {} is not established", V1Alpha1Bot::crd_name())),
+    }
+}
+
+/// Check the webhook validating Bots is registered with a CA bundle and its Service has ready endpoints
+///
+/// # Arguments
+/// * `client` - The kube client
+pub async fn check_validating_webhook(client: &Client) -> CheckResult {
+    let name = "Validating webhook";
+    let configurations = match Api::<ValidatingWebhookConfiguration>::all(client.clone()).list(&ListParams::default()).await {
+        Ok(configurations) => configurations.items,
+        Err(e) => return CheckResult::fail(name, format!("Kubernetes API unavailable: {}", e)),
+    };
+    let (webhook, service) = match bot_webhook_service(configurations) {
+        Ok(webhook_service) => webhook_service,
+        Err(result) => return result,
+    };
+
+    match Api::<Endpoints>::namespaced(client.clone(), &service.namespace).get_opt(&service.name).await {
+        Ok(endpoints) => webhook_endpoints_result(&webhook, &service, endpoints),
+        Err(e) => CheckResult::fail(name, format!("Kubernetes API unavailable: {}", e)),
+    }
+}
+
+/// Find the webhook validating Bots and the Service it calls
+///
+/// # Returns
+/// The name of the webhook and its Service, or the failed check when it isn't usable
+fn bot_webhook_service(configurations: Vec<ValidatingWebhookConfiguration>) -> Result<(String, ServiceReference), CheckResult> {
+    let name = "Validating webhook";
+    let Some(webhook) = configurations
+        .into_iter()
+        .flat_map(|configuration| configuration.webhooks.unwrap_or_default())
+        .find(|webhook| {
+            webhook.client_config.service
+                .as_ref()
+                .is_some_and(|service| service.path.as_deref() == Some(BOT_VALIDATE_PATH))
+        })
+    else {
+        return Err(CheckResult::fail(name, format!("No ValidatingWebhookConfiguration calls {}", BOT_VALIDATE_PATH)));
+    };
+
+    if webhook.client_config.ca_bundle.as_ref().is_none_or(|ca_bundle| ca_bundle.0.is_empty()) {
+        return Err(CheckResult::fail(name, format!("Webhook {} has no caBundle, check it is injected", webhook.name)));
+    }
+
+    match webhook.client_config.service {
+        Some(service) => Ok((webhook.name, service)),
+        None => Err(CheckResult::fail(name, format!("Webhook {} doesn't call a Service", webhook.name))),
+    }
+}
+
+/// Check the Service of the webhook has ready endpoints
+fn webhook_endpoints_result(webhook: &str, service: &ServiceReference, endpoints: Option<Endpoints>) -> CheckResult {
+    let name = "Validating webhook";
+    let ready = endpoints
+        .and_then(|endpoints| endpoints.subsets)
+        .unwrap_or_default()
+        .iter()
+        .any(|subset| subset.addresses.as_ref().is_some_and(|addresses| !addresses.is_empty()));
+
+    match ready {
+        true => CheckResult::pass(name, format!("Webhook {} is served by {}/{}", webhook, service.namespace, service.name)),
+        false => CheckResult::fail(name, format!("Service {}/{} of webhook {} has no ready endpoints", service.namespace, service.name, webhook)),
+    }
+}
+
+/// Check the current identity can perform every verb the controller needs on each resource
+///
+/// The access is reviewed for the identity the client runs as, so the doctor should be run as
+/// the controller's ServiceAccount, e.g. by impersonating it with the kubeconfig's `as` field.
+///
+/// # Arguments
+/// * `client` - The kube client
+///
+/// # Returns
+/// A result per resource
+pub async fn check_rbac(client: &Client) -> Vec<CheckResult> {
+    let api = Api::<SelfSubjectAccessReview>::all(client.clone());
+    let mut results = vec![];
+
+    for (group, resource, verbs) in REQUIRED_PERMISSIONS {
+        let mut denied = vec![];
+
+        for verb in verbs.iter() {
+            match api.create(&PostParams::default(), &access_review(group, resource, verb)).await {
+                Ok(review) if review.status.as_ref().is_some_and(|status| status.allowed) => (),
+                Ok(_) => denied.push(verb.to_string()),
+                Err(e) => denied.push(format!("{} ({})", verb, e)),
+            }
+        }
+
+        results.push(rbac_result(group, resource, verbs, &denied));
+    }
+
+    results
+}
+
+/// Create the review of the access to a verb on a resource, which may be a subresource
+fn access_review(group: &str, resource: &str, verb: &str) -> SelfSubjectAccessReview {
+    let (resource, subresource) = match resource.split_once('/') {
+        Some((resource, subresource)) => (resource, Some(subresource.to_string())),
+        None => (resource, None),
+    };
+
+    SelfSubjectAccessReview {
+        spec: SelfSubjectAccessReviewSpec {
+            resource_attributes: Some(ResourceAttributes {
+                group: Some(group.to_string()),
+                resource: Some(resource.to_string()),
+                subresource,
+                verb: Some(verb.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// Check none of the verbs needed on a resource were denied
+fn rbac_result(group: &str, resource: &str, verbs: &[&str], denied: &[String]) -> CheckResult {
+    let name = match group.is_empty() {
+        true => format!("RBAC {}", resource),
+        false => format!("RBAC {}.{}", resource, group),
+    };
+
+    match denied.is_empty() {
+        true => CheckResult::pass(&name, format!("{} allowed", verbs.join(", "))),
+        false => CheckResult::fail(&name, format!("{} denied", denied.join(", "))),
+    }
+}
+
+/// Check the storage classes of the Bots with a PVC exist, or that there is a default one
+///
+/// # Arguments
+/// * `client` - The kube client
+pub async fn check_storage_classes(client: &Client) -> CheckResult {
+    let name = "Storage classes";
+    let bots = match Api::<V1Alpha1Bot>::all(client.clone()).list(&ListParams::default()).await {
+        Ok(bots) => bots.items,
+        Err(e) => return CheckResult::fail(name, format!("Failed to list Bots: {}", e)),
+    };
+    let pvcs: Vec<Option<String>> = bots
+        .into_iter()
+        .filter(|bot| bot.spec.pvc.enabled)
+        .map(|bot| bot.spec.pvc.storage_class)
+        .collect();
+    if pvcs.is_empty() {
+        return CheckResult::pass(name, "No Bot has a PVC enabled".to_string());
+    }
+
+    match Api::<StorageClass>::all(client.clone()).list(&ListParams::default()).await {
+        Ok(storage_classes) => storage_classes_result(pvcs, &storage_classes.items),
+        Err(e) => CheckResult::fail(name, format!("Failed to list StorageClasses: {}", e)),
+    }
+}
+
+/// Check the storage classes of the PVCs exist, a PVC without one using the default StorageClass
+///
+/// # Arguments
+/// * `pvcs` - The storage class of each Bot with a PVC
+/// * `storage_classes` - The StorageClasses of the cluster
+fn storage_classes_result(pvcs: Vec<Option<String>>, storage_classes: &[StorageClass]) -> CheckResult {
+    let name = "Storage classes";
+    let has_default = storage_classes.iter().any(|storage_class| {
+        storage_class.metadata.annotations
+            .as_ref()
+            .and_then(|annotations| annotations.get(DEFAULT_STORAGE_CLASS_ANNOTATION))
+            .is_some_and(|value| value == "true")
+    });
+
+    let mut missing: Vec<String> = pvcs
+        .into_iter()
+        .filter_map(|storage_class| match storage_class {
+            Some(storage_class) => (!storage_classes.iter().any(|existing| existing.metadata.name.as_ref() == Some(&storage_class)))
+                .then_some(storage_class),
+            None => (!has_default).then(|| "the default StorageClass".to_string()),
+        })
+        .collect();
+    missing.sort();
+    missing.dedup();
+
+    match missing.is_empty() {
+        true => CheckResult::pass(name, "The StorageClasses of the Bots' PVCs exist".to_string()),
+        false => CheckResult::fail(name, format!("Missing {}", missing.join(", "))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::StatusCode;
+    use serde_json::{json, Value};
+
+    use crate::controller::mock::mock_client;
+
+    fn bot_crd(established: bool) -> CustomResourceDefinition {
+        let mut crd = generate_crds(Some(b"ca"))
+            .unwrap()
+            .into_iter()
+            .find(|crd| crd.metadata.name.as_deref() == Some(V1Alpha1Bot::crd_name()))
+            .unwrap();
+        crd.status = Some(serde_json::from_value(json!({
+            "conditions": [{"type": "Established", "status": if established { "True" } else { "False" }}],
+        })).unwrap());
+        crd
+    }
+
+    fn webhook_configuration(webhook: Value) -> ValidatingWebhookConfiguration {
+        serde_json::from_value(json!({
+            "metadata": {"name": "freqtrade-operator"},
+            "webhooks": [webhook],
+        })).unwrap()
+    }
+
+    fn bot_webhook(ca_bundle: &str) -> Value {
+        json!({
+            "name": "bots.freqtrade.io",
+            "admissionReviewVersions": ["v1"],
+            "sideEffects": "None",
+            "clientConfig": {
+                "caBundle": ca_bundle,
+                "service": {"namespace": "freqtrade", "name": "freqtrade-operator-webhook", "path": BOT_VALIDATE_PATH},
+            },
+        })
+    }
+
+    fn storage_class(name: &str, default: bool) -> StorageClass {
+        serde_json::from_value(json!({
+            "metadata": {
+                "name": name,
+                "annotations": {DEFAULT_STORAGE_CLASS_ANNOTATION: default.to_string()},
+            },
+            "provisioner": "local",
+        })).unwrap()
+    }
+
+    #[test]
+    fn established_bot_crd_passes() {
+        let result = bot_crd_result(Some(bot_crd(true)));
+
+        assert!(result.passed, "{}", result.message);
+        assert_eq!(result.message, "CRD bots.freqtrade.io serves v1alpha1, v1alpha2");
+    }
+
+    #[test]
+    fn missing_or_unestablished_bot_crd_fails() {
+        let result = bot_crd_result(None);
+        assert!(!result.passed);
+        assert_eq!(result.message, "CRD bots.freqtrade.io is not installed");
+
+        let result = bot_crd_result(Some(bot_crd(false)));
+        assert!(!result.passed);
+        assert_eq!(result.message, "CRD bots.freqtrade.io is not established");
+    }
+
+    #[test]
+    fn outdated_bot_crd_fails() {
+        let mut crd = bot_crd(true);
+        crd.spec.versions.retain(|version| version.name == "v1alpha1");
+
+        let result = bot_crd_result(Some(crd));
+
+        assert!(!result.passed);
+        assert_eq!(result.message, "CRD bots.freqtrade.io doesn't serve v1alpha2, upgrade the CRDs");
+    }
+
+    #[test]
+    fn bot_webhook_service_is_found() {
+        let configurations = vec![webhook_configuration(bot_webhook("Y2E="))];
+
+        let (webhook, service) = bot_webhook_service(configurations).unwrap();
+
+        assert_eq!(webhook, "bots.freqtrade.io");
+        assert_eq!((service.namespace.as_str(), service.name.as_str()), ("freqtrade", "freqtrade-operator-webhook"));
+    }
+
+    #[test]
+    fn missing_bot_webhook_or_ca_bundle_fails() {
+        let mut other_webhook = bot_webhook("Y2E=");
+        other_webhook["clientConfig"]["service"]["path"] = json!("/other");
+
+        let result = bot_webhook_service(vec![webhook_configuration(other_webhook)]).unwrap_err();
+        assert!(!result.passed);
+        assert_eq!(result.message, format!("No ValidatingWebhookConfiguration calls {}", BOT_VALIDATE_PATH));
+
+        let result = bot_webhook_service(vec![webhook_configuration(bot_webhook(""))]).unwrap_err();
+        assert!(!result.passed);
+        assert_eq!(result.message, "Webhook bots.freqtrade.io has no caBundle, check it is injected");
+    }
+
+    #[test]
+    fn webhook_needs_ready_endpoints() {
+        let service = ServiceReference {
+            namespace: "freqtrade".to_string(),
+            name: "freqtrade-operator-webhook".to_string(),
+            ..Default::default()
+        };
+        let endpoints = |subsets: Value| Some(serde_json::from_value::<Endpoints>(json!({"metadata": {}, "subsets": subsets})).unwrap());
+
+        let result = webhook_endpoints_result("bots.freqtrade.io", &service, endpoints(json!([{"addresses": [{"ip": "10.0.0.1"}]}])));
+        assert!(result.passed);
+        assert_eq!(result.message, "Webhook bots.freqtrade.io is served by freqtrade/freqtrade-operator-webhook");
+
+        for endpoints in [None, endpoints(json!([{"notReadyAddresses": [{"ip": "10.0.0.1"}]}]))] {
+            let result = webhook_endpoints_result("bots.freqtrade.io", &service, endpoints);
+            assert!(!result.passed);
+            assert_eq!(result.message, "Service freqtrade/freqtrade-operator-webhook of webhook bots.freqtrade.io has no ready endpoints");
+        }
+    }
+
+    #[test]
+    fn access_reviews_split_subresources() {
+        let attributes = access_review("freqtrade.io", "bots/status", "patch").spec.resource_attributes.unwrap();
+
+        assert_eq!(attributes.group.as_deref(), Some("freqtrade.io"));
+        assert_eq!(attributes.resource.as_deref(), Some("bots"));
+        assert_eq!(attributes.subresource.as_deref(), Some("status"));
+        assert_eq!(attributes.verb.as_deref(), Some("patch"));
+    }
+
+    #[test]
+    fn rbac_fails_on_any_denied_verb() {
+        let result = rbac_result("", "pods", &["get", "list"], &[]);
+        assert!(result.passed);
+        assert_eq!((result.name.as_str(), result.message.as_str()), ("RBAC pods", "get, list allowed"));
+
+        let result = rbac_result("apps", "deployments", &["get", "delete"], &["delete".to_string()]);
+        assert!(!result.passed);
+        assert_eq!((result.name.as_str(), result.message.as_str()), ("RBAC deployments.apps", "delete denied"));
+    }
+
+    #[tokio::test]
+    async fn rbac_is_reviewed_for_every_required_verb() {
+        let (client, _requests) = mock_client(|request| {
+            let attributes = &request.body["spec"]["resourceAttributes"];
+            let allowed = !(attributes["resource"] == "secrets" && attributes["verb"] == "delete");
+            (StatusCode::CREATED, json!({
+                "apiVersion": "authorization.k8s.io/v1",
+                "kind": "SelfSubjectAccessReview",
+                "metadata": {},
+                "spec": request.body["spec"],
+                "status": {"allowed": allowed},
+            }))
+        });
+
+        let results = check_rbac(&client).await;
+
+        assert_eq!(results.len(), REQUIRED_PERMISSIONS.len());
+        let failed: Vec<_> = results.iter().filter(|result| !result.passed).collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!((failed[0].name.as_str(), failed[0].message.as_str()), ("RBAC secrets", "delete denied"));
+    }
+
+    #[test]
+    fn storage_classes_of_pvcs_must_exist() {
+        let storage_classes = [storage_class("fast", false), storage_class("standard", true)];
+
+        let result = storage_classes_result(vec![Some("fast".to_string()), None], &storage_classes);
+        assert!(result.passed);
+
+        let result = storage_classes_result(vec![Some("slow".to_string()), Some("slow".to_string()), Some("fast".to_string())], &storage_classes);
+        assert!(!result.passed);
+        assert_eq!(result.message, "Missing slow");
+    }
+
+    #[test]
+    fn pvcs_without_a_storage_class_need_a_default() {
+        let result = storage_classes_result(vec![None], &[storage_class("fast", false)]);
+
+        assert!(!result.passed);
+        assert_eq!(result.message, "Missing the default StorageClass");
+    }
+}
@@ -0,0 +1,340 @@
+use kube::{
+    api::{Api, ObjectMeta},
+    Client,
+};
+use k8s_openapi::api::core::v1::Secret;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+use k8s_openapi::ByteString;
+use std::collections::BTreeMap;
+
+use crate::crd::{
+    ClusterCustomResource,
+    hub::{bot::{Bot, ExchangeSecrets}, common::{Condition, SecretItem, SecretKeyRef}, exchange_credentials::ExchangeCredentials},
+};
+use crate::error::Result;
+
+pub static EXCHANGE_CREDENTIALS_RESOLVED_CONDITION: &str = "ExchangeCredentialsResolved";
+
+
+/// The ExchangeCredentials of a bot, resolved into a Secret in the bot's namespace
+pub struct ResolvedExchangeCredentials {
+    /// The `ExchangeCredentialsResolved` condition
+    pub condition: Condition,
+    /// The Secret holding the resolved credentials, if they could be resolved
+    pub secret: Option<Secret>,
+}
+
+/// The name of the Secret holding the resolved exchange credentials of a bot
+///
+/// # Arguments
+/// * `name` - The name of the bot
+pub fn exchange_secret_name(name: &str) -> String {
+    format!("{}-exchange", name)
+}
+
+/// Whether a bot references the given ExchangeCredentials
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+/// * `credentials_name` - The name of the ExchangeCredentials
+pub fn references_exchange_credentials(bot: &Bot, credentials_name: &str) -> bool {
+    bot.spec.secrets.exchange_ref.as_ref().is_some_and(|exchange_ref| exchange_ref.name == credentials_name)
+}
+
+/// Whether Bots in a namespace are allowed to reference ExchangeCredentials
+///
+/// # Arguments
+/// * `credentials` - The ExchangeCredentials Hub
+/// * `namespace` - The namespace of the Bot
+pub fn allows_namespace(credentials: &ExchangeCredentials, namespace: &str) -> bool {
+    credentials.spec.allowed_namespaces
+        .iter()
+        .any(|allowed| allowed == "*" || allowed == namespace)
+}
+
+/// Whether ExchangeCredentials reference the given Secret through a `secretKeyRef`
+///
+/// # Arguments
+/// * `credentials` - The ExchangeCredentials Hub
+/// * `secret_name` - The name of the Secret
+/// * `secret_namespace` - The namespace of the Secret
+pub fn credentials_reference_secret(credentials: &ExchangeCredentials, secret_name: &str, secret_namespace: &str) -> bool {
+    credentials.spec.secret_namespace.as_deref() == Some(secret_namespace)
+        && [&credentials.spec.key, &credentials.spec.secret, &credentials.spec.password, &credentials.spec.uid]
+            .into_iter()
+            .any(|item| matches!(item, Some(SecretItem::SecretKeyRef { secret_key_ref }) if secret_key_ref.name == secret_name))
+}
+
+/// Resolve the ExchangeCredentials referenced by a bot
+///
+/// The ExchangeCredentials are cluster-scoped, so their values are copied into a Secret in the
+/// bot's namespace and the bot's exchange secrets are rewired to it. The env vars are then
+/// rendered exactly like inline exchange secrets. Bots outside of the credentials'
+/// `allowedNamespaces` are refused, so a tenant can't copy another tenant's keys.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub to resolve the credentials for
+/// * `client` - The kube client
+/// * `name` - The name of the bot
+/// * `namespace` - The namespace of the bot
+/// * `owner_ref` - The owner reference for the Secret
+///
+/// # Returns
+/// The resolved credentials, or None if the bot doesn't reference ExchangeCredentials
+pub async fn resolve_exchange_credentials<C>(
+    bot: &mut Bot,
+    client: &Client,
+    name: &str,
+    namespace: &str,
+    owner_ref: &OwnerReference,
+) -> Result<Option<ResolvedExchangeCredentials>>
+where
+    C: ClusterCustomResource,
+    ExchangeCredentials: From<C>,
+{
+    let Some(exchange_ref) = bot.spec.secrets.exchange_ref.clone() else {
+        return Ok(None);
+    };
+
+    let unresolved = |reason: &str, message: String| -> Result<Option<ResolvedExchangeCredentials>> {
+        Ok(Some(ResolvedExchangeCredentials {
+            condition: exchange_credentials_condition("False", reason, message),
+            secret: None,
+        }))
+    };
+
+    let credentials = match Api::<C>::all(client.clone()).get_opt(&exchange_ref.name).await? {
+        Some(credentials) => ExchangeCredentials::from(credentials),
+        None => return unresolved(
+            "CredentialsNotFound",
+            format!("ExchangeCredentials `{}` were not found", exchange_ref.name),
+        ),
+    };
+
+    if !allows_namespace(&credentials, namespace) {
+        return unresolved(
+            "NamespaceNotAllowed",
+            format!("ExchangeCredentials `{}` don't allow Bots in namespace `{}`", exchange_ref.name, namespace),
+        );
+    }
+
+    if credentials.spec.exchange != bot.spec.exchange {
+        return unresolved(
+            "ExchangeMismatch",
+            format!(
+                "ExchangeCredentials `{}` are for exchange `{}`, not `{}`",
+                exchange_ref.name, credentials.spec.exchange, bot.spec.exchange
+            ),
+        );
+    }
+
+    let mut data = BTreeMap::new();
+    for (key, item) in [
+        ("key", &credentials.spec.key),
+        ("secret", &credentials.spec.secret),
+        ("password", &credentials.spec.password),
+        ("uid", &credentials.spec.uid),
+    ] {
+        let value = match item {
+            Some(SecretItem::Value { value }) => value.clone().into_bytes(),
+            Some(SecretItem::SecretKeyRef { secret_key_ref }) => {
+                let Some(secret_namespace) = credentials.spec.secret_namespace.as_deref() else {
+                    return unresolved(
+                        "SecretNamespaceMissing",
+                        format!("ExchangeCredentials `{}` reference Secrets without a secretNamespace", exchange_ref.name),
+                    );
+                };
+
+                match Api::<Secret>::namespaced(client.clone(), secret_namespace)
+                    .get_opt(&secret_key_ref.name)
+                    .await?
+                    .and_then(|secret| secret.data)
+                    .and_then(|data| data.get(&secret_key_ref.key).cloned())
+                {
+                    Some(value) => value.0,
+                    None => return unresolved(
+                        "SecretNotFound",
+                        format!(
+                            "Secret key `{}/{}` of `{}` was not found",
+                            secret_key_ref.name, secret_key_ref.key, secret_namespace
+                        ),
+                    ),
+                }
+            },
+            None => continue,
+        };
+
+        data.insert(key.to_string(), ByteString(value));
+    }
+
+    // Rewire the exchange secrets to the resolved Secret
+    let secret_name = exchange_secret_name(name);
+    let secret_item = |key: &str| data.contains_key(key).then(|| SecretItem::SecretKeyRef {
+        secret_key_ref: SecretKeyRef {
+            name: secret_name.clone(),
+            key: key.to_string(),
+        },
+    });
+    bot.spec.secrets.exchange = Some(ExchangeSecrets {
+        key: secret_item("key"),
+        secret: secret_item("secret"),
+        password: secret_item("password"),
+        uid: secret_item("uid"),
+    });
+
+    Ok(Some(ResolvedExchangeCredentials {
+        condition: exchange_credentials_condition(
+            "True",
+            "CredentialsResolved",
+            format!("Using ExchangeCredentials `{}`", exchange_ref.name),
+        ),
+        secret: Some(Secret {
+            metadata: ObjectMeta {
+                name: Some(secret_name.clone()),
+                namespace: Some(namespace.to_string()),
+                owner_references: Some(vec![owner_ref.clone()]),
+                ..Default::default()
+            },
+            data: Some(data),
+            ..Default::default()
+        }),
+    }))
+}
+
+fn exchange_credentials_condition(status: &str, reason: &str, message: String) -> Condition {
+    Condition {
+        type_: EXCHANGE_CREDENTIALS_RESOLVED_CONDITION.to_string(),
+        status: status.to_string(),
+        reason: Some(reason.to_string()),
+        message: Some(message),
+        last_transition_time: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::StatusCode;
+    use serde_json::{json, Value};
+    use crate::controller::mock::mock_client;
+    use crate::crd::v1alpha1::{bot::Bot as V1Alpha1Bot, exchange_credentials::ExchangeCredentials as V1Alpha1ExchangeCredentials};
+
+    fn credentials_spec(spec: Value) -> Value {
+        let mut credentials = json!({ "exchange": "binance", "allowedNamespaces": ["trading"] });
+        credentials.as_object_mut().unwrap().extend(spec.as_object().cloned().unwrap());
+        credentials
+    }
+
+    fn credentials(spec: Value) -> ExchangeCredentials {
+        ExchangeCredentials::from(serde_json::from_value::<V1Alpha1ExchangeCredentials>(json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "ExchangeCredentials",
+            "metadata": { "name": "binance" },
+            "spec": credentials_spec(spec),
+        })).unwrap())
+    }
+
+    fn bot(exchange: &str) -> Bot {
+        Bot::from(serde_json::from_value::<V1Alpha1Bot>(json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "Bot",
+            "metadata": { "name": "bot", "namespace": "trading" },
+            "spec": { "exchange": exchange, "strategy": { "name": "SampleStrategy" }, "secrets": { "exchangeRef": { "name": "binance" } } },
+        })).unwrap())
+    }
+
+    #[test]
+    fn namespaces_are_allowed_by_name_or_wildcard() {
+        assert!(allows_namespace(&credentials(json!({})), "trading"));
+        assert!(!allows_namespace(&credentials(json!({})), "other"));
+        assert!(allows_namespace(&credentials(json!({ "allowedNamespaces": ["*"] })), "other"));
+        assert!(!allows_namespace(&credentials(json!({ "allowedNamespaces": [] })), "trading"));
+    }
+
+    #[test]
+    fn bots_are_mapped_to_the_credentials_they_reference() {
+        assert!(references_exchange_credentials(&bot("binance"), "binance"));
+        assert!(!references_exchange_credentials(&bot("binance"), "kraken"));
+    }
+
+    #[test]
+    fn credentials_are_mapped_to_the_secrets_they_reference() {
+        let credentials = credentials(json!({
+            "secretNamespace": "secrets",
+            "key": { "value": "key" },
+            "secret": { "secretKeyRef": { "name": "binance", "key": "secret" } },
+        }));
+
+        assert!(credentials_reference_secret(&credentials, "binance", "secrets"));
+        assert!(!credentials_reference_secret(&credentials, "binance", "trading"));
+        assert!(!credentials_reference_secret(&credentials, "other", "secrets"));
+    }
+
+    // Resolve the credentials of `bot` against an API server holding `credentials` and a
+    // `secrets/binance` Secret
+    async fn resolve(mut bot: Bot, credentials: Option<Value>) -> (Bot, ResolvedExchangeCredentials) {
+        let (client, _requests) = mock_client(move |request| match (request.path.as_str(), &credentials) {
+            ("/apis/freqtrade.io/v1alpha1/exchangecredentials/binance", Some(spec)) => (StatusCode::OK, json!({
+                "apiVersion": "freqtrade.io/v1alpha1",
+                "kind": "ExchangeCredentials",
+                "metadata": { "name": "binance" },
+                "spec": credentials_spec(spec.clone()),
+            })),
+            ("/api/v1/namespaces/secrets/secrets/binance", _) => (StatusCode::OK, json!({
+                "apiVersion": "v1",
+                "kind": "Secret",
+                "metadata": { "name": "binance", "namespace": "secrets" },
+                "data": { "secret": "c2VjcmV0" },
+            })),
+            _ => (StatusCode::NOT_FOUND, json!({ "kind": "Status", "apiVersion": "v1", "status": "Failure", "reason": "NotFound", "code": 404 })),
+        });
+        let owner_ref = OwnerReference { name: "bot".to_string(), uid: "bot-uid".to_string(), ..Default::default() };
+
+        let resolved = resolve_exchange_credentials::<V1Alpha1ExchangeCredentials>(&mut bot, &client, "bot", "trading", &owner_ref)
+            .await
+            .unwrap()
+            .unwrap();
+        (bot, resolved)
+    }
+
+    #[tokio::test]
+    async fn credentials_are_copied_into_the_bot_namespace() {
+        let (bot, resolved) = resolve(bot("binance"), Some(json!({
+            "secretNamespace": "secrets",
+            "key": { "value": "key" },
+            "secret": { "secretKeyRef": { "name": "binance", "key": "secret" } },
+        }))).await;
+
+        assert_eq!(resolved.condition.status, "True");
+        let secret = resolved.secret.unwrap();
+        assert_eq!(secret.metadata.name.as_deref(), Some("bot-exchange"));
+        assert_eq!(secret.metadata.namespace.as_deref(), Some("trading"));
+        assert_eq!(secret.data.unwrap(), BTreeMap::from([
+            ("key".to_string(), ByteString(b"key".to_vec())),
+            ("secret".to_string(), ByteString(b"secret".to_vec())),
+        ]));
+        // The bot's exchange secrets are rewired to the copy, only for the keys set
+        let exchange = bot.spec.secrets.exchange.unwrap();
+        assert_eq!(exchange.key, Some(SecretItem::SecretKeyRef { secret_key_ref: SecretKeyRef { name: "bot-exchange".to_string(), key: "key".to_string() } }));
+        assert_eq!(exchange.password, None);
+    }
+
+    #[tokio::test]
+    async fn unusable_credentials_are_reported() {
+        let cases = [
+            (bot("binance"), None, "CredentialsNotFound"),
+            (bot("binance"), Some(json!({ "allowedNamespaces": ["other"] })), "NamespaceNotAllowed"),
+            (bot("kraken"), Some(json!({})), "ExchangeMismatch"),
+            (bot("binance"), Some(json!({ "key": { "secretKeyRef": { "name": "binance", "key": "key" } } })), "SecretNamespaceMissing"),
+            (bot("binance"), Some(json!({ "secretNamespace": "secrets", "key": { "secretKeyRef": { "name": "binance", "key": "key" } } })), "SecretNotFound"),
+        ];
+
+        for (bot, credentials, reason) in cases {
+            let (bot, resolved) = resolve(bot, credentials).await;
+
+            assert_eq!((resolved.condition.status.as_str(), resolved.condition.reason.as_deref()), ("False", Some(reason)));
+            assert!(resolved.secret.is_none(), "{}", reason);
+            assert_eq!(bot.spec.secrets.exchange, None, "{}", reason);
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use axum::{
+    extract::Extension,
+    http::StatusCode,
+    response::{IntoResponse, Json},
+    routing::get,
+    Router,
+};
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
+use kube::{api::Api, CustomResourceExt};
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+use crate::controller::context::Context;
+use crate::crd::v1alpha1::bot::Bot as V1Alpha1Bot;
+
+#[derive(Serialize)]
+struct HealthResponse {
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    #[serde(rename = "lastReconciles", skip_serializing_if = "Option::is_none")]
+    last_reconciles: Option<BTreeMap<&'static str, u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    crds: Option<BTreeMap<String, bool>>,
+}
+
+impl HealthResponse {
+    fn new(status: &'static str, reason: Option<String>) -> Self {
+        Self { status, reason, last_reconciles: None, crds: None }
+    }
+}
+
+/// Create the router serving the controller's health probes
+///
+/// `/healthz` answers as long as the process is alive, `/readyz` checks the controller
+/// can do its job and reports its diagnostics, and `/metrics` exposes the controller's metrics
+/// in the Prometheus format.
+///
+/// # Arguments
+/// * `ctx` - The controller context
+///
+/// # Returns
+/// The router for the health endpoints
+pub fn create_health_router(ctx: Arc<Context>) -> Router {
+    Router::new()
+        .route("/healthz", get(|| async {
+            Json(HealthResponse::new("ok", None))
+        }))
+        .route("/readyz", get(readyz))
+        .route("/metrics", get(metrics))
+        .layer(Extension(ctx))
+}
+
+/// Serve the health endpoints over plain HTTP
+///
+/// # Arguments
+/// * `addr` - The address to listen on
+/// * `router` - The router for the health endpoints
+pub async fn serve_health(addr: String, router: Router) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    axum::serve(listener, router).await
+}
+
+async fn readyz(Extension(ctx): Extension<Arc<Context>>) -> impl IntoResponse {
+    let (status, response) = match readiness(&ctx).await {
+        Ok(()) => (StatusCode::OK, HealthResponse::new("ok", None)),
+        Err(reason) => (StatusCode::SERVICE_UNAVAILABLE, HealthResponse::new("unavailable", Some(reason))),
+    };
+    let diagnostics = &ctx.state.diagnostics;
+
+    (status, Json(HealthResponse {
+        last_reconciles: Some(diagnostics.last_reconciles()),
+        crds: Some(diagnostics.crds()),
+        ..response
+    }))
+}
+
+async fn metrics(Extension(ctx): Extension<Arc<Context>>) -> impl IntoResponse {
+    let diagnostics = &ctx.state.diagnostics;
+    let mut metrics = format!(
+        "# HELP freqtrade_operator_reconciles_in_flight The number of reconciles currently running\n\
+         # TYPE freqtrade_operator_reconciles_in_flight gauge\n\
+         freqtrade_operator_reconciles_in_flight {}\n\
+         # HELP freqtrade_operator_last_reconcile_timestamp_seconds The time of the last successful reconcile per kind\n\
+         # TYPE freqtrade_operator_last_reconcile_timestamp_seconds gauge\n",
+        ctx.reconciles_in_flight(),
+    );
+    for (kind, timestamp) in diagnostics.last_reconciles() {
+        let _ = writeln!(metrics, "freqtrade_operator_last_reconcile_timestamp_seconds{{kind=\"{}\"}} {}", kind, timestamp);
+    }
+
+    metrics.push_str(
+        "# HELP freqtrade_operator_crd_installed Whether a CRD the operator depends on was installed when last checked\n\
+         # TYPE freqtrade_operator_crd_installed gauge\n"
+    );
+    for (crd, installed) in diagnostics.crds() {
+        let _ = writeln!(metrics, "freqtrade_operator_crd_installed{{crd=\"{}\"}} {}", crd, u8::from(installed));
+    }
+    ctx.render_bot_metrics(&mut metrics);
+
+    metrics
+}
+
+/// Check whether the controller is ready
+///
+/// The controller is ready when the Kubernetes API is reachable, the Bot CRD is established
+/// and, when `activity_timeout_seconds` is set, a controller emitted a reconcile result recently.
+///
+/// # Arguments
+/// * `ctx` - The controller context
+///
+/// # Returns
+/// A Result with the reason the controller is not ready as the error
+async fn readiness(ctx: &Context) -> Result<(), String> {
+    let crd = Api::<CustomResourceDefinition>::all(ctx.client.clone())
+        .get_opt(V1Alpha1Bot::crd_name())
+        .await
+        .map_err(|e| format!("Kubernetes API unavailable: {}", e))?;
+    ctx.state.diagnostics.record_crd(V1Alpha1Bot::crd_name(), crd.is_some());
+    let crd = crd.ok_or_else(|| format!("CRD {} is not installed", V1Alpha1Bot::crd_name()))?;
+
+    let established = crd.status
+        .and_then(|status| status.conditions)
+        .unwrap_or_default()
+        .iter()
+        .any(|condition| condition.type_ == "Established" && condition.status == "True");
+    if !established {
+        return Err(format!("CRD {} is not established", V1Alpha1Bot::crd_name()));
+    }
+
+    // Bots are requeued every 30 seconds, so a long silence means the controllers are stuck.
+    // The check is opt-in since a cluster without any resource never emits a result.
+    let timeout = ctx.controller_config().health.activity_timeout_seconds;
+    let idle = Utc::now().signed_duration_since(ctx.last_activity()).num_seconds();
+    if timeout > 0 && idle > timeout as i64 {
+        return Err(format!("No reconcile result for {} seconds", idle));
+    }
+
+    Ok(())
+}
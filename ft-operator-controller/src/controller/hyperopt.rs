@@ -0,0 +1,456 @@
+use kube::{
+    api::{Api, DeleteParams, ListParams, ObjectMeta, PostParams, ResourceExt},
+    runtime::{
+        controller::{Action, Controller},
+        finalizer::{finalizer, Event as Finalizer},
+        watcher,
+    },
+};
+use k8s_openapi::api::batch::v1::Job;
+use k8s_openapi::api::core::v1::{ConfigMap, Pod};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+use chrono::Utc;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::time::Duration;
+
+use ft_operator_common::config::AppConfig;
+use ft_operator_common::telemetry::info;
+
+use crate::controller::{
+    bot::USER_DATA_PATH,
+    context::Context,
+    job::{bot_job, strategy_path},
+    strategy::resolve_strategy,
+    utils::{apply, patch_status, requeue, runtime_config, scoped_api, FIELD_MANAGER},
+};
+use crate::crd::{
+    NamespacedCustomResource,
+    hub::{bot::Bot, hyperopt::{Hyperopt, HyperoptPhase}},
+    v1alpha1::bot::Bot as V1Alpha1Bot,
+};
+use crate::error::{ControllerError, Result};
+
+pub static FINALIZER: &str = "hyperopts.finalizers.freqtrade.io";
+
+// The annotation on the Job recording the strategy being optimized, used to name the results
+static STRATEGY_ANNOTATION: &str = "freqtrade.io/strategy-name";
+
+// The kubelet cuts termination messages longer than this
+const MAX_TERMINATION_MESSAGE_BYTES: usize = 4096;
+
+
+/// The name of the ConfigMap holding the best parameters of a hyperopt
+///
+/// # Arguments
+/// * `name` - The name of the hyperopt
+pub fn results_config_map_name(name: &str) -> String {
+    format!("{}-results", name)
+}
+
+/// The path of the best parameters of a hyperopt, relative to the bot's user data directory
+///
+/// # Arguments
+/// * `name` - The name of the hyperopt
+pub fn best_params_path(name: &str) -> String {
+    format!("hyperopt_results/{}.best.json", name)
+}
+
+/// Create the hyperopt Job from a Hyperopt Hub and the Bot it references
+///
+/// The Job runs `freqtrade hyperopt` and writes the best parameters found to the bot's PVC and
+/// to the container's termination message, where the controller picks them up once the Job
+/// succeeds. The epochs are stored in the hyperopt results directory of the bot's PVC, so they
+/// survive retries.
+///
+/// # Arguments
+/// * `hyperopt` - The Hyperopt Hub to create the Job from
+/// * `bot` - The Bot Hub being optimized
+/// * `name` - The name of the Job
+/// * `namespace` - The namespace of the Job
+/// * `owner_ref` - The owner reference for the Job
+/// * `config` - The application configuration
+///
+/// # Returns
+/// The Job resource
+pub fn hyperopt_job(hyperopt: &Hyperopt, bot: &Bot, name: &str, namespace: &str, owner_ref: OwnerReference, config: &AppConfig) -> Job {
+    let spec = &hyperopt.spec;
+    let common_args = [
+        "--config".to_string(),
+        "/etc/freqtrade/config.json".to_string(),
+        "--userdir".to_string(),
+        USER_DATA_PATH.to_string(),
+    ];
+    let params_file = format!("{}/{}", USER_DATA_PATH, best_params_path(name));
+
+    // The arguments are passed positionally to avoid quoting them into the script
+    let command = [
+        "sh".to_string(),
+        "-c".to_string(),
+        format!(
+            "freqtrade hyperopt \"$@\" && freqtrade hyperopt-show --best --print-json --no-header {} > {} && cp {} /dev/termination-log",
+            common_args.join(" "),
+            params_file,
+            params_file,
+        ),
+        "hyperopt".to_string(),
+    ]
+    .into_iter()
+    .chain(common_args.clone())
+    .chain([
+        "--strategy".to_string(),
+        bot.spec.strategy.name.clone(),
+        "--strategy-path".to_string(),
+        strategy_path(bot).to_string(),
+        "--epochs".to_string(),
+        spec.epochs.to_string(),
+        "--hyperopt-loss".to_string(),
+        spec.loss_function.clone(),
+        // The strategy is mounted read-only, the parameters are surfaced through the ConfigMap instead
+        "--disable-param-export".to_string(),
+    ])
+    .chain((!spec.spaces.is_empty()).then(|| vec!["--spaces".to_string()]).into_iter().flatten())
+    .chain(spec.spaces.clone())
+    .chain(spec.timerange.clone().map(|timerange| vec!["--timerange".to_string(), timerange]).into_iter().flatten())
+    .collect();
+
+    let mut job = bot_job(bot, name, namespace, owner_ref, "hyperopt", command, "hyperopt_results", config);
+    job.metadata.annotations
+        .get_or_insert_with(BTreeMap::new)
+        .insert(STRATEGY_ANNOTATION.to_string(), bot.spec.strategy.name.clone());
+    if let Some(job_spec) = job.spec.as_mut() {
+        job_spec.backoff_limit = Some(spec.backoff_limit);
+        job_spec.active_deadline_seconds = spec.job_timeout;
+        job_spec.ttl_seconds_after_finished = spec.ttl_seconds_after_finished;
+    }
+
+    job
+}
+
+pub struct HyperoptController;
+
+impl HyperoptController {
+    /// Create a new controller for the Hyperopt resource
+    ///
+    /// # Arguments
+    /// * `ctx` - The controller context
+    /// * `namespace` - The namespace to watch, or `None` to watch all namespaces
+    ///
+    /// # Returns
+    /// The controller for the Hyperopt resource
+    pub async fn create_controller<T>(ctx: Arc<Context>, namespace: Option<&str>) -> Controller<T>
+    where
+        T: NamespacedCustomResource,
+        Hyperopt: From<T>,
+    {
+        let client = ctx.client.clone();
+        let hyperopt = scoped_api::<T>(client.clone(), namespace);
+        let job = scoped_api::<Job>(client.clone(), namespace);
+
+        Controller::new(hyperopt, watcher::Config::default())
+            .with_config(runtime_config(&ctx))
+            .owns(job, watcher::Config::default())
+    }
+
+    /// Reconcile the Hyperopt resource
+    ///
+    /// This function is responsible for reconciling the Hyperopt resource.
+    ///
+    /// # Arguments
+    /// * `hyperopt` - The Hyperopt resource to reconcile
+    /// * `ctx` - The controller context
+    ///
+    /// # Returns
+    /// An action to take after reconciling the Hyperopt resource
+    pub async fn reconcile<T>(hyperopt: Arc<T>, ctx: Arc<Context>) -> Result<Action>
+    where
+        T: NamespacedCustomResource,
+        Hyperopt: From<T>,
+    {
+        let _in_flight = ctx.track_reconcile();
+        let namespace = hyperopt.namespace().ok_or(
+            ControllerError::MissingObjectKeyError(
+                "Expected Hyperopt to be namespaced via metadata.namespace"
+            )
+        )?;
+        let owner_ref = hyperopt.controller_owner_ref(&()).ok_or_else(|| {
+            ControllerError::MissingObjectKeyError(
+                "Expected Hyperopt to have an owner reference"
+            )
+        })?;
+        let api = Api::<T>::namespaced(ctx.client.clone(), &namespace);
+
+        finalizer(&api, FINALIZER, hyperopt, |event| async {
+            match event {
+                Finalizer::Apply(hyperopt) => reconcile_hyperopt(&hyperopt, &ctx, &namespace, &owner_ref).await,
+                Finalizer::Cleanup(hyperopt) => cleanup_hyperopt(&hyperopt, &ctx, &namespace).await,
+            }
+        })
+        .await
+        .map_err(|e| ControllerError::FinalizerError(e.to_string()))
+    }
+}
+
+/// Reconcile the hyperopt resource
+///
+/// This function is responsible for creating the hyperopt Job once, tracking its phase and
+/// copying the best parameters into the results ConfigMap when it succeeds. A finished
+/// hyperopt is left alone, so a Job removed by its TTL is not created again.
+///
+/// # Arguments
+/// * `hyperopt` - The hyperopt resource to reconcile
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the hyperopt resource
+/// * `owner_ref` - The owner reference for the Job and results ConfigMap
+///
+/// # Returns
+/// An action to take after reconciling the hyperopt resource
+async fn reconcile_hyperopt<T>(hyperopt: &T, ctx: &Context, namespace: &str, owner_ref: &OwnerReference) -> Result<Action>
+where
+    T: NamespacedCustomResource,
+    Hyperopt: From<T>,
+{
+    let name = hyperopt.name_any();
+    let hub = Hyperopt::from(hyperopt.clone());
+    let job_api = Api::<Job>::namespaced(ctx.client.clone(), namespace);
+
+    let finished = hub.status.as_ref().is_some_and(|status| {
+        status.phase == HyperoptPhase::Succeeded.to_string() || status.phase == HyperoptPhase::Failed.to_string()
+    });
+    if finished {
+        return Ok(Action::await_change());
+    }
+
+    let job = match job_api.get_opt(&name).await? {
+        Some(job) => job,
+        None => {
+            // Bots are read at their storage version
+            let mut bot = match Api::<V1Alpha1Bot>::namespaced(ctx.client.clone(), namespace)
+                .get_opt(&hub.spec.bot_ref.name)
+                .await?
+            {
+                Some(bot) => Bot::from(bot),
+                None => {
+                    update_status(hyperopt, ctx, namespace, HyperoptPhase::Pending, Some(format!("Bot `{}` was not found", hub.spec.bot_ref.name))).await?;
+                    return Ok(requeue(hyperopt, ctx, Duration::from_secs(30)));
+                },
+            };
+
+            if !bot.spec.pvc.enabled {
+                update_status(hyperopt, ctx, namespace, HyperoptPhase::Failed, Some(format!("Bot `{}` has no PVC to read data from", hub.spec.bot_ref.name))).await?;
+                return Ok(Action::await_change());
+            }
+
+            let condition = resolve_strategy(&mut bot, &ctx.client, namespace).await?;
+            if let Some(condition) = condition.filter(|condition| condition.status == "False") {
+                update_status(hyperopt, ctx, namespace, HyperoptPhase::Pending, condition.message).await?;
+                return Ok(requeue(hyperopt, ctx, Duration::from_secs(30)));
+            }
+
+            info!(
+                event = "CreatingHyperoptJob",
+                hyperopt = name.as_str()
+            );
+            job_api.create(
+                &PostParams { field_manager: Some(FIELD_MANAGER.to_string()), ..Default::default() },
+                &hyperopt_job(&hub, &bot, &name, namespace, owner_ref.clone(), &ctx.state.config()),
+            ).await?
+        },
+    };
+
+    let job_status = job.status.clone().unwrap_or_default();
+    if job_status.succeeded.unwrap_or(0) > 0 {
+        let Some(params) = best_params(ctx, namespace, &name).await? else {
+            update_status(hyperopt, ctx, namespace, HyperoptPhase::Failed, Some("The hyperopt Job did not report any results".to_string())).await?;
+            return Ok(Action::await_change());
+        };
+        let params = match check_best_params(params, &name) {
+            Ok(params) => params,
+            Err(message) => {
+                update_status(hyperopt, ctx, namespace, HyperoptPhase::Failed, Some(message)).await?;
+                return Ok(Action::await_change());
+            },
+        };
+        let strategy_name = job.annotations().get(STRATEGY_ANNOTATION).cloned().unwrap_or_else(|| "params".to_string());
+
+        info!(
+            event = "SurfacingHyperoptResults",
+            hyperopt = name.as_str()
+        );
+        let config_map_name = results_config_map_name(&name);
+        apply(
+            &Api::<ConfigMap>::namespaced(ctx.client.clone(), namespace),
+            ConfigMap {
+                metadata: ObjectMeta {
+                    name: Some(config_map_name.clone()),
+                    namespace: Some(namespace.to_string()),
+                    owner_references: Some(vec![owner_ref.clone()]),
+                    ..Default::default()
+                },
+                data: Some(BTreeMap::from([(format!("{}.json", strategy_name), params)])),
+                ..Default::default()
+            },
+            &config_map_name,
+        ).await?;
+
+        update_status(hyperopt, ctx, namespace, HyperoptPhase::Succeeded, None).await?;
+    } else if let Some(failed) = job_status.conditions.unwrap_or_default().into_iter().find(|condition| {
+        // The Job fails once its retries are exhausted or its timeout is reached
+        condition.type_ == "Failed" && condition.status == "True"
+    }) {
+        update_status(hyperopt, ctx, namespace, HyperoptPhase::Failed, failed.message).await?;
+    } else if job_status.active.unwrap_or(0) > 0 {
+        update_status(hyperopt, ctx, namespace, HyperoptPhase::Running, None).await?;
+    } else {
+        update_status(hyperopt, ctx, namespace, HyperoptPhase::Pending, None).await?;
+    }
+
+    Ok(Action::await_change())
+}
+
+/// Read the best parameters reported by a succeeded hyperopt Job
+///
+/// # Arguments
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the Job
+/// * `name` - The name of the Job
+///
+/// # Returns
+/// The best parameters as JSON, or None if no pod of the Job reported them
+async fn best_params(ctx: &Context, namespace: &str, name: &str) -> Result<Option<String>> {
+    let pods = Api::<Pod>::namespaced(ctx.client.clone(), namespace)
+        .list(&ListParams::default().labels(&format!("job-name={}", name)))
+        .await?;
+
+    Ok(pods.items
+        .into_iter()
+        .filter_map(|pod| pod.status?.container_statuses)
+        .flatten()
+        .filter_map(|status| status.state?.terminated)
+        .find(|terminated| terminated.exit_code == 0)
+        .and_then(|terminated| terminated.message)
+        .filter(|message| !message.trim().is_empty()))
+}
+
+/// Check the best parameters reported by a hyperopt Job are complete JSON
+///
+/// Parameters longer than a termination message are cut by the kubelet, they are then only
+/// available from the bot's PVC.
+///
+/// # Arguments
+/// * `params` - The termination message of the Job
+/// * `name` - The name of the hyperopt
+///
+/// # Returns
+/// The parameters, or the reason they can't be surfaced
+fn check_best_params(params: String, name: &str) -> std::result::Result<String, String> {
+    match serde_json::from_str::<serde_json::Value>(&params) {
+        Ok(_) => Ok(params),
+        Err(_) if params.len() >= MAX_TERMINATION_MESSAGE_BYTES => Err(format!(
+            "The best parameters are larger than the {} bytes a termination message can hold, they are kept in `{}` on the bot's PVC",
+            MAX_TERMINATION_MESSAGE_BYTES,
+            best_params_path(name),
+        )),
+        Err(err) => Err(format!("The hyperopt Job reported parameters that aren't valid JSON: {}", err)),
+    }
+}
+
+/// Cleanup the hyperopt resource
+///
+/// This function is responsible for deleting the hyperopt Job along with its pods, the
+/// results ConfigMap is garbage collected through its owner reference.
+///
+/// # Arguments
+/// * `hyperopt` - The hyperopt resource to cleanup
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the hyperopt resource
+///
+/// # Returns
+/// An action to take after cleaning up the hyperopt resource
+async fn cleanup_hyperopt<T>(hyperopt: &T, ctx: &Context, namespace: &str) -> Result<Action>
+where
+    T: NamespacedCustomResource,
+    Hyperopt: From<T>,
+{
+    let job_api = Api::<Job>::namespaced(ctx.client.clone(), namespace);
+
+    if job_api.get_opt(&hyperopt.name_any()).await?.is_some() {
+        info!(
+            event = "DeletingHyperoptJob",
+            hyperopt = hyperopt.name_any().as_str()
+        );
+        job_api.delete(&hyperopt.name_any(), &DeleteParams::background()).await?;
+    }
+
+    Ok(Action::await_change())
+}
+
+/// Update the status of the hyperopt resource
+///
+/// The completion time and results ConfigMap are set once the Job finishes, and nothing is
+/// patched when the phase and message are unchanged to avoid triggering the watch.
+///
+/// # Arguments
+/// * `hyperopt` - The hyperopt resource to update
+/// * `ctx` - The controller context
+/// * `namespace` - The namespace of the hyperopt resource
+/// * `phase` - The phase to set the hyperopt resource to
+/// * `message` - Details about the phase
+///
+/// # Returns
+/// A result indicating success or failure
+async fn update_status<T>(hyperopt: &T, ctx: &Context, namespace: &str, phase: HyperoptPhase, message: Option<String>) -> Result<()>
+where
+    T: NamespacedCustomResource,
+    Hyperopt: From<T>,
+{
+    let name = hyperopt.name_any();
+    let status = Hyperopt::from(hyperopt.clone()).status;
+
+    if status.as_ref().is_some_and(|status| status.phase == phase.to_string() && status.message == message) {
+        return Ok(());
+    }
+
+    let finished = matches!(phase, HyperoptPhase::Succeeded | HyperoptPhase::Failed);
+    let completion_time = status
+        .and_then(|status| status.completion_time)
+        .or_else(|| finished.then(Utc::now));
+    let results_config_map = (phase == HyperoptPhase::Succeeded).then(|| results_config_map_name(&name));
+
+    patch_status(&Api::<T>::namespaced(ctx.client.clone(), namespace), &name, json!({
+        "phase": phase.to_string(),
+        "message": message,
+        "completionTime": completion_time,
+        "resultsConfigMap": results_config_map,
+        "lastUpdated": Utc::now(),
+    })).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn complete_best_params_are_surfaced() {
+        let params = r#"{"params": {"buy": {"rsi": 30}}}"#.to_string();
+        assert_eq!(check_best_params(params.clone(), "hyperopt"), Ok(params));
+    }
+
+    #[test]
+    fn truncated_best_params_point_at_the_pvc() {
+        let params = format!(r#"{{"params": {{"buy": "{}"#, "x".repeat(MAX_TERMINATION_MESSAGE_BYTES))[..MAX_TERMINATION_MESSAGE_BYTES].to_string();
+
+        let message = check_best_params(params, "hyperopt").unwrap_err();
+        assert!(message.contains("4096 bytes"), "{}", message);
+        assert!(message.contains("`hyperopt_results/hyperopt.best.json`"), "{}", message);
+    }
+
+    #[test]
+    fn invalid_best_params_are_reported() {
+        let message = check_best_params("Traceback".to_string(), "hyperopt").unwrap_err();
+        assert!(message.starts_with("The hyperopt Job reported parameters that aren't valid JSON"), "{}", message);
+    }
+}
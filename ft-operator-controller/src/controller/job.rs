@@ -0,0 +1,187 @@
+use kube::api::ObjectMeta;
+use k8s_openapi::api::batch::v1::{Job, JobSpec};
+use k8s_openapi::api::core::v1::{
+    ConfigMapVolumeSource, Container, EnvVar, KeyToPath, LocalObjectReference, PersistentVolumeClaimVolumeSource,
+    PodSpec, PodTemplateSpec, Volume, VolumeMount,
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+use std::collections::BTreeMap;
+
+use ft_operator_common::config::AppConfig;
+
+use crate::controller::{bot::{strategy_parameters_key, USER_DATA_PATH}, utils::{merge_pull_secrets, with_defaults}};
+use crate::crd::hub::bot::Bot;
+
+
+/// The directory the strategy of a bot is mounted in
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+pub fn strategy_path(bot: &Bot) -> &'static str {
+    match bot.spec.strategy.config_map_name {
+        Some(_) => "/etc/freqtrade/strategy",
+        None => "/etc/freqtrade",
+    }
+}
+
+/// Create a one-off Job running a freqtrade command against a Bot
+///
+/// The Job uses the bot's image, ConfigMap and strategy. The candle data is mounted read-only
+/// from the bot's PVC, only the given results directory is writable. The Job is not retried.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub the Job runs against
+/// * `name` - The name of the Job
+/// * `namespace` - The namespace of the Job
+/// * `owner_ref` - The owner reference for the Job
+/// * `component` - The component label of the Job, also used as the container name
+/// * `command` - The command of the container
+/// * `results_dir` - The directory of the bot's PVC the Job writes its results to
+/// * `config` - The application configuration
+///
+/// # Returns
+/// The Job resource
+#[allow(clippy::too_many_arguments)]
+pub fn bot_job(
+    bot: &Bot,
+    name: &str,
+    namespace: &str,
+    owner_ref: OwnerReference,
+    component: &str,
+    command: Vec<String>,
+    results_dir: &str,
+    config: &AppConfig,
+) -> Job {
+    let bot_name = bot.child_name();
+    let image = &bot.spec.image;
+    let strategy = &bot.spec.strategy;
+    let image_pull_secrets = merge_pull_secrets(&config.controller.default_image_pull_secrets, image.pull_secrets.clone());
+
+    Job {
+        metadata: ObjectMeta {
+            name: Some(name.to_string()),
+            namespace: Some(namespace.to_string()),
+            owner_references: Some(vec![owner_ref]),
+            annotations: with_defaults(&config.controller.default_annotations, None),
+            labels: with_defaults(&config.controller.default_labels, Some(BTreeMap::from([
+                ("freqtrade.io/bot-name".to_string(), bot_name.clone()),
+                (format!("freqtrade.io/{}-name", component), name.to_string()),
+                ("app.kubernetes.io/component".to_string(), component.to_string()),
+                ("app.kubernetes.io/part-of".to_string(), "freqtrade".to_string()),
+                ("app.kubernetes.io/managed-by".to_string(), "freqtrade-operator".to_string()),
+            ]))),
+            ..Default::default()
+        },
+        spec: Some(JobSpec {
+            backoff_limit: Some(0),
+            template: PodTemplateSpec {
+                spec: Some(PodSpec {
+                    restart_policy: Some("Never".to_string()),
+                    image_pull_secrets: image_pull_secrets.map(|secrets| secrets
+                        .into_iter()
+                        .map(|secret| LocalObjectReference { name: secret })
+                        .collect()
+                    ),
+                    node_selector: bot.spec.deployment.node_selector.clone(),
+                    tolerations: bot.spec.deployment.tolerations.clone(),
+                    affinity: bot.spec.deployment.affinity.clone(),
+                    containers: vec![Container {
+                        name: component.to_string(),
+                        image: Some(image.image_name(&config.controller.default_image_repo, &config.controller.default_image_tag)),
+                        image_pull_policy: image.pull_policy.clone().or(config.controller.default_image_pull_policy.clone()),
+                        command: Some(command),
+                        env: Some(vec![EnvVar {
+                            name: "FREQTRADE__EXCHANGE__NAME".to_string(),
+                            value: Some(bot.spec.exchange.clone()),
+                            ..Default::default()
+                        }]),
+                        resources: bot.spec.deployment.resources.clone(),
+                        volume_mounts: Some(vec![
+                            VolumeMount {
+                                name: "config".to_string(),
+                                mount_path: "/etc/freqtrade".to_string(),
+                                read_only: Some(true),
+                                ..Default::default()
+                            },
+                            VolumeMount {
+                                name: "user-data".to_string(),
+                                mount_path: format!("{}/data", USER_DATA_PATH),
+                                sub_path: Some("data".to_string()),
+                                read_only: Some(true),
+                                ..Default::default()
+                            },
+                            VolumeMount {
+                                name: "user-data".to_string(),
+                                mount_path: format!("{}/{}", USER_DATA_PATH, results_dir),
+                                sub_path: Some(results_dir.to_string()),
+                                ..Default::default()
+                            },
+                        ]
+                        .into_iter()
+                        .chain(strategy.config_map_name.as_ref().map(|_| VolumeMount {
+                            name: "strategy".to_string(),
+                            mount_path: strategy_path(bot).to_string(),
+                            read_only: Some(true),
+                            ..Default::default()
+                        }))
+                        .collect()),
+                        ..Default::default()
+                    }],
+                    volumes: Some(vec![
+                        Volume {
+                            name: "config".to_string(),
+                            config_map: Some(ConfigMapVolumeSource {
+                                name: bot_name.clone(),
+                                items: Some(
+                                    vec![
+                                        KeyToPath {
+                                            key: "config.json".to_string(),
+                                            path: "config.json".to_string(),
+                                            ..Default::default()
+                                        },
+                                    ]
+                                    .into_iter()
+                                    .chain(strategy.config_map_name.is_none().then(|| KeyToPath {
+                                        key: "strategy.py".to_string(),
+                                        path: "strategy.py".to_string(),
+                                        ..Default::default()
+                                    }))
+                                    .chain(strategy_parameters_key(bot).map(|key| KeyToPath {
+                                        key: key.clone(),
+                                        path: key,
+                                        ..Default::default()
+                                    }))
+                                    .collect()
+                                ),
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        },
+                        Volume {
+                            name: "user-data".to_string(),
+                            persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+                                claim_name: bot_name.clone(),
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        },
+                    ]
+                    .into_iter()
+                    .chain(strategy.config_map_name.clone().map(|config_map_name| Volume {
+                        name: "strategy".to_string(),
+                        config_map: Some(ConfigMapVolumeSource {
+                            name: config_map_name,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }))
+                    .collect()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,87 @@
+use hyper::{Method, StatusCode};
+use kube::Client;
+use tokio::time::Duration;
+
+use crate::controller::bot_api::{api_authorization, api_base_url, api_request, ApiClient};
+use crate::crd::hub::bot::Bot;
+
+// How long a single call to the bot's API may take
+static API_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+
+/// Stop a bot from opening new trades through its REST API
+///
+/// The bot is reached through its Service and authenticated with its API username and password.
+/// The bot keeps managing its open trades. `/stopentry` is named `/stopbuy` in older freqtrade
+/// versions, which is called instead when it is missing.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub to stop
+/// * `client` - The kube client, to read the API credentials
+/// * `http` - The HTTP client to call the bot's API with
+/// * `name` - The name of the bot
+/// * `namespace` - The namespace of the bot
+///
+/// # Returns
+/// A Result with why the bot couldn't be reached as the error
+pub async fn stop_entries(bot: &Bot, client: &Client, http: &ApiClient, name: &str, namespace: &str) -> Result<(), String> {
+    let authorization = api_authorization(bot, client, namespace).await?;
+    let base_url = api_base_url(bot, name, namespace);
+    let url = format!("{}/stopentry", base_url);
+
+    match api_request(http, &url, Method::POST, &authorization, API_REQUEST_TIMEOUT).await {
+        Err(e) if e == format!("{} responded with {}", url, StatusCode::NOT_FOUND) => {
+            api_request(http, &format!("{}/stopbuy", base_url), Method::POST, &authorization, API_REQUEST_TIMEOUT).await?;
+        },
+        result => {
+            result?;
+        },
+    }
+
+    Ok(())
+}
+
+/// The number of open trades of a bot, read from its REST API
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+/// * `client` - The kube client, to read the API credentials
+/// * `http` - The HTTP client to call the bot's API with
+/// * `name` - The name of the bot
+/// * `namespace` - The namespace of the bot
+///
+/// # Returns
+/// A Result containing the number of open trades, or why they couldn't be read
+pub async fn open_trades(bot: &Bot, client: &Client, http: &ApiClient, name: &str, namespace: &str) -> Result<usize, String> {
+    let authorization = api_authorization(bot, client, namespace).await?;
+    let base_url = api_base_url(bot, name, namespace);
+
+    // `/status` lists the open trades
+    Ok(api_request(http, &format!("{}/status", base_url), Method::GET, &authorization, API_REQUEST_TIMEOUT)
+        .await?
+        .as_array()
+        .map_or(0, |trades| trades.len()))
+}
+
+/// Stop a bot through its REST API
+///
+/// Stopping the bot stops it from managing its open trades, so its entries are stopped and its
+/// trades waited on first.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub to stop
+/// * `client` - The kube client, to read the API credentials
+/// * `http` - The HTTP client to call the bot's API with
+/// * `name` - The name of the bot
+/// * `namespace` - The namespace of the bot
+///
+/// # Returns
+/// A Result with why the bot couldn't be stopped as the error
+pub async fn stop_bot(bot: &Bot, client: &Client, http: &ApiClient, name: &str, namespace: &str) -> Result<(), String> {
+    let authorization = api_authorization(bot, client, namespace).await?;
+    let base_url = api_base_url(bot, name, namespace);
+
+    api_request(http, &format!("{}/stop", base_url), Method::POST, &authorization, API_REQUEST_TIMEOUT).await?;
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{PoisonError, RwLock};
+
+use crate::crd::hub::bot::BotPhase;
+
+// Every phase has a series per bot, 1 for the current phase, so a transition doesn't leave the
+// previous phase behind
+static PHASES: [BotPhase; 5] = [BotPhase::Pending, BotPhase::Running, BotPhase::Error, BotPhase::Deleting, BotPhase::Suspended];
+
+
+/// The metrics of a bot
+#[derive(Clone, Debug, Default)]
+struct BotSeries {
+    phase: Option<String>,
+    reconciles: u64,
+    reconcile_errors: u64,
+}
+
+/// The metrics of every bot, labeled by the bot's namespace and name
+///
+/// The series of a bot are removed when it is deleted, so a large fleet churning bots doesn't
+/// accumulate stale series.
+#[derive(Debug, Default)]
+pub struct BotMetrics {
+    // By namespace and name
+    bots: RwLock<BTreeMap<(String, String), BotSeries>>,
+}
+
+impl BotMetrics {
+    /// Record the phase of a bot
+    ///
+    /// # Arguments
+    /// * `namespace` - The namespace of the bot
+    /// * `name` - The name of the bot
+    /// * `phase` - The phase of the bot
+    pub fn record_phase(&self, namespace: &str, name: &str, phase: &BotPhase) {
+        self.bots
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry((namespace.to_string(), name.to_string()))
+            .or_default()
+            .phase = Some(phase.to_string());
+    }
+
+    /// Record the result of a reconcile of a bot
+    ///
+    /// A successful reconcile is only counted for a bot that already has series, since the
+    /// reconcile finalizing a deleted bot succeeds after its series were removed.
+    ///
+    /// # Arguments
+    /// * `namespace` - The namespace of the bot
+    /// * `name` - The name of the bot
+    /// * `succeeded` - Whether the reconcile succeeded
+    pub fn record_reconcile(&self, namespace: &str, name: &str, succeeded: bool) {
+        let mut bots = self.bots.write().unwrap_or_else(PoisonError::into_inner);
+        let key = (namespace.to_string(), name.to_string());
+
+        if succeeded {
+            if let Some(series) = bots.get_mut(&key) {
+                series.reconciles += 1;
+            }
+        } else {
+            let series = bots.entry(key).or_default();
+            series.reconciles += 1;
+            series.reconcile_errors += 1;
+        }
+    }
+
+    /// Remove the series of a bot
+    ///
+    /// # Arguments
+    /// * `namespace` - The namespace of the bot
+    /// * `name` - The name of the bot
+    pub fn remove(&self, namespace: &str, name: &str) {
+        self.bots
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&(namespace.to_string(), name.to_string()));
+    }
+
+    /// Append the metrics in the Prometheus format
+    ///
+    /// # Arguments
+    /// * `metrics` - The metrics to append to
+    pub fn render(&self, metrics: &mut String) {
+        let bots = self.bots.read().unwrap_or_else(PoisonError::into_inner);
+
+        metrics.push_str(
+            "# HELP freqtrade_operator_bot_phase Whether a bot is in a phase\n\
+             # TYPE freqtrade_operator_bot_phase gauge\n"
+        );
+        for ((namespace, name), series) in bots.iter() {
+            let Some(current) = series.phase.as_deref() else {
+                continue;
+            };
+            for phase in PHASES.iter().map(BotPhase::to_string) {
+                let _ = writeln!(
+                    metrics,
+                    "freqtrade_operator_bot_phase{{namespace=\"{}\",name=\"{}\",phase=\"{}\"}} {}",
+                    namespace, name, phase, u8::from(phase == current),
+                );
+            }
+        }
+
+        metrics.push_str(
+            "# HELP freqtrade_operator_bot_reconciles_total The number of reconciles of a bot by result\n\
+             # TYPE freqtrade_operator_bot_reconciles_total counter\n"
+        );
+        for ((namespace, name), series) in bots.iter() {
+            for (result, count) in [("success", series.reconciles - series.reconcile_errors), ("error", series.reconcile_errors)] {
+                let _ = writeln!(
+                    metrics,
+                    "freqtrade_operator_bot_reconciles_total{{namespace=\"{}\",name=\"{}\",result=\"{}\"}} {}",
+                    namespace, name, result, count,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use kube::{client::Body, Client};
+use hyper::{Method, Request, Response, StatusCode};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+/// A request made to the mocked API server
+#[derive(Clone, Debug)]
+pub struct MockRequest {
+    pub method: Method,
+    pub path: String,
+    pub body: Value,
+}
+
+/// Create a kube Client answered in-process instead of by an API server
+///
+/// Every request is answered by `respond` and then sent on the returned channel, so tests can
+/// assert on the requests the controllers make.
+///
+/// # Arguments
+/// * `respond` - Returns the status and JSON body of the response to a request
+///
+/// # Returns
+/// The Client and the receiver of its requests
+pub fn mock_client<F>(respond: F) -> (Client, UnboundedReceiver<MockRequest>)
+where
+    F: Fn(&MockRequest) -> (StatusCode, Value) + Send + 'static,
+{
+    let (service, mut handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+    let (sender, receiver) = unbounded_channel();
+
+    tokio::spawn(async move {
+        while let Some((request, send)) = handle.next_request().await {
+            let (parts, body) = request.into_parts();
+            let body = body.collect().await.map(|body| body.to_bytes()).unwrap_or_default();
+            let request = MockRequest {
+                method: parts.method,
+                path: parts.uri.path().to_string(),
+                body: serde_json::from_slice(&body).unwrap_or(Value::Null),
+            };
+
+            // The request is recorded before it is answered, so it is seen once the response is.
+            // The test may have stopped listening once it saw what it was waiting for.
+            let (status, body) = respond(&request);
+            let _ = sender.send(request);
+            send.send_response(
+                Response::builder()
+                    .status(status)
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&body).unwrap_or_default()))
+                    .expect("The mocked response is valid"),
+            );
+        }
+    });
+
+    (Client::new(service, "default"), receiver)
+}
@@ -1,4 +1,24 @@
 pub mod utils;
 pub mod context;
+pub mod conditions;
+pub mod health;
+pub mod metrics;
+pub mod store;
 pub mod traits;
-pub mod bot;
\ No newline at end of file
+pub mod bot;
+pub mod bot_api;
+pub mod bot_fleet;
+pub mod database;
+pub mod signals;
+pub mod ui;
+pub mod strategy;
+pub mod exchange_credentials;
+pub mod job;
+pub mod lifecycle;
+pub mod backtest;
+pub mod hyperopt;
+pub mod data_download;
+pub mod template;
+pub mod doctor;
+#[cfg(test)]
+pub mod mock;
\ No newline at end of file
@@ -0,0 +1,200 @@
+use kube::{
+    api::{Api, ObjectMeta},
+    Client,
+};
+use k8s_openapi::api::core::v1::Secret;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+use k8s_openapi::ByteString;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+use crate::controller::bot::{api_credentials_secret_name, generated_api_secrets, generates_api_credentials};
+use crate::controller::utils::resolve_secret_item;
+use crate::crd::{NamespacedCustomResource, hub::bot::Bot};
+use crate::error::Result;
+
+pub static PRODUCERS_RESOLVED_CONDITION: &str = "ProducersResolved";
+pub static SIGNALS_SECRET_KEY: &str = "producers";
+
+
+/// The producers of a consumer bot, resolved from the referenced Bot resources
+pub struct ResolvedProducers {
+    /// The `external_message_consumer.producers` entries for freqtrade
+    pub producers: Vec<Value>,
+    /// The references that could not be resolved, as `namespace/name`
+    pub missing: Vec<String>,
+    /// The references to producers in other namespaces which don't allow the consumer, as
+    /// `namespace/name`
+    pub denied: Vec<String>,
+}
+
+/// The name of the Secret holding the resolved producers of a bot
+///
+/// # Arguments
+/// * `name` - The name of the consumer bot
+pub fn signals_secret_name(name: &str) -> String {
+    format!("{}-signals", name)
+}
+
+/// Whether a producer bot allows a consumer bot to consume its signals
+///
+/// A producer's websocket token is copied into the consumer's namespace, so a producer in
+/// another namespace must opt in through `signals.allowedConsumerNamespaces`.
+///
+/// # Arguments
+/// * `producer` - The producer Bot Hub
+/// * `producer_namespace` - The namespace of the producer bot
+/// * `consumer_namespace` - The namespace of the consumer bot
+pub fn allows_consumer(producer: &Bot, producer_namespace: &str, consumer_namespace: &str) -> bool {
+    producer_namespace == consumer_namespace
+        || producer.spec.signals.allowed_consumer_namespaces
+            .iter()
+            .any(|allowed| allowed == "*" || allowed == consumer_namespace)
+}
+
+/// Resolve the producers referenced by a bot
+///
+/// Each producer is resolved to its Service DNS name, API port and websocket token. The token
+/// is read from the referenced Secret when the producer uses a secretKeyRef. A producer in
+/// another namespace is only resolved when it allows the consumer's namespace, and when the
+/// operator watches every namespace, since the changes to a producer outside of the consumer's
+/// watched namespace would never re-reconcile it.
+///
+/// # Arguments
+/// * `bot` - The consumer Bot Hub
+/// * `client` - The kube client
+/// * `namespace` - The namespace of the consumer bot
+/// * `all_namespaces` - Whether the operator watches every namespace
+///
+/// # Returns
+/// The resolved producers, any dangling references and the references denied
+pub async fn resolve_producers<T>(bot: &Bot, client: &Client, namespace: &str, all_namespaces: bool) -> Result<ResolvedProducers>
+where
+    T: NamespacedCustomResource,
+    Bot: From<T>,
+{
+    let mut resolved = ResolvedProducers { producers: vec![], missing: vec![], denied: vec![] };
+
+    for producer_ref in &bot.spec.signals.producers {
+        let producer_namespace = producer_ref.bot_ref.namespace.as_deref().unwrap_or(namespace);
+        if producer_namespace != namespace && !all_namespaces {
+            resolved.denied.push(format!("{}/{}", producer_namespace, producer_ref.bot_ref.name));
+            continue;
+        }
+
+        let producer = match Api::<T>::namespaced(client.clone(), producer_namespace)
+            .get_opt(&producer_ref.bot_ref.name)
+            .await?
+        {
+            Some(producer) => Bot::from(producer),
+            None => {
+                resolved.missing.push(format!("{}/{}", producer_namespace, producer_ref.bot_ref.name));
+                continue;
+            },
+        };
+
+        if !allows_consumer(&producer, producer_namespace, namespace) {
+            resolved.denied.push(format!("{}/{}", producer_namespace, producer_ref.bot_ref.name));
+            continue;
+        }
+
+        // A producer without API secrets uses its generated API credentials
+        let api = match generates_api_credentials(&producer) {
+            true => Some(generated_api_secrets(&api_credentials_secret_name(&producer.child_name()))),
+            false => producer.spec.secrets.api.clone(),
+        };
+        let ws_token = match api.as_ref().and_then(|api| api.ws_token.as_ref()) {
+            Some(ws_token) => resolve_secret_item(client, producer_namespace, ws_token).await?,
+            None => None,
+        };
+
+        // A producer without an API or a websocket token can't be consumed
+        match ws_token.filter(|_| producer.spec.api.enabled) {
+            Some(ws_token) => resolved.producers.push(json!({
+                "name": format!("{}-{}", producer_namespace, producer_ref.bot_ref.name),
+                "host": format!("{}.{}.svc", producer.child_name(), producer_namespace),
+                "port": producer.spec.api.port,
+                "secure": false,
+                "ws_token": ws_token,
+            })),
+            None => resolved.missing.push(format!("{}/{}", producer_namespace, producer_ref.bot_ref.name)),
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Create the Secret holding the resolved producers of a bot
+///
+/// # Arguments
+/// * `name` - The name of the Secret
+/// * `namespace` - The namespace of the Secret
+/// * `owner_ref` - The owner reference for the Secret
+/// * `producers` - The resolved producers
+///
+/// # Returns
+/// The Secret resource
+pub fn signals_secret(name: &str, namespace: &str, owner_ref: OwnerReference, producers: &[Value]) -> Secret {
+    Secret {
+        metadata: ObjectMeta {
+            name: Some(name.to_string()),
+            namespace: Some(namespace.to_string()),
+            owner_references: Some(vec![owner_ref]),
+            ..Default::default()
+        },
+        data: Some(BTreeMap::from([
+            (SIGNALS_SECRET_KEY.to_string(), ByteString(Value::from(producers.to_vec()).to_string().into_bytes())),
+        ])),
+        ..Default::default()
+    }
+}
+
+/// Whether a bot consumes signals from the given producer
+///
+/// # Arguments
+/// * `consumer` - The consumer Bot Hub
+/// * `consumer_namespace` - The namespace of the consumer bot
+/// * `producer_name` - The name of the producer bot
+/// * `producer_namespace` - The namespace of the producer bot
+pub fn consumes_from(consumer: &Bot, consumer_namespace: &str, producer_name: &str, producer_namespace: &str) -> bool {
+    consumer.spec.signals.producers.iter().any(|producer| {
+        producer.bot_ref.name == producer_name
+            && producer.bot_ref.namespace.as_deref().unwrap_or(consumer_namespace) == producer_namespace
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crd::v1alpha1::bot::Bot as V1Alpha1Bot;
+
+    fn producer(allowed_consumer_namespaces: Value) -> Bot {
+        Bot::from(serde_json::from_value::<V1Alpha1Bot>(json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "Bot",
+            "metadata": { "name": "producer", "namespace": "signals" },
+            "spec": {
+                "exchange": "binance",
+                "strategy": { "name": "SampleStrategy" },
+                "signals": { "allowedConsumerNamespaces": allowed_consumer_namespaces },
+            },
+        })).unwrap())
+    }
+
+    #[test]
+    fn allows_consumers_in_the_same_namespace() {
+        assert!(allows_consumer(&producer(json!([])), "signals", "signals"));
+    }
+
+    #[test]
+    fn denies_consumers_in_other_namespaces_by_default() {
+        assert!(!allows_consumer(&producer(json!([])), "signals", "tenant-a"));
+        assert!(!allows_consumer(&producer(json!(["tenant-b"])), "signals", "tenant-a"));
+    }
+
+    #[test]
+    fn allows_consumers_in_allowed_namespaces() {
+        assert!(allows_consumer(&producer(json!(["tenant-a"])), "signals", "tenant-a"));
+        assert!(allows_consumer(&producer(json!(["*"])), "signals", "tenant-a"));
+    }
+}
@@ -0,0 +1,536 @@
+use futures::FutureExt;
+use k8s_openapi::api::{apps::v1::Deployment, core::v1::{ConfigMap, PersistentVolumeClaim, Pod, Secret, Service}, networking::v1::Ingress};
+use kube::{api::{Api, ListParams, PartialObjectMeta}, runtime::reflector::{ObjectRef, Store}, Resource, ResourceExt};
+use serde::de::DeserializeOwned;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::sync::{Mutex, PoisonError, RwLock};
+
+use crate::controller::utils::label_selector;
+use crate::error::Result;
+
+
+/// The cached objects of a kind of child resource, filled by the watches of the Bot controller
+///
+/// The cache lags behind the controller's own writes, so the resourceVersion of every write is
+/// remembered until the cache has observed it. An object older than the last write to it, or
+/// missing from the cache before every watch has listed, is read with a GET instead.
+pub struct ChildStore<K>
+where
+    K: Resource<DynamicType = ()> + Clone + 'static,
+{
+    // One per watched namespace
+    stores: RwLock<Vec<Store<K>>>,
+    // The resourceVersion of the last write by namespace and name, None once deleted
+    written: Mutex<HashMap<(String, String), Option<String>>>,
+}
+
+impl<K> Default for ChildStore<K>
+where
+    K: Resource<DynamicType = ()> + Clone + 'static,
+{
+    fn default() -> Self {
+        Self {
+            stores: RwLock::default(),
+            written: Mutex::default(),
+        }
+    }
+}
+
+impl<K> ChildStore<K>
+where
+    K: Resource<DynamicType = ()> + Clone + Debug + DeserializeOwned + 'static,
+{
+    /// Add the store of a watch
+    ///
+    /// # Arguments
+    /// * `store` - The store filled by the watch
+    pub fn add(&self, store: Store<K>) {
+        self.stores.write().unwrap_or_else(PoisonError::into_inner).push(store);
+    }
+
+    /// Remember a write to an object, so it is read with a GET until the cache observed it
+    ///
+    /// # Arguments
+    /// * `namespace` - The namespace of the object
+    /// * `name` - The name of the object
+    /// * `object` - The object returned by the write, None when it was deleted
+    pub fn record_write(&self, namespace: &str, name: &str, object: Option<&K>) {
+        self.written
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert((namespace.to_string(), name.to_string()), object.and_then(|object| object.resource_version()));
+    }
+
+    /// Get the current state of an object
+    ///
+    /// # Arguments
+    /// * `api` - The API of the object's kind in its namespace, for the GET on a cache miss
+    /// * `namespace` - The namespace of the object
+    /// * `name` - The name of the object
+    ///
+    /// # Returns
+    /// A Result containing the object, or None when it doesn't exist
+    pub async fn get(&self, api: &Api<K>, namespace: &str, name: &str) -> Result<Option<K>> {
+        if let Some(object) = self.cached(namespace, name) {
+            return Ok(object);
+        }
+
+        let object = api.get_opt(name).await?;
+
+        // The cache may have missed the write for another one since, so the object read is what
+        // it has to catch up to
+        if let Some(written) = self.written
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_mut(&(namespace.to_string(), name.to_string()))
+        {
+            *written = object.as_ref().and_then(|object| object.resource_version());
+        }
+
+        Ok(object)
+    }
+
+    /// The cached state of an object, once the cache has observed the last write to it
+    ///
+    /// The write is only forgotten once the cache has observed it, so every read until then
+    /// goes to the API server. An object missing from the cache doesn't exist once every watch
+    /// has listed.
+    ///
+    /// # Arguments
+    /// * `namespace` - The namespace of the object
+    /// * `name` - The name of the object
+    ///
+    /// # Returns
+    /// The cached object, None when it has to be read with a GET
+    fn cached(&self, namespace: &str, name: &str) -> Option<Option<K>> {
+        let object_ref = ObjectRef::<K>::new(name).within(namespace);
+        let key = (namespace.to_string(), name.to_string());
+        let stores = self.stores.read().unwrap_or_else(PoisonError::into_inner);
+        let cached = stores.iter().find_map(|store| store.get(&object_ref));
+        let listed = !stores.is_empty()
+            && stores.iter().all(|store| matches!(store.wait_until_ready().now_or_never(), Some(Ok(()))));
+
+        let mut written = self.written.lock().unwrap_or_else(PoisonError::into_inner);
+        match written.get(&key) {
+            Some(version) if cached.as_ref().and_then(|object| object.resource_version()) != *version => None,
+            Some(_) => {
+                written.remove(&key);
+                Some(cached.map(|object| object.as_ref().clone()))
+            },
+            None if cached.is_none() && !listed => None,
+            None => Some(cached.map(|object| object.as_ref().clone())),
+        }
+    }
+}
+
+/// The ConfigMaps or Secrets referenced by the bots, read again only once they changed
+///
+/// ConfigMaps and Secrets are only watched for their metadata, since their bodies can be large
+/// and most of them aren't referenced by a bot. A referenced object is read with a GET the first
+/// time and once the watch has seen a new resourceVersion of it, rather than on every reconcile.
+pub struct MetadataStore<K>
+where
+    K: Resource<DynamicType = ()> + Clone + 'static,
+{
+    // The metadata of the objects, one per watched namespace
+    stores: RwLock<Vec<Store<PartialObjectMeta<K>>>>,
+    // The last read of each referenced object by namespace and name
+    read: Mutex<HashMap<(String, String), K>>,
+}
+
+impl<K> Default for MetadataStore<K>
+where
+    K: Resource<DynamicType = ()> + Clone + 'static,
+{
+    fn default() -> Self {
+        Self {
+            stores: RwLock::default(),
+            read: Mutex::default(),
+        }
+    }
+}
+
+impl<K> MetadataStore<K>
+where
+    K: Resource<DynamicType = ()> + Clone + Debug + DeserializeOwned + 'static,
+{
+    /// Add the store of a metadata watch
+    ///
+    /// # Arguments
+    /// * `store` - The store filled by the watch
+    pub fn add(&self, store: Store<PartialObjectMeta<K>>) {
+        self.stores.write().unwrap_or_else(PoisonError::into_inner).push(store);
+    }
+
+    /// Remember a write to an object, so it is read with a GET until the watch has seen it
+    ///
+    /// # Arguments
+    /// * `namespace` - The namespace of the object
+    /// * `name` - The name of the object
+    /// * `object` - The object returned by the write, None when it was deleted
+    pub fn record_write(&self, namespace: &str, name: &str, object: Option<&K>) {
+        let key = (namespace.to_string(), name.to_string());
+        let mut read = self.read.lock().unwrap_or_else(PoisonError::into_inner);
+        match object {
+            Some(object) => read.insert(key, object.clone()),
+            None => read.remove(&key),
+        };
+    }
+
+    /// Get the current state of an object
+    ///
+    /// An object the watch hasn't seen is read with a GET, so one created since or not listed yet
+    /// isn't reported missing.
+    ///
+    /// # Arguments
+    /// * `api` - The API of the object's kind in its namespace, for the GET of a changed object
+    /// * `namespace` - The namespace of the object
+    /// * `name` - The name of the object
+    ///
+    /// # Returns
+    /// A Result containing the object, or None when it doesn't exist
+    pub async fn get(&self, api: &Api<K>, namespace: &str, name: &str) -> Result<Option<K>> {
+        let key = (namespace.to_string(), name.to_string());
+        let object_ref = ObjectRef::<PartialObjectMeta<K>>::new(name).within(namespace);
+        let version = self.stores
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .find_map(|store| store.get(&object_ref))
+            .and_then(|metadata| metadata.resource_version());
+
+        if let Some(version) = &version {
+            let read = self.read.lock().unwrap_or_else(PoisonError::into_inner);
+            if let Some(object) = read.get(&key).filter(|object| object.resource_version().as_ref() == Some(version)) {
+                return Ok(Some(object.clone()));
+            }
+        }
+
+        let object = api.get_opt(name).await?;
+        let mut read = self.read.lock().unwrap_or_else(PoisonError::into_inner);
+        match &object {
+            Some(object) => read.insert(key, object.clone()),
+            None => read.remove(&key),
+        };
+
+        Ok(object)
+    }
+}
+
+/// The pods of the bots, filled by a watch of the pods labelled as a bot's
+///
+/// The pods are listed from the API server until every watch has listed them once, so a bot
+/// reconciled right after the start isn't seen without its pods.
+#[derive(Default)]
+pub struct PodStore {
+    // One per watched namespace
+    stores: RwLock<Vec<Store<Pod>>>,
+}
+
+impl PodStore {
+    /// Add the store of a watch
+    ///
+    /// # Arguments
+    /// * `store` - The store filled by the watch
+    pub fn add(&self, store: Store<Pod>) {
+        self.stores.write().unwrap_or_else(PoisonError::into_inner).push(store);
+    }
+
+    /// List the pods matching a set of labels
+    ///
+    /// # Arguments
+    /// * `api` - The API of the Pods in the namespace, for the LIST until the watches are ready
+    /// * `namespace` - The namespace of the pods
+    /// * `labels` - The labels the pods must all have
+    ///
+    /// # Returns
+    /// A Result containing the matching pods
+    pub async fn list(&self, api: &Api<Pod>, namespace: &str, labels: &BTreeMap<String, String>) -> Result<Vec<Pod>> {
+        let stores = self.stores.read().unwrap_or_else(PoisonError::into_inner).clone();
+        let ready = !stores.is_empty()
+            && stores.iter().all(|store| matches!(store.wait_until_ready().now_or_never(), Some(Ok(()))));
+
+        if !ready {
+            return Ok(api.list(&ListParams::default().labels(&label_selector(labels))).await?.items);
+        }
+
+        Ok(stores
+            .iter()
+            .flat_map(|store| store.state())
+            .filter(|pod| pod.namespace().as_deref() == Some(namespace))
+            .filter(|pod| labels.iter().all(|(key, value)| pod.labels().get(key) == Some(value)))
+            .map(|pod| pod.as_ref().clone())
+            .collect())
+    }
+}
+
+/// The cached child resources of the bots, their pods, and the ConfigMaps and Secrets they reference
+#[derive(Default)]
+pub struct BotChildStores {
+    pub pvcs: ChildStore<PersistentVolumeClaim>,
+    pub deployments: ChildStore<Deployment>,
+    pub services: ChildStore<Service>,
+    pub ingresses: ChildStore<Ingress>,
+    pub pods: PodStore,
+    pub config_maps: MetadataStore<ConfigMap>,
+    pub secrets: MetadataStore<Secret>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::{Method, StatusCode};
+    use k8s_openapi::ByteString;
+    use kube::{api::ObjectMeta, runtime::{reflector::{self, store::Writer}, watcher}};
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tokio::sync::mpsc::UnboundedReceiver;
+
+    use crate::controller::mock::{mock_client, MockRequest};
+
+    fn service(resource_version: &str) -> Service {
+        Service {
+            metadata: ObjectMeta {
+                name: Some("bot".to_string()),
+                namespace: Some("default".to_string()),
+                resource_version: Some(resource_version.to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn child_store() -> (ChildStore<Service>, Writer<Service>) {
+        let (store, mut writer) = reflector::store();
+        writer.apply_watcher_event(&watcher::Event::InitDone);
+        let child_store = ChildStore::default();
+        child_store.add(store);
+        (child_store, writer)
+    }
+
+    #[test]
+    fn reads_the_cached_object_without_a_write() {
+        let (store, mut writer) = child_store();
+        writer.apply_watcher_event(&watcher::Event::Apply(service("1")));
+
+        assert_eq!(store.cached("default", "bot"), Some(Some(service("1"))));
+    }
+
+    #[test]
+    fn reads_a_missing_object_from_the_api_until_listed() {
+        let (cache, mut writer) = reflector::store();
+        let store = ChildStore::<Service>::default();
+        store.add(cache);
+
+        assert_eq!(store.cached("default", "bot"), None);
+
+        // Once listed, the object missing from the cache doesn't exist
+        writer.apply_watcher_event(&watcher::Event::InitDone);
+        assert_eq!(store.cached("default", "bot"), Some(None));
+    }
+
+    #[test]
+    fn reads_a_missing_object_from_the_api_without_a_watch() {
+        assert_eq!(ChildStore::<Service>::default().cached("default", "bot"), None);
+    }
+
+    #[test]
+    fn reads_its_writes_until_the_cache_observes_them() {
+        let (store, mut writer) = child_store();
+        writer.apply_watcher_event(&watcher::Event::Apply(service("1")));
+        store.record_write("default", "bot", Some(&service("2")));
+
+        // Every read goes to the API server until the cache has caught up, not only the first
+        assert_eq!(store.cached("default", "bot"), None);
+        assert_eq!(store.cached("default", "bot"), None);
+
+        writer.apply_watcher_event(&watcher::Event::Apply(service("2")));
+        assert_eq!(store.cached("default", "bot"), Some(Some(service("2"))));
+
+        // The write is forgotten once observed, so a later change by someone else is read
+        writer.apply_watcher_event(&watcher::Event::Apply(service("3")));
+        assert_eq!(store.cached("default", "bot"), Some(Some(service("3"))));
+    }
+
+    #[test]
+    fn reads_its_deletes_until_the_cache_observes_them() {
+        let (store, mut writer) = child_store();
+        writer.apply_watcher_event(&watcher::Event::Apply(service("1")));
+        store.record_write("default", "bot", None);
+
+        assert_eq!(store.cached("default", "bot"), None);
+
+        writer.apply_watcher_event(&watcher::Event::Delete(service("1")));
+        assert_eq!(store.cached("default", "bot"), Some(None));
+    }
+
+    fn secret_metadata(resource_version: &str) -> PartialObjectMeta<Secret> {
+        PartialObjectMeta {
+            metadata: ObjectMeta {
+                name: Some("exchange".to_string()),
+                namespace: Some("default".to_string()),
+                resource_version: Some(resource_version.to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    // A Secret store over an API server answering with the Secret in `secret`, or 404 when None
+    fn secret_store(secret: Arc<Mutex<Option<Value>>>) -> (MetadataStore<Secret>, Writer<PartialObjectMeta<Secret>>, Api<Secret>, UnboundedReceiver<MockRequest>) {
+        let (client, requests) = mock_client(move |_| match secret.lock().unwrap().clone() {
+            Some(secret) => (StatusCode::OK, secret),
+            None => (StatusCode::NOT_FOUND, json!({ "kind": "Status", "apiVersion": "v1", "status": "Failure", "reason": "NotFound", "code": 404 })),
+        });
+        let (store, mut writer) = reflector::store();
+        writer.apply_watcher_event(&watcher::Event::InitDone);
+        let secret_store = MetadataStore::default();
+        secret_store.add(store);
+
+        (secret_store, writer, Api::namespaced(client, "default"), requests)
+    }
+
+    fn exchange_secret(resource_version: &str, value: &str) -> Value {
+        json!({
+            "apiVersion": "v1",
+            "kind": "Secret",
+            "metadata": { "name": "exchange", "namespace": "default", "resourceVersion": resource_version },
+            "data": { "key": value },
+        })
+    }
+
+    fn gets(requests: &mut UnboundedReceiver<MockRequest>) -> usize {
+        let mut gets = 0;
+        while let Ok(request) = requests.try_recv() {
+            assert_eq!(request.method, Method::GET);
+            assert_eq!(request.path, "/api/v1/namespaces/default/secrets/exchange");
+            gets += 1;
+        }
+        gets
+    }
+
+    #[tokio::test]
+    async fn reads_an_unchanged_secret_once() {
+        let response = Arc::new(Mutex::new(Some(exchange_secret("1", "YQ=="))));
+        let (store, mut writer, api, mut requests) = secret_store(response.clone());
+        writer.apply_watcher_event(&watcher::Event::Apply(secret_metadata("1")));
+
+        for _ in 0..3 {
+            let secret = store.get(&api, "default", "exchange").await.unwrap().unwrap();
+            assert_eq!(secret.data.unwrap()["key"], ByteString(b"a".to_vec()));
+        }
+        assert_eq!(gets(&mut requests), 1);
+
+        // A change seen by the watch is read again
+        *response.lock().unwrap() = Some(exchange_secret("2", "Yg=="));
+        writer.apply_watcher_event(&watcher::Event::Apply(secret_metadata("2")));
+        let secret = store.get(&api, "default", "exchange").await.unwrap().unwrap();
+        assert_eq!(secret.data.unwrap()["key"], ByteString(b"b".to_vec()));
+        assert_eq!(gets(&mut requests), 1);
+    }
+
+    #[tokio::test]
+    async fn reads_a_missing_secret_until_it_is_created() {
+        let response = Arc::new(Mutex::new(None));
+        let (store, mut writer, api, mut requests) = secret_store(response.clone());
+
+        assert_eq!(store.get(&api, "default", "exchange").await.unwrap(), None);
+        assert_eq!(store.get(&api, "default", "exchange").await.unwrap(), None);
+        assert_eq!(gets(&mut requests), 2);
+
+        // Created before the watch has seen it
+        *response.lock().unwrap() = Some(exchange_secret("1", "YQ=="));
+        assert!(store.get(&api, "default", "exchange").await.unwrap().is_some());
+
+        writer.apply_watcher_event(&watcher::Event::Apply(secret_metadata("1")));
+        assert!(store.get(&api, "default", "exchange").await.unwrap().is_some());
+        assert_eq!(gets(&mut requests), 1);
+    }
+
+    #[tokio::test]
+    async fn reads_its_writes_until_the_watch_has_seen_them() {
+        let response = Arc::new(Mutex::new(Some(exchange_secret("1", "YQ=="))));
+        let (store, mut writer, api, mut requests) = secret_store(response.clone());
+        writer.apply_watcher_event(&watcher::Event::Apply(secret_metadata("1")));
+        store.get(&api, "default", "exchange").await.unwrap();
+        assert_eq!(gets(&mut requests), 1);
+
+        // The watch still has the version before the write, whose read is stale
+        let written: Secret = serde_json::from_value(exchange_secret("2", "Yg==")).unwrap();
+        *response.lock().unwrap() = Some(exchange_secret("2", "Yg=="));
+        store.record_write("default", "exchange", Some(&written));
+        let secret = store.get(&api, "default", "exchange").await.unwrap().unwrap();
+        assert_eq!(secret.data.unwrap()["key"], ByteString(b"b".to_vec()));
+        assert_eq!(gets(&mut requests), 1);
+
+        writer.apply_watcher_event(&watcher::Event::Apply(secret_metadata("2")));
+        store.get(&api, "default", "exchange").await.unwrap();
+        assert_eq!(gets(&mut requests), 0);
+    }
+
+    fn pod(name: &str, namespace: &str, bot_name: &str) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some(namespace.to_string()),
+                labels: Some(BTreeMap::from([
+                    ("freqtrade.io/bot-name".to_string(), bot_name.to_string()),
+                    ("app.kubernetes.io/component".to_string(), "bot".to_string()),
+                ])),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn bot_labels(bot_name: &str) -> BTreeMap<String, String> {
+        BTreeMap::from([("freqtrade.io/bot-name".to_string(), bot_name.to_string())])
+    }
+
+    fn pod_names(pods: Vec<Pod>) -> Vec<String> {
+        pods.into_iter().map(|pod| pod.name_any()).collect()
+    }
+
+    #[tokio::test]
+    async fn lists_pods_from_the_api_until_the_watch_is_ready() {
+        let (client, mut requests) = mock_client(|_| (StatusCode::OK, json!({
+            "apiVersion": "v1",
+            "kind": "PodList",
+            "metadata": {},
+            "items": [serde_json::to_value(pod("bot-1", "default", "bot")).unwrap()],
+        })));
+        let api = Api::<Pod>::namespaced(client, "default");
+        let (store, mut writer) = reflector::store();
+        let pod_store = PodStore::default();
+        pod_store.add(store);
+
+        assert_eq!(pod_names(pod_store.list(&api, "default", &bot_labels("bot")).await.unwrap()), ["bot-1"]);
+        let request = requests.try_recv().unwrap();
+        assert_eq!((request.method, request.path.as_str()), (Method::GET, "/api/v1/namespaces/default/pods"));
+
+        // Once listed by the watch, the pods are read from it
+        writer.apply_watcher_event(&watcher::Event::InitDone);
+        writer.apply_watcher_event(&watcher::Event::Apply(pod("bot-2", "default", "bot")));
+        assert_eq!(pod_names(pod_store.list(&api, "default", &bot_labels("bot")).await.unwrap()), ["bot-2"]);
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn lists_the_pods_matching_the_namespace_and_labels() {
+        let (client, mut requests) = mock_client(|_| (StatusCode::INTERNAL_SERVER_ERROR, json!({})));
+        let api = Api::<Pod>::namespaced(client, "default");
+        let (store, mut writer) = reflector::store();
+        writer.apply_watcher_event(&watcher::Event::InitDone);
+        for pod in [pod("bot-1", "default", "bot"), pod("other-1", "default", "other"), pod("bot-1", "trading", "bot")] {
+            writer.apply_watcher_event(&watcher::Event::Apply(pod));
+        }
+        let pod_store = PodStore::default();
+        pod_store.add(store);
+
+        let pods = pod_store.list(&api, "default", &bot_labels("bot")).await.unwrap();
+        assert_eq!(pods.iter().map(|pod| pod.namespace().unwrap_or_default()).collect::<Vec<_>>(), ["default"]);
+        assert_eq!(pod_names(pods), ["bot-1"]);
+        assert!(requests.try_recv().is_err());
+    }
+}
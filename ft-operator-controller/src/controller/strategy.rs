@@ -0,0 +1,335 @@
+use kube::{
+    api::{Api, ListParams, ResourceExt},
+    runtime::{
+        controller::{Action, Controller},
+        reflector::ObjectRef,
+        watcher,
+    },
+    Client,
+};
+use chrono::Utc;
+use serde_json::json;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use ft_operator_common::telemetry::info;
+
+use crate::controller::{context::Context, utils::{patch_status, runtime_config, scoped_api}};
+use crate::crd::{
+    NamespacedCustomResource,
+    hub::{bot::Bot, common::Condition, strategy::{Strategy, StrategyPhase}},
+    v1alpha1::{bot::Bot as V1Alpha1Bot, strategy::Strategy as V1Alpha1Strategy},
+};
+use crate::error::{ControllerError, Result};
+
+pub static STRATEGY_RESOLVED_CONDITION: &str = "StrategyResolved";
+
+
+/// Whether a bot references the given Strategy
+///
+/// Strategies can only be referenced from the same namespace.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub
+/// * `bot_namespace` - The namespace of the bot
+/// * `strategy_name` - The name of the Strategy
+/// * `strategy_namespace` - The namespace of the Strategy
+pub fn references_strategy(bot: &Bot, bot_namespace: &str, strategy_name: &str, strategy_namespace: &str) -> bool {
+    bot_namespace == strategy_namespace
+        && bot.spec.strategy.strategy_ref.as_ref().is_some_and(|strategy_ref| strategy_ref.name == strategy_name)
+}
+
+/// Resolve the Strategy referenced by a bot
+///
+/// The referenced Strategy is inlined into the bot's strategy spec, so it is rendered
+/// into the ConfigMap and config hash exactly like an inline strategy. Strategies are
+/// read at their storage version.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub to resolve the strategy for
+/// * `client` - The kube client
+/// * `namespace` - The namespace of the bot
+///
+/// # Returns
+/// The `StrategyResolved` condition, or None if the bot doesn't reference a Strategy
+pub async fn resolve_strategy(bot: &mut Bot, client: &Client, namespace: &str) -> Result<Option<Condition>> {
+    let Some(strategy_ref) = bot.spec.strategy.strategy_ref.clone() else {
+        return Ok(None);
+    };
+
+    let strategy = match Api::<V1Alpha1Strategy>::namespaced(client.clone(), namespace)
+        .get_opt(&strategy_ref.name)
+        .await?
+    {
+        Some(strategy) => Strategy::from(strategy),
+        None => return Ok(Some(strategy_condition(
+            "False",
+            "StrategyNotFound",
+            format!("Strategy `{}` was not found in namespace `{}`", strategy_ref.name, namespace),
+        ))),
+    };
+
+    if let Err(message) = strategy.spec.validate() {
+        return Ok(Some(strategy_condition(
+            "False",
+            "StrategyInvalid",
+            format!("Strategy `{}` is invalid: {}", strategy_ref.name, message),
+        )));
+    }
+
+    bot.spec.strategy.name = strategy.spec.class_name;
+    bot.spec.strategy.source = strategy.spec.source;
+    bot.spec.strategy.config_map_name = strategy.spec.config_map_name;
+
+    Ok(Some(strategy_condition(
+        "True",
+        "StrategyResolved",
+        format!("Using Strategy `{}`", strategy_ref.name),
+    )))
+}
+
+fn strategy_condition(status: &str, reason: &str, message: String) -> Condition {
+    Condition {
+        type_: STRATEGY_RESOLVED_CONDITION.to_string(),
+        status: status.to_string(),
+        reason: Some(reason.to_string()),
+        message: Some(message),
+        last_transition_time: None,
+    }
+}
+
+/// The Strategies whose number of referencing Bots changes with a Bot
+///
+/// That is the Strategy the Bot references and the one it referenced before, which is taken
+/// from and updated in the last references of the Bots.
+///
+/// # Arguments
+/// * `strategy_refs` - The Strategy last referenced by each Bot, by namespace and name
+/// * `bot` - The Bot that changed
+///
+/// # Returns
+/// The names of the Strategies to recount, in the Bot's namespace
+fn strategies_to_recount(strategy_refs: &mut HashMap<(String, String), String>, bot: &V1Alpha1Bot) -> BTreeSet<String> {
+    let key = (bot.namespace().unwrap_or_default(), bot.name_any());
+    let deleting = bot.metadata.deletion_timestamp.is_some();
+    let strategy_ref = Bot::from(bot.clone()).spec.strategy.strategy_ref.map(|strategy_ref| strategy_ref.name);
+
+    let previous = match (&strategy_ref, deleting) {
+        (Some(name), false) => strategy_refs.insert(key, name.clone()),
+        _ => strategy_refs.remove(&key),
+    };
+
+    previous.into_iter().chain(strategy_ref).collect()
+}
+
+pub struct StrategyController;
+
+impl StrategyController {
+    /// Create a new controller for the Strategy resource
+    ///
+    /// The controller also watches Bots so the number of referencing Bots stays current, including
+    /// for the Strategy a Bot stopped referencing.
+    ///
+    /// # Arguments
+    /// * `ctx` - The controller context
+    /// * `namespace` - The namespace to watch, or `None` to watch all namespaces
+    ///
+    /// # Returns
+    /// The controller for the Strategy resource
+    pub async fn create_controller<T>(ctx: Arc<Context>, namespace: Option<&str>) -> Controller<T>
+    where
+        T: NamespacedCustomResource,
+        Strategy: From<T>,
+    {
+        let client = ctx.client.clone();
+        let strategy = scoped_api::<T>(client.clone(), namespace);
+        let bot = scoped_api::<V1Alpha1Bot>(client.clone(), namespace);
+
+        // The Strategy a Bot referenced before its strategyRef changed or was removed has to be
+        // recounted too, so the last reference of every Bot is remembered
+        let strategy_refs: Mutex<HashMap<(String, String), String>> = Mutex::default();
+
+        Controller::new(strategy, watcher::Config::default())
+            .with_config(runtime_config(&ctx))
+            .watches(bot, watcher::Config::default(), move |bot: V1Alpha1Bot| {
+                let namespace = bot.namespace().unwrap_or_default();
+                let mut strategy_refs = strategy_refs.lock().unwrap_or_else(PoisonError::into_inner);
+
+                strategies_to_recount(&mut strategy_refs, &bot)
+                    .into_iter()
+                    .map(|name| ObjectRef::<T>::new(&name).within(&namespace))
+                    .collect::<Vec<_>>()
+            })
+    }
+
+    /// Reconcile the Strategy resource
+    ///
+    /// This function is responsible for validating the Strategy and keeping its status,
+    /// including the number of Bots referencing it, up to date.
+    ///
+    /// # Arguments
+    /// * `strategy` - The Strategy resource to reconcile
+    /// * `ctx` - The controller context
+    ///
+    /// # Returns
+    /// An action to take after reconciling the Strategy resource
+    pub async fn reconcile<T>(strategy: Arc<T>, ctx: Arc<Context>) -> Result<Action>
+    where
+        T: NamespacedCustomResource,
+        Strategy: From<T>,
+    {
+        let _in_flight = ctx.track_reconcile();
+        let namespace = strategy.namespace().ok_or(
+            ControllerError::MissingObjectKeyError(
+                "Expected Strategy to be namespaced via metadata.namespace"
+            )
+        )?;
+        let name = strategy.name_any();
+        let hub = Strategy::from(strategy.as_ref().clone());
+
+        let referenced_by = Api::<V1Alpha1Bot>::namespaced(ctx.client.clone(), &namespace)
+            .list(&ListParams::default())
+            .await?
+            .items
+            .into_iter()
+            .filter(|bot| references_strategy(&Bot::from(bot.clone()), &namespace, &name, &namespace))
+            .count() as u32;
+
+        let (phase, message) = match hub.spec.validate() {
+            Ok(()) => (StrategyPhase::Valid, None),
+            Err(message) => (StrategyPhase::Invalid, Some(message)),
+        };
+
+        // Only patch the status when something changed to avoid triggering the watch, the
+        // message is always sent so a stale one is cleared
+        let unchanged = hub.status.as_ref().is_some_and(|status| {
+            status.phase == phase.to_string()
+                && status.message == message
+                && status.referenced_by == referenced_by
+        });
+
+        if !unchanged {
+            info!(
+                event = "UpdatingStrategyStatus",
+                strategy = name.as_str()
+            );
+            patch_status(&Api::<T>::namespaced(ctx.client.clone(), &namespace), &name, json!({
+                "phase": phase.to_string(),
+                "message": message,
+                "referencedBy": referenced_by,
+                "lastUpdated": Utc::now(),
+            })).await?;
+        }
+
+        Ok(Action::await_change())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::{Method, StatusCode};
+    use serde_json::Value;
+    use ft_operator_common::{config::AppConfig, state::State};
+    use crate::controller::mock::mock_client;
+
+    fn bot(name: &str, namespace: &str, strategy_ref: Option<&str>) -> Value {
+        json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "Bot",
+            "metadata": { "name": name, "namespace": namespace },
+            "spec": {
+                "exchange": "binance",
+                "strategy": { "name": "SampleStrategy", "strategyRef": strategy_ref.map(|name| json!({ "name": name })) },
+            },
+        })
+    }
+
+    fn strategy(status: Value) -> V1Alpha1Strategy {
+        serde_json::from_value(json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "Strategy",
+            "metadata": { "name": "strategy", "namespace": "default" },
+            "spec": { "className": "SampleStrategy", "source": "class SampleStrategy(IStrategy): pass" },
+            "status": status,
+        })).unwrap()
+    }
+
+    // Reconcile the Strategy against the Bots, returning the status patched if any
+    async fn reconcile(strategy: V1Alpha1Strategy, bots: Vec<Value>) -> Option<Value> {
+        let object = serde_json::to_value(&strategy).unwrap();
+        let (client, mut requests) = mock_client(move |request| match request.method {
+            Method::GET => (StatusCode::OK, json!({ "apiVersion": "freqtrade.io/v1alpha1", "kind": "BotList", "metadata": {}, "items": bots })),
+            _ => (StatusCode::OK, object.clone()),
+        });
+        let ctx = Arc::new(Context::new(client, Arc::new(State::new(AppConfig::default()))));
+
+        StrategyController::reconcile(Arc::new(strategy), ctx).await.unwrap();
+
+        let mut status = None;
+        while let Ok(request) = requests.try_recv() {
+            if request.method == Method::PATCH {
+                assert_eq!(request.path, "/apis/freqtrade.io/v1alpha1/namespaces/default/strategies/strategy/status");
+                status = Some(request.body["status"].clone());
+            }
+        }
+        status
+    }
+
+    #[tokio::test]
+    async fn referencing_bots_are_counted() {
+        let status = reconcile(strategy(Value::Null), vec![
+            bot("a", "default", Some("strategy")),
+            bot("b", "default", Some("strategy")),
+            bot("c", "default", Some("other")),
+            bot("d", "default", None),
+        ]).await.unwrap();
+
+        assert_eq!(status["phase"], "valid");
+        assert_eq!(status["referencedBy"], 2);
+        assert_eq!(status["message"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn unchanged_status_is_not_patched() {
+        let status = json!({ "phase": "valid", "referencedBy": 1, "lastUpdated": "2025-01-01T00:00:00Z" });
+
+        assert_eq!(reconcile(strategy(status.clone()), vec![bot("a", "default", Some("strategy"))]).await, None);
+        // A Bot that stopped referencing the Strategy is counted out
+        assert_eq!(reconcile(strategy(status), vec![bot("a", "default", None)]).await.unwrap()["referencedBy"], 0);
+    }
+
+    fn changed_bot(strategy_ref: Option<&str>, deleting: bool) -> V1Alpha1Bot {
+        let mut bot = bot("bot", "default", strategy_ref);
+        if deleting {
+            bot["metadata"]["deletionTimestamp"] = json!("2025-01-01T00:00:00Z");
+        }
+        serde_json::from_value(bot).unwrap()
+    }
+
+    fn recounted(strategy_refs: &mut HashMap<(String, String), String>, strategy_ref: Option<&str>, deleting: bool) -> Vec<String> {
+        strategies_to_recount(strategy_refs, &changed_bot(strategy_ref, deleting)).into_iter().collect()
+    }
+
+    #[test]
+    fn strategies_are_recounted_on_every_reference_change() {
+        let mut strategy_refs = HashMap::new();
+
+        assert_eq!(recounted(&mut strategy_refs, Some("a"), false), vec!["a"]);
+        assert_eq!(recounted(&mut strategy_refs, Some("a"), false), vec!["a"]);
+        // The Strategy the Bot moved away from is recounted too
+        assert_eq!(recounted(&mut strategy_refs, Some("b"), false), vec!["a", "b"]);
+        assert_eq!(recounted(&mut strategy_refs, None, false), vec!["b"]);
+        assert_eq!(recounted(&mut strategy_refs, None, false), Vec::<String>::new());
+        assert!(strategy_refs.is_empty());
+    }
+
+    #[test]
+    fn deleted_bot_is_forgotten() {
+        let mut strategy_refs = HashMap::new();
+        recounted(&mut strategy_refs, Some("a"), false);
+
+        assert_eq!(recounted(&mut strategy_refs, Some("a"), true), vec!["a"]);
+        assert!(strategy_refs.is_empty());
+    }
+}
@@ -0,0 +1,134 @@
+use k8s_openapi::api::{apps::v1::Deployment, core::v1::{ConfigMap, PersistentVolumeClaim, Service}};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+use serde::Serialize;
+use serde_json::Value;
+
+use ft_operator_common::config::AppConfig;
+
+use crate::controller::bot::{api_credentials_secret_name, generated_api_secrets, generates_api_credentials};
+use crate::controller::{traits::{FromHub, TryFromHub}, ui::{UiDeployment, UiIngress, UiService}};
+use crate::crd::{hub::bot::Bot, v1alpha1::bot::Bot as V1Alpha1Bot, v1alpha2::bot::Bot as V1Alpha2Bot};
+use crate::error::{ControllerError, Result};
+
+// The uid of the owner reference when the manifest has none, since it isn't created yet
+static PLACEHOLDER_UID: &str = "00000000-0000-0000-0000-000000000000";
+
+
+/// Render the resources the controller creates for a Bot, without a cluster
+///
+/// The resources are built from the manifest alone, so a referenced Strategy or
+/// ExchangeCredentials isn't resolved and the config hash annotation isn't set. Generated API
+/// credentials are referenced but their Secret isn't rendered, since it holds random values.
+///
+/// # Arguments
+/// * `manifest` - The Bot manifest
+/// * `config` - The operator configuration holding the defaults
+///
+/// # Returns
+/// A Result containing the resources, in the order they are applied
+pub fn render_bot(manifest: Value, config: &AppConfig) -> Result<Vec<Value>> {
+    let api_version = manifest["apiVersion"].as_str().unwrap_or_default().to_string();
+    let mut bot = match api_version.as_str() {
+        "freqtrade.io/v1alpha1" => serde_json::from_value::<V1Alpha1Bot>(manifest).map(Bot::from),
+        "freqtrade.io/v1alpha2" => serde_json::from_value::<V1Alpha2Bot>(manifest).map(Bot::from),
+        _ => return Err(ControllerError::InvalidManifestError(format!("unsupported Bot apiVersion `{}`", api_version))),
+    }
+        .map_err(|e| ControllerError::InvalidManifestError(e.to_string()))?;
+
+    let bot_name = bot.metadata.name
+        .clone()
+        .ok_or(ControllerError::InvalidManifestError("metadata.name is required".to_string()))?;
+    let name = bot.child_name();
+    let namespace = bot.metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+    if generates_api_credentials(&bot) {
+        bot.spec.secrets.api = Some(generated_api_secrets(&api_credentials_secret_name(&name)));
+    }
+    let owner_ref = OwnerReference {
+        api_version,
+        kind: "Bot".to_string(),
+        name: bot_name,
+        uid: bot.metadata.uid.clone().unwrap_or_else(|| PLACEHOLDER_UID.to_string()),
+        controller: Some(true),
+        block_owner_deletion: Some(true),
+    };
+
+    let mut resources = vec![
+        to_value(&ConfigMap::try_from_hub(&bot, &name, &namespace, owner_ref.clone(), config)?)?,
+    ];
+    if bot.spec.pvc.enabled {
+        resources.push(to_value(&PersistentVolumeClaim::from_hub(&bot, &name, &namespace, owner_ref.clone(), config))?);
+    }
+    resources.push(to_value(&Deployment::from_hub(&bot, &name, &namespace, owner_ref.clone(), config))?);
+    resources.push(to_value(&Service::from_hub(&bot, &name, &namespace, owner_ref.clone(), config))?);
+
+    if let Some(ui) = bot.spec.ui.as_ref().filter(|ui| ui.enabled) {
+        resources.push(to_value(&UiDeployment::from_hub(&bot, &name, &namespace, owner_ref.clone(), config).0)?);
+        resources.push(to_value(&UiService::from_hub(&bot, &name, &namespace, owner_ref.clone(), config).0)?);
+        if ui.ingress.is_some() {
+            resources.push(to_value(&UiIngress::from_hub(&bot, &name, &namespace, owner_ref, config).0)?);
+        }
+    }
+
+    Ok(resources)
+}
+
+fn to_value<T: Serialize>(resource: &T) -> Result<Value> {
+    serde_json::to_value(resource).map_err(|e| ControllerError::UnknownError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+    use serde_json::json;
+
+    use super::*;
+
+    /// Compare the rendered resources to `testdata/template/<name>`, or rewrite it when
+    /// `UPDATE_GOLDEN` is set
+    fn assert_golden(name: &str, resources: &[Value]) {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata/template").join(name);
+        let rendered: String = resources
+            .iter()
+            .map(|resource| format!("---\n{}", serde_norway::to_string(resource).unwrap()))
+            .collect();
+
+        if env::var_os("UPDATE_GOLDEN").is_some() {
+            fs::write(&path, &rendered).unwrap();
+        }
+        assert_eq!(rendered, fs::read_to_string(&path).unwrap(), "{} is outdated, rerun with UPDATE_GOLDEN=1", path.display());
+    }
+
+    #[test]
+    fn minimal_bot_is_rendered() {
+        let manifest = json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "Bot",
+            "metadata": {"name": "bot", "namespace": "trading"},
+            "spec": {
+                "exchange": "binance",
+                "strategy": {"name": "SampleStrategy", "configMapName": "strategy"},
+            },
+        });
+
+        assert_golden("minimal_bot.yaml", &render_bot(manifest, &AppConfig::default()).unwrap());
+    }
+
+    #[test]
+    fn bot_with_pvc_service_ports_and_ui_is_rendered() {
+        let manifest = json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "Bot",
+            "metadata": {"name": "bot", "namespace": "trading", "uid": "5f0c6a4e-2b1d-4c9e-8f3a-7d6b5e4c3a21"},
+            "spec": {
+                "exchange": "binance",
+                "strategy": {"name": "SampleStrategy", "configMapName": "strategy"},
+                "config": {"dry_run": true, "stake_currency": "USDT"},
+                "pvc": {"size": "5Gi", "storageClass": "standard", "deletionPolicy": "Retain"},
+                "service": {"serviceType": "NodePort", "ports": [{"name": "debug", "port": 5678, "targetPort": "5678"}]},
+                "ui": {"ingress": {"host": "bot.example.com", "tlsSecretName": "bot-tls"}},
+            },
+        });
+
+        assert_golden("full_bot.yaml", &render_bot(manifest, &AppConfig::default()).unwrap());
+    }
+}
@@ -3,6 +3,7 @@ use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
 use ft_operator_common::config::AppConfig;
 
 use crate::crd::hub::traits::Hub;
+use crate::error::Result;
 
 // Trait to get a Resource from a Hub CustomResourceDefinition
 pub trait FromHub<T>
@@ -12,6 +13,16 @@ where
     fn from_hub(hub: &T, name: &str, namespace: &str, owner_ref: OwnerReference, config: &AppConfig) -> Self;
 }
 
+// Trait to get a Resource from a Hub CustomResourceDefinition, for Resources
+// whose rendering can fail
+pub trait TryFromHub<T>
+where
+    T: Hub,
+    Self: Sized,
+{
+    fn try_from_hub(hub: &T, name: &str, namespace: &str, owner_ref: OwnerReference, config: &AppConfig) -> Result<Self>;
+}
+
 // Trait to detect if a child Resource has drifted from another instance
 // of the same Resource, where Resource has implemented the FromHub trait
 pub trait ResourceDrift<T>
@@ -0,0 +1,558 @@
+use kube::{api::{Api, DeleteParams, ObjectMeta}, Client, Resource, ResourceExt};
+use serde::{de::DeserializeOwned, Serialize};
+use k8s_openapi::api::apps::v1::{Deployment, DeploymentSpec};
+use k8s_openapi::api::core::v1::{
+    Container, ContainerPort, EnvVar, LocalObjectReference, PodSpec, PodTemplateSpec, Service, ServicePort, ServiceSpec,
+};
+use k8s_openapi::api::networking::v1::{
+    HTTPIngressPath, HTTPIngressRuleValue, Ingress, IngressBackend, IngressRule, IngressServiceBackend, IngressSpec,
+    IngressTLS, ServiceBackendPort,
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, OwnerReference};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use ft_operator_common::config::AppConfig;
+use ft_operator_common::telemetry::info;
+
+use crate::controller::{store::{BotChildStores, ChildStore}, traits::{FromHub, ResourceDrift}, utils::{apply, delete, merge_pull_secrets, metadata_drifted, with_defaults}};
+use crate::crd::hub::{bot::Bot, common::Condition};
+use crate::error::Result;
+
+pub static UI_READY_CONDITION: &str = "UIReady";
+
+/// The FreqUI Deployment of a Bot, named `<bot>-ui`
+pub struct UiDeployment(pub Deployment);
+/// The FreqUI Service of a Bot, named `<bot>-ui`
+pub struct UiService(pub Service);
+/// The FreqUI Ingress of a Bot, named `<bot>-ui`
+pub struct UiIngress(pub Ingress);
+
+
+/// The name of the FreqUI resources of a bot
+///
+/// # Arguments
+/// * `name` - The name of the bot
+pub fn ui_name(name: &str) -> String {
+    format!("{}-ui", name)
+}
+
+fn ui_labels(name: &str) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("freqtrade.io/bot-name".to_string(), name.to_string()),
+        ("app.kubernetes.io/name".to_string(), ui_name(name)),
+        ("app.kubernetes.io/instance".to_string(), ui_name(name)),
+    ])
+}
+
+impl FromHub<Bot> for UiDeployment {
+    /// Create a FreqUI Deployment resource from a Bot CRD
+    ///
+    /// The UI talks to the bot through the bot's Service, which is passed to the
+    /// container as `FREQTRADE_API_URL`.
+    ///
+    /// # Arguments
+    /// * `bot` - The Bot CRD to create the Deployment from
+    /// * `name` - The name of the bot
+    /// * `namespace` - The namespace of the Deployment resource
+    /// * `owner_ref` - The owner reference for the Deployment resource
+    /// * `config` - The application configuration
+    ///
+    /// # Returns
+    /// The FreqUI Deployment resource
+    fn from_hub(bot: &Bot, name: &str, namespace: &str, owner_ref: OwnerReference, config: &AppConfig) -> Self {
+        let ui = bot.spec.ui.clone().unwrap_or_default();
+        let identifying_labels = ui_labels(name);
+        let image_pull_secrets = merge_pull_secrets(&config.controller.default_image_pull_secrets, bot.spec.image.pull_secrets.clone());
+
+        UiDeployment(Deployment {
+            metadata: ObjectMeta {
+                name: Some(ui_name(name)),
+                namespace: Some(namespace.to_string()),
+                owner_references: Some(vec![owner_ref]),
+                annotations: with_defaults(&config.controller.default_annotations, None),
+                labels: with_defaults(&config.controller.default_labels, Some(identifying_labels
+                    .clone()
+                    .into_iter()
+                    .chain([
+                        ("app.kubernetes.io/component".to_string(), "ui".to_string()),
+                        ("app.kubernetes.io/part-of".to_string(), "freqtrade".to_string()),
+                        ("app.kubernetes.io/managed-by".to_string(), "freqtrade-operator".to_string()),
+                    ])
+                    .collect()
+                )),
+                ..Default::default()
+            },
+            spec: Some(DeploymentSpec {
+                replicas: Some(1),
+                selector: LabelSelector {
+                    match_labels: Some(identifying_labels.clone()),
+                    ..Default::default()
+                },
+                template: PodTemplateSpec {
+                    metadata: Some(ObjectMeta {
+                        labels: Some(identifying_labels),
+                        ..Default::default()
+                    }),
+                    spec: Some(PodSpec {
+                        image_pull_secrets: image_pull_secrets.map(|secrets| secrets
+                            .into_iter()
+                            .map(|secret| LocalObjectReference { name: secret })
+                            .collect()
+                        ),
+                        containers: vec![Container {
+                            name: "frequi".to_string(),
+                            image: Some(ui.image),
+                            env: Some(vec![EnvVar {
+                                name: "FREQTRADE_API_URL".to_string(),
+                                value: Some(format!("http://{}.{}.svc:{}", name, namespace, bot.spec.api.port)),
+                                ..Default::default()
+                            }]),
+                            ports: Some(vec![ContainerPort {
+                                name: Some("http".to_string()),
+                                container_port: ui.port as i32,
+                                ..Default::default()
+                            }]),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }),
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+}
+
+impl ResourceDrift<Bot> for UiDeployment {
+    /// Determine if the FreqUI Deployment has drifted from another FreqUI Deployment
+    ///
+    /// # Arguments
+    /// * `other` - The other FreqUI Deployment to compare against
+    ///
+    /// # Returns
+    /// Whether the FreqUI Deployment has drifted from the other FreqUI Deployment
+    fn has_drifted(&self, other: &Self) -> bool {
+        let pod_spec = |deployment: &Deployment| deployment.spec
+            .as_ref()
+            .and_then(|spec| spec.template.spec.clone())
+            .unwrap_or_default();
+        let (self_spec, other_spec) = (pod_spec(&self.0), pod_spec(&other.0));
+
+        if metadata_drifted(&self.0.metadata, &other.0.metadata)
+            || self_spec.image_pull_secrets != other_spec.image_pull_secrets
+            || self_spec.containers.len() != other_spec.containers.len()
+        {
+            return true;
+        }
+
+        self_spec.containers.iter().zip(other_spec.containers.iter()).any(|(self_container, other_container)| {
+            self_container.image != other_container.image
+                || self_container.env != other_container.env
+                || self_container.ports.as_ref().map(|ports| ports.iter().map(|port| port.container_port).collect::<Vec<_>>())
+                    != other_container.ports.as_ref().map(|ports| ports.iter().map(|port| port.container_port).collect::<Vec<_>>())
+        })
+    }
+}
+
+impl FromHub<Bot> for UiService {
+    /// Create a FreqUI Service resource from a Bot CRD
+    ///
+    /// # Arguments
+    /// * `bot` - The Bot CRD to create the Service from
+    /// * `name` - The name of the bot
+    /// * `namespace` - The namespace of the Service resource
+    /// * `owner_ref` - The owner reference for the Service resource
+    /// * `config` - The application configuration
+    ///
+    /// # Returns
+    /// The FreqUI Service resource
+    fn from_hub(bot: &Bot, name: &str, namespace: &str, owner_ref: OwnerReference, config: &AppConfig) -> Self {
+        let ui = bot.spec.ui.clone().unwrap_or_default();
+
+        UiService(Service {
+            metadata: ObjectMeta {
+                name: Some(ui_name(name)),
+                namespace: Some(namespace.to_string()),
+                owner_references: Some(vec![owner_ref]),
+                annotations: with_defaults(&config.controller.default_annotations, None),
+                labels: with_defaults(&config.controller.default_labels, None),
+                ..Default::default()
+            },
+            spec: Some(ServiceSpec {
+                type_: Some("ClusterIP".to_string()),
+                selector: Some(ui_labels(name)),
+                ports: Some(vec![ServicePort {
+                    name: Some("http".to_string()),
+                    port: ui.port as i32,
+                    target_port: Some(IntOrString::String("http".to_string())),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+}
+
+impl ResourceDrift<Bot> for UiService {
+    /// Determine if the FreqUI Service has drifted from another FreqUI Service
+    ///
+    /// # Arguments
+    /// * `other` - The other FreqUI Service to compare against
+    ///
+    /// # Returns
+    /// Whether the FreqUI Service has drifted from the other FreqUI Service
+    fn has_drifted(&self, other: &Self) -> bool {
+        let ports = |service: &Service| service.spec
+            .as_ref()
+            .and_then(|spec| spec.ports.as_ref())
+            .map(|ports| ports.iter().map(|port| (port.port, port.target_port.clone())).collect::<Vec<_>>());
+
+        metadata_drifted(&self.0.metadata, &other.0.metadata)
+            || self.0.spec.as_ref().and_then(|spec| spec.selector.as_ref()) != other.0.spec.as_ref().and_then(|spec| spec.selector.as_ref())
+            || ports(&self.0) != ports(&other.0)
+    }
+}
+
+impl FromHub<Bot> for UiIngress {
+    /// Create a FreqUI Ingress resource from a Bot CRD
+    ///
+    /// # Arguments
+    /// * `bot` - The Bot CRD to create the Ingress from
+    /// * `name` - The name of the bot
+    /// * `namespace` - The namespace of the Ingress resource
+    /// * `owner_ref` - The owner reference for the Ingress resource
+    /// * `config` - The application configuration
+    ///
+    /// # Returns
+    /// The FreqUI Ingress resource
+    fn from_hub(bot: &Bot, name: &str, namespace: &str, owner_ref: OwnerReference, config: &AppConfig) -> Self {
+        let ui = bot.spec.ui.clone().unwrap_or_default();
+        let ingress = ui.ingress.clone().unwrap_or_default();
+
+        UiIngress(Ingress {
+            metadata: ObjectMeta {
+                name: Some(ui_name(name)),
+                namespace: Some(namespace.to_string()),
+                owner_references: Some(vec![owner_ref]),
+                annotations: with_defaults(&config.controller.default_annotations, ingress.annotations.clone()),
+                labels: with_defaults(&config.controller.default_labels, None),
+                ..Default::default()
+            },
+            spec: Some(IngressSpec {
+                ingress_class_name: ingress.class_name.clone(),
+                rules: Some(vec![IngressRule {
+                    host: Some(ingress.host.clone()),
+                    http: Some(HTTPIngressRuleValue {
+                        paths: vec![HTTPIngressPath {
+                            path: Some("/".to_string()),
+                            path_type: "Prefix".to_string(),
+                            backend: IngressBackend {
+                                service: Some(IngressServiceBackend {
+                                    name: ui_name(name),
+                                    port: Some(ServiceBackendPort {
+                                        name: Some("http".to_string()),
+                                        ..Default::default()
+                                    }),
+                                }),
+                                ..Default::default()
+                            },
+                        }],
+                    }),
+                }]),
+                tls: ingress.tls_secret_name.as_ref().map(|secret_name| vec![IngressTLS {
+                    hosts: Some(vec![ingress.host.clone()]),
+                    secret_name: Some(secret_name.clone()),
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+}
+
+impl ResourceDrift<Bot> for UiIngress {
+    /// Determine if the FreqUI Ingress has drifted from another FreqUI Ingress
+    ///
+    /// # Arguments
+    /// * `other` - The other FreqUI Ingress to compare against
+    ///
+    /// # Returns
+    /// Whether the FreqUI Ingress has drifted from the other FreqUI Ingress
+    fn has_drifted(&self, other: &Self) -> bool {
+        self.0.metadata.annotations != other.0.metadata.annotations
+            || metadata_drifted(&self.0.metadata, &other.0.metadata)
+            || self.0.spec != other.0.spec
+    }
+}
+
+/// Reconcile the FreqUI resources of the bot
+///
+/// When the UI is enabled this applies the FreqUI Deployment, Service and optional Ingress
+/// and reports the readiness of the Deployment. When it is disabled the FreqUI resources owned
+/// by the bot are deleted. The resources are read from the child stores, so a disabled UI
+/// doesn't cost a request per reconcile.
+///
+/// # Arguments
+/// * `bot` - The Bot Hub to reconcile the UI for
+/// * `client` - The kube client
+/// * `stores` - The child stores the FreqUI resources are read from
+/// * `name` - The name of the Bot
+/// * `namespace` - The namespace of the Bot
+/// * `owner_ref` - The owner reference for the FreqUI resources
+/// * `config` - The application configuration
+///
+/// # Returns
+/// The `UIReady` condition, or None if the UI is disabled
+pub async fn reconcile_ui(bot: &Bot, client: &Client, stores: &BotChildStores, name: &str, namespace: &str, owner_ref: &OwnerReference, config: &AppConfig) -> Result<Option<Condition>> {
+    let deployment_api = Api::<Deployment>::namespaced(client.clone(), namespace);
+    let service_api = Api::<Service>::namespaced(client.clone(), namespace);
+    let ingress_api = Api::<Ingress>::namespaced(client.clone(), namespace);
+    let ui_name = ui_name(name);
+
+    let deployment = stores.deployments.get(&deployment_api, namespace, &ui_name).await?;
+    let service = stores.services.get(&service_api, namespace, &ui_name).await?;
+    let ingress = stores.ingresses.get(&ingress_api, namespace, &ui_name).await?;
+
+    let Some(ui) = bot.spec.ui.as_ref().filter(|ui| ui.enabled) else {
+        delete_owned(&deployment_api, &stores.deployments, deployment, namespace, &owner_ref.uid, "DeletingUIDeployment", name).await?;
+        delete_owned(&service_api, &stores.services, service, namespace, &owner_ref.uid, "DeletingUIService", name).await?;
+        delete_owned(&ingress_api, &stores.ingresses, ingress, namespace, &owner_ref.uid, "DeletingUIIngress", name).await?;
+        return Ok(None);
+    };
+
+    let service_object = UiService::from_hub(bot, name, namespace, owner_ref.clone(), config);
+    if service.map(UiService).as_ref().is_none_or(|service| ResourceDrift::<Bot>::has_drifted(service, &service_object)) {
+        info!(event = "ApplyingUIService", bot = name);
+        let applied = apply(&service_api, service_object.0, &ui_name).await?;
+        stores.services.record_write(namespace, &ui_name, Some(&applied));
+    }
+
+    if ui.ingress.is_some() {
+        let ingress_object = UiIngress::from_hub(bot, name, namespace, owner_ref.clone(), config);
+        if ingress.map(UiIngress).as_ref().is_none_or(|ingress| ResourceDrift::<Bot>::has_drifted(ingress, &ingress_object)) {
+            info!(event = "ApplyingUIIngress", bot = name);
+            let applied = apply(&ingress_api, ingress_object.0, &ui_name).await?;
+            stores.ingresses.record_write(namespace, &ui_name, Some(&applied));
+        }
+    } else {
+        delete_owned(&ingress_api, &stores.ingresses, ingress, namespace, &owner_ref.uid, "DeletingUIIngress", name).await?;
+    }
+
+    let deployment_object = UiDeployment::from_hub(bot, name, namespace, owner_ref.clone(), config);
+    let mut deployment = deployment;
+    if deployment.clone().map(UiDeployment).as_ref().is_none_or(|deployment| ResourceDrift::<Bot>::has_drifted(deployment, &deployment_object)) {
+        info!(event = "ApplyingUIDeployment", bot = name);
+        let applied = apply(&deployment_api, deployment_object.0, &ui_name).await?;
+        stores.deployments.record_write(namespace, &ui_name, Some(&applied));
+        deployment = Some(applied);
+    }
+
+    let ready_replicas = deployment
+        .and_then(|deployment| deployment.status)
+        .and_then(|status| status.ready_replicas)
+        .unwrap_or(0);
+
+    Ok(Some(Condition {
+        type_: UI_READY_CONDITION.to_string(),
+        status: if ready_replicas > 0 { "True" } else { "False" }.to_string(),
+        reason: Some(if ready_replicas > 0 { "DeploymentReady" } else { "DeploymentNotReady" }.to_string()),
+        message: Some(format!("Deployment `{}` has {}/1 ready replicas", ui_name, ready_replicas)),
+        last_transition_time: None,
+    }))
+}
+
+/// Delete a FreqUI resource of a bot, unless it doesn't exist or isn't owned by the bot
+///
+/// A resource named like the UI that the bot doesn't own was never its to delete.
+///
+/// # Arguments
+/// * `api` - The API of the resource's kind in the bot's namespace
+/// * `store` - The child store the resource was read from
+/// * `object` - The current resource, None when it doesn't exist
+/// * `namespace` - The namespace of the Bot
+/// * `uid` - The UID of the Bot
+/// * `event` - The event logged when the resource is deleted
+/// * `name` - The name of the Bot
+async fn delete_owned<K>(api: &Api<K>, store: &ChildStore<K>, object: Option<K>, namespace: &str, uid: &str, event: &str, name: &str) -> Result<()>
+where
+    K: Clone + Debug + Serialize + DeserializeOwned + Resource<DynamicType = ()>,
+{
+    let Some(object) = object.filter(|object| object.owner_references().iter().any(|owner| owner.uid == uid)) else {
+        return Ok(());
+    };
+
+    let ui_name = object.name_any();
+    info!(event = event, bot = name);
+    delete(api, &ui_name, &DeleteParams::default()).await?;
+    store.record_write(namespace, &ui_name, None);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::{Method, StatusCode};
+    use kube::runtime::{reflector, watcher};
+    use serde_json::{json, Value};
+    use tokio::sync::mpsc::UnboundedReceiver;
+
+    use crate::controller::mock::{mock_client, MockRequest};
+    use crate::crd::v1alpha1::bot::Bot as V1Alpha1Bot;
+
+    fn bot(ui: Value) -> Bot {
+        Bot::from(serde_json::from_value::<V1Alpha1Bot>(json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "Bot",
+            "metadata": { "name": "bot", "namespace": "default", "uid": "bot-uid" },
+            "spec": { "exchange": "binance", "strategy": { "name": "SampleStrategy" }, "ui": ui },
+        })).unwrap())
+    }
+
+    fn owner_ref(uid: &str) -> OwnerReference {
+        OwnerReference { api_version: "freqtrade.io/v1alpha1".to_string(), kind: "Bot".to_string(), name: "bot".to_string(), uid: uid.to_string(), ..Default::default() }
+    }
+
+    // The UI resources of a bot with an ingress, owned by the Bot with `uid`
+    fn ui_resources(uid: &str) -> (Deployment, Service, Ingress) {
+        let bot = bot(json!({ "ingress": { "host": "bot.example.com" } }));
+        let config = AppConfig::default();
+        let mut deployment = UiDeployment::from_hub(&bot, "bot", "default", owner_ref(uid), &config).0;
+        let mut service = UiService::from_hub(&bot, "bot", "default", owner_ref(uid), &config).0;
+        let mut ingress = UiIngress::from_hub(&bot, "bot", "default", owner_ref(uid), &config).0;
+        for metadata in [&mut deployment.metadata, &mut service.metadata, &mut ingress.metadata] {
+            metadata.resource_version = Some("1".to_string());
+        }
+
+        (deployment, service, ingress)
+    }
+
+    // A child store whose watch has listed the objects
+    fn listed<K>(store: &ChildStore<K>, objects: Vec<K>)
+    where
+        K: Resource<DynamicType = ()> + Clone + Debug + DeserializeOwned + 'static,
+    {
+        let (cache, mut writer) = reflector::store();
+        writer.apply_watcher_event(&watcher::Event::InitDone);
+        for object in objects {
+            writer.apply_watcher_event(&watcher::Event::Apply(object));
+        }
+        store.add(cache);
+    }
+
+    fn listed_stores(resources: Option<(Deployment, Service, Ingress)>) -> BotChildStores {
+        let stores = BotChildStores::default();
+        let (deployments, services, ingresses) = match resources {
+            Some((deployment, service, ingress)) => (vec![deployment], vec![service], vec![ingress]),
+            None => (vec![], vec![], vec![]),
+        };
+        listed(&stores.deployments, deployments);
+        listed(&stores.services, services);
+        listed(&stores.ingresses, ingresses);
+        stores
+    }
+
+    // An API server without any UI resource, answering an apply with the object applied
+    fn api_server() -> (Client, UnboundedReceiver<MockRequest>) {
+        mock_client(|request| match request.method {
+            Method::GET => (StatusCode::NOT_FOUND, json!({ "kind": "Status", "apiVersion": "v1", "status": "Failure", "reason": "NotFound", "code": 404 })),
+            Method::DELETE => (StatusCode::OK, json!({ "kind": "Status", "apiVersion": "v1", "status": "Success", "code": 200 })),
+            _ => (StatusCode::OK, request.body.clone()),
+        })
+    }
+
+    fn sent(requests: &mut UnboundedReceiver<MockRequest>) -> Vec<(String, String)> {
+        let mut sent = vec![];
+        while let Ok(request) = requests.try_recv() {
+            sent.push((request.method.to_string(), request.path));
+        }
+        sent
+    }
+
+    async fn reconcile(bot: &Bot, client: &Client, stores: &BotChildStores) -> Option<Condition> {
+        reconcile_ui(bot, client, stores, "bot", "default", &owner_ref("bot-uid"), &AppConfig::default()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn enabled_ui_is_applied_and_reported_not_ready() {
+        let (client, mut requests) = api_server();
+
+        let condition = reconcile(&bot(json!({})), &client, &BotChildStores::default()).await.unwrap();
+
+        assert_eq!(sent(&mut requests), vec![
+            ("GET".to_string(), "/apis/apps/v1/namespaces/default/deployments/bot-ui".to_string()),
+            ("GET".to_string(), "/api/v1/namespaces/default/services/bot-ui".to_string()),
+            ("GET".to_string(), "/apis/networking.k8s.io/v1/namespaces/default/ingresses/bot-ui".to_string()),
+            ("PATCH".to_string(), "/api/v1/namespaces/default/services/bot-ui".to_string()),
+            ("PATCH".to_string(), "/apis/apps/v1/namespaces/default/deployments/bot-ui".to_string()),
+        ]);
+        assert_eq!(condition.type_, UI_READY_CONDITION);
+        assert_eq!((condition.status.as_str(), condition.reason.as_deref()), ("False", Some("DeploymentNotReady")));
+    }
+
+    #[tokio::test]
+    async fn unchanged_ui_is_read_from_the_stores() {
+        let (client, mut requests) = api_server();
+        let (mut deployment, service, ingress) = ui_resources("bot-uid");
+        deployment.status = Some(Default::default());
+        deployment.status.as_mut().unwrap().ready_replicas = Some(1);
+        let stores = listed_stores(Some((deployment, service, ingress)));
+
+        let condition = reconcile(&bot(json!({ "ingress": { "host": "bot.example.com" } })), &client, &stores).await.unwrap();
+
+        assert_eq!(sent(&mut requests), vec![]);
+        assert_eq!((condition.status.as_str(), condition.reason.as_deref()), ("True", Some("DeploymentReady")));
+    }
+
+    #[tokio::test]
+    async fn disabled_ui_deletes_the_owned_resources() {
+        let (client, mut requests) = api_server();
+        let stores = listed_stores(Some(ui_resources("bot-uid")));
+
+        // The Bot doesn't report the UI, e.g. when it was disabled before its status was written
+        let condition = reconcile(&bot(json!({ "enabled": false })), &client, &stores).await;
+
+        assert_eq!(condition, None);
+        assert_eq!(sent(&mut requests), vec![
+            ("DELETE".to_string(), "/apis/apps/v1/namespaces/default/deployments/bot-ui".to_string()),
+            ("DELETE".to_string(), "/api/v1/namespaces/default/services/bot-ui".to_string()),
+            ("DELETE".to_string(), "/apis/networking.k8s.io/v1/namespaces/default/ingresses/bot-ui".to_string()),
+        ]);
+
+        // The deletes are read from the API server until the stores observe them
+        assert_eq!(reconcile(&bot(json!({ "enabled": false })), &client, &stores).await, None);
+        assert_eq!(sent(&mut requests).iter().map(|(method, _)| method.as_str()).collect::<Vec<_>>(), vec!["GET", "GET", "GET"]);
+    }
+
+    #[tokio::test]
+    async fn disabled_ui_keeps_resources_it_does_not_own() {
+        let (client, mut requests) = api_server();
+        let stores = listed_stores(Some(ui_resources("other-uid")));
+
+        assert_eq!(reconcile(&bot(Value::Null), &client, &stores).await, None);
+        assert_eq!(sent(&mut requests), vec![]);
+    }
+
+    #[tokio::test]
+    async fn disabled_ui_without_resources_makes_no_request() {
+        let (client, mut requests) = api_server();
+
+        assert_eq!(reconcile(&bot(Value::Null), &client, &listed_stores(None)).await, None);
+        assert_eq!(sent(&mut requests), vec![]);
+    }
+
+    #[tokio::test]
+    async fn removed_ingress_is_deleted() {
+        let (client, mut requests) = api_server();
+        let stores = listed_stores(Some(ui_resources("bot-uid")));
+
+        assert!(reconcile(&bot(json!({})), &client, &stores).await.is_some());
+        assert_eq!(sent(&mut requests), vec![
+            ("DELETE".to_string(), "/apis/networking.k8s.io/v1/namespaces/default/ingresses/bot-ui".to_string()),
+        ]);
+    }
+}
@@ -1,20 +1,30 @@
 use kube::{
-    api::{Api, Patch, PatchParams}, core::response::Status, runtime::controller::Action, Client, Resource
+    api::{Api, DeleteParams, ObjectMeta, Patch, PatchParams, ResourceExt}, core::{response::Status, NamespaceResourceScope}, runtime::{controller::{Action, Config as RuntimeConfig, Controller}, events::EventType, reflector::ObjectRef}, Client, Resource
 };
-use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::{apps::v1::Deployment, core::v1::Secret};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::fmt::Debug;
-use tokio::time::Duration;
+use std::future::Future;
+use futures::StreamExt;
 use either::Either;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use rand::Rng;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Duration};
 
-use ft_operator_common::telemetry::error;
+use ft_operator_common::config::{ConflictPolicy, DeletionPropagation};
+use ft_operator_common::telemetry::{debug, error, info};
 
 use crate::controller::context::Context;
+use crate::crd::hub::common::SecretItem;
 use crate::error::{ControllerError, Result};
 
 pub static FIELD_MANAGER: &str = "operator.freqtrade.io";
+// How many times a status patch is attempted when it conflicts with another writer
+static STATUS_PATCH_ATTEMPTS: u32 = 3;
 
 
 /// Create a new kube client by inferring the kubeconfig from the environment
@@ -26,21 +36,282 @@ pub async fn create_k8s_client() -> Result<Client> {
     Client::try_default().await.map_err(ControllerError::from)
 }
 
-/// Error policy to log the error and requeue the object after 30 seconds
+/// Create an API client for a namespaced resource type, scoped to a namespace
+/// or to all namespaces
+///
+/// # Arguments
+/// * `client`: The kube client
+/// * `namespace`: The namespace to scope the API to, or `None` for all namespaces
+///
+/// # Returns
+/// The API client for the resource type
+pub fn scoped_api<T>(client: Client, namespace: Option<&str>) -> Api<T>
+where
+    T: Resource<DynamicType = (), Scope = NamespaceResourceScope>,
+{
+    match namespace {
+        Some(namespace) => Api::namespaced(client, namespace),
+        None => Api::all(client),
+    }
+}
+
+/// The runtime configuration shared by the controllers
+///
+/// Reconciles are limited to `max_concurrent_reconciles` at a time per controller, so a restart
+/// with many resources doesn't flood the API server. A limit of 0 leaves them unbounded. Triggers
+/// for an object arriving within `reconcile_debounce_millis` of each other are merged into one
+/// reconcile.
+///
+/// # Arguments
+/// * `ctx`: The context of the controller
+///
+/// # Returns
+/// The runtime configuration for a controller
+pub fn runtime_config(ctx: &Context) -> RuntimeConfig {
+    let config = ctx.controller_config();
+
+    RuntimeConfig::default()
+        .concurrency(config.max_concurrent_reconciles)
+        .debounce(Duration::from_millis(config.reconcile_debounce_millis))
+}
+
+/// Requeue an object after a delay spread by `requeue_jitter_percent`
+///
+/// Objects reconciled at the same time, such as after a restart, would otherwise stay in
+/// lockstep and hit the API server in the same second on every requeue.
+///
+/// # Arguments
+/// * `object`: The object to requeue
+/// * `ctx`: The context of the controller
+/// * `delay`: The delay before the object is reconciled again
+///
+/// # Returns
+/// An Action to requeue the object after the jittered delay
+pub fn requeue<T>(object: &T, ctx: &Context, delay: Duration) -> Action
+where
+    T: ResourceExt,
+{
+    let delay = jittered(delay, ctx.controller_config().requeue_jitter_percent);
+
+    debug!(
+        event = "Requeue",
+        name = object.name_any().as_str(),
+        delay_millis = delay.as_millis() as u64,
+    );
+
+    Action::requeue(delay)
+}
+
+/// Spread a delay randomly by up to a percentage of it in either direction
+///
+/// # Arguments
+/// * `delay`: The delay to spread
+/// * `percent`: The maximum spread, as a percentage of the delay
+///
+/// # Returns
+/// The jittered delay
+pub fn jittered(delay: Duration, percent: u8) -> Duration {
+    let spread = delay.as_secs_f64() * f64::from(percent.min(100)) / 100.0;
+    if spread == 0.0 {
+        return delay;
+    }
+
+    Duration::from_secs_f64(delay.as_secs_f64() + rand::thread_rng().gen_range(-spread..=spread))
+}
+
+/// Merge operator-wide default labels or annotations with those set on a resource
+///
+/// # Arguments
+/// * `defaults`: The defaults from the controller config
+/// * `values`: The labels or annotations set by the Bot, which win on conflict
+///
+/// # Returns
+/// The merged labels or annotations, or `values` when there are no defaults
+pub fn with_defaults(defaults: &BTreeMap<String, String>, values: Option<BTreeMap<String, String>>) -> Option<BTreeMap<String, String>> {
+    if defaults.is_empty() {
+        return values;
+    }
+
+    Some(defaults.clone().into_iter().chain(values.into_iter().flatten()).collect())
+}
+
+/// Merge operator-wide default image pull secrets with those set on a resource
+///
+/// The resource's own secrets come first in their order, followed by the defaults it doesn't
+/// list already, so the result is the same on every reconcile.
+///
+/// # Arguments
+/// * `defaults`: The default image pull secrets from the controller config
+/// * `secrets`: The image pull secrets set by the Bot
+///
+/// # Returns
+/// The merged image pull secrets, or None when there are none
+pub fn merge_pull_secrets(defaults: &[String], secrets: Option<Vec<String>>) -> Option<Vec<String>> {
+    let mut merged: Vec<String> = vec![];
+    for secret in secrets.into_iter().flatten().chain(defaults.iter().cloned()) {
+        if !merged.contains(&secret) {
+            merged.push(secret);
+        }
+    }
+
+    (!merged.is_empty()).then_some(merged)
+}
+
+/// The label selector matching objects with all of a set of labels
+///
+/// # Arguments
+/// * `labels`: The labels to match
+///
+/// # Returns
+/// The selector, e.g. `app.kubernetes.io/name=bot,freqtrade.io/bot-name=bot`
+pub fn label_selector(labels: &BTreeMap<String, String>) -> String {
+    labels
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Whether an existing object is missing any of the desired labels or annotations, or has
+/// another value for one of them
+///
+/// Labels and annotations added by others, such as `deployment.kubernetes.io/revision`, are
+/// ignored.
+///
+/// # Arguments
+/// * `existing`: The metadata of the object in the cluster
+/// * `desired`: The metadata of the object built by the controller
+///
+/// # Returns
+/// Whether the metadata has drifted
+pub fn metadata_drifted(existing: &ObjectMeta, desired: &ObjectMeta) -> bool {
+    let missing = |existing: &Option<BTreeMap<String, String>>, desired: &Option<BTreeMap<String, String>>| desired
+        .iter()
+        .flatten()
+        .any(|(key, value)| existing.as_ref().and_then(|existing| existing.get(key)) != Some(value));
+
+    missing(&existing.labels, &desired.labels) || missing(&existing.annotations, &desired.annotations)
+}
+
+/// Resolve the value of a secret item, reading the Secret it references if any
+///
+/// # Arguments
+/// * `client`: The kube client
+/// * `namespace`: The namespace of the referenced Secret
+/// * `secret_item`: The secret item to resolve
+///
+/// # Returns
+/// A Result containing the value, or None when the Secret or its key doesn't exist
+pub async fn resolve_secret_item(client: &Client, namespace: &str, secret_item: &SecretItem) -> Result<Option<String>> {
+    match secret_item {
+        SecretItem::Value { value } => Ok(Some(value.clone())),
+        SecretItem::SecretKeyRef { secret_key_ref } => Ok(Api::<Secret>::namespaced(client.clone(), namespace)
+            .get_opt(&secret_key_ref.name)
+            .await?
+            .and_then(|secret| secret.data)
+            .and_then(|data| data.get(&secret_key_ref.key).cloned())
+            .map(|value| String::from_utf8_lossy(&value.0).to_string())),
+    }
+}
+
+/// Error policy to log the error, publish it as a Warning Event on the object
+/// and requeue the object with an exponential backoff
+/// 
+/// The backoff is tracked per object in the context, and reset once the object
+/// reconciles successfully.
 /// 
 /// # Arguments
-/// * `_object`: The object that caused the error
-/// * `_error`: The error that occurred
-/// * `_ctx`: The context of the controller
+/// * `object`: The object that caused the error
+/// * `error`: The error that occurred
+/// * `ctx`: The context of the controller
 ///
 /// # Returns
-/// An Action to requeue the object after 30 seconds
-pub fn error_policy<T>(_object: Arc<T>, _error: &ControllerError, _ctx: Arc<Context>) -> Action {
+/// An Action to requeue the object after its backoff
+pub fn error_policy<T>(object: Arc<T>, error: &ControllerError, ctx: Arc<Context>) -> Action
+where
+    T: Resource<DynamicType = ()> + Send + Sync + 'static,
+{
+    // Retryable errors aren't failures of the object, so they neither grow its backoff nor
+    // publish an Event
+    if error.is_retryable() {
+        let config = ctx.controller_config();
+        let delay = jittered(Duration::from_secs(config.error_backoff_base_seconds), config.requeue_jitter_percent);
+
+        info!(
+            event = "RetryingReconcile",
+            error = %error,
+            delay_seconds = delay.as_secs(),
+        );
+
+        return Action::requeue(delay);
+    }
+
+    let backoff = jittered(
+        ctx.next_backoff(&ObjectRef::from_obj(object.as_ref())),
+        ctx.controller_config().requeue_jitter_percent,
+    );
+
     error!(
         event = "Error",
-        error = %_error,
+        error = %error,
+        backoff_seconds = backoff.as_secs(),
     );
-    Action::requeue(Duration::from_secs(30))
+
+    // The error policy is synchronous, so the Event is published in the background
+    let note = error.to_string();
+    tokio::spawn(async move {
+        ctx.publish_event(object.as_ref(), EventType::Warning, "ReconcileError", "Reconcile", Some(note)).await;
+    });
+
+    Action::requeue(backoff)
+}
+
+/// Run a controller in the background, recording the outcome of every reconcile
+///
+/// # Arguments
+/// * `controller`: The controller to run
+/// * `reconcile`: The reconcile function of the controller
+/// * `error_policy`: The error policy of the controller
+/// * `ctx`: The context of the controller
+/// * `kind`: The kind of the reconciled resource
+/// * `version`: The version of the reconciled resource
+///
+/// # Returns
+/// The handle of the task running the controller
+pub fn spawn_controller<K, ReconcilerFut>(
+    controller: Controller<K>,
+    reconcile: impl FnMut(Arc<K>, Arc<Context>) -> ReconcilerFut + Send + 'static,
+    error_policy: impl Fn(Arc<K>, &ControllerError, Arc<Context>) -> Action + Send + Sync + 'static,
+    ctx: Arc<Context>,
+    kind: &'static str,
+    version: &'static str,
+) -> JoinHandle<()>
+where
+    K: Clone + Resource<DynamicType = ()> + DeserializeOwned + Debug + Send + Sync + 'static,
+    ReconcilerFut: Future<Output = Result<Action>> + Send + 'static,
+{
+    tokio::spawn(async move {
+        controller.run(reconcile, error_policy, ctx.clone())
+            .for_each(|r| {
+                ctx.record_activity();
+                // The per-bot metrics only cover Bots
+                if kind == "Bot" {
+                    ctx.record_bot_reconcile(&r);
+                }
+                // A successful reconcile resets the object's error backoff
+                if let Ok((object, _)) = &r {
+                    ctx.reset_backoff(object);
+                    ctx.state.diagnostics.record_reconcile(kind);
+                }
+                async move {
+                    match r {
+                        Ok(_) => info!(event = "Reconciled", kind = kind, version = version),
+                        Err(e) => error!(event = "ReconcileError", error = %e),
+                    }
+                }
+            })
+            .await
+    })
 }
 
 /// Apply a Resource to the cluster
@@ -63,24 +334,132 @@ where
     ).await.map_err(ControllerError::from)
 }
 
+/// Apply a Resource to the cluster, resolving a conflict with an existing object of the same
+/// name according to the conflict policy
+///
+/// A conflict happens when another field manager, such as a previous Helm release, manages
+/// fields of the existing object. `force` takes the fields over, `adopt-if-unowned` only does
+/// so when no other controller owns the object, and `fail` leaves it alone.
+///
+/// # Arguments
+/// * `api`: The API client for the resource type
+/// * `obj`: The object to apply
+/// * `name`: The name of the object
+/// * `policy`: The conflict policy
+///
+/// # Returns
+/// A Result containing the applied object, or a ConflictError explaining why it wasn't applied
+pub async fn apply_with_conflict_policy<T>(api: &Api<T>, obj: T, name: &str, policy: &ConflictPolicy) -> Result<T>
+where
+    T: Clone + Debug + Serialize + DeserializeOwned + Resource<DynamicType = ()>,
+{
+    let conflict = match apply(api, obj.clone(), name).await {
+        Err(ControllerError::KubeError(kube::Error::Api(error))) if error.code == 409 => error.message.clone(),
+        result => return result,
+    };
+
+    if *policy == ConflictPolicy::AdoptIfUnowned {
+        let owner_uids: Vec<String> = obj.owner_references().iter().map(|owner| owner.uid.clone()).collect();
+        let existing = api.get(name).await?;
+
+        if let Some(owner) = existing.owner_references()
+            .iter()
+            .find(|owner| owner.controller == Some(true) && !owner_uids.contains(&owner.uid))
+        {
+            return Err(ControllerError::ConflictError(format!(
+                "`{}` is controlled by {} `{}`: {}", name, owner.kind, owner.name, conflict
+            )));
+        }
+    }
+
+    if *policy == ConflictPolicy::Fail {
+        return Err(ControllerError::ConflictError(format!("`{}`: {}", name, conflict)));
+    }
+
+    info!(
+        event = "ForceApplying",
+        name = name,
+        conflict = conflict.as_str(),
+    );
+    api.patch(
+        name,
+        &PatchParams::apply(FIELD_MANAGER).force(),
+        &Patch::Apply(obj),
+    ).await.map_err(ControllerError::from)
+}
+
+/// Merge patch the status subresource of a Resource, retrying when it conflicts with another writer
+///
+/// The merge patch carries no resourceVersion, so a conflict is transient and the same patch is
+/// retried after a short delay. Once the attempts are exhausted the conflict is returned as a
+/// retryable StatusConflictError.
+///
+/// # Arguments
+/// * `api`: The API client for the resource type
+/// * `name`: The name of the object
+/// * `status`: The status fields to patch, null values are removed
+///
+/// # Returns
+/// A Result containing the patched object or an error
+pub async fn patch_status<T>(api: &Api<T>, name: &str, status: Value) -> Result<T>
+where
+    T: Clone + DeserializeOwned + Debug,
+{
+    let patch = Patch::Merge(json!({ "status": status }));
+    let mut attempt = 1;
+
+    loop {
+        match api.patch_status(name, &PatchParams::apply(FIELD_MANAGER), &patch).await {
+            Err(kube::Error::Api(error)) if error.code == 409 => {
+                if attempt >= STATUS_PATCH_ATTEMPTS {
+                    return Err(ControllerError::StatusConflictError(format!("`{}`: {}", name, error.message)));
+                }
+                info!(
+                    event = "RetryingStatusPatch",
+                    name = name,
+                    attempt = attempt,
+                );
+                sleep(Duration::from_millis(200 * u64::from(attempt))).await;
+                attempt += 1;
+            },
+            result => return result.map_err(ControllerError::from),
+        }
+    }
+}
+
 /// Delete a Resource
 /// 
 /// # Arguments
 /// * `api`: The API client for the resource type
 /// * `name`: The name of the object to delete
+/// * `params`: The delete parameters, such as the propagation policy
 /// 
 /// # Returns
 /// A Result containing either the deleted object or a Status indicating the deletion was successful
-pub async fn delete<T>(api: &Api<T>, name: &str) -> Result<Either<T, Status>>
+pub async fn delete<T>(api: &Api<T>, name: &str, params: &DeleteParams) -> Result<Either<T, Status>>
 where
     T: Clone + Debug + Serialize + DeserializeOwned + Resource<DynamicType = ()>,
 {
     api.delete(
         name,
-        &Default::default()
+        params
     ).await.map_err(ControllerError::from)
 }
 
+/// The delete parameters for a propagation policy
+///
+/// # Arguments
+/// * `propagation`: How the dependents of the deleted object are deleted
+///
+/// # Returns
+/// The delete parameters
+pub fn delete_params(propagation: &DeletionPropagation) -> DeleteParams {
+    match propagation {
+        DeletionPropagation::Background => DeleteParams::background(),
+        DeletionPropagation::Foreground => DeleteParams::foreground(),
+    }
+}
+
 /// Patch a Resource
 /// 
 /// # Arguments
@@ -104,8 +483,8 @@ where
 /// * `name`: The name of the Deployment to rollout
 /// 
 /// # Returns
-/// A Result indicating success or an error
-pub async fn rollout(api: &Api<Deployment>, name: &str) -> Result<()> {
+/// A Result containing the patched Deployment or an error
+pub async fn rollout(api: &Api<Deployment>, name: &str) -> Result<Deployment> {
     patch::<Deployment>(api, name, &Patch::Merge(
         serde_json::json!({
             "spec": {
@@ -118,7 +497,244 @@ pub async fn rollout(api: &Api<Deployment>, name: &str) -> Result<()> {
                 }
             }
         }),
-    )).await?;
+    )).await
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{future, StreamExt};
+    use hyper::StatusCode;
+    use k8s_openapi::api::core::v1::ConfigMap;
+    use kube::runtime::{watcher, Controller};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::time::timeout;
+
+    use ft_operator_common::config::AppConfig;
+    use ft_operator_common::state::State;
+
+    use crate::controller::mock::{mock_client, MockRequest};
+    use crate::crd::v1alpha1::bot::Bot;
+
+    fn bot() -> Bot {
+        serde_json::from_value(json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "Bot",
+            "metadata": { "name": "bot", "namespace": "default", "uid": "bot-uid" },
+            "spec": { "exchange": "binance", "strategy": { "name": "SampleStrategy" } },
+        }))
+        .unwrap()
+    }
+
+    // The most reconciles of 6 ConfigMaps in flight at once, under a concurrency limit
+    async fn max_reconciles_in_flight(max_concurrent_reconciles: u16) -> usize {
+        let config_maps: Vec<Value> = (0..6)
+            .map(|i| json!({ "metadata": { "name": format!("config-{}", i), "namespace": "default", "resourceVersion": "1" } }))
+            .collect();
+        let (client, _requests) = mock_client(move |_| (StatusCode::OK, json!({ "metadata": { "resourceVersion": "1" }, "items": config_maps })));
+        let mut config = AppConfig::default();
+        config.controller.max_concurrent_reconciles = max_concurrent_reconciles;
+        config.controller.reconcile_debounce_millis = 0;
+        let ctx = Arc::new(Context::new(client.clone(), Arc::new(State::new(config))));
+
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let reconciled = Arc::new(AtomicUsize::new(0));
+        let (max, count) = (max_in_flight.clone(), reconciled.clone());
+        let running = tokio::spawn(
+            Controller::new(Api::<ConfigMap>::all(client), watcher::Config::default())
+                .with_config(runtime_config(&ctx))
+                .run(
+                    move |_, ctx: Arc<Context>| {
+                        let (max, count) = (max.clone(), count.clone());
+                        async move {
+                            let _in_flight = ctx.track_reconcile();
+                            max.fetch_max(ctx.reconciles_in_flight(), Ordering::Relaxed);
+                            sleep(Duration::from_millis(50)).await;
+                            count.fetch_add(1, Ordering::Relaxed);
+                            Ok::<_, ControllerError>(Action::await_change())
+                        }
+                    },
+                    |_, _, _| Action::await_change(),
+                    ctx,
+                )
+                .for_each(|_| future::ready(()))
+        );
+
+        timeout(Duration::from_secs(5), async {
+            while reconciled.load(Ordering::Relaxed) < 6 {
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("The ConfigMaps are reconciled");
+        running.abort();
+
+        max_in_flight.load(Ordering::Relaxed)
+    }
+
+    #[tokio::test]
+    async fn concurrent_reconciles_are_limited() {
+        assert_eq!(max_reconciles_in_flight(2).await, 2);
+    }
+
+    #[tokio::test]
+    async fn concurrent_reconciles_are_unbounded_without_a_limit() {
+        assert_eq!(max_reconciles_in_flight(0).await, 6);
+    }
+
+    // Patch the status of a Bot against an API server failing the first patches with `status`
+    async fn patch_status_failing(status: StatusCode, failures: usize) -> (Result<Bot>, Vec<MockRequest>) {
+        let attempts = AtomicUsize::new(0);
+        let (client, mut requests) = mock_client(move |_| match attempts.fetch_add(1, Ordering::Relaxed) < failures {
+            true => (status, json!({
+                "kind": "Status",
+                "apiVersion": "v1",
+                "status": "Failure",
+                "message": "Operation cannot be fulfilled on bots.freqtrade.io \"bot\": the object has been modified",
+                "reason": "Conflict",
+                "code": status.as_u16(),
+            })),
+            false => (StatusCode::OK, serde_json::to_value(bot()).unwrap()),
+        });
+
+        let result = patch_status(&Api::<Bot>::namespaced(client, "default"), "bot", json!({ "phase": "Running" })).await;
+        let mut sent = vec![];
+        while let Ok(request) = requests.try_recv() {
+            sent.push(request);
+        }
+
+        (result, sent)
+    }
+
+    #[tokio::test]
+    async fn status_conflicts_are_retried() {
+        let (result, requests) = patch_status_failing(StatusCode::CONFLICT, 2).await;
+
+        assert!(result.is_ok(), "{:?}", result);
+        assert_eq!(requests.len(), 3);
+        for request in requests {
+            assert_eq!(request.method, "PATCH");
+            assert_eq!(request.path, "/apis/freqtrade.io/v1alpha1/namespaces/default/bots/bot/status");
+            assert_eq!(request.body, json!({ "status": { "phase": "Running" } }));
+        }
+    }
+
+    #[tokio::test]
+    async fn lasting_status_conflicts_are_retryable() {
+        let (result, requests) = patch_status_failing(StatusCode::CONFLICT, usize::MAX).await;
 
-    Ok(())
-}
\ No newline at end of file
+        let error = result.unwrap_err();
+        assert!(matches!(error, ControllerError::StatusConflictError(_)), "{:?}", error);
+        assert!(error.is_retryable());
+        assert_eq!(requests.len(), STATUS_PATCH_ATTEMPTS as usize);
+    }
+
+    #[tokio::test]
+    async fn other_status_errors_are_not_retried() {
+        let (result, requests) = patch_status_failing(StatusCode::UNPROCESSABLE_ENTITY, usize::MAX).await;
+
+        let error = result.unwrap_err();
+        assert!(matches!(error, ControllerError::KubeError(_)), "{:?}", error);
+        assert!(!error.is_retryable());
+        assert_eq!(requests.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn reconcile_errors_publish_a_warning() {
+        let (client, mut requests) = mock_client(|request| (StatusCode::CREATED, request.body.clone()));
+        let ctx = Arc::new(Context::new(client, Arc::new(State::new(AppConfig::default()))));
+
+        error_policy(Arc::new(bot()), &ControllerError::ConflictError("Service `bot` exists".to_string()), ctx);
+
+        let request = timeout(Duration::from_secs(5), requests.recv()).await.unwrap().unwrap();
+        assert_eq!(request.path, "/apis/events.k8s.io/v1/namespaces/default/events");
+        assert_eq!(request.body["type"], json!("Warning"));
+        assert_eq!(request.body["reason"], json!("ReconcileError"));
+        assert_eq!(request.body["action"], json!("Reconcile"));
+        assert_eq!(request.body["note"], json!("conflict with an existing object: Service `bot` exists"));
+        assert_eq!(request.body["regarding"]["name"], json!("bot"));
+    }
+
+    #[tokio::test]
+    async fn retryable_errors_publish_nothing() {
+        let (client, mut requests) = mock_client(|request| (StatusCode::CREATED, request.body.clone()));
+        let ctx = Arc::new(Context::new(client, Arc::new(State::new(AppConfig::default()))));
+
+        error_policy(Arc::new(bot()), &ControllerError::StatusConflictError("the object has been modified".to_string()), ctx.clone());
+
+        assert!(timeout(Duration::from_millis(200), requests.recv()).await.is_err());
+    }
+
+    #[test]
+    fn jittered_stays_within_the_spread() {
+        let delay = Duration::from_secs(30);
+        let delays: Vec<Duration> = (0..1000).map(|_| jittered(delay, 20)).collect();
+
+        assert!(delays.iter().all(|jittered| (Duration::from_secs(24)..=Duration::from_secs(36)).contains(jittered)));
+        // The requeues of the bots are spread out rather than all landing on the same delay
+        assert!(delays.iter().any(|jittered| *jittered != delays[0]));
+    }
+
+    #[test]
+    fn jittered_keeps_the_delay_without_a_spread() {
+        assert_eq!(jittered(Duration::from_secs(30), 0), Duration::from_secs(30));
+        assert_eq!(jittered(Duration::ZERO, 20), Duration::ZERO);
+    }
+
+    #[test]
+    fn jittered_never_goes_negative() {
+        // The percentage is capped at 100, so the delay is at worst zero
+        assert!((0..1000).all(|_| jittered(Duration::from_secs(1), 255) <= Duration::from_secs(2)));
+    }
+
+    fn labels(labels: &[(&str, &str)]) -> BTreeMap<String, String> {
+        labels.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn values_win_over_the_defaults() {
+        let defaults = labels(&[("team", "platform"), ("cost-center", "cc-1")]);
+
+        assert_eq!(
+            with_defaults(&defaults, Some(labels(&[("team", "trading")]))),
+            Some(labels(&[("team", "trading"), ("cost-center", "cc-1")])),
+        );
+        assert_eq!(with_defaults(&defaults, None), Some(defaults.clone()));
+        assert_eq!(with_defaults(&BTreeMap::new(), None), None);
+    }
+
+    #[test]
+    fn metadata_drifts_on_missing_or_changed_desired_values() {
+        let desired = ObjectMeta { labels: Some(labels(&[("team", "trading")])), ..Default::default() };
+
+        assert!(metadata_drifted(&ObjectMeta::default(), &desired));
+        assert!(metadata_drifted(&ObjectMeta { labels: Some(labels(&[("team", "platform")])), ..Default::default() }, &desired));
+        assert!(!metadata_drifted(&desired, &desired));
+
+        // Keys set by others are left alone
+        let existing = ObjectMeta {
+            labels: Some(labels(&[("team", "trading"), ("added-by", "others")])),
+            annotations: Some(labels(&[("deployment.kubernetes.io/revision", "2")])),
+            ..Default::default()
+        };
+        assert!(!metadata_drifted(&existing, &desired));
+    }
+
+    fn secrets(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn pull_secrets_are_merged_with_the_defaults() {
+        // Bot only
+        assert_eq!(merge_pull_secrets(&[], Some(secrets(&["bot"]))), Some(secrets(&["bot"])));
+        // Config only
+        assert_eq!(merge_pull_secrets(&secrets(&["mirror"]), None), Some(secrets(&["mirror"])));
+        // Combined, the Bot's own first and without duplicates
+        assert_eq!(
+            merge_pull_secrets(&secrets(&["mirror", "shared"]), Some(secrets(&["shared", "bot"]))),
+            Some(secrets(&["shared", "bot", "mirror"])),
+        );
+        assert_eq!(merge_pull_secrets(&[], Some(vec![])), None);
+        assert_eq!(merge_pull_secrets(&[], None), None);
+    }
+}
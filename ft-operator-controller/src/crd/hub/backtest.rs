@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use schemars::JsonSchema;
+
+use crate::crd::{hub::traits::Hub, v1alpha1};
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Backtest {
+    pub metadata: ObjectMeta,
+    pub spec: BacktestSpec,
+    pub status: Option<BacktestStatus>,
+}
+
+impl Hub for Backtest {}
+
+impl From<v1alpha1::backtest::Backtest> for Backtest {
+    fn from(backtest: v1alpha1::backtest::Backtest) -> Self {
+        Backtest {
+            metadata: backtest.metadata,
+            spec: backtest.spec.into(),
+            status: backtest.status.map(|status| status.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BacktestSpec {
+    pub bot_ref: BacktestBotRef,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timerange: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeframe: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_open_trades: Option<i32>,
+}
+
+impl From<v1alpha1::backtest::BacktestSpec> for BacktestSpec {
+    fn from(spec: v1alpha1::backtest::BacktestSpec) -> Self {
+        BacktestSpec {
+            bot_ref: spec.bot_ref.into(),
+            timerange: spec.timerange,
+            timeframe: spec.timeframe,
+            max_open_trades: spec.max_open_trades,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BacktestBotRef {
+    pub name: String,
+}
+
+impl From<v1alpha1::backtest::BacktestBotRef> for BacktestBotRef {
+    fn from(bot_ref: v1alpha1::backtest::BacktestBotRef) -> Self {
+        BacktestBotRef {
+            name: bot_ref.name,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BacktestStatus {
+    pub phase: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_time: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub results_path: Option<String>,
+    pub last_updated: Option<DateTime<Utc>>,
+}
+
+impl From<v1alpha1::backtest::BacktestStatus> for BacktestStatus {
+    fn from(status: v1alpha1::backtest::BacktestStatus) -> Self {
+        BacktestStatus {
+            phase: status.phase,
+            message: status.message,
+            completion_time: status.completion_time,
+            results_path: status.results_path,
+            last_updated: status.last_updated,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BacktestPhase {
+    // The backtest Job is waiting to run
+    Pending,
+    // The backtest Job is running
+    Running,
+    // The backtest Job succeeded
+    Succeeded,
+    // The backtest Job failed
+    Failed,
+}
+
+impl Display for BacktestPhase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            BacktestPhase::Pending => write!(f, "pending"),
+            BacktestPhase::Running => write!(f, "running"),
+            BacktestPhase::Succeeded => write!(f, "succeeded"),
+            BacktestPhase::Failed => write!(f, "failed"),
+        }
+    }
+}
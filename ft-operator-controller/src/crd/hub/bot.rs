@@ -8,7 +8,7 @@ use serde_json::Value;
 use std::{fmt::{Display, Formatter, Result as FmtResult}, collections::BTreeMap};
 use schemars::JsonSchema;
 
-use crate::crd::{hub::traits::Hub, hub::common::SecretItem, v1alpha1};
+use crate::crd::{hub::traits::Hub, hub::common::{Condition, SecretItem}, v1alpha1, v1alpha2};
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Bot {
@@ -19,8 +19,67 @@ pub struct Bot {
 
 impl Hub for Bot {}
 
+impl Bot {
+    /// The name of the resources created for the bot, `spec.nameOverride` or else the bot's name
+    pub fn child_name(&self) -> String {
+        self.spec.name_override
+            .clone()
+            .unwrap_or_else(|| self.metadata.name.clone().unwrap_or_default())
+    }
+}
+
+/// Annotation holding the pod annotations of a Bot while it is stored at v1alpha1
+pub static POD_ANNOTATIONS_ANNOTATION: &str = "freqtrade.io/pod-annotations";
+
 impl From<v1alpha1::bot::Bot> for Bot {
     fn from(bot: v1alpha1::bot::Bot) -> Self {
+        let mut metadata = bot.metadata;
+        let mut spec: BotSpec = bot.spec.into();
+
+        // Restore the pod annotations stashed when converting from a later version
+        if let Some(pod_annotations) = metadata.annotations
+            .as_mut()
+            .and_then(|annotations| annotations.remove(POD_ANNOTATIONS_ANNOTATION))
+        {
+            if let Ok(pod_annotations) = serde_json::from_str(&pod_annotations) {
+                spec.deployment.pod_annotations = pod_annotations;
+            }
+            if metadata.annotations.as_ref().is_some_and(|annotations| annotations.is_empty()) {
+                metadata.annotations = None;
+            }
+        }
+
+        Bot {
+            metadata,
+            spec,
+            status: bot.status.map(|status| status.into()),
+        }
+    }
+}
+
+impl From<Bot> for v1alpha1::bot::Bot {
+    fn from(bot: Bot) -> Self {
+        let mut metadata = bot.metadata;
+
+        // v1alpha1 uses the deployment annotations for the pods, so differing pod annotations
+        // are stashed in an annotation to convert back without loss
+        if bot.spec.deployment.pod_annotations != bot.spec.deployment.annotations {
+            metadata.annotations.get_or_insert_with(BTreeMap::new).insert(
+                POD_ANNOTATIONS_ANNOTATION.to_string(),
+                serde_json::to_string(&bot.spec.deployment.pod_annotations).unwrap_or_default(),
+            );
+        }
+
+        v1alpha1::bot::Bot {
+            metadata,
+            spec: bot.spec.into(),
+            status: bot.status.map(|status| status.into()),
+        }
+    }
+}
+
+impl From<v1alpha2::bot::Bot> for Bot {
+    fn from(bot: v1alpha2::bot::Bot) -> Self {
         Bot {
             metadata: bot.metadata,
             spec: bot.spec.into(),
@@ -29,12 +88,24 @@ impl From<v1alpha1::bot::Bot> for Bot {
     }
 }
 
+impl From<Bot> for v1alpha2::bot::Bot {
+    fn from(bot: Bot) -> Self {
+        v1alpha2::bot::Bot {
+            metadata: bot.metadata,
+            spec: bot.spec.into(),
+            status: bot.status.map(|status| status.into()),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct BotSpec {
     pub exchange: String,
     #[serde(default = "default_database")]
     pub database: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postgres: Option<BotPostgresSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub config: Option<BTreeMap<String, Value>>,
     pub strategy: BotStrategySpec,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -50,6 +121,24 @@ pub struct BotSpec {
     pub pvc: BotPvcSpec,
     #[serde(default)]
     pub deployment: BotDeploymentSpec,
+    #[serde(default)]
+    pub data: BotDataSpec,
+    #[serde(default)]
+    pub monitoring: BotMonitoringSpec,
+    #[serde(default)]
+    pub signals: BotSignalsSpec,
+    #[serde(default)]
+    pub notifications: BotNotificationsSpec,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ui: Option<BotUiSpec>,
+    #[serde(default)]
+    pub lifecycle: BotLifecycleSpec,
+    #[serde(default)]
+    pub suspend: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_override: Option<String>,
+    #[serde(default)]
+    pub status_reporting: BotStatusReportingSpec,
 }
 
 impl From<v1alpha1::bot::BotSpec> for BotSpec {
@@ -57,6 +146,91 @@ impl From<v1alpha1::bot::BotSpec> for BotSpec {
         BotSpec {
             exchange: spec.exchange,
             database: spec.database,
+            postgres: spec.postgres.map(|postgres| postgres.into()),
+            config: spec.config,
+            strategy: spec.strategy.into(),
+            model: spec.model.map(|model| model.into()),
+            image: spec.image.into(),
+            secrets: spec.secrets.into(),
+            api: spec.api.into(),
+            service: spec.service.into(),
+            pvc: spec.pvc.into(),
+            deployment: spec.deployment.into(),
+            data: spec.data.into(),
+            monitoring: spec.monitoring.into(),
+            signals: spec.signals.into(),
+            notifications: spec.notifications.into(),
+            ui: spec.ui.map(|ui| ui.into()),
+            lifecycle: spec.lifecycle.into(),
+            suspend: spec.suspend,
+            name_override: spec.name_override,
+            status_reporting: spec.status_reporting.into(),
+        }
+    }
+}
+
+impl From<BotSpec> for v1alpha1::bot::BotSpec {
+    fn from(spec: BotSpec) -> Self {
+        v1alpha1::bot::BotSpec {
+            exchange: spec.exchange,
+            database: spec.database,
+            postgres: spec.postgres.map(|postgres| postgres.into()),
+            config: spec.config,
+            strategy: spec.strategy.into(),
+            model: spec.model.map(|model| model.into()),
+            image: spec.image.into(),
+            secrets: spec.secrets.into(),
+            api: spec.api.into(),
+            service: spec.service.into(),
+            pvc: spec.pvc.into(),
+            deployment: spec.deployment.into(),
+            data: spec.data.into(),
+            monitoring: spec.monitoring.into(),
+            signals: spec.signals.into(),
+            notifications: spec.notifications.into(),
+            ui: spec.ui.map(|ui| ui.into()),
+            lifecycle: spec.lifecycle.into(),
+            suspend: spec.suspend,
+            name_override: spec.name_override,
+            status_reporting: spec.status_reporting.into(),
+        }
+    }
+}
+
+impl From<v1alpha2::bot::BotSpec> for BotSpec {
+    fn from(spec: v1alpha2::bot::BotSpec) -> Self {
+        BotSpec {
+            exchange: spec.exchange,
+            database: spec.database,
+            postgres: spec.postgres.map(|postgres| postgres.into()),
+            config: spec.config,
+            strategy: spec.strategy.into(),
+            model: spec.model.map(|model| model.into()),
+            image: spec.image.into(),
+            secrets: spec.secrets.into(),
+            api: spec.api.into(),
+            service: spec.service.into(),
+            pvc: spec.pvc.into(),
+            deployment: spec.deployment.into(),
+            data: spec.data.into(),
+            monitoring: spec.monitoring.into(),
+            signals: spec.signals.into(),
+            notifications: spec.notifications.into(),
+            ui: spec.ui.map(|ui| ui.into()),
+            lifecycle: spec.lifecycle.into(),
+            suspend: spec.suspend,
+            name_override: spec.name_override,
+            status_reporting: spec.status_reporting.into(),
+        }
+    }
+}
+
+impl From<BotSpec> for v1alpha2::bot::BotSpec {
+    fn from(spec: BotSpec) -> Self {
+        v1alpha2::bot::BotSpec {
+            exchange: spec.exchange,
+            database: spec.database,
+            postgres: spec.postgres.map(|postgres| postgres.into()),
             config: spec.config,
             strategy: spec.strategy.into(),
             model: spec.model.map(|model| model.into()),
@@ -66,6 +240,15 @@ impl From<v1alpha1::bot::BotSpec> for BotSpec {
             service: spec.service.into(),
             pvc: spec.pvc.into(),
             deployment: spec.deployment.into(),
+            data: spec.data.into(),
+            monitoring: spec.monitoring.into(),
+            signals: spec.signals.into(),
+            notifications: spec.notifications.into(),
+            ui: spec.ui.map(|ui| ui.into()),
+            lifecycle: spec.lifecycle.into(),
+            suspend: spec.suspend,
+            name_override: spec.name_override,
+            status_reporting: spec.status_reporting.into(),
         }
     }
 }
@@ -79,38 +262,224 @@ fn default_database() -> String {
 #[serde(rename_all = "camelCase")]
 pub struct BotStatus {
     pub phase: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
     pub last_updated: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<Vec<Condition>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ready_replicas: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_replicas: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pod_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub applied_config_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_credentials_secret: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_endpoint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freqtrade_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_pod_template_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_trades: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closed_trades: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profit_abs: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profit_ratio: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trades_reported_at: Option<DateTime<Utc>>,
 }
 
 impl From<v1alpha1::bot::BotStatus> for BotStatus {
     fn from(status: v1alpha1::bot::BotStatus) -> Self {
         BotStatus {
             phase: status.phase,
+            reason: status.reason,
+            message: status.message,
+            last_updated: status.last_updated,
+            conditions: status.conditions.map(|conditions| conditions.into_iter().map(|condition| condition.into()).collect()),
+            ready_replicas: status.ready_replicas,
+            updated_replicas: status.updated_replicas,
+            pod_name: status.pod_name,
+            applied_config_hash: status.applied_config_hash,
+            strategy_hash: status.strategy_hash,
+            api_credentials_secret: status.api_credentials_secret,
+            api_endpoint: status.api_endpoint,
+            freqtrade_version: status.freqtrade_version,
+            version_pod_template_hash: status.version_pod_template_hash,
+            open_trades: status.open_trades,
+            closed_trades: status.closed_trades,
+            profit_abs: status.profit_abs,
+            profit_ratio: status.profit_ratio,
+            trades_reported_at: status.trades_reported_at,
+        }
+    }
+}
+
+impl From<BotStatus> for v1alpha1::bot::BotStatus {
+    fn from(status: BotStatus) -> Self {
+        v1alpha1::bot::BotStatus {
+            phase: status.phase,
+            reason: status.reason,
+            message: status.message,
             last_updated: status.last_updated,
+            conditions: status.conditions.map(|conditions| conditions.into_iter().map(|condition| condition.into()).collect()),
+            ready_replicas: status.ready_replicas,
+            updated_replicas: status.updated_replicas,
+            pod_name: status.pod_name,
+            applied_config_hash: status.applied_config_hash,
+            strategy_hash: status.strategy_hash,
+            api_credentials_secret: status.api_credentials_secret,
+            api_endpoint: status.api_endpoint,
+            freqtrade_version: status.freqtrade_version,
+            version_pod_template_hash: status.version_pod_template_hash,
+            open_trades: status.open_trades,
+            closed_trades: status.closed_trades,
+            profit_abs: status.profit_abs,
+            profit_ratio: status.profit_ratio,
+            trades_reported_at: status.trades_reported_at,
+        }
+    }
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[derive(Default)]
+pub struct BotPostgresSpec {
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cluster_ref: Option<PostgresClusterRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub managed: Option<ManagedPostgresSpec>,
+}
+
+impl BotPostgresSpec {
+    /// The name of the CloudNativePG Cluster backing the bot, or None if provisioning is disabled
+    pub fn cluster_name(&self, bot_name: &str) -> Option<String> {
+        if !self.enabled {
+            return None;
+        }
+
+        match &self.cluster_ref {
+            Some(cluster_ref) => Some(cluster_ref.name.clone()),
+            None => Some(format!("{}-db", bot_name)),
+        }
+    }
+
+    /// Whether the operator is responsible for creating the Cluster
+    pub fn is_managed(&self) -> bool {
+        self.enabled && self.cluster_ref.is_none()
+    }
+}
+
+impl From<v1alpha1::bot::BotPostgresSpec> for BotPostgresSpec {
+    fn from(spec: v1alpha1::bot::BotPostgresSpec) -> Self {
+        BotPostgresSpec {
+            enabled: spec.enabled,
+            cluster_ref: spec.cluster_ref.map(|cluster_ref| cluster_ref.into()),
+            managed: spec.managed.map(|managed| managed.into()),
+        }
+    }
+}
+
+impl From<BotPostgresSpec> for v1alpha1::bot::BotPostgresSpec {
+    fn from(spec: BotPostgresSpec) -> Self {
+        v1alpha1::bot::BotPostgresSpec {
+            enabled: spec.enabled,
+            cluster_ref: spec.cluster_ref.map(|cluster_ref| cluster_ref.into()),
+            managed: spec.managed.map(|managed| managed.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct PostgresClusterRef {
+    pub name: String,
+}
+
+impl From<v1alpha1::bot::PostgresClusterRef> for PostgresClusterRef {
+    fn from(cluster_ref: v1alpha1::bot::PostgresClusterRef) -> Self {
+        PostgresClusterRef {
+            name: cluster_ref.name,
+        }
+    }
+}
+
+impl From<PostgresClusterRef> for v1alpha1::bot::PostgresClusterRef {
+    fn from(cluster_ref: PostgresClusterRef) -> Self {
+        v1alpha1::bot::PostgresClusterRef {
+            name: cluster_ref.name,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct ManagedPostgresSpec {
+    pub storage: String,
+    pub instances: i32,
+}
+
+impl Default for ManagedPostgresSpec {
+    fn default() -> Self {
+        ManagedPostgresSpec {
+            storage: "1Gi".to_string(),
+            instances: 1,
+        }
+    }
+}
+
+impl From<v1alpha1::bot::ManagedPostgresSpec> for ManagedPostgresSpec {
+    fn from(spec: v1alpha1::bot::ManagedPostgresSpec) -> Self {
+        ManagedPostgresSpec {
+            storage: spec.storage,
+            instances: spec.instances,
+        }
+    }
+}
+
+impl From<ManagedPostgresSpec> for v1alpha1::bot::ManagedPostgresSpec {
+    fn from(spec: ManagedPostgresSpec) -> Self {
+        v1alpha1::bot::ManagedPostgresSpec {
+            storage: spec.storage,
+            instances: spec.instances,
         }
     }
 }
 
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[derive(Default)]
 pub struct BotImageSpec {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub repository: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tag: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pull_policy: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pull_secrets: Option<Vec<String>>,
 }
 
-impl Default for BotImageSpec {
-    fn default() -> Self {
-        BotImageSpec {
-            repository: Some("freqtradeorg/freqtrade".to_string()),
-            tag: Some("stable".to_string()),
-            pull_policy: None,
-            pull_secrets: None,
+impl BotImageSpec {
+    /// The full image reference, a digest pins the exact image so the tag is ignored when one is set
+    pub fn image_name(&self, default_repo: &str, default_tag: &str) -> String {
+        let repository = self.repository.as_deref().unwrap_or(default_repo);
+
+        match &self.digest {
+            Some(digest) => format!("{}@{}", repository, digest),
+            None => format!("{}:{}", repository, self.tag.as_deref().unwrap_or(default_tag)),
         }
     }
 }
@@ -120,6 +489,19 @@ impl From<v1alpha1::bot::BotImageSpec> for BotImageSpec {
         BotImageSpec {
             repository: spec.repository,
             tag: spec.tag,
+            digest: spec.digest,
+            pull_policy: spec.pull_policy,
+            pull_secrets: spec.pull_secrets,
+        }
+    }
+}
+
+impl From<BotImageSpec> for v1alpha1::bot::BotImageSpec {
+    fn from(spec: BotImageSpec) -> Self {
+        v1alpha1::bot::BotImageSpec {
+            repository: spec.repository,
+            tag: spec.tag,
+            digest: spec.digest,
             pull_policy: spec.pull_policy,
             pull_secrets: spec.pull_secrets,
         }
@@ -129,16 +511,40 @@ impl From<v1alpha1::bot::BotImageSpec> for BotImageSpec {
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 pub struct BotSecrets {
     pub exchange: Option<ExchangeSecrets>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exchange_ref: Option<ExchangeCredentialsRef>,
     pub api: Option<ApiSecrets>,
     pub telegram: Option<TelegramSecrets>,
+    pub discord: Option<WebhookUrlSecrets>,
+    pub slack: Option<WebhookUrlSecrets>,
+    #[serde(default)]
+    pub rollout_on_change: bool,
 }
 
 impl From<v1alpha1::bot::BotSecrets> for BotSecrets {
     fn from(secrets: v1alpha1::bot::BotSecrets) -> Self {
         BotSecrets {
             exchange: secrets.exchange.map(|exchange| exchange.into()),
+            exchange_ref: secrets.exchange_ref.map(|exchange_ref| exchange_ref.into()),
+            api: secrets.api.map(|api| api.into()),
+            telegram: secrets.telegram.map(|telegram| telegram.into()),
+            discord: secrets.discord.map(|discord| discord.into()),
+            slack: secrets.slack.map(|slack| slack.into()),
+            rollout_on_change: secrets.rollout_on_change,
+        }
+    }
+}
+
+impl From<BotSecrets> for v1alpha1::bot::BotSecrets {
+    fn from(secrets: BotSecrets) -> Self {
+        v1alpha1::bot::BotSecrets {
+            exchange: secrets.exchange.map(|exchange| exchange.into()),
+            exchange_ref: secrets.exchange_ref.map(|exchange_ref| exchange_ref.into()),
             api: secrets.api.map(|api| api.into()),
             telegram: secrets.telegram.map(|telegram| telegram.into()),
+            discord: secrets.discord.map(|discord| discord.into()),
+            slack: secrets.slack.map(|slack| slack.into()),
+            rollout_on_change: secrets.rollout_on_change,
         }
     }
 }
@@ -163,17 +569,79 @@ impl From<v1alpha1::bot::ApiSecrets> for ApiSecrets {
     }
 }
 
+impl From<ApiSecrets> for v1alpha1::bot::ApiSecrets {
+    fn from(secrets: ApiSecrets) -> Self {
+        v1alpha1::bot::ApiSecrets {
+            username: secrets.username.map(|username| username.into()),
+            password: secrets.password.map(|password| password.into()),
+            ws_token: secrets.ws_token.map(|ws_token| ws_token.into()),
+            jwt_secret_key: secrets.jwt_secret_key.map(|jwt_secret_key| jwt_secret_key.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct ExchangeCredentialsRef {
+    pub name: String,
+}
+
+impl From<v1alpha1::bot::ExchangeCredentialsRef> for ExchangeCredentialsRef {
+    fn from(exchange_ref: v1alpha1::bot::ExchangeCredentialsRef) -> Self {
+        ExchangeCredentialsRef {
+            name: exchange_ref.name,
+        }
+    }
+}
+
+impl From<ExchangeCredentialsRef> for v1alpha1::bot::ExchangeCredentialsRef {
+    fn from(exchange_ref: ExchangeCredentialsRef) -> Self {
+        v1alpha1::bot::ExchangeCredentialsRef {
+            name: exchange_ref.name,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct WebhookUrlSecrets {
+    pub webhook_url: SecretItem,
+}
+
+impl From<v1alpha1::bot::WebhookUrlSecrets> for WebhookUrlSecrets {
+    fn from(secrets: v1alpha1::bot::WebhookUrlSecrets) -> Self {
+        WebhookUrlSecrets {
+            webhook_url: secrets.webhook_url.into(),
+        }
+    }
+}
+
+impl From<WebhookUrlSecrets> for v1alpha1::bot::WebhookUrlSecrets {
+    fn from(secrets: WebhookUrlSecrets) -> Self {
+        v1alpha1::bot::WebhookUrlSecrets {
+            webhook_url: secrets.webhook_url.into(),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 pub struct TelegramSecrets {
     pub token: Option<SecretItem>,
-    pub chat_id: Option<String>,
+    pub chat_id: Option<SecretItem>,
 }
 
 impl From<v1alpha1::bot::TelegramSecrets> for TelegramSecrets {
     fn from(secrets: v1alpha1::bot::TelegramSecrets) -> Self {
         TelegramSecrets {
             token: secrets.token.map(|token| token.into()),
-            chat_id: secrets.chat_id,
+            chat_id: secrets.chat_id.map(|chat_id| chat_id.into()),
+        }
+    }
+}
+
+impl From<TelegramSecrets> for v1alpha1::bot::TelegramSecrets {
+    fn from(secrets: TelegramSecrets) -> Self {
+        v1alpha1::bot::TelegramSecrets {
+            token: secrets.token.map(|token| token.into()),
+            chat_id: secrets.chat_id.map(|chat_id| chat_id.into()),
         }
     }
 }
@@ -197,6 +665,17 @@ impl From<v1alpha1::bot::ExchangeSecrets> for ExchangeSecrets {
     }
 }
 
+impl From<ExchangeSecrets> for v1alpha1::bot::ExchangeSecrets {
+    fn from(secrets: ExchangeSecrets) -> Self {
+        v1alpha1::bot::ExchangeSecrets {
+            key: secrets.key.map(|key| key.into()),
+            secret: secrets.secret.map(|secret| secret.into()),
+            password: secrets.password.map(|password| password.into()),
+            uid: secrets.uid.map(|uid| uid.into()),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 pub struct BotStrategySpec {
     pub name: String,
@@ -204,6 +683,10 @@ pub struct BotStrategySpec {
     pub config_map_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy_ref: Option<StrategyRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<BTreeMap<String, Value>>,
 }
 
 impl From<v1alpha1::bot::BotStrategySpec> for BotStrategySpec {
@@ -212,6 +695,41 @@ impl From<v1alpha1::bot::BotStrategySpec> for BotStrategySpec {
             name: spec.name,
             config_map_name: spec.config_map_name,
             source: spec.source,
+            strategy_ref: spec.strategy_ref.map(|strategy_ref| strategy_ref.into()),
+            parameters: spec.parameters,
+        }
+    }
+}
+
+impl From<BotStrategySpec> for v1alpha1::bot::BotStrategySpec {
+    fn from(spec: BotStrategySpec) -> Self {
+        v1alpha1::bot::BotStrategySpec {
+            name: spec.name,
+            config_map_name: spec.config_map_name,
+            source: spec.source,
+            strategy_ref: spec.strategy_ref.map(|strategy_ref| strategy_ref.into()),
+            parameters: spec.parameters,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct StrategyRef {
+    pub name: String,
+}
+
+impl From<v1alpha1::bot::StrategyRef> for StrategyRef {
+    fn from(strategy_ref: v1alpha1::bot::StrategyRef) -> Self {
+        StrategyRef {
+            name: strategy_ref.name,
+        }
+    }
+}
+
+impl From<StrategyRef> for v1alpha1::bot::StrategyRef {
+    fn from(strategy_ref: StrategyRef) -> Self {
+        v1alpha1::bot::StrategyRef {
+            name: strategy_ref.name,
         }
     }
 }
@@ -224,6 +742,8 @@ pub struct BotModelSpec {
     pub config_map_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resources: Option<ResourceRequirements>,
 }
 
 impl Default for BotModelSpec {
@@ -232,6 +752,7 @@ impl Default for BotModelSpec {
             name: "LightGBMRegressor".to_string(),
             config_map_name: None,
             source: None,
+            resources: None,
         }
     }
 }
@@ -242,16 +763,31 @@ impl From<v1alpha1::bot::BotModelSpec> for BotModelSpec {
             name: spec.name,
             config_map_name: spec.config_map_name,
             source: spec.source,
+            resources: spec.resources,
         }
     }
 }
 
-
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
-pub struct BotApiSpec {
-    pub enabled: bool,
+impl From<BotModelSpec> for v1alpha1::bot::BotModelSpec {
+    fn from(spec: BotModelSpec) -> Self {
+        v1alpha1::bot::BotModelSpec {
+            name: spec.name,
+            config_map_name: spec.config_map_name,
+            source: spec.source,
+            resources: spec.resources,
+        }
+    }
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct BotApiSpec {
+    pub enabled: bool,
     pub host: String,
     pub port: u16,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cors_origins: Vec<String>,
+    pub health_check: bool,
 }
 
 impl Default for BotApiSpec {
@@ -260,6 +796,8 @@ impl Default for BotApiSpec {
             enabled: true,
             host: "0.0.0.0".to_string(),
             port: 8080,
+            cors_origins: vec![],
+            health_check: true,
         }
     }
 }
@@ -270,6 +808,20 @@ impl From<v1alpha1::bot::BotApiSpec> for BotApiSpec {
             enabled: spec.enabled,
             host: spec.host,
             port: spec.port,
+            cors_origins: spec.cors_origins,
+            health_check: spec.health_check,
+        }
+    }
+}
+
+impl From<BotApiSpec> for v1alpha1::bot::BotApiSpec {
+    fn from(spec: BotApiSpec) -> Self {
+        v1alpha1::bot::BotApiSpec {
+            enabled: spec.enabled,
+            host: spec.host,
+            port: spec.port,
+            cors_origins: spec.cors_origins,
+            health_check: spec.health_check,
         }
     }
 }
@@ -307,6 +859,17 @@ impl From<v1alpha1::bot::BotServiceSpec> for BotServiceSpec {
     }
 }
 
+impl From<BotServiceSpec> for v1alpha1::bot::BotServiceSpec {
+    fn from(spec: BotServiceSpec) -> Self {
+        v1alpha1::bot::BotServiceSpec {
+            service_type: spec.service_type,
+            annotations: spec.annotations,
+            labels: spec.labels,
+            ports: spec.ports.into_iter().map(|port| port.into()).collect(),
+        }
+    }
+}
+
 impl BotServiceSpec {
     pub fn ensure_api_port(&mut self, api_port: u16) {
         if !self.ports.iter().any(|port| port.name == "api") {
@@ -317,6 +880,16 @@ impl BotServiceSpec {
             });
         }
     }
+
+    pub fn ensure_metrics_port(&mut self, metrics_port: u16) {
+        if !self.ports.iter().any(|port| port.name == "metrics") {
+            self.ports.push(BotServicePort {
+                name: "metrics".to_string(),
+                port: metrics_port,
+                target_port: "metrics".to_string(),
+            });
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
@@ -336,6 +909,16 @@ impl From<v1alpha1::bot::BotServicePort> for BotServicePort {
     }
 }
 
+impl From<BotServicePort> for v1alpha1::bot::BotServicePort {
+    fn from(port: BotServicePort) -> Self {
+        v1alpha1::bot::BotServicePort {
+            name: port.name,
+            port: port.port,
+            target_port: port.target_port,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 pub struct BotPvcSpec {
     pub enabled: bool,
@@ -346,6 +929,9 @@ pub struct BotPvcSpec {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub storage_class: Option<String>,
     pub size: String,
+    pub bootstrap: bool,
+    #[serde(default)]
+    pub deletion_policy: PvcDeletionPolicy,
 }
 
 impl Default for BotPvcSpec {
@@ -356,6 +942,8 @@ impl Default for BotPvcSpec {
             labels: None,
             storage_class: None,
             size: "1Gi".to_string(),
+            bootstrap: true,
+            deletion_policy: PvcDeletionPolicy::default(),
         }
     }
 }
@@ -368,6 +956,514 @@ impl From<v1alpha1::bot::BotPvcSpec> for BotPvcSpec {
             labels: spec.labels,
             storage_class: spec.storage_class,
             size: spec.size,
+            bootstrap: spec.bootstrap,
+            deletion_policy: spec.deletion_policy.into(),
+        }
+    }
+}
+
+impl From<BotPvcSpec> for v1alpha1::bot::BotPvcSpec {
+    fn from(spec: BotPvcSpec) -> Self {
+        v1alpha1::bot::BotPvcSpec {
+            enabled: spec.enabled,
+            annotations: spec.annotations,
+            labels: spec.labels,
+            storage_class: spec.storage_class,
+            size: spec.size,
+            bootstrap: spec.bootstrap,
+            deletion_policy: spec.deletion_policy.into(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[derive(Default)]
+pub struct BotDataSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download: Option<BotDataDownloadSpec>,
+}
+
+impl From<v1alpha1::bot::BotDataSpec> for BotDataSpec {
+    fn from(spec: v1alpha1::bot::BotDataSpec) -> Self {
+        BotDataSpec {
+            download: spec.download.map(|download| download.into()),
+        }
+    }
+}
+
+impl From<BotDataSpec> for v1alpha1::bot::BotDataSpec {
+    fn from(spec: BotDataSpec) -> Self {
+        v1alpha1::bot::BotDataSpec {
+            download: spec.download.map(|download| download.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[derive(Default)]
+pub struct BotDataDownloadSpec {
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timerange: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timeframes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pairs: Option<Vec<String>>,
+}
+
+impl From<v1alpha1::bot::BotDataDownloadSpec> for BotDataDownloadSpec {
+    fn from(spec: v1alpha1::bot::BotDataDownloadSpec) -> Self {
+        BotDataDownloadSpec {
+            enabled: spec.enabled,
+            timerange: spec.timerange,
+            timeframes: spec.timeframes,
+            pairs: spec.pairs,
+        }
+    }
+}
+
+impl From<BotDataDownloadSpec> for v1alpha1::bot::BotDataDownloadSpec {
+    fn from(spec: BotDataDownloadSpec) -> Self {
+        v1alpha1::bot::BotDataDownloadSpec {
+            enabled: spec.enabled,
+            timerange: spec.timerange,
+            timeframes: spec.timeframes,
+            pairs: spec.pairs,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[derive(Default)]
+pub struct BotSignalsSpec {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub producers: Vec<BotProducerSpec>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_consumer_namespaces: Vec<String>,
+}
+
+impl From<v1alpha1::bot::BotSignalsSpec> for BotSignalsSpec {
+    fn from(spec: v1alpha1::bot::BotSignalsSpec) -> Self {
+        BotSignalsSpec {
+            producers: spec.producers.into_iter().map(|producer| producer.into()).collect(),
+            allowed_consumer_namespaces: spec.allowed_consumer_namespaces,
+        }
+    }
+}
+
+impl From<BotSignalsSpec> for v1alpha1::bot::BotSignalsSpec {
+    fn from(spec: BotSignalsSpec) -> Self {
+        v1alpha1::bot::BotSignalsSpec {
+            producers: spec.producers.into_iter().map(|producer| producer.into()).collect(),
+            allowed_consumer_namespaces: spec.allowed_consumer_namespaces,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct BotProducerSpec {
+    pub bot_ref: BotRef,
+}
+
+impl From<v1alpha1::bot::BotProducerSpec> for BotProducerSpec {
+    fn from(spec: v1alpha1::bot::BotProducerSpec) -> Self {
+        BotProducerSpec {
+            bot_ref: spec.bot_ref.into(),
+        }
+    }
+}
+
+impl From<BotProducerSpec> for v1alpha1::bot::BotProducerSpec {
+    fn from(spec: BotProducerSpec) -> Self {
+        v1alpha1::bot::BotProducerSpec {
+            bot_ref: spec.bot_ref.into(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct BotRef {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+}
+
+impl From<v1alpha1::bot::BotRef> for BotRef {
+    fn from(bot_ref: v1alpha1::bot::BotRef) -> Self {
+        BotRef {
+            name: bot_ref.name,
+            namespace: bot_ref.namespace,
+        }
+    }
+}
+
+impl From<BotRef> for v1alpha1::bot::BotRef {
+    fn from(bot_ref: BotRef) -> Self {
+        v1alpha1::bot::BotRef {
+            name: bot_ref.name,
+            namespace: bot_ref.namespace,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[derive(Default)]
+pub struct BotMonitoringSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exporter: Option<BotExporterSpec>,
+}
+
+impl BotMonitoringSpec {
+    /// The exporter configuration, if the exporter sidecar is enabled
+    pub fn enabled_exporter(&self) -> Option<&BotExporterSpec> {
+        self.exporter.as_ref().filter(|exporter| exporter.enabled)
+    }
+}
+
+impl From<v1alpha1::bot::BotMonitoringSpec> for BotMonitoringSpec {
+    fn from(spec: v1alpha1::bot::BotMonitoringSpec) -> Self {
+        BotMonitoringSpec {
+            exporter: spec.exporter.map(|exporter| exporter.into()),
+        }
+    }
+}
+
+impl From<BotMonitoringSpec> for v1alpha1::bot::BotMonitoringSpec {
+    fn from(spec: BotMonitoringSpec) -> Self {
+        v1alpha1::bot::BotMonitoringSpec {
+            exporter: spec.exporter.map(|exporter| exporter.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct BotExporterSpec {
+    pub enabled: bool,
+    pub image: String,
+    pub port: u16,
+}
+
+impl Default for BotExporterSpec {
+    fn default() -> Self {
+        BotExporterSpec {
+            enabled: true,
+            image: "kavurankayastudios/freqtrade_exporter:latest".to_string(),
+            port: 9090,
+        }
+    }
+}
+
+impl From<v1alpha1::bot::BotExporterSpec> for BotExporterSpec {
+    fn from(spec: v1alpha1::bot::BotExporterSpec) -> Self {
+        BotExporterSpec {
+            enabled: spec.enabled,
+            image: spec.image,
+            port: spec.port,
+        }
+    }
+}
+
+impl From<BotExporterSpec> for v1alpha1::bot::BotExporterSpec {
+    fn from(spec: BotExporterSpec) -> Self {
+        v1alpha1::bot::BotExporterSpec {
+            enabled: spec.enabled,
+            image: spec.image,
+            port: spec.port,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct BotUiSpec {
+    pub enabled: bool,
+    pub image: String,
+    pub port: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingress: Option<BotUiIngressSpec>,
+}
+
+impl Default for BotUiSpec {
+    fn default() -> Self {
+        BotUiSpec {
+            enabled: true,
+            image: "freqtradeorg/frequi:latest".to_string(),
+            port: 80,
+            ingress: None,
+        }
+    }
+}
+
+impl From<v1alpha1::bot::BotUiSpec> for BotUiSpec {
+    fn from(spec: v1alpha1::bot::BotUiSpec) -> Self {
+        BotUiSpec {
+            enabled: spec.enabled,
+            image: spec.image,
+            port: spec.port,
+            ingress: spec.ingress.map(|ingress| ingress.into()),
+        }
+    }
+}
+
+impl From<BotUiSpec> for v1alpha1::bot::BotUiSpec {
+    fn from(spec: BotUiSpec) -> Self {
+        v1alpha1::bot::BotUiSpec {
+            enabled: spec.enabled,
+            image: spec.image,
+            port: spec.port,
+            ingress: spec.ingress.map(|ingress| ingress.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[derive(Default)]
+pub struct BotUiIngressSpec {
+    pub host: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_secret_name: Option<String>,
+}
+
+impl From<v1alpha1::bot::BotUiIngressSpec> for BotUiIngressSpec {
+    fn from(spec: v1alpha1::bot::BotUiIngressSpec) -> Self {
+        BotUiIngressSpec {
+            host: spec.host,
+            class_name: spec.class_name,
+            annotations: spec.annotations,
+            tls_secret_name: spec.tls_secret_name,
+        }
+    }
+}
+
+impl From<BotUiIngressSpec> for v1alpha1::bot::BotUiIngressSpec {
+    fn from(spec: BotUiIngressSpec) -> Self {
+        v1alpha1::bot::BotUiIngressSpec {
+            host: spec.host,
+            class_name: spec.class_name,
+            annotations: spec.annotations,
+            tls_secret_name: spec.tls_secret_name,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BotLifecycleSpec {
+    pub stop_on_delete: bool,
+    pub stop_timeout_seconds: u64,
+}
+
+impl Default for BotLifecycleSpec {
+    fn default() -> Self {
+        BotLifecycleSpec {
+            stop_on_delete: false,
+            stop_timeout_seconds: 60,
+        }
+    }
+}
+
+impl From<v1alpha1::bot::BotLifecycleSpec> for BotLifecycleSpec {
+    fn from(spec: v1alpha1::bot::BotLifecycleSpec) -> Self {
+        BotLifecycleSpec {
+            stop_on_delete: spec.stop_on_delete,
+            stop_timeout_seconds: spec.stop_timeout_seconds,
+        }
+    }
+}
+
+impl From<BotLifecycleSpec> for v1alpha1::bot::BotLifecycleSpec {
+    fn from(spec: BotLifecycleSpec) -> Self {
+        v1alpha1::bot::BotLifecycleSpec {
+            stop_on_delete: spec.stop_on_delete,
+            stop_timeout_seconds: spec.stop_timeout_seconds,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BotStatusReportingSpec {
+    pub enabled: bool,
+    pub interval_seconds: u64,
+}
+
+impl Default for BotStatusReportingSpec {
+    fn default() -> Self {
+        BotStatusReportingSpec {
+            enabled: false,
+            interval_seconds: 60,
+        }
+    }
+}
+
+impl From<v1alpha1::bot::BotStatusReportingSpec> for BotStatusReportingSpec {
+    fn from(spec: v1alpha1::bot::BotStatusReportingSpec) -> Self {
+        BotStatusReportingSpec {
+            enabled: spec.enabled,
+            interval_seconds: spec.interval_seconds,
+        }
+    }
+}
+
+impl From<BotStatusReportingSpec> for v1alpha1::bot::BotStatusReportingSpec {
+    fn from(spec: BotStatusReportingSpec) -> Self {
+        v1alpha1::bot::BotStatusReportingSpec {
+            enabled: spec.enabled,
+            interval_seconds: spec.interval_seconds,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[derive(Default)]
+pub struct BotNotificationsSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<BotWebhookNotificationSpec>,
+}
+
+impl From<v1alpha1::bot::BotNotificationsSpec> for BotNotificationsSpec {
+    fn from(spec: v1alpha1::bot::BotNotificationsSpec) -> Self {
+        BotNotificationsSpec {
+            webhook: spec.webhook.map(|webhook| webhook.into()),
+        }
+    }
+}
+
+impl From<BotNotificationsSpec> for v1alpha1::bot::BotNotificationsSpec {
+    fn from(spec: BotNotificationsSpec) -> Self {
+        v1alpha1::bot::BotNotificationsSpec {
+            webhook: spec.webhook.map(|webhook| webhook.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct BotWebhookNotificationSpec {
+    pub enabled: bool,
+    pub url: SecretItem,
+    pub format: WebhookFormat,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+}
+
+impl From<v1alpha1::bot::BotWebhookNotificationSpec> for BotWebhookNotificationSpec {
+    fn from(spec: v1alpha1::bot::BotWebhookNotificationSpec) -> Self {
+        BotWebhookNotificationSpec {
+            enabled: spec.enabled,
+            url: spec.url.into(),
+            format: spec.format.into(),
+            retries: spec.retries,
+        }
+    }
+}
+
+impl From<BotWebhookNotificationSpec> for v1alpha1::bot::BotWebhookNotificationSpec {
+    fn from(spec: BotWebhookNotificationSpec) -> Self {
+        v1alpha1::bot::BotWebhookNotificationSpec {
+            enabled: spec.enabled,
+            url: spec.url.into(),
+            format: spec.format.into(),
+            retries: spec.retries,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    #[default]
+    Form,
+    Json,
+    Raw,
+}
+
+impl Display for WebhookFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            WebhookFormat::Form => write!(f, "form"),
+            WebhookFormat::Json => write!(f, "json"),
+            WebhookFormat::Raw => write!(f, "raw"),
+        }
+    }
+}
+
+impl From<v1alpha1::bot::WebhookFormat> for WebhookFormat {
+    fn from(format: v1alpha1::bot::WebhookFormat) -> Self {
+        match format {
+            v1alpha1::bot::WebhookFormat::Form => WebhookFormat::Form,
+            v1alpha1::bot::WebhookFormat::Json => WebhookFormat::Json,
+            v1alpha1::bot::WebhookFormat::Raw => WebhookFormat::Raw,
+        }
+    }
+}
+
+impl From<WebhookFormat> for v1alpha1::bot::WebhookFormat {
+    fn from(format: WebhookFormat) -> Self {
+        match format {
+            WebhookFormat::Form => v1alpha1::bot::WebhookFormat::Form,
+            WebhookFormat::Json => v1alpha1::bot::WebhookFormat::Json,
+            WebhookFormat::Raw => v1alpha1::bot::WebhookFormat::Raw,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema, Default)]
+pub enum PvcDeletionPolicy {
+    #[default]
+    Delete,
+    Retain,
+}
+
+impl From<v1alpha1::bot::PvcDeletionPolicy> for PvcDeletionPolicy {
+    fn from(policy: v1alpha1::bot::PvcDeletionPolicy) -> Self {
+        match policy {
+            v1alpha1::bot::PvcDeletionPolicy::Delete => PvcDeletionPolicy::Delete,
+            v1alpha1::bot::PvcDeletionPolicy::Retain => PvcDeletionPolicy::Retain,
+        }
+    }
+}
+
+impl From<PvcDeletionPolicy> for v1alpha1::bot::PvcDeletionPolicy {
+    fn from(policy: PvcDeletionPolicy) -> Self {
+        match policy {
+            PvcDeletionPolicy::Delete => v1alpha1::bot::PvcDeletionPolicy::Delete,
+            PvcDeletionPolicy::Retain => v1alpha1::bot::PvcDeletionPolicy::Retain,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub enum DeploymentUpdateStrategy {
+    Recreate,
+    RollingUpdate,
+}
+
+impl Display for DeploymentUpdateStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            DeploymentUpdateStrategy::Recreate => write!(f, "Recreate"),
+            DeploymentUpdateStrategy::RollingUpdate => write!(f, "RollingUpdate"),
+        }
+    }
+}
+
+impl From<v1alpha1::bot::DeploymentUpdateStrategy> for DeploymentUpdateStrategy {
+    fn from(strategy: v1alpha1::bot::DeploymentUpdateStrategy) -> Self {
+        match strategy {
+            v1alpha1::bot::DeploymentUpdateStrategy::Recreate => DeploymentUpdateStrategy::Recreate,
+            v1alpha1::bot::DeploymentUpdateStrategy::RollingUpdate => DeploymentUpdateStrategy::RollingUpdate,
+        }
+    }
+}
+
+impl From<DeploymentUpdateStrategy> for v1alpha1::bot::DeploymentUpdateStrategy {
+    fn from(strategy: DeploymentUpdateStrategy) -> Self {
+        match strategy {
+            DeploymentUpdateStrategy::Recreate => v1alpha1::bot::DeploymentUpdateStrategy::Recreate,
+            DeploymentUpdateStrategy::RollingUpdate => v1alpha1::bot::DeploymentUpdateStrategy::RollingUpdate,
         }
     }
 }
@@ -380,6 +1476,8 @@ pub struct BotDeploymentSpec {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub annotations: Option<BTreeMap<String, String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pod_annotations: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub labels: Option<BTreeMap<String, String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub node_selector: Option<BTreeMap<String, String>>,
@@ -403,14 +1501,100 @@ pub struct BotDeploymentSpec {
     pub volume_mounts: Vec<VolumeMount>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub env: Vec<EnvVar>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_strategy: Option<DeploymentUpdateStrategy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision_history_limit: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_ready_seconds: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress_deadline_seconds: Option<i32>,
 }
 
 
 impl From<v1alpha1::bot::BotDeploymentSpec> for BotDeploymentSpec {
     fn from(spec: v1alpha1::bot::BotDeploymentSpec) -> Self {
         BotDeploymentSpec {
+            command: spec.command,
+            pod_annotations: spec.annotations.clone(),
+            annotations: spec.annotations,
+            labels: spec.labels,
+            node_selector: spec.node_selector,
+            resources: spec.resources,
+            affinity: spec.affinity,
+            tolerations: spec.tolerations,
+            pod_security_context: spec.pod_security_context,
+            security_context: spec.security_context,
+            containers: spec.containers,
+            init_containers: spec.init_containers,
+            volumes: spec.volumes,
+            volume_mounts: spec.volume_mounts,
+            env: spec.env,
+            update_strategy: spec.update_strategy.map(|strategy| strategy.into()),
+            revision_history_limit: spec.revision_history_limit,
+            min_ready_seconds: spec.min_ready_seconds,
+            progress_deadline_seconds: spec.progress_deadline_seconds,
+        }
+    }
+}
+
+impl From<BotDeploymentSpec> for v1alpha1::bot::BotDeploymentSpec {
+    fn from(spec: BotDeploymentSpec) -> Self {
+        v1alpha1::bot::BotDeploymentSpec {
+            command: spec.command,
+            annotations: spec.annotations,
+            labels: spec.labels,
+            node_selector: spec.node_selector,
+            resources: spec.resources,
+            affinity: spec.affinity,
+            tolerations: spec.tolerations,
+            pod_security_context: spec.pod_security_context,
+            security_context: spec.security_context,
+            containers: spec.containers,
+            init_containers: spec.init_containers,
+            volumes: spec.volumes,
+            volume_mounts: spec.volume_mounts,
+            env: spec.env,
+            update_strategy: spec.update_strategy.map(|strategy| strategy.into()),
+            revision_history_limit: spec.revision_history_limit,
+            min_ready_seconds: spec.min_ready_seconds,
+            progress_deadline_seconds: spec.progress_deadline_seconds,
+        }
+    }
+}
+
+impl From<v1alpha2::bot::BotDeploymentSpec> for BotDeploymentSpec {
+    fn from(spec: v1alpha2::bot::BotDeploymentSpec) -> Self {
+        BotDeploymentSpec {
+            command: spec.command,
+            annotations: spec.annotations,
+            pod_annotations: spec.pod_annotations,
+            labels: spec.labels,
+            node_selector: spec.node_selector,
+            resources: spec.resources,
+            affinity: spec.affinity,
+            tolerations: spec.tolerations,
+            pod_security_context: spec.pod_security_context,
+            security_context: spec.security_context,
+            containers: spec.containers,
+            init_containers: spec.init_containers,
+            volumes: spec.volumes,
+            volume_mounts: spec.volume_mounts,
+            env: spec.env,
+            update_strategy: spec.update_strategy.map(|strategy| strategy.into()),
+            revision_history_limit: spec.revision_history_limit,
+            min_ready_seconds: spec.min_ready_seconds,
+            progress_deadline_seconds: spec.progress_deadline_seconds,
+        }
+    }
+}
+
+impl From<BotDeploymentSpec> for v1alpha2::bot::BotDeploymentSpec {
+    fn from(spec: BotDeploymentSpec) -> Self {
+        v1alpha2::bot::BotDeploymentSpec {
             command: spec.command,
             annotations: spec.annotations,
+            pod_annotations: spec.pod_annotations,
             labels: spec.labels,
             node_selector: spec.node_selector,
             resources: spec.resources,
@@ -423,6 +1607,10 @@ impl From<v1alpha1::bot::BotDeploymentSpec> for BotDeploymentSpec {
             volumes: spec.volumes,
             volume_mounts: spec.volume_mounts,
             env: spec.env,
+            update_strategy: spec.update_strategy.map(|strategy| strategy.into()),
+            revision_history_limit: spec.revision_history_limit,
+            min_ready_seconds: spec.min_ready_seconds,
+            progress_deadline_seconds: spec.progress_deadline_seconds,
         }
     }
 }
@@ -438,6 +1626,8 @@ pub enum BotPhase {
     Error,
     // The bot is being deleted
     Deleting,
+    // The bot is suspended
+    Suspended,
 }
 
 impl Display for BotPhase {
@@ -447,6 +1637,7 @@ impl Display for BotPhase {
             BotPhase::Running => write!(f, "running"),
             BotPhase::Error => write!(f, "error"),
             BotPhase::Deleting => write!(f, "deleting"),
+            BotPhase::Suspended => write!(f, "suspended"),
         }
     }
 }
@@ -458,6 +1649,389 @@ impl From<v1alpha1::bot::BotPhase> for BotPhase {
             v1alpha1::bot::BotPhase::Running => BotPhase::Running,
             v1alpha1::bot::BotPhase::Error => BotPhase::Error,
             v1alpha1::bot::BotPhase::Deleting => BotPhase::Deleting,
+            v1alpha1::bot::BotPhase::Suspended => BotPhase::Suspended,
+        }
+    }
+}
+
+impl From<BotPhase> for v1alpha1::bot::BotPhase {
+    fn from(phase: BotPhase) -> Self {
+        match phase {
+            BotPhase::Pending => v1alpha1::bot::BotPhase::Pending,
+            BotPhase::Running => v1alpha1::bot::BotPhase::Running,
+            BotPhase::Error => v1alpha1::bot::BotPhase::Error,
+            BotPhase::Deleting => v1alpha1::bot::BotPhase::Deleting,
+            BotPhase::Suspended => v1alpha1::bot::BotPhase::Suspended,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kube::CustomResourceExt;
+    use serde_json::json;
+
+    /// Fields of Kubernetes types, converted as a whole rather than field by field
+    const KUBERNETES_FIELDS: [&str; 10] = [
+        "resources", "affinity", "tolerations", "podSecurityContext", "securityContext",
+        "containers", "initContainers", "volumes", "volumeMounts", "env",
+    ];
+
+    /// A v1alpha1 spec with every field set, away from its default where there is one
+    fn full_v1alpha1_spec() -> Value {
+        json!({
+            "exchange": "binance",
+            "database": "sqlite:///trades.db",
+            "postgres": {
+                "enabled": true,
+                "clusterRef": { "name": "db" },
+                "managed": { "storage": "5Gi", "instances": 2 },
+            },
+            "config": { "stake_currency": "USDT", "exchange": { "pair_whitelist": ["BTC/USDT"] } },
+            "strategy": {
+                "name": "SampleStrategy",
+                "configMapName": "strategy",
+                "source": "class SampleStrategy(IStrategy): pass",
+                "strategyRef": { "name": "sample" },
+                "parameters": { "buy": { "rsi": 30 } },
+            },
+            "model": {
+                "name": "XGBoostRegressor",
+                "configMapName": "model",
+                "source": "class Model: pass",
+                "resources": { "limits": { "nvidia.com/gpu": "1" } },
+            },
+            "image": {
+                "repository": "freqtradeorg/freqtrade",
+                "tag": "2025.1",
+                "digest": "sha256:0000000000000000000000000000000000000000000000000000000000000000",
+                "pullPolicy": "Always",
+                "pullSecrets": ["registry"],
+            },
+            "secrets": {
+                "exchange": {
+                    "key": { "value": "key" },
+                    "secret": { "secretKeyRef": { "name": "exchange", "key": "secret" } },
+                    "password": { "value": "password" },
+                    "uid": { "secretKeyRef": { "name": "exchange", "key": "uid" } },
+                },
+                "exchangeRef": { "name": "binance" },
+                "api": {
+                    "username": { "value": "freqtrader" },
+                    "password": { "secretKeyRef": { "name": "api", "key": "password" } },
+                    "wsToken": { "value": "token" },
+                    "jwtSecretKey": { "secretKeyRef": { "name": "api", "key": "jwt" } },
+                },
+                "telegram": {
+                    "token": { "secretKeyRef": { "name": "telegram", "key": "token" } },
+                    "chatId": { "value": "1234" },
+                },
+                "discord": { "webhookUrl": { "value": "https://discord.com/api/webhooks/1" } },
+                "slack": { "webhookUrl": { "secretKeyRef": { "name": "slack", "key": "url" } } },
+                "rolloutOnChange": true,
+            },
+            "api": {
+                "enabled": false,
+                "host": "127.0.0.1",
+                "port": 8081,
+                "corsOrigins": ["https://frequi.example.com"],
+                "healthCheck": false,
+            },
+            "service": {
+                "serviceType": "NodePort",
+                "annotations": { "service": "a" },
+                "labels": { "service": "b" },
+                "ports": [{ "name": "metrics", "port": 9091, "targetPort": "metrics" }],
+            },
+            "pvc": {
+                "enabled": false,
+                "annotations": { "pvc": "a" },
+                "labels": { "pvc": "b" },
+                "storageClass": "fast",
+                "size": "2Gi",
+                "bootstrap": false,
+                "deletionPolicy": "Retain",
+            },
+            "deployment": {
+                "command": ["freqtrade", "trade"],
+                "annotations": { "deployment": "a" },
+                "labels": { "deployment": "b" },
+                "nodeSelector": { "kubernetes.io/arch": "amd64" },
+                "resources": { "requests": { "cpu": "500m", "memory": "1Gi" } },
+                "affinity": {
+                    "nodeAffinity": {
+                        "requiredDuringSchedulingIgnoredDuringExecution": {
+                            "nodeSelectorTerms": [{ "matchExpressions": [{ "key": "zone", "operator": "In", "values": ["a"] }] }],
+                        },
+                    },
+                },
+                "tolerations": [{ "key": "dedicated", "operator": "Equal", "value": "trading", "effect": "NoSchedule" }],
+                "podSecurityContext": { "fsGroup": 1000 },
+                "securityContext": { "runAsNonRoot": true },
+                "containers": [{ "name": "sidecar", "image": "busybox" }],
+                "initContainers": [{ "name": "init", "image": "busybox" }],
+                "volumes": [{ "name": "extra", "emptyDir": {} }],
+                "volumeMounts": [{ "name": "extra", "mountPath": "/extra" }],
+                "env": [{ "name": "TZ", "value": "UTC" }],
+                "updateStrategy": "RollingUpdate",
+                "revisionHistoryLimit": 3,
+                "minReadySeconds": 10,
+                "progressDeadlineSeconds": 300,
+            },
+            "data": {
+                "download": {
+                    "enabled": true,
+                    "timerange": "20240101-",
+                    "timeframes": ["5m", "1h"],
+                    "pairs": ["BTC/USDT"],
+                },
+            },
+            "monitoring": {
+                "exporter": { "enabled": false, "image": "exporter:1.0", "port": 9100 },
+            },
+            "signals": {
+                "producers": [{ "botRef": { "name": "producer", "namespace": "signals" } }],
+                "allowedConsumerNamespaces": ["consumers"],
+            },
+            "notifications": {
+                "webhook": {
+                    "enabled": false,
+                    "url": { "secretKeyRef": { "name": "webhook", "key": "url" } },
+                    "format": "json",
+                    "retries": 5,
+                },
+            },
+            "ui": {
+                "enabled": false,
+                "image": "freqtradeorg/frequi:2.0",
+                "port": 8000,
+                "ingress": {
+                    "host": "frequi.example.com",
+                    "className": "nginx",
+                    "annotations": { "ingress": "a" },
+                    "tlsSecretName": "frequi-tls",
+                },
+            },
+            "lifecycle": { "stopOnDelete": true, "stopTimeoutSeconds": 120 },
+            "suspend": true,
+            "nameOverride": "trader",
+            "statusReporting": { "enabled": true, "intervalSeconds": 30 },
+        })
+    }
+
+    /// A v1alpha2 spec with every field set, with pod annotations differing from the deployment's
+    fn full_v1alpha2_spec() -> Value {
+        let mut spec = full_v1alpha1_spec();
+        spec["deployment"]["podAnnotations"] = json!({ "pod": "c" });
+        spec
+    }
+
+    /// A status with every field set
+    fn full_status() -> Value {
+        json!({
+            "phase": "running",
+            "reason": "Running",
+            "message": "Bot is running",
+            "lastUpdated": "2025-01-01T00:00:00Z",
+            "conditions": [{
+                "type": "DatabaseReady",
+                "status": "True",
+                "reason": "ClusterReady",
+                "message": "Database is ready",
+                "lastTransitionTime": "2025-01-01T00:00:00Z",
+            }],
+            "readyReplicas": 1,
+            "updatedReplicas": 1,
+            "podName": "bot-0",
+            "appliedConfigHash": "config",
+            "strategyHash": "strategy",
+            "apiCredentialsSecret": "bot-api",
+            "apiEndpoint": "http://bot.default.svc:8080",
+            "freqtradeVersion": "2025.1",
+            "versionPodTemplateHash": "abc",
+            "openTrades": 2,
+            "closedTrades": 10,
+            "profitAbs": 12.5,
+            "profitRatio": 0.05,
+            "tradesReportedAt": "2025-01-01T00:00:00Z",
+        })
+    }
+
+    fn full_bot(version: &str, spec: Value) -> Value {
+        json!({
+            "apiVersion": format!("freqtrade.io/{version}"),
+            "kind": "Bot",
+            "metadata": { "name": "bot", "namespace": "default", "annotations": { "team": "a" } },
+            "spec": spec,
+            "status": full_status(),
+        })
+    }
+
+    /// Paths of the properties of `schema` left unset in `value`
+    fn unset_properties(schema: &Value, value: &Value, path: &str) -> Vec<String> {
+        let mut unset = vec![];
+        for (name, property) in schema["properties"].as_object().into_iter().flatten() {
+            let path = format!("{path}.{name}");
+            match value.get(name) {
+                Some(_) if KUBERNETES_FIELDS.contains(&name.as_str()) => (),
+                Some(value) => unset.extend(unset_properties(property, value, &path)),
+                // Only one variant of an untagged enum can be set at once
+                None if schema.get("oneOf").or(schema.get("anyOf")).is_some() => (),
+                None => unset.push(path),
+            }
         }
+        if let Some(items) = schema.get("items") {
+            match value.get(0) {
+                Some(item) => unset.extend(unset_properties(items, item, &format!("{path}[]"))),
+                None => unset.push(format!("{path}[]")),
+            }
+        }
+        unset
+    }
+
+    /// Assert `bot` sets every property of the CRD's schema, and deserializes without losing any
+    fn assert_fully_set<K: CustomResourceExt + Serialize>(bot: &K, value: &Value) {
+        let crd = serde_json::to_value(K::crd()).unwrap();
+        let schema = &crd["spec"]["versions"][0]["schema"]["openAPIV3Schema"];
+        assert_eq!(unset_properties(schema, value, ""), Vec::<String>::new());
+        assert_eq!(&serde_json::to_value(bot).unwrap(), value);
+    }
+
+    fn v1alpha1_bot(metadata: Value, spec: Value) -> v1alpha1::bot::Bot {
+        serde_json::from_value(json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "Bot",
+            "metadata": metadata,
+            "spec": spec,
+        }))
+        .unwrap()
+    }
+
+    fn v1alpha2_bot(spec: Value) -> v1alpha2::bot::Bot {
+        serde_json::from_value(json!({
+            "apiVersion": "freqtrade.io/v1alpha2",
+            "kind": "Bot",
+            "metadata": { "name": "bot", "namespace": "default" },
+            "spec": spec,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn v1alpha1_round_trips_through_the_hub() {
+        let bot = v1alpha1_bot(
+            json!({ "name": "bot", "namespace": "default", "annotations": { "team": "a" } }),
+            json!({
+                "exchange": "binance",
+                "config": { "stake_currency": "USDT", "max_open_trades": 3 },
+                "strategy": { "name": "SampleStrategy", "source": "class SampleStrategy(IStrategy): pass" },
+                "deployment": {
+                    "annotations": { "prometheus.io/scrape": "true" },
+                    "env": [{ "name": "TZ", "value": "UTC" }],
+                },
+                "pvc": { "size": "2Gi" },
+                "suspend": true,
+            }),
+        );
+
+        assert_eq!(v1alpha1::bot::Bot::from(Bot::from(bot.clone())), bot);
+    }
+
+    #[test]
+    fn every_v1alpha1_field_round_trips_through_the_hub() {
+        let value = full_bot("v1alpha1", full_v1alpha1_spec());
+        let bot: v1alpha1::bot::Bot = serde_json::from_value(value.clone()).unwrap();
+        assert_fully_set(&bot, &value);
+
+        assert_eq!(v1alpha1::bot::Bot::from(Bot::from(bot.clone())), bot);
+        // Through a v1alpha2 client and back, as served at both versions
+        let served = v1alpha2::bot::Bot::from(Bot::from(bot.clone()));
+        assert_eq!(v1alpha1::bot::Bot::from(Bot::from(served)), bot);
+    }
+
+    #[test]
+    fn every_v1alpha2_field_round_trips_through_the_hub() {
+        let value = full_bot("v1alpha2", full_v1alpha2_spec());
+        let bot: v1alpha2::bot::Bot = serde_json::from_value(value.clone()).unwrap();
+        assert_fully_set(&bot, &value);
+
+        assert_eq!(v1alpha2::bot::Bot::from(Bot::from(bot.clone())), bot);
+        // Through the v1alpha1 storage version and back
+        let stored = v1alpha1::bot::Bot::from(Bot::from(bot.clone()));
+        assert_eq!(v1alpha2::bot::Bot::from(Bot::from(stored)), bot);
+    }
+
+    #[test]
+    fn secret_items_round_trip_through_the_hub() {
+        let bot = v1alpha1_bot(
+            json!({ "name": "bot", "namespace": "default" }),
+            json!({
+                "exchange": "binance",
+                "strategy": { "name": "SampleStrategy", "configMapName": "strategy" },
+                "secrets": {
+                    "exchange": {
+                        "key": { "value": "key" },
+                        "secret": { "secretKeyRef": { "name": "exchange", "key": "secret" } },
+                    },
+                    "telegram": {
+                        "token": { "secretKeyRef": { "name": "telegram", "key": "token" } },
+                        "chatId": { "value": "1234" },
+                    },
+                },
+            }),
+        );
+        let hub = Bot::from(bot.clone());
+
+        // The untagged variants are told apart by their fields, not a tag
+        let exchange = hub.spec.secrets.exchange.as_ref().unwrap();
+        assert!(matches!(&exchange.key, Some(SecretItem::Value { value }) if value == "key"));
+        assert!(matches!(&exchange.secret, Some(SecretItem::SecretKeyRef { secret_key_ref }) if secret_key_ref.key == "secret"));
+        assert_eq!(v1alpha1::bot::Bot::from(hub), bot);
+    }
+
+    #[test]
+    fn v1alpha2_pod_annotations_survive_a_v1alpha1_round_trip() {
+        let bot = v1alpha2_bot(json!({
+            "exchange": "binance",
+            "strategy": { "name": "SampleStrategy", "configMapName": "strategy" },
+            "deployment": {
+                "annotations": { "team": "a" },
+                "podAnnotations": { "sidecar.istio.io/inject": "false" },
+            },
+        }));
+
+        // Stored at v1alpha1, which has no pod annotations of its own
+        let stored = v1alpha1::bot::Bot::from(Bot::from(bot.clone()));
+        assert!(stored.metadata.annotations.as_ref().is_some_and(|annotations| annotations.contains_key(POD_ANNOTATIONS_ANNOTATION)));
+
+        let restored = v1alpha2::bot::Bot::from(Bot::from(stored));
+        assert_eq!(restored, bot);
+    }
+
+    #[test]
+    fn matching_pod_annotations_are_not_stashed() {
+        let bot = v1alpha2_bot(json!({
+            "exchange": "binance",
+            "strategy": { "name": "SampleStrategy", "configMapName": "strategy" },
+            "deployment": {
+                "annotations": { "team": "a" },
+                "podAnnotations": { "team": "a" },
+            },
+        }));
+
+        let stored = v1alpha1::bot::Bot::from(Bot::from(bot.clone()));
+        assert_eq!(stored.metadata.annotations, None);
+        assert_eq!(v1alpha2::bot::Bot::from(Bot::from(stored)), bot);
+    }
+
+    #[test]
+    fn postgres_spec_uses_the_served_field_names() {
+        let spec: BotPostgresSpec = serde_json::from_value(json!({ "clusterRef": { "name": "db" } })).unwrap();
+        assert!(!spec.enabled);
+        assert_eq!(spec.cluster_ref, Some(PostgresClusterRef { name: "db".to_string() }));
+
+        let value = serde_json::to_value(&spec).unwrap();
+        assert_eq!(value, json!({ "enabled": false, "clusterRef": { "name": "db" } }));
     }
 }
@@ -0,0 +1,116 @@
+use chrono::{DateTime, Utc};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use schemars::JsonSchema;
+
+use crate::crd::{hub::{bot::{BotSpec, ExchangeCredentialsRef}, traits::Hub}, v1alpha1};
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BotFleet {
+    pub metadata: ObjectMeta,
+    pub spec: BotFleetSpec,
+    pub status: Option<BotFleetStatus>,
+}
+
+impl Hub for BotFleet {}
+
+impl From<v1alpha1::bot_fleet::BotFleet> for BotFleet {
+    fn from(bot_fleet: v1alpha1::bot_fleet::BotFleet) -> Self {
+        BotFleet {
+            metadata: bot_fleet.metadata,
+            spec: bot_fleet.spec.into(),
+            status: bot_fleet.status.map(|status| status.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BotFleetSpec {
+    pub template: BotFleetTemplate,
+    #[serde(default)]
+    pub instances: Vec<BotFleetInstance>,
+}
+
+impl From<v1alpha1::bot_fleet::BotFleetSpec> for BotFleetSpec {
+    fn from(spec: v1alpha1::bot_fleet::BotFleetSpec) -> Self {
+        BotFleetSpec {
+            template: spec.template.into(),
+            instances: spec.instances.into_iter().map(|instance| instance.into()).collect(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BotFleetTemplate {
+    #[serde(default)]
+    pub metadata: BotFleetTemplateMetadata,
+    pub spec: BotSpec,
+}
+
+impl From<v1alpha1::bot_fleet::BotFleetTemplate> for BotFleetTemplate {
+    fn from(template: v1alpha1::bot_fleet::BotFleetTemplate) -> Self {
+        BotFleetTemplate {
+            metadata: template.metadata.into(),
+            spec: template.spec.into(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct BotFleetTemplateMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<BTreeMap<String, String>>,
+}
+
+impl From<v1alpha1::bot_fleet::BotFleetTemplateMetadata> for BotFleetTemplateMetadata {
+    fn from(metadata: v1alpha1::bot_fleet::BotFleetTemplateMetadata) -> Self {
+        BotFleetTemplateMetadata {
+            labels: metadata.labels,
+            annotations: metadata.annotations,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BotFleetInstance {
+    pub name_suffix: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exchange: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exchange_ref: Option<ExchangeCredentialsRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<BTreeMap<String, Value>>,
+}
+
+impl From<v1alpha1::bot_fleet::BotFleetInstance> for BotFleetInstance {
+    fn from(instance: v1alpha1::bot_fleet::BotFleetInstance) -> Self {
+        BotFleetInstance {
+            name_suffix: instance.name_suffix,
+            exchange: instance.exchange,
+            exchange_ref: instance.exchange_ref.map(|exchange_ref| exchange_ref.into()),
+            config: instance.config,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BotFleetStatus {
+    pub ready_bots: u32,
+    pub total_bots: u32,
+    pub last_updated: Option<DateTime<Utc>>,
+}
+
+impl From<v1alpha1::bot_fleet::BotFleetStatus> for BotFleetStatus {
+    fn from(status: v1alpha1::bot_fleet::BotFleetStatus) -> Self {
+        BotFleetStatus {
+            ready_bots: status.ready_bots,
+            total_bots: status.total_bots,
+            last_updated: status.last_updated,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use schemars::JsonSchema;
 
@@ -21,6 +22,15 @@ impl From<v1alpha1::common::SecretItem> for SecretItem {
     }
 }
 
+impl From<SecretItem> for v1alpha1::common::SecretItem {
+    fn from(secret_item: SecretItem) -> Self {
+        match secret_item {
+            SecretItem::Value { value } => v1alpha1::common::SecretItem::Value { value },
+            SecretItem::SecretKeyRef { secret_key_ref } => v1alpha1::common::SecretItem::SecretKeyRef { secret_key_ref: secret_key_ref.into() },
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 pub struct SecretKeyRef {
     pub name: String,
@@ -34,4 +44,51 @@ impl From<v1alpha1::common::SecretKeyRef> for SecretKeyRef {
             key: secret_key_ref.key,
         }
     }
-}
\ No newline at end of file
+}
+
+impl From<SecretKeyRef> for v1alpha1::common::SecretKeyRef {
+    fn from(secret_key_ref: SecretKeyRef) -> Self {
+        v1alpha1::common::SecretKeyRef {
+            name: secret_key_ref.name,
+            key: secret_key_ref.key,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Condition {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_transition_time: Option<DateTime<Utc>>,
+}
+
+impl From<v1alpha1::common::Condition> for Condition {
+    fn from(condition: v1alpha1::common::Condition) -> Self {
+        Condition {
+            type_: condition.type_,
+            status: condition.status,
+            reason: condition.reason,
+            message: condition.message,
+            last_transition_time: condition.last_transition_time,
+        }
+    }
+}
+
+impl From<Condition> for v1alpha1::common::Condition {
+    fn from(condition: Condition) -> Self {
+        v1alpha1::common::Condition {
+            type_: condition.type_,
+            status: condition.status,
+            reason: condition.reason,
+            message: condition.message,
+            last_transition_time: condition.last_transition_time,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+use crate::crd::{hub::{bot::{BotImageSpec, ExchangeSecrets}, traits::Hub}, v1alpha1};
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DataDownload {
+    pub metadata: ObjectMeta,
+    pub spec: DataDownloadSpec,
+    pub status: Option<DataDownloadStatus>,
+}
+
+impl Hub for DataDownload {}
+
+impl From<v1alpha1::data_download::DataDownload> for DataDownload {
+    fn from(data_download: v1alpha1::data_download::DataDownload) -> Self {
+        DataDownload {
+            metadata: data_download.metadata,
+            spec: data_download.spec.into(),
+            status: data_download.status.map(|status| status.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DataDownloadSpec {
+    pub schedule: String,
+    pub exchange: String,
+    pub pairs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timeframes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timerange: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pvc_ref: Option<DataDownloadPvcRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bot_ref: Option<DataDownloadBotRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<BotImageSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exchange_secrets: Option<ExchangeSecrets>,
+}
+
+impl DataDownloadSpec {
+    /// The name of the PVC the data is downloaded onto
+    ///
+    /// Bots store their data on a PVC named after the bot, `pvcRef` takes precedence and must be
+    /// used for a bot with a `nameOverride`.
+    pub fn pvc_name(&self) -> Option<String> {
+        self.pvc_ref
+            .as_ref()
+            .map(|pvc_ref| pvc_ref.name.clone())
+            .or_else(|| self.bot_ref.as_ref().map(|bot_ref| bot_ref.name.clone()))
+    }
+}
+
+impl From<v1alpha1::data_download::DataDownloadSpec> for DataDownloadSpec {
+    fn from(spec: v1alpha1::data_download::DataDownloadSpec) -> Self {
+        DataDownloadSpec {
+            schedule: spec.schedule,
+            exchange: spec.exchange,
+            pairs: spec.pairs,
+            timeframes: spec.timeframes,
+            timerange: spec.timerange,
+            pvc_ref: spec.pvc_ref.map(|pvc_ref| pvc_ref.into()),
+            bot_ref: spec.bot_ref.map(|bot_ref| bot_ref.into()),
+            image: spec.image.map(|image| image.into()),
+            exchange_secrets: spec.exchange_secrets.map(|exchange_secrets| exchange_secrets.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DataDownloadPvcRef {
+    pub name: String,
+}
+
+impl From<v1alpha1::data_download::DataDownloadPvcRef> for DataDownloadPvcRef {
+    fn from(pvc_ref: v1alpha1::data_download::DataDownloadPvcRef) -> Self {
+        DataDownloadPvcRef {
+            name: pvc_ref.name,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DataDownloadBotRef {
+    pub name: String,
+}
+
+impl From<v1alpha1::data_download::DataDownloadBotRef> for DataDownloadBotRef {
+    fn from(bot_ref: v1alpha1::data_download::DataDownloadBotRef) -> Self {
+        DataDownloadBotRef {
+            name: bot_ref.name,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DataDownloadStatus {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_schedule_time: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_successful_time: Option<DateTime<Utc>>,
+    pub last_updated: Option<DateTime<Utc>>,
+}
+
+impl From<v1alpha1::data_download::DataDownloadStatus> for DataDownloadStatus {
+    fn from(status: v1alpha1::data_download::DataDownloadStatus) -> Self {
+        DataDownloadStatus {
+            message: status.message,
+            last_schedule_time: status.last_schedule_time,
+            last_successful_time: status.last_successful_time,
+            last_updated: status.last_updated,
+        }
+    }
+}
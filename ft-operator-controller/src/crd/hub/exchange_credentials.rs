@@ -0,0 +1,53 @@
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+use crate::crd::{hub::traits::Hub, hub::common::SecretItem, v1alpha1};
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExchangeCredentials {
+    pub metadata: ObjectMeta,
+    pub spec: ExchangeCredentialsSpec,
+}
+
+impl Hub for ExchangeCredentials {}
+
+impl From<v1alpha1::exchange_credentials::ExchangeCredentials> for ExchangeCredentials {
+    fn from(credentials: v1alpha1::exchange_credentials::ExchangeCredentials) -> Self {
+        ExchangeCredentials {
+            metadata: credentials.metadata,
+            spec: credentials.spec.into(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExchangeCredentialsSpec {
+    pub exchange: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_namespaces: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<SecretItem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<SecretItem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<SecretItem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<SecretItem>,
+}
+
+impl From<v1alpha1::exchange_credentials::ExchangeCredentialsSpec> for ExchangeCredentialsSpec {
+    fn from(spec: v1alpha1::exchange_credentials::ExchangeCredentialsSpec) -> Self {
+        ExchangeCredentialsSpec {
+            exchange: spec.exchange,
+            allowed_namespaces: spec.allowed_namespaces,
+            secret_namespace: spec.secret_namespace,
+            key: spec.key.map(|key| key.into()),
+            secret: spec.secret.map(|secret| secret.into()),
+            password: spec.password.map(|password| password.into()),
+            uid: spec.uid.map(|uid| uid.into()),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use chrono::{DateTime, Utc};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use schemars::JsonSchema;
+
+use crate::crd::{hub::traits::Hub, v1alpha1};
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Hyperopt {
+    pub metadata: ObjectMeta,
+    pub spec: HyperoptSpec,
+    pub status: Option<HyperoptStatus>,
+}
+
+impl Hub for Hyperopt {}
+
+impl From<v1alpha1::hyperopt::Hyperopt> for Hyperopt {
+    fn from(hyperopt: v1alpha1::hyperopt::Hyperopt) -> Self {
+        Hyperopt {
+            metadata: hyperopt.metadata,
+            spec: hyperopt.spec.into(),
+            status: hyperopt.status.map(|status| status.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HyperoptSpec {
+    pub bot_ref: HyperoptBotRef,
+    #[serde(default = "default_epochs")]
+    pub epochs: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spaces: Vec<String>,
+    #[serde(default = "default_loss_function")]
+    pub loss_function: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timerange: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job_timeout: Option<i64>,
+    #[serde(default)]
+    pub backoff_limit: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_seconds_after_finished: Option<i32>,
+}
+
+impl From<v1alpha1::hyperopt::HyperoptSpec> for HyperoptSpec {
+    fn from(spec: v1alpha1::hyperopt::HyperoptSpec) -> Self {
+        HyperoptSpec {
+            bot_ref: spec.bot_ref.into(),
+            epochs: spec.epochs,
+            spaces: spec.spaces,
+            loss_function: spec.loss_function,
+            timerange: spec.timerange,
+            job_timeout: spec.job_timeout,
+            backoff_limit: spec.backoff_limit,
+            ttl_seconds_after_finished: spec.ttl_seconds_after_finished,
+        }
+    }
+}
+
+fn default_epochs() -> u32 {
+    100
+}
+
+fn default_loss_function() -> String {
+    "SharpeHyperOptLossDaily".to_string()
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HyperoptBotRef {
+    pub name: String,
+}
+
+impl From<v1alpha1::hyperopt::HyperoptBotRef> for HyperoptBotRef {
+    fn from(bot_ref: v1alpha1::hyperopt::HyperoptBotRef) -> Self {
+        HyperoptBotRef {
+            name: bot_ref.name,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HyperoptStatus {
+    pub phase: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_time: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub results_config_map: Option<String>,
+    pub last_updated: Option<DateTime<Utc>>,
+}
+
+impl From<v1alpha1::hyperopt::HyperoptStatus> for HyperoptStatus {
+    fn from(status: v1alpha1::hyperopt::HyperoptStatus) -> Self {
+        HyperoptStatus {
+            phase: status.phase,
+            message: status.message,
+            completion_time: status.completion_time,
+            results_config_map: status.results_config_map,
+            last_updated: status.last_updated,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HyperoptPhase {
+    // The hyperopt Job is waiting to run
+    Pending,
+    // The hyperopt Job is running
+    Running,
+    // The hyperopt Job succeeded and its results were surfaced
+    Succeeded,
+    // The hyperopt Job failed
+    Failed,
+}
+
+impl Display for HyperoptPhase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            HyperoptPhase::Pending => write!(f, "pending"),
+            HyperoptPhase::Running => write!(f, "running"),
+            HyperoptPhase::Succeeded => write!(f, "succeeded"),
+            HyperoptPhase::Failed => write!(f, "failed"),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use chrono::{DateTime, Utc};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use schemars::JsonSchema;
+
+use crate::crd::{hub::traits::Hub, v1alpha1};
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Strategy {
+    pub metadata: ObjectMeta,
+    pub spec: StrategySpec,
+    pub status: Option<StrategyStatus>,
+}
+
+impl Hub for Strategy {}
+
+impl From<v1alpha1::strategy::Strategy> for Strategy {
+    fn from(strategy: v1alpha1::strategy::Strategy) -> Self {
+        Strategy {
+            metadata: strategy.metadata,
+            spec: strategy.spec.into(),
+            status: strategy.status.map(|status| status.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StrategySpec {
+    pub class_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_map_name: Option<String>,
+}
+
+impl StrategySpec {
+    /// Validate the strategy, returning why it is invalid if so
+    pub fn validate(&self) -> Result<(), String> {
+        let is_identifier = !self.class_name.is_empty()
+            && !self.class_name.starts_with(|c: char| c.is_ascii_digit())
+            && self.class_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+        if !is_identifier {
+            return Err(format!("className `{}` is not a valid Python class name", self.class_name));
+        }
+
+        match (&self.source, &self.config_map_name) {
+            (Some(_), Some(_)) => Err("only one of source or configMapName can be set".to_string()),
+            (None, None) => Err("one of source or configMapName must be set".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl From<v1alpha1::strategy::StrategySpec> for StrategySpec {
+    fn from(spec: v1alpha1::strategy::StrategySpec) -> Self {
+        StrategySpec {
+            class_name: spec.class_name,
+            source: spec.source,
+            config_map_name: spec.config_map_name,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StrategyStatus {
+    pub phase: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub referenced_by: u32,
+    pub last_updated: Option<DateTime<Utc>>,
+}
+
+impl From<v1alpha1::strategy::StrategyStatus> for StrategyStatus {
+    fn from(status: v1alpha1::strategy::StrategyStatus) -> Self {
+        StrategyStatus {
+            phase: status.phase,
+            message: status.message,
+            referenced_by: status.referenced_by,
+            last_updated: status.last_updated,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyPhase {
+    // The strategy spec is valid
+    Valid,
+    // The strategy spec is invalid
+    Invalid,
+}
+
+impl Display for StrategyPhase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            StrategyPhase::Valid => write!(f, "valid"),
+            StrategyPhase::Invalid => write!(f, "invalid"),
+        }
+    }
+}
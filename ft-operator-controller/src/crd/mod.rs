@@ -1,6 +1,7 @@
 pub mod hub;
 pub mod utils;
 pub mod v1alpha1;
+pub mod v1alpha2;
 
 use kube::{Resource, CustomResourceExt, core::object::{HasStatus, HasSpec}};
 use k8s_openapi::{NamespaceResourceScope, ClusterResourceScope};
@@ -43,7 +44,6 @@ pub trait ClusterCustomResource:
     Clone
         + Resource<Scope = ClusterResourceScope, DynamicType = ()>
         + CustomResourceExt
-        + HasStatus
         + HasSpec
         + JsonSchema
         + DeserializeOwned
@@ -59,7 +59,6 @@ where
     T: Clone
         + Resource<Scope = ClusterResourceScope, DynamicType = ()>
         + CustomResourceExt
-        + HasStatus
         + HasSpec
         + JsonSchema
         + DeserializeOwned
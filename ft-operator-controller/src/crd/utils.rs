@@ -1,15 +1,148 @@
 use kube::CustomResourceExt;
+use k8s_openapi::ByteString;
 use kube::core::crd::merge_crds;
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::{
+    CustomResourceConversion, CustomResourceDefinition, ServiceReference, WebhookClientConfig, WebhookConversion,
+};
 
-use crate::crd::v1alpha1::bot::Bot as V1Alpha1Bot;
+use crate::crd::v1alpha1::{
+    backtest::Backtest as V1Alpha1Backtest,
+    bot::Bot as V1Alpha1Bot,
+    bot_fleet::BotFleet as V1Alpha1BotFleet,
+    data_download::DataDownload as V1Alpha1DataDownload,
+    exchange_credentials::ExchangeCredentials as V1Alpha1ExchangeCredentials,
+    hyperopt::Hyperopt as V1Alpha1Hyperopt,
+    strategy::Strategy as V1Alpha1Strategy,
+};
+use crate::crd::v1alpha2::bot::Bot as V1Alpha2Bot;
+use crate::error::{ControllerError, Result};
 
 
+/// Set the conversion strategy of a CRD served at several versions
+///
+/// The conversion is served by the webhook at `/convert`, the client config defaults to the
+/// webhook service of the Helm chart. The API server can't call the webhook without its CA,
+/// so until there is one only the stored version is served and nothing is converted.
+///
+/// # Arguments
+/// * `crd` - The merged CRD
+/// * `ca_bundle` - The CA bundle of the webhook certificate, if known
+///
+/// # Returns
+/// The CRD converting between its versions through the webhook, or served at its stored
+/// version only
+fn with_webhook_conversion(mut crd: CustomResourceDefinition, ca_bundle: Option<&[u8]>) -> CustomResourceDefinition {
+    let Some(ca_bundle) = ca_bundle else {
+        for version in crd.spec.versions.iter_mut().filter(|version| !version.storage) {
+            version.served = false;
+        }
+        crd.spec.conversion = Some(CustomResourceConversion {
+            strategy: "None".to_string(),
+            webhook: None,
+        });
+        return crd;
+    };
+
+    crd.spec.conversion = Some(CustomResourceConversion {
+        strategy: "Webhook".to_string(),
+        webhook: Some(WebhookConversion {
+            conversion_review_versions: vec!["v1".to_string()],
+            client_config: Some(WebhookClientConfig {
+                ca_bundle: Some(ByteString(ca_bundle.to_vec())),
+                service: Some(ServiceReference {
+                    name: "freqtrade-operator-webhook".to_string(),
+                    namespace: "freqtrade-operator".to_string(),
+                    path: Some("/convert".to_string()),
+                    port: Some(8443),
+                }),
+                ..Default::default()
+            }),
+        }),
+    });
+    crd
+}
+
+/// Merge the versions of a kind into a single CRD
+///
+/// # Arguments
+/// * `crds` - The CRD of each version of the kind
+/// * `stored_version` - The version the kind is stored at
+fn merge(crds: Vec<CustomResourceDefinition>, stored_version: &str) -> Result<CustomResourceDefinition> {
+    merge_crds(crds, stored_version).map_err(|e| ControllerError::CrdGenerationError(e.to_string()))
+}
+
 /// Generate the CRDs for the operator
-pub fn generate_crds() {
-    for crd in vec![
-        merge_crds(vec![V1Alpha1Bot::crd()], "v1alpha1").expect("failed to merge Bot CRDs"),
-    ] {
-        println!("---");
-        println!("{}", serde_norway::to_string(&crd).unwrap());
+///
+/// # Arguments
+/// * `ca_bundle` - The CA bundle of the conversion webhook's certificate, without which Bots
+///   are only served at v1alpha1
+///
+/// # Returns
+/// A Result containing the CRD of every kind, with all of its versions
+pub fn generate_crds(ca_bundle: Option<&[u8]>) -> Result<Vec<CustomResourceDefinition>> {
+    Ok(vec![
+        with_webhook_conversion(merge(vec![V1Alpha1Bot::crd(), V1Alpha2Bot::crd()], "v1alpha1")?, ca_bundle),
+        merge(vec![V1Alpha1BotFleet::crd()], "v1alpha1")?,
+        merge(vec![V1Alpha1Strategy::crd()], "v1alpha1")?,
+        merge(vec![V1Alpha1ExchangeCredentials::crd()], "v1alpha1")?,
+        merge(vec![V1Alpha1Backtest::crd()], "v1alpha1")?,
+        merge(vec![V1Alpha1Hyperopt::crd()], "v1alpha1")?,
+        merge(vec![V1Alpha1DataDownload::crd()], "v1alpha1")?,
+    ])
+}
+
+/// Serialize a CRD to YAML
+///
+/// # Arguments
+/// * `crd` - The CRD to serialize
+pub fn crd_to_yaml(crd: &CustomResourceDefinition) -> Result<String> {
+    serde_norway::to_string(crd).map_err(|e| ControllerError::CrdGenerationError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_kind_is_generated_once() {
+        let kinds: Vec<_> = generate_crds(None).unwrap().into_iter().map(|crd| crd.spec.names.kind).collect();
+
+        assert_eq!(kinds, vec!["Bot", "BotFleet", "Strategy", "ExchangeCredentials", "Backtest", "Hyperopt", "DataDownload"]);
+    }
+
+    #[test]
+    fn bot_is_served_at_both_versions_and_stored_at_v1alpha1() {
+        let crds = generate_crds(Some(b"ca")).unwrap();
+        let bot = crds.iter().find(|crd| crd.spec.names.kind == "Bot").unwrap();
+
+        let versions: Vec<_> = bot.spec.versions.iter().map(|version| (version.name.as_str(), version.served, version.storage)).collect();
+        assert_eq!(versions, vec![("v1alpha1", true, true), ("v1alpha2", true, false)]);
+
+        let conversion = bot.spec.conversion.as_ref().unwrap();
+        assert_eq!(conversion.strategy, "Webhook");
+        let client_config = conversion.webhook.as_ref().unwrap().client_config.as_ref().unwrap();
+        assert_eq!(client_config.ca_bundle, Some(ByteString(b"ca".to_vec())));
+        assert_eq!(client_config.service.as_ref().unwrap().path.as_deref(), Some("/convert"));
+    }
+
+    #[test]
+    fn bot_is_only_served_at_v1alpha1_without_a_ca() {
+        let crds = generate_crds(None).unwrap();
+        let bot = crds.iter().find(|crd| crd.spec.names.kind == "Bot").unwrap();
+
+        let versions: Vec<_> = bot.spec.versions.iter().map(|version| (version.name.as_str(), version.served, version.storage)).collect();
+        assert_eq!(versions, vec![("v1alpha1", true, true), ("v1alpha2", false, false)]);
+
+        let conversion = bot.spec.conversion.as_ref().unwrap();
+        assert_eq!(conversion.strategy, "None");
+        assert!(conversion.webhook.is_none());
+    }
+
+    #[test]
+    fn single_version_kinds_have_no_conversion() {
+        for crd in generate_crds(None).unwrap().into_iter().filter(|crd| crd.spec.names.kind != "Bot") {
+            assert_eq!(crd.spec.versions.len(), 1, "{}", crd.spec.names.kind);
+            assert!(crd.spec.conversion.is_none(), "{}", crd.spec.names.kind);
+        }
     }
 }
@@ -0,0 +1,55 @@
+use kube::CustomResource;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+#[kube(
+    kind = "Backtest",
+    group = "freqtrade.io",
+    version = "v1alpha1",
+    status = "BacktestStatus",
+    doc = "Backtest runs a Freqtrade backtest of a Bot as a Kubernetes Job.",
+    derive = "PartialEq",
+    printcolumn = r#"{"name":"Bot", "type":"string", "description":"Bot being backtested", "jsonPath":".spec.botRef.name"}"#,
+    printcolumn = r#"{"name":"Phase", "type":"string", "description":"Current phase of the resource", "jsonPath":".status.phase"}"#,
+    printcolumn = r#"{"name":"Completed", "type":"date", "description":"When the backtest completed", "jsonPath":".status.completionTime"}"#,
+    namespaced
+)]
+#[serde(rename_all = "camelCase")]
+pub struct BacktestSpec {
+    /// Reference to the Bot in the same namespace whose image, config, strategy and data are backtested
+    pub bot_ref: BacktestBotRef,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The timerange to backtest, e.g. `20240101-20240301`
+    pub timerange: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The timeframe to backtest, overrides the timeframe of the strategy
+    pub timeframe: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The maximum number of open trades, overrides the bot's config
+    pub max_open_trades: Option<i32>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct BacktestBotRef {
+    /// The name of the Bot
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BacktestStatus {
+    /// The phase of the backtest Job, one of `pending`, `running`, `succeeded` or `failed`
+    pub phase: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Details about the current phase
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// When the backtest Job completed
+    pub completion_time: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The path of the results file, relative to the root of the Bot's PVC
+    pub results_path: Option<String>,
+    pub last_updated: Option<DateTime<Utc>>,
+}
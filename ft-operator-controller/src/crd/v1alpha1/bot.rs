@@ -9,7 +9,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::crd::v1alpha1::common::SecretItem;
+use crate::crd::v1alpha1::common::{Condition, SecretItem, deserialize_secret_item_or_string, secret_item_or_string_schema};
 
 #[derive(CustomResource, Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
 #[kube(
@@ -21,7 +21,12 @@ use crate::crd::v1alpha1::common::SecretItem;
     derive = "PartialEq",
     printcolumn = r#"{"name":"Phase", "type":"string", "description":"Current phase of the resource", "jsonPath":".status.phase"}"#,
     printcolumn = r#"{"name":"Exchange", "type":"string", "description":"Exchange the bot is trading on", "jsonPath":".spec.exchange"}"#,
+    printcolumn = r#"{"name":"Open Trades", "type":"integer", "description":"Number of open trades, when status reporting is enabled", "jsonPath":".status.openTrades"}"#,
+    printcolumn = r#"{"name":"Profit", "type":"number", "description":"Absolute profit of the closed trades, when status reporting is enabled", "jsonPath":".status.profitAbs"}"#,
     printcolumn = r#"{"name":"Last Updated", "type":"date", "description":"Last time the resource was updated", "jsonPath":".status.lastUpdated"}"#,
+    printcolumn = r#"{"name":"Config Hash", "type":"string", "description":"Hash of the config the bot's Deployment runs", "jsonPath":".status.appliedConfigHash", "priority":1}"#,
+    printcolumn = r#"{"name":"API Endpoint", "type":"string", "description":"URL the bot's API is reachable at", "jsonPath":".status.apiEndpoint", "priority":1}"#,
+    printcolumn = r#"{"name":"Version", "type":"string", "description":"Version of freqtrade the bot runs", "jsonPath":".status.freqtradeVersion", "priority":1}"#,
     namespaced
 )]
 #[serde(rename_all = "camelCase")]
@@ -31,6 +36,9 @@ pub struct BotSpec {
     #[serde(default = "default_database")]
     /// Database URL to use for the bot
     pub database: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// PostgreSQL provisioning via CloudNativePG, takes precedence over `database` when enabled
+    pub postgres: Option<BotPostgresSpec>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[schemars(schema_with = "any_nested_object_schema")]
     /// Configuration for the bot.
@@ -58,13 +66,40 @@ pub struct BotSpec {
     #[serde(default)]
     /// Deployment resource additional configuration
     pub deployment: BotDeploymentSpec,
+    #[serde(default)]
+    /// Market data configuration for the bot
+    pub data: BotDataSpec,
+    #[serde(default)]
+    /// Monitoring configuration for the bot
+    pub monitoring: BotMonitoringSpec,
+    #[serde(default)]
+    /// External message signals consumed by the bot
+    pub signals: BotSignalsSpec,
+    #[serde(default)]
+    /// Notification configuration for the bot
+    pub notifications: BotNotificationsSpec,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// FreqUI deployment managed alongside the bot
+    pub ui: Option<BotUiSpec>,
+    #[serde(default)]
+    /// Lifecycle configuration for the bot
+    pub lifecycle: BotLifecycleSpec,
+    #[serde(default)]
+    /// Whether the bot is suspended, scaling its Deployment to zero while keeping its other resources
+    pub suspend: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Name of the resources created for the bot instead of the bot's name. Can not be changed once the bot exists
+    pub name_override: Option<String>,
+    #[serde(default)]
+    /// Periodic reporting of the bot's trades in its status, through its API
+    pub status_reporting: BotStatusReportingSpec,
 }
 
-fn default_database() -> String {
+pub(crate) fn default_database() -> String {
     "sqlite:///database.db".to_string()
 }
 
-fn any_nested_object_schema(_: &mut schemars::generate::SchemaGenerator) -> schemars::Schema {
+pub(crate) fn any_nested_object_schema(_: &mut schemars::generate::SchemaGenerator) -> schemars::Schema {
     serde_json::from_value(serde_json::json!({
         "type": "object",
         "additionalProperties": {
@@ -79,19 +114,113 @@ fn any_nested_object_schema(_: &mut schemars::generate::SchemaGenerator) -> sche
 #[serde(rename_all = "camelCase")]
 pub struct BotStatus {
     pub phase: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// A machine-readable reason for the bot's phase, e.g. `CrashLoopBackOff`
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// A human-readable message with details about the bot's phase
+    pub message: Option<String>,
     pub last_updated: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The latest observations of the bot's state
+    pub conditions: Option<Vec<Condition>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The number of ready pods of the bot's Deployment
+    pub ready_replicas: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The number of pods of the bot's Deployment running its latest spec
+    pub updated_replicas: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The name of the newest running pod of the bot
+    pub pod_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The config hash the bot's Deployment was last rolled out with
+    pub applied_config_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The hash of the strategy source the bot runs
+    pub strategy_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The Secret holding the generated API credentials of the bot
+    pub api_credentials_secret: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The URL the bot's API is reachable at, through its LoadBalancer or the cluster DNS
+    pub api_endpoint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The version of freqtrade the bot runs, as reported by its API
+    pub freqtrade_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The pod template hash of the pod the freqtrade version was read from
+    pub version_pod_template_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The number of open trades of the bot, when status reporting is enabled
+    pub open_trades: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The number of closed trades of the bot, when status reporting is enabled
+    pub closed_trades: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The absolute profit of the bot's closed trades in the stake currency, when status reporting is enabled
+    pub profit_abs: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The profit ratio of the bot's closed trades, when status reporting is enabled
+    pub profit_ratio: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Last time the trades were queried from the bot's API
+    pub trades_reported_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[derive(Default)]
+pub struct BotPostgresSpec {
+    /// Whether to use a CloudNativePG Cluster as the bot's database
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Reference to an existing CloudNativePG Cluster in the same namespace
+    pub cluster_ref: Option<PostgresClusterRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Settings for the Cluster created by the operator when no `clusterRef` is given
+    pub managed: Option<ManagedPostgresSpec>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PostgresClusterRef {
+    /// The name of the CloudNativePG Cluster
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ManagedPostgresSpec {
+    /// The storage size of the Cluster, defaults to `1Gi`
+    pub storage: String,
+    /// The number of instances in the Cluster, defaults to `1`
+    pub instances: i32,
+}
+
+impl Default for ManagedPostgresSpec {
+    fn default() -> Self {
+        ManagedPostgresSpec {
+            storage: "1Gi".to_string(),
+            instances: 1,
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase", default)]
+#[derive(Default)]
 pub struct BotImageSpec {
     #[serde(skip_serializing_if = "Option::is_none")]
-    /// Repository to pull the image from
+    /// Repository to pull the image from, defaults to the operator's `controller.default_image_repo`
     pub repository: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    /// Tag to pull
+    /// Tag to pull, defaults to the operator's `controller.default_image_tag`
     pub tag: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    /// Digest to pull (e.g. `sha256:...`), takes precedence over the tag when set
+    pub digest: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     /// Image pull policy
     pub pull_policy: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -99,27 +228,25 @@ pub struct BotImageSpec {
     pub pull_secrets: Option<Vec<String>>,
 }
 
-impl Default for BotImageSpec {
-    fn default() -> Self {
-        BotImageSpec {
-            repository: Some("freqtradeorg/freqtrade".to_string()),
-            tag: Some("stable".to_string()),
-            pull_policy: None,
-            pull_secrets: None,
-        }
-    }
-}
-
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase", default)]
 #[derive(Default)]
 pub struct BotSecrets {
     /// Exchange secrets to use for the bot
     pub exchange: Option<ExchangeSecrets>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Reference to ExchangeCredentials to use instead of inline exchange secrets
+    pub exchange_ref: Option<ExchangeCredentialsRef>,
     /// API secrets to use for the bot
     pub api: Option<ApiSecrets>,
     /// Telegram secrets to use for the bot
     pub telegram: Option<TelegramSecrets>,
+    /// Discord secrets to use for the bot
+    pub discord: Option<WebhookUrlSecrets>,
+    /// Slack secrets to use for the bot
+    pub slack: Option<WebhookUrlSecrets>,
+    /// Whether to roll the bot when a Secret it references changes
+    pub rollout_on_change: bool,
 }
 
 
@@ -138,14 +265,31 @@ pub struct ApiSecrets {
 }
 
 
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct ExchangeCredentialsRef {
+    /// The name of the ExchangeCredentials
+    pub name: String,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookUrlSecrets {
+    /// The webhook URL to send messages to
+    pub webhook_url: SecretItem,
+}
+
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase", default)]
 #[derive(Default)]
 pub struct TelegramSecrets {
     /// The Telegram token
     pub token: Option<SecretItem>,
-    /// The Telegram chat ID to send messages to
-    pub chat_id: Option<String>,
+    #[serde(deserialize_with = "deserialize_secret_item_or_string")]
+    #[schemars(schema_with = "secret_item_or_string_schema")]
+    /// The Telegram chat ID to send messages to, either a plain string or a secret item
+    pub chat_id: Option<SecretItem>,
 }
 
 
@@ -167,7 +311,8 @@ pub struct ExchangeSecrets {
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BotStrategySpec {
-    /// The strategy class name to use
+    #[serde(default)]
+    /// The strategy class name to use, taken from the Strategy when `strategyRef` is set
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     /// The ConfigMap to pull the source from, containing the `strategy.py` key
@@ -175,6 +320,20 @@ pub struct BotStrategySpec {
     #[serde(skip_serializing_if = "Option::is_none")]
     /// The source code for the strategy
     pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Reference to a Strategy in the same namespace to use instead of an inline strategy
+    pub strategy_ref: Option<StrategyRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "any_nested_object_schema")]
+    /// The strategy parameters file, e.g. exported by hyperopt, written next to the strategy as
+    /// `<name>.json` so freqtrade loads it. Can not be used with `configMapName`
+    pub parameters: Option<BTreeMap<String, Value>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct StrategyRef {
+    /// The name of the Strategy
+    pub name: String,
 }
 
 
@@ -189,6 +348,9 @@ pub struct BotModelSpec {
     #[serde(skip_serializing_if = "Option::is_none")]
     /// The source code for the model
     pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The compute resources for the bot's container when a model is used, takes precedence over `deployment.resources`
+    pub resources: Option<ResourceRequirements>,
 }
 
 
@@ -198,6 +360,7 @@ impl Default for BotModelSpec {
             name: "LightGBMRegressor".to_string(),
             config_map_name: None,
             source: None,
+            resources: None,
         }
     }
 }
@@ -212,6 +375,11 @@ pub struct BotApiSpec {
     pub host: String,
     /// The port to bind the API to
     pub port: u16,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    /// The origins allowed to make cross-origin requests to the API, e.g. a FreqUI host
+    pub cors_origins: Vec<String>,
+    /// Whether the controller pings the API to report the bot as running, defaults to `true`
+    pub health_check: bool,
 }
 
 impl Default for BotApiSpec {
@@ -220,6 +388,8 @@ impl Default for BotApiSpec {
             enabled: true,
             host: "0.0.0.0".to_string(),
             port: 8080,
+            cors_origins: vec![],
+            health_check: true,
         }
     }
 }
@@ -258,7 +428,7 @@ pub struct BotServicePort {
     pub name: String,
     /// The port to expose
     pub port: u16,
-    /// The target port to forward to
+    /// The target port to forward to, a container port name or number
     pub target_port: String,
 }
 
@@ -278,6 +448,10 @@ pub struct BotPvcSpec {
     pub storage_class: Option<String>,
     /// The size of the PVC, defaults to `1Gi`
     pub size: String,
+    /// Whether to bootstrap the freqtrade `user_data` directory on the PVC with an init container, defaults to `true`
+    pub bootstrap: bool,
+    /// What happens to the PVC when the bot is deleted, defaults to `Delete`
+    pub deletion_policy: PvcDeletionPolicy,
 }
 
 impl Default for BotPvcSpec {
@@ -288,16 +462,241 @@ impl Default for BotPvcSpec {
             labels: None,
             storage_class: None,
             size: "1Gi".to_string(),
+            bootstrap: true,
+            deletion_policy: PvcDeletionPolicy::default(),
         }
     }
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub enum DeploymentUpdateStrategy {
+    /// Stop the old pod before the new one is started
+    Recreate,
+    /// Start the new pod before the old one is stopped
+    RollingUpdate,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema, Default)]
+pub enum PvcDeletionPolicy {
+    /// Delete the PVC along with the bot, waiting for it to be gone before the bot is removed
+    #[default]
+    Delete,
+    /// Keep the PVC when the bot is deleted, it is no longer owned by the bot
+    Retain,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[derive(Default)]
+pub struct BotDataSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Download candle data with an init container before the bot starts
+    pub download: Option<BotDataDownloadSpec>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[derive(Default)]
+pub struct BotDataDownloadSpec {
+    /// Whether to download data before the bot starts, requires the PVC to be enabled
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The timerange to download, in the `YYYYMMDD-YYYYMMDD` format where either side may be omitted
+    pub timerange: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    /// The timeframes to download, e.g. `5m` or `1h`
+    pub timeframes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The pairs to download, defaults to the pair whitelist in the bot's config
+    pub pairs: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[derive(Default)]
+pub struct BotSignalsSpec {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    /// The producer bots to consume signals from
+    pub producers: Vec<BotProducerSpec>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    /// The namespaces of the consumer bots allowed to consume the bot's signals, `*` allows every
+    /// namespace. Consumer bots in the bot's own namespace are always allowed.
+    pub allowed_consumer_namespaces: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BotProducerSpec {
+    /// Reference to the producer Bot
+    pub bot_ref: BotRef,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BotRef {
+    /// The name of the Bot
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The namespace of the Bot, defaults to the namespace of the referencing resource
+    pub namespace: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[derive(Default)]
+pub struct BotMonitoringSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The freqtrade metrics exporter sidecar
+    pub exporter: Option<BotExporterSpec>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BotExporterSpec {
+    /// Whether the exporter sidecar is enabled or not
+    pub enabled: bool,
+    /// The exporter image to use
+    pub image: String,
+    /// The port the exporter serves metrics on
+    pub port: u16,
+}
+
+impl Default for BotExporterSpec {
+    fn default() -> Self {
+        BotExporterSpec {
+            enabled: true,
+            image: "kavurankayastudios/freqtrade_exporter:latest".to_string(),
+            port: 9090,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BotUiSpec {
+    /// Whether the FreqUI deployment is enabled or not
+    pub enabled: bool,
+    /// The FreqUI image to use
+    pub image: String,
+    /// The port FreqUI serves on
+    pub port: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Ingress exposing FreqUI
+    pub ingress: Option<BotUiIngressSpec>,
+}
+
+impl Default for BotUiSpec {
+    fn default() -> Self {
+        BotUiSpec {
+            enabled: true,
+            image: "freqtradeorg/frequi:latest".to_string(),
+            port: 80,
+            ingress: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BotUiIngressSpec {
+    /// The host to serve FreqUI on
+    pub host: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The IngressClass to use, defaults to the cluster default
+    pub class_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Additional annotations to add to the ingress
+    pub annotations: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The name of the Secret holding the TLS certificate for the host
+    pub tls_secret_name: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BotLifecycleSpec {
+    /// Whether to stop the bot through its API before it is deleted, so it stops trading
+    /// before its Deployment is torn down, defaults to `false`
+    pub stop_on_delete: bool,
+    /// How long to wait for the bot's open trades to close after stopping it, defaults to `60`
+    pub stop_timeout_seconds: u64,
+}
+
+impl Default for BotLifecycleSpec {
+    fn default() -> Self {
+        BotLifecycleSpec {
+            stop_on_delete: false,
+            stop_timeout_seconds: 60,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BotStatusReportingSpec {
+    /// Whether to report the bot's open and closed trades and profit in its status, defaults to `false`
+    pub enabled: bool,
+    /// How often the trades are queried from the bot's API, defaults to `60`, raised to `30` when shorter
+    pub interval_seconds: u64,
+}
+
+impl Default for BotStatusReportingSpec {
+    fn default() -> Self {
+        BotStatusReportingSpec {
+            enabled: false,
+            interval_seconds: 60,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[derive(Default)]
+pub struct BotNotificationsSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// The freqtrade webhook notification
+    pub webhook: Option<BotWebhookNotificationSpec>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BotWebhookNotificationSpec {
+    #[serde(default = "default_webhook_enabled")]
+    /// Whether webhook notifications are enabled or not
+    pub enabled: bool,
+    /// The URL to send the webhook notifications to
+    pub url: SecretItem,
+    #[serde(default)]
+    /// The format of the webhook payload
+    pub format: WebhookFormat,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The number of times to retry a failed webhook call
+    pub retries: Option<u32>,
+}
+
+fn default_webhook_enabled() -> bool {
+    true
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    /// Send the payload as form data
+    #[default]
+    Form,
+    /// Send the payload as JSON
+    Json,
+    /// Send the payload as raw text
+    Raw,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase", default)]
 #[derive(Default)]
 pub struct BotDeploymentSpec {
     #[serde(skip_serializing_if = "Option::is_none")]
-    /// A custom command to run in the container, overrides the default command
+    /// A custom command to run in the container, overrides the default command.
+    /// A `$CMD` entry is replaced with the default command
     pub command: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     /// Additional annotations to add to the deployment
@@ -338,6 +737,20 @@ pub struct BotDeploymentSpec {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     /// Additional environment variables to add to the deployment
     pub env: Vec<EnvVar>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// How the deployment's pod is replaced, defaults to `Recreate` when the PVC is enabled
+    /// since it can only be mounted by one pod, and `RollingUpdate` otherwise
+    pub update_strategy: Option<DeploymentUpdateStrategy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The number of old ReplicaSets kept for rollbacks, defaults to the operator's default of 3
+    pub revision_history_limit: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// How long the bot's pod must be ready before it counts as available, defaults to 0
+    pub min_ready_seconds: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// How long the Deployment may take to become available before it is reported as failed, defaults
+    /// to 600, or 3600 when `model` is set since FreqAI bots take longer to start
+    pub progress_deadline_seconds: Option<i32>,
 }
 
 
@@ -352,6 +765,8 @@ pub enum BotPhase {
     Error,
     // The bot is being deleted
     Deleting,
+    // The bot is suspended
+    Suspended,
 }
 
 impl Display for BotPhase {
@@ -361,6 +776,7 @@ impl Display for BotPhase {
             BotPhase::Running => write!(f, "running"),
             BotPhase::Error => write!(f, "error"),
             BotPhase::Deleting => write!(f, "deleting"),
+            BotPhase::Suspended => write!(f, "suspended"),
         }
     }
 }
\ No newline at end of file
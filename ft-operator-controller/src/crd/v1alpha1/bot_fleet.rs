@@ -0,0 +1,76 @@
+use kube::CustomResource;
+use std::collections::BTreeMap;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::crd::v1alpha1::bot::{any_nested_object_schema, BotSpec, ExchangeCredentialsRef};
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+#[kube(
+    kind = "BotFleet",
+    group = "freqtrade.io",
+    version = "v1alpha1",
+    status = "BotFleetStatus",
+    doc = "BotFleet creates and keeps in sync a set of Bots from a common template.",
+    derive = "PartialEq",
+    printcolumn = r#"{"name":"Ready", "type":"integer", "description":"Number of running Bots", "jsonPath":".status.readyBots"}"#,
+    printcolumn = r#"{"name":"Total", "type":"integer", "description":"Number of Bots in the fleet", "jsonPath":".status.totalBots"}"#,
+    printcolumn = r#"{"name":"Last Updated", "type":"date", "description":"Last time the resource was updated", "jsonPath":".status.lastUpdated"}"#,
+    namespaced
+)]
+#[serde(rename_all = "camelCase")]
+pub struct BotFleetSpec {
+    /// The template of the Bots in the fleet
+    pub template: BotFleetTemplate,
+    #[serde(default)]
+    /// The instances of the fleet, each creating a Bot from the template
+    pub instances: Vec<BotFleetInstance>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct BotFleetTemplate {
+    #[serde(default)]
+    /// Labels and annotations of the Bots
+    pub metadata: BotFleetTemplateMetadata,
+    /// The spec of the Bots
+    pub spec: BotSpec,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema, Default)]
+pub struct BotFleetTemplateMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Labels to add to the Bots
+    pub labels: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Annotations to add to the Bots
+    pub annotations: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BotFleetInstance {
+    /// The suffix of the Bot's name, the Bot is named `<fleet>-<nameSuffix>`
+    pub name_suffix: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The exchange of the Bot, overrides the template's exchange
+    pub exchange: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Reference to the ExchangeCredentials of the Bot, replaces the template's exchange secrets
+    pub exchange_ref: Option<ExchangeCredentialsRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "any_nested_object_schema")]
+    /// A JSON merge patch applied to the template's config, a `null` value removes the key
+    pub config: Option<BTreeMap<String, Value>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BotFleetStatus {
+    /// The number of running Bots in the fleet
+    pub ready_bots: u32,
+    /// The number of Bots in the fleet
+    pub total_bots: u32,
+    pub last_updated: Option<DateTime<Utc>>,
+}
@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
 use schemars::JsonSchema;
 
 
@@ -12,10 +13,92 @@ pub enum SecretItem {
     SecretKeyRef { secret_key_ref: SecretKeyRef },
 }
 
+/// Deserialize an optional SecretItem, also accepting a plain string as an inline value
+///
+/// This keeps fields that used to be plain strings compatible with existing resources.
+pub fn deserialize_secret_item_or_string<'de, D>(deserializer: D) -> Result<Option<SecretItem>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum SecretItemOrString {
+        String(String),
+        SecretItem(SecretItem),
+    }
+
+    Ok(Option::<SecretItemOrString>::deserialize(deserializer)?.map(|item| match item {
+        SecretItemOrString::String(value) => SecretItem::Value { value },
+        SecretItemOrString::SecretItem(secret_item) => secret_item,
+    }))
+}
+
+/// The schema for a SecretItem that may also be given as a plain string
+///
+/// A structural schema can't express a string or an object, so the field is left
+/// unvalidated by the API server and checked by the admission webhook instead.
+pub fn secret_item_or_string_schema(_: &mut schemars::generate::SchemaGenerator) -> schemars::Schema {
+    serde_json::from_value(serde_json::json!({
+        "x-kubernetes-preserve-unknown-fields": true,
+    }))
+    .unwrap()
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
 pub struct SecretKeyRef {
     /// The name of the Secret to reference
     pub name: String,
     /// The key in the Secret to reference
     pub key: String,
-}
\ No newline at end of file
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Condition {
+    #[serde(rename = "type")]
+    /// The type of the condition, e.g. `DatabaseReady`
+    pub type_: String,
+    /// The status of the condition, one of `True`, `False` or `Unknown`
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// A machine-readable reason for the condition's last transition
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// A human-readable message with details about the transition
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The last time the condition transitioned from one status to another
+    pub last_transition_time: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::crd::v1alpha1::bot::TelegramSecrets;
+
+    #[test]
+    fn literal_chat_id_round_trips() {
+        let telegram: TelegramSecrets = serde_json::from_value(json!({ "chatId": "-1001234" })).unwrap();
+        assert_eq!(telegram.chat_id, Some(SecretItem::Value { value: "-1001234".to_string() }));
+
+        // It is written back as an inline value, which reads back the same
+        let written = serde_json::to_value(&telegram).unwrap();
+        assert_eq!(written["chatId"], json!({ "value": "-1001234" }));
+        assert_eq!(serde_json::from_value::<TelegramSecrets>(written).unwrap(), telegram);
+    }
+
+    #[test]
+    fn chat_id_accepts_secret_items() {
+        let telegram: TelegramSecrets = serde_json::from_value(json!({
+            "chatId": { "secretKeyRef": { "name": "telegram", "key": "chat_id" } },
+        }))
+        .unwrap();
+        assert_eq!(telegram.chat_id, Some(SecretItem::SecretKeyRef {
+            secret_key_ref: SecretKeyRef { name: "telegram".to_string(), key: "chat_id".to_string() },
+        }));
+
+        let missing: TelegramSecrets = serde_json::from_value(json!({ "chatId": null })).unwrap();
+        assert_eq!(missing.chat_id, None);
+    }
+}
@@ -0,0 +1,73 @@
+use kube::CustomResource;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::crd::v1alpha1::bot::{BotImageSpec, ExchangeSecrets};
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+#[kube(
+    kind = "DataDownload",
+    group = "freqtrade.io",
+    version = "v1alpha1",
+    status = "DataDownloadStatus",
+    doc = "DataDownload periodically downloads candle data onto a PVC with a Kubernetes CronJob.",
+    derive = "PartialEq",
+    printcolumn = r#"{"name":"Schedule", "type":"string", "description":"Schedule of the download", "jsonPath":".spec.schedule"}"#,
+    printcolumn = r#"{"name":"Last Success", "type":"date", "description":"When the last download succeeded", "jsonPath":".status.lastSuccessfulTime"}"#,
+    namespaced
+)]
+#[serde(rename_all = "camelCase")]
+pub struct DataDownloadSpec {
+    /// The cron schedule of the download, e.g. `0 3 * * *`
+    pub schedule: String,
+    /// The exchange to download the data from
+    pub exchange: String,
+    /// The pairs to download
+    pub pairs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    /// The timeframes to download, defaults to the freqtrade default timeframes
+    pub timeframes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The timerange to download, e.g. `20240101-`
+    pub timerange: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Reference to the PVC in the same namespace to download the data onto
+    pub pvc_ref: Option<DataDownloadPvcRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Reference to the Bot in the same namespace whose PVC the data is downloaded onto, used instead of `pvcRef`
+    pub bot_ref: Option<DataDownloadBotRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The freqtrade image to run the download with, defaults to the controller's default image
+    pub image: Option<BotImageSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Exchange secrets for exchanges that require authentication to download data
+    pub exchange_secrets: Option<ExchangeSecrets>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct DataDownloadPvcRef {
+    /// The name of the PVC
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct DataDownloadBotRef {
+    /// The name of the Bot
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DataDownloadStatus {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Details about why the CronJob could not be created
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// When the download was last scheduled
+    pub last_schedule_time: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// When the download last succeeded
+    pub last_successful_time: Option<DateTime<Utc>>,
+    pub last_updated: Option<DateTime<Utc>>,
+}
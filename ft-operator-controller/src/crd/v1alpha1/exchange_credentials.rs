@@ -0,0 +1,40 @@
+use kube::CustomResource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::crd::v1alpha1::common::SecretItem;
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+#[kube(
+    kind = "ExchangeCredentials",
+    plural = "exchangecredentials",
+    group = "freqtrade.io",
+    version = "v1alpha1",
+    doc = "ExchangeCredentials are exchange secrets shared by the Bots referencing them.",
+    derive = "PartialEq",
+    printcolumn = r#"{"name":"Exchange", "type":"string", "description":"Exchange the credentials are for", "jsonPath":".spec.exchange"}"#
+)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeCredentialsSpec {
+    /// Name of the exchange the credentials are for
+    pub exchange: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    /// The namespaces of the Bots allowed to reference the credentials, `*` allows every
+    /// namespace. No Bot can reference them when empty.
+    pub allowed_namespaces: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The namespace of the Secrets referenced by `secretKeyRef`s
+    pub secret_namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The exchange API key
+    pub key: Option<SecretItem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The exchange API secret
+    pub secret: Option<SecretItem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The exchange API password
+    pub password: Option<SecretItem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The exchange userid
+    pub uid: Option<SecretItem>,
+}
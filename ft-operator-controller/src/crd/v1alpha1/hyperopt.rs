@@ -0,0 +1,76 @@
+use kube::CustomResource;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+#[kube(
+    kind = "Hyperopt",
+    group = "freqtrade.io",
+    version = "v1alpha1",
+    status = "HyperoptStatus",
+    doc = "Hyperopt runs a Freqtrade hyperparameter optimization of a Bot's strategy as a Kubernetes Job.",
+    derive = "PartialEq",
+    printcolumn = r#"{"name":"Bot", "type":"string", "description":"Bot being optimized", "jsonPath":".spec.botRef.name"}"#,
+    printcolumn = r#"{"name":"Phase", "type":"string", "description":"Current phase of the resource", "jsonPath":".status.phase"}"#,
+    printcolumn = r#"{"name":"Results", "type":"string", "description":"ConfigMap holding the best parameters", "jsonPath":".status.resultsConfigMap"}"#,
+    namespaced
+)]
+#[serde(rename_all = "camelCase")]
+pub struct HyperoptSpec {
+    /// Reference to the Bot in the same namespace whose image, config, strategy and data are optimized
+    pub bot_ref: HyperoptBotRef,
+    #[serde(default = "default_epochs")]
+    /// The number of epochs to run
+    pub epochs: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    /// The spaces to optimize, e.g. `buy`, `sell` or `roi`, defaults to the freqtrade default spaces
+    pub spaces: Vec<String>,
+    #[serde(default = "default_loss_function")]
+    /// The class name of the hyperopt loss function
+    pub loss_function: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The timerange to optimize over, e.g. `20240101-20240301`
+    pub timerange: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The maximum duration of the Job in seconds before it is terminated
+    pub job_timeout: Option<i64>,
+    #[serde(default)]
+    /// The number of retries before the Job is considered failed, retries keep the results
+    /// gathered so far on the Bot's PVC
+    pub backoff_limit: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The number of seconds a finished Job is kept for before it is deleted
+    pub ttl_seconds_after_finished: Option<i32>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct HyperoptBotRef {
+    /// The name of the Bot
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HyperoptStatus {
+    /// The phase of the hyperopt Job, one of `pending`, `running`, `succeeded` or `failed`
+    pub phase: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Details about the current phase
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// When the hyperopt Job completed
+    pub completion_time: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The name of the ConfigMap holding the best parameters found
+    pub results_config_map: Option<String>,
+    pub last_updated: Option<DateTime<Utc>>,
+}
+
+fn default_epochs() -> u32 {
+    100
+}
+
+fn default_loss_function() -> String {
+    "SharpeHyperOptLossDaily".to_string()
+}
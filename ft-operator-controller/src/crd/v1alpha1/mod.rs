@@ -1,2 +1,8 @@
 pub mod common;
-pub mod bot;
\ No newline at end of file
+pub mod bot;
+pub mod strategy;
+pub mod exchange_credentials;
+pub mod backtest;
+pub mod hyperopt;
+pub mod data_download;
+pub mod bot_fleet;
\ No newline at end of file
@@ -0,0 +1,43 @@
+use kube::CustomResource;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+#[kube(
+    kind = "Strategy",
+    group = "freqtrade.io",
+    version = "v1alpha1",
+    status = "StrategyStatus",
+    doc = "Strategy is a Freqtrade strategy shared by the Bots referencing it.",
+    derive = "PartialEq",
+    printcolumn = r#"{"name":"Class Name", "type":"string", "description":"Strategy class name", "jsonPath":".spec.className"}"#,
+    printcolumn = r#"{"name":"Phase", "type":"string", "description":"Current phase of the resource", "jsonPath":".status.phase"}"#,
+    printcolumn = r#"{"name":"Referenced By", "type":"integer", "description":"Number of Bots referencing the strategy", "jsonPath":".status.referencedBy"}"#,
+    namespaced
+)]
+#[serde(rename_all = "camelCase")]
+pub struct StrategySpec {
+    /// The strategy class name
+    pub class_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The source code for the strategy
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The ConfigMap to pull the source from, containing the `strategy.py` key
+    pub config_map_name: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StrategyStatus {
+    /// The phase of the strategy, `valid` or `invalid`
+    pub phase: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Details on why the strategy is invalid
+    pub message: Option<String>,
+    #[serde(default)]
+    /// The number of Bots referencing the strategy
+    pub referenced_by: u32,
+    pub last_updated: Option<DateTime<Utc>>,
+}
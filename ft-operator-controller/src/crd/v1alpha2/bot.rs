@@ -0,0 +1,172 @@
+use kube::CustomResource;
+use k8s_openapi::api::core::v1::{
+    Affinity, Toleration, PodSecurityContext, ResourceRequirements, SecurityContext,
+    Container, Volume, VolumeMount, EnvVar,
+};
+use std::collections::BTreeMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::crd::v1alpha1::bot::{any_nested_object_schema, default_database};
+
+// Only the deployment spec changed in v1alpha2, the other types are shared with v1alpha1
+pub use crate::crd::v1alpha1::bot::{
+    BotStatus, BotPostgresSpec, PostgresClusterRef, ManagedPostgresSpec, BotImageSpec, BotSecrets,
+    ApiSecrets, ExchangeCredentialsRef, WebhookUrlSecrets, TelegramSecrets, ExchangeSecrets,
+    BotStrategySpec, StrategyRef, BotModelSpec, BotApiSpec, BotServiceSpec, BotServicePort,
+    BotPvcSpec, BotDataSpec, BotDataDownloadSpec, BotSignalsSpec, BotProducerSpec, BotRef,
+    BotMonitoringSpec, BotExporterSpec, BotUiSpec, BotUiIngressSpec, BotNotificationsSpec,
+    BotWebhookNotificationSpec, WebhookFormat, BotPhase, PvcDeletionPolicy, BotLifecycleSpec,
+    BotStatusReportingSpec, DeploymentUpdateStrategy,
+};
+
+#[derive(CustomResource, Deserialize, Serialize, Clone, Debug, PartialEq, JsonSchema)]
+#[kube(
+    kind = "Bot",
+    group = "freqtrade.io",
+    version = "v1alpha2",
+    status = "BotStatus",
+    doc = "Bot is a specification for a Freqtrade bot running in a Kubernetes cluster.",
+    derive = "PartialEq",
+    printcolumn = r#"{"name":"Phase", "type":"string", "description":"Current phase of the resource", "jsonPath":".status.phase"}"#,
+    printcolumn = r#"{"name":"Exchange", "type":"string", "description":"Exchange the bot is trading on", "jsonPath":".spec.exchange"}"#,
+    printcolumn = r#"{"name":"Open Trades", "type":"integer", "description":"Number of open trades, when status reporting is enabled", "jsonPath":".status.openTrades"}"#,
+    printcolumn = r#"{"name":"Profit", "type":"number", "description":"Absolute profit of the closed trades, when status reporting is enabled", "jsonPath":".status.profitAbs"}"#,
+    printcolumn = r#"{"name":"Last Updated", "type":"date", "description":"Last time the resource was updated", "jsonPath":".status.lastUpdated"}"#,
+    printcolumn = r#"{"name":"Config Hash", "type":"string", "description":"Hash of the config the bot's Deployment runs", "jsonPath":".status.appliedConfigHash", "priority":1}"#,
+    printcolumn = r#"{"name":"API Endpoint", "type":"string", "description":"URL the bot's API is reachable at", "jsonPath":".status.apiEndpoint", "priority":1}"#,
+    printcolumn = r#"{"name":"Version", "type":"string", "description":"Version of freqtrade the bot runs", "jsonPath":".status.freqtradeVersion", "priority":1}"#,
+    namespaced
+)]
+#[serde(rename_all = "camelCase")]
+pub struct BotSpec {
+    /// Name of the exchange the bot is trading on.
+    pub exchange: String,
+    #[serde(default = "default_database")]
+    /// Database URL to use for the bot
+    pub database: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// PostgreSQL provisioning via CloudNativePG, takes precedence over `database` when enabled
+    pub postgres: Option<BotPostgresSpec>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "any_nested_object_schema")]
+    /// Configuration for the bot.
+    pub config: Option<BTreeMap<String, Value>>,
+    /// Strategy to use for the bot
+    pub strategy: BotStrategySpec,
+    /// Model to use for the bot
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<BotModelSpec>,
+    #[serde(default)]
+    /// Image to use for the bot
+    pub image: BotImageSpec,
+    #[serde(default)]
+    /// Secrets to use for the bot
+    pub secrets: BotSecrets,
+    #[serde(default)]
+    /// API configuration for the bot
+    pub api: BotApiSpec,
+    #[serde(default)]
+    /// Service resource additional configuration
+    pub service: BotServiceSpec,
+    #[serde(default)]
+    /// PersistentVolumeClaim resource configuration
+    pub pvc: BotPvcSpec,
+    #[serde(default)]
+    /// Deployment resource additional configuration
+    pub deployment: BotDeploymentSpec,
+    #[serde(default)]
+    /// Market data configuration for the bot
+    pub data: BotDataSpec,
+    #[serde(default)]
+    /// Monitoring configuration for the bot
+    pub monitoring: BotMonitoringSpec,
+    #[serde(default)]
+    /// External message signals consumed by the bot
+    pub signals: BotSignalsSpec,
+    #[serde(default)]
+    /// Notification configuration for the bot
+    pub notifications: BotNotificationsSpec,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// FreqUI deployment managed alongside the bot
+    pub ui: Option<BotUiSpec>,
+    #[serde(default)]
+    /// Lifecycle configuration for the bot
+    pub lifecycle: BotLifecycleSpec,
+    #[serde(default)]
+    /// Whether the bot is suspended, scaling its Deployment to zero while keeping its other resources
+    pub suspend: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Name of the resources created for the bot instead of the bot's name. Can not be changed once the bot exists
+    pub name_override: Option<String>,
+    #[serde(default)]
+    /// Periodic reporting of the bot's trades in its status, through its API
+    pub status_reporting: BotStatusReportingSpec,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+#[derive(Default)]
+pub struct BotDeploymentSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// A custom command to run in the container, overrides the default command.
+    /// A `$CMD` entry is replaced with the default command
+    pub command: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Additional annotations to add to the deployment
+    pub annotations: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Additional annotations to add to the deployment's pods
+    pub pod_annotations: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Additional labels to add to the deployment
+    pub labels: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Node selector to use for the deployment
+    pub node_selector: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The compute resource constraints and requests for the deployment
+    pub resources: Option<ResourceRequirements>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The affinity rules for the deployment
+    pub affinity: Option<Affinity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The tolerations for the deployment
+    pub tolerations: Option<Vec<Toleration>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The pod's security context
+    pub pod_security_context: Option<PodSecurityContext>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The container's security context
+    pub security_context: Option<SecurityContext>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    /// Additional containers to add to the deployment
+    pub containers: Vec<Container>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    /// Additional init containers to add to the deployment
+    pub init_containers: Vec<Container>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    /// Additional volumes to add to the deployment
+    pub volumes: Vec<Volume>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    /// Additional volume mounts to add to the pod's main container
+    pub volume_mounts: Vec<VolumeMount>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    /// Additional environment variables to add to the deployment
+    pub env: Vec<EnvVar>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// How the deployment's pod is replaced, defaults to `Recreate` when the PVC is enabled
+    /// since it can only be mounted by one pod, and `RollingUpdate` otherwise
+    pub update_strategy: Option<DeploymentUpdateStrategy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The number of old ReplicaSets kept for rollbacks, defaults to the operator's default of 3
+    pub revision_history_limit: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// How long the bot's pod must be ready before it counts as available, defaults to 0
+    pub min_ready_seconds: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// How long the Deployment may take to become available before it is reported as failed, defaults
+    /// to 600, or 3600 when `model` is set since FreqAI bots take longer to start
+    pub progress_deadline_seconds: Option<i32>,
+}
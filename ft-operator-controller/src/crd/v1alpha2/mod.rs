@@ -0,0 +1 @@
+pub mod bot;
\ No newline at end of file
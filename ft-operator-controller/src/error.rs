@@ -13,8 +13,28 @@ pub enum ControllerError {
     MissingObjectKeyError(&'static str),
     #[error("finalizer error: {0}")]
     FinalizerError(String),
+    #[error("cleanup timed out: {0}")]
+    CleanupTimeoutError(String),
+    #[error("conflict with an existing object: {0}")]
+    ConflictError(String),
+    #[error("status update conflicted: {0}")]
+    StatusConflictError(String),
+    #[error("failed to generate CRD: {0}")]
+    CrdGenerationError(String),
+    #[error("failed to serialize: {0}")]
+    SerializationError(#[from] serde_json::Error),
+    #[error("invalid manifest: {0}")]
+    InvalidManifestError(String),
     #[error("unknown error: {0}")]
     UnknownError(String),
 }
 
+impl ControllerError {
+    /// Whether the error is transient and the reconcile can be retried as is, rather than a
+    /// failure of the object
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ControllerError::StatusConflictError(_))
+    }
+}
+
 pub type Result<T> = result::Result<T, ControllerError>;
\ No newline at end of file
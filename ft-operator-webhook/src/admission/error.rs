@@ -7,8 +7,57 @@ pub enum AdmissionError {
     InvalidKind(String, String),
     #[error("invalid version: {0} for {1}")]
     InvalidVersion(String, String),
+    #[error("apiVersion and kind are required")]
+    MissingTypeMeta,
     #[error("validation error: {0}")]
     ValidationError(String),
+    #[error("validation failed:\n{}", format_violations(.0))]
+    Violations(Vec<Violation>),
 }
 
-pub type AdmissionResult<T> = Result<T, AdmissionError>;
\ No newline at end of file
+/// A field of an object failing validation
+///
+/// The reason is one of the Kubernetes field error types, such as `FieldValueInvalid`, so the
+/// violations can be returned as the causes of the admission response's status.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub field: String,
+    pub reason: String,
+    pub message: String,
+}
+
+impl Violation {
+    pub fn new(field: &str, reason: &str, message: String) -> Self {
+        Self { field: field.to_string(), reason: reason.to_string(), message }
+    }
+
+    pub fn invalid(field: &str, message: String) -> Self {
+        Self::new(field, "FieldValueInvalid", message)
+    }
+
+    pub fn required(field: &str, message: String) -> Self {
+        Self::new(field, "FieldValueRequired", message)
+    }
+
+    pub fn forbidden(field: &str, message: String) -> Self {
+        Self::new(field, "FieldValueForbidden", message)
+    }
+
+    pub fn duplicate(field: &str, message: String) -> Self {
+        Self::new(field, "FieldValueDuplicate", message)
+    }
+
+    pub fn not_supported(field: &str, message: String) -> Self {
+        Self::new(field, "FieldValueNotSupported", message)
+    }
+}
+
+fn format_violations(violations: &[Violation]) -> String {
+    violations
+        .iter()
+        .map(|violation| format!("- {}", violation.message))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub type AdmissionResult<T> = Result<T, AdmissionError>;
@@ -0,0 +1,85 @@
+use serde_json::Value;
+use kube::core::DynamicObject;
+
+use crate::admission::{error::{AdmissionResult, AdmissionError}, utils::is_valid_timerange};
+
+
+fn validate_hyperopt_v1alpha1(spec: &Value) -> AdmissionResult<()> {
+    // The spaces supported by freqtrade hyperopt
+    const SPACES: &[&str] = &[
+        "all",
+        "buy",
+        "sell",
+        "enter",
+        "exit",
+        "roi",
+        "stoploss",
+        "trailing",
+        "protection",
+        "trades",
+        "default",
+    ];
+
+    if spec.get("epochs").and_then(Value::as_u64) == Some(0) {
+        return Err(AdmissionError::ValidationError("epochs must be greater than 0".to_string()));
+    }
+
+    if let Some(spaces) = spec.get("spaces").and_then(Value::as_array) {
+        for space in spaces {
+            let space = space.as_str().unwrap_or_default();
+            if !SPACES.contains(&space) {
+                return Err(AdmissionError::ValidationError(format!(
+                    "spaces `{}` is invalid, expected one of {}",
+                    space,
+                    SPACES.join(", ")
+                )));
+            }
+        }
+    }
+
+    if spec.get("lossFunction").and_then(Value::as_str).is_some_and(|loss_function| loss_function.is_empty()) {
+        return Err(AdmissionError::ValidationError("lossFunction must not be empty".to_string()));
+    }
+
+    if let Some(timerange) = spec.get("timerange").and_then(Value::as_str)
+        && !is_valid_timerange(timerange)
+    {
+        return Err(AdmissionError::ValidationError(format!(
+            "timerange `{}` is invalid, expected `YYYYMMDD-YYYYMMDD`, `YYYYMMDD-` or `-YYYYMMDD`",
+            timerange
+        )));
+    }
+
+    for field in ["jobTimeout", "backoffLimit", "ttlSecondsAfterFinished"] {
+        if spec.get(field).and_then(Value::as_i64).is_some_and(|value| value < 0) {
+            return Err(AdmissionError::ValidationError(format!("{} must not be negative", field)));
+        }
+    }
+    if spec.get("jobTimeout").and_then(Value::as_i64) == Some(0) {
+        return Err(AdmissionError::ValidationError("jobTimeout must be greater than 0".to_string()));
+    }
+
+    Ok(())
+}
+
+pub fn validate_hyperopt_crd(payload: &DynamicObject) -> AdmissionResult<()> {
+    let Some(payload_types) = payload.types.clone() else {
+        return Err(AdmissionError::MissingTypeMeta);
+    };
+
+    if payload_types.kind != "Hyperopt" {
+        return Err(AdmissionError::InvalidKind(payload_types.kind, "Hyperopt".to_string()));
+    }
+
+    let version = payload_types
+        .api_version
+        .split("/")
+        .last()
+        .unwrap_or(&payload_types.api_version);
+    let json_spec = serde_json::to_value(payload.data.get("spec")).unwrap();
+
+    match version {
+        "v1alpha1" => validate_hyperopt_v1alpha1(&json_spec),
+        _ => Err(AdmissionError::InvalidVersion(version.to_string(), "Hyperopt".to_string())),
+    }
+}
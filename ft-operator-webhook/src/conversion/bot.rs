@@ -0,0 +1,76 @@
+use serde_json::Value;
+
+use ft_operator_controller::crd::{
+    hub::bot::Bot,
+    v1alpha1::bot::Bot as V1Alpha1Bot,
+    v1alpha2::bot::Bot as V1Alpha2Bot,
+};
+
+use crate::conversion::error::{ConversionError, ConversionResult};
+
+
+/// Convert a Bot object to the desired API version
+///
+/// The object is converted to the Bot Hub first, so any pair of versions can be converted.
+///
+/// # Arguments
+/// * `object` - The Bot object to convert
+/// * `desired_api_version` - The API version to convert the object to, e.g. `freqtrade.io/v1alpha2`
+///
+/// # Returns
+/// The converted Bot object
+pub fn convert_bot(object: Value, desired_api_version: &str) -> ConversionResult<Value> {
+    let api_version = object
+        .get("apiVersion")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+
+    if api_version == desired_api_version {
+        return Ok(object);
+    }
+
+    let hub: Bot = match api_version.as_str() {
+        "freqtrade.io/v1alpha1" => serde_json::from_value::<V1Alpha1Bot>(object)?.into(),
+        "freqtrade.io/v1alpha2" => serde_json::from_value::<V1Alpha2Bot>(object)?.into(),
+        _ => return Err(ConversionError::InvalidVersion(api_version, "Bot".to_string())),
+    };
+
+    match desired_api_version {
+        "freqtrade.io/v1alpha1" => Ok(serde_json::to_value(V1Alpha1Bot::from(hub))?),
+        "freqtrade.io/v1alpha2" => Ok(serde_json::to_value(V1Alpha2Bot::from(hub))?),
+        _ => Err(ConversionError::InvalidVersion(desired_api_version.to_string(), "Bot".to_string())),
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bot(api_version: &str) -> Value {
+        json!({
+            "apiVersion": api_version,
+            "kind": "Bot",
+            "metadata": {"name": "bot", "namespace": "default"},
+            "spec": {"exchange": "binance", "strategy": {"name": "SampleStrategy", "configMapName": "strategy"}},
+        })
+    }
+
+    #[test]
+    fn bots_at_the_desired_version_are_left_as_they_are() {
+        // Not even the defaults are filled in
+        assert_eq!(convert_bot(bot("freqtrade.io/v1alpha2"), "freqtrade.io/v1alpha2").unwrap(), bot("freqtrade.io/v1alpha2"));
+    }
+
+    #[test]
+    fn unknown_versions_are_not_converted() {
+        assert_eq!(
+            convert_bot(bot("freqtrade.io/v1beta1"), "freqtrade.io/v1alpha1").unwrap_err().to_string(),
+            "invalid version: freqtrade.io/v1beta1 for Bot",
+        );
+        assert_eq!(
+            convert_bot(bot("freqtrade.io/v1alpha1"), "freqtrade.io/v1beta1").unwrap_err().to_string(),
+            "invalid version: freqtrade.io/v1beta1 for Bot",
+        );
+    }
+}
@@ -0,0 +1,14 @@
+use thiserror::Error;
+use std::result::Result;
+
+#[derive(Error, Debug)]
+pub enum ConversionError {
+    #[error("invalid kind: {0}")]
+    InvalidKind(String),
+    #[error("invalid version: {0} for {1}")]
+    InvalidVersion(String, String),
+    #[error("invalid object: {0}")]
+    InvalidObject(#[from] serde_json::Error),
+}
+
+pub type ConversionResult<T> = Result<T, ConversionError>;
\ No newline at end of file
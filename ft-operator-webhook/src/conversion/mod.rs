@@ -0,0 +1,34 @@
+pub mod error;
+pub mod bot;
+
+use serde_json::Value;
+
+use crate::conversion::{bot::convert_bot, error::{ConversionError, ConversionResult}};
+
+
+/// Convert an object of any kind served at several versions to the desired API version
+///
+/// # Arguments
+/// * `object` - The object to convert
+/// * `desired_api_version` - The API version to convert the object to
+///
+/// # Returns
+/// The converted object
+pub fn convert(object: Value, desired_api_version: &str) -> ConversionResult<Value> {
+    match object.get("kind").and_then(Value::as_str).unwrap_or_default() {
+        "Bot" => convert_bot(object, desired_api_version),
+        kind => Err(ConversionError::InvalidKind(kind.to_string())),
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn unknown_kinds_are_not_converted() {
+        let object = json!({"apiVersion": "freqtrade.io/v1alpha1", "kind": "Strategy", "metadata": {"name": "strategy"}});
+
+        assert_eq!(convert(object, "freqtrade.io/v1alpha2").unwrap_err().to_string(), "invalid kind: Strategy");
+    }
+}
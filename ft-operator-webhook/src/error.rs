@@ -3,6 +3,12 @@
 // SPDX-License-Identifier: ISC
 
 use anyhow::Error;
+use axum::{
+    extract::rejection::JsonRejection,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
 use serde::{Deserialize, Serialize};
 use std::result;
 
@@ -38,6 +44,20 @@ impl APIError {
         }
     }
 
+    pub fn not_found(path: &str) -> Self {
+        Self {
+            code: 40401,
+            message: format!("Not found: {}", path),
+        }
+    }
+
+    pub fn payload_too_large(message: &str) -> Self {
+        Self {
+            code: 41301,
+            message: message.to_string(),
+        }
+    }
+
     pub fn not_implemented() -> Self {
         Self {
             code: 50001,
@@ -46,6 +66,24 @@ impl APIError {
     }
 }
 
+// The HTTP status is the first three digits of the code
+impl IntoResponse for APIError {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.code / 100).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (status, Json(self)).into_response()
+    }
+}
+
+impl From<JsonRejection> for APIError {
+    fn from(rejection: JsonRejection) -> Self {
+        match rejection {
+            JsonRejection::MissingJsonContentType(_) => APIError::invalid_content_type(&rejection.body_text()),
+            _ if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE => APIError::payload_too_large(&rejection.body_text()),
+            _ => APIError::invalid_data_format(&rejection.body_text()),
+        }
+    }
+}
+
 impl From<Error> for APIError {
     fn from(error: Error) -> Self {
         APIError::unexpected_error(&error.to_string())
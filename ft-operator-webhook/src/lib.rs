@@ -8,4 +8,5 @@ extern crate self as ft_operator_webhook;
 pub mod router;
 pub mod server;
 pub mod error;
-pub mod admission;
\ No newline at end of file
+pub mod admission;
+pub mod conversion;
\ No newline at end of file
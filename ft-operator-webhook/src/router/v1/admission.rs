@@ -1,39 +1,223 @@
 use axum::{
-    extract::Extension,
+    extract::{rejection::JsonRejection, Extension},
     response::IntoResponse,
     routing::post,
     Router,
     Json,
 };
+use serde_json::Value;
 use std::sync::Arc;
-use kube::core::{admission::{AdmissionRequest, AdmissionResponse, AdmissionReview}, DynamicObject};
+use kube::core::{admission::{AdmissionRequest, AdmissionResponse, AdmissionReview, Operation}, response::{StatusCause, StatusDetails}, DynamicObject};
 
 use ft_operator_common::state::State;
+use ft_operator_common::telemetry::info_span;
 
-use crate::admission::bot::validate_bot_crd;
+use crate::error::APIResult;
+use crate::admission::{bot::{bot_crd_warnings, mutate_bot_crd, validate_bot_crd, validation_bypass}, error::{AdmissionError, AdmissionResult}, hyperopt::validate_hyperopt_crd};
 
 pub fn router() -> Router {
-    Router::new().route("/freqtrade.io/bot/validate", post(validate_bot_crd_endpoint))
+    Router::new()
+        .route("/freqtrade.io/bot/validate", post(validate_bot_crd_endpoint))
+        .route("/freqtrade.io/bot/mutate", post(mutate_bot_crd_endpoint))
+        .route("/freqtrade.io/hyperopt/validate", post(validate_hyperopt_crd_endpoint))
 }
 
-async fn validate_bot_crd_endpoint(Extension(_state): Extension<Arc<State>>, payload: Json<AdmissionReview<DynamicObject>>) -> impl IntoResponse {
+async fn validate_bot_crd_endpoint(
+    Extension(state): Extension<Arc<State>>,
+    payload: Result<Json<AdmissionReview<DynamicObject>>, JsonRejection>,
+) -> APIResult<impl IntoResponse> {
+    let payload = payload?;
+    let config = state.config();
+    let bypass = payload.0.request
+        .as_ref()
+        .map(|request| validation_bypass(request, &config.webhook))
+        .unwrap_or_default();
+    if !bypass.is_empty() {
+        state.diagnostics.record_validation_bypass();
+    }
+
+    review(
+        payload,
+        |object, old_object| validate_bot_crd(object, old_object, &config.webhook, &bypass),
+        |object| bot_crd_warnings(object, &config.webhook),
+    )
+}
+
+async fn mutate_bot_crd_endpoint(
+    Extension(_state): Extension<Arc<State>>,
+    payload: Result<Json<AdmissionReview<DynamicObject>>, JsonRejection>,
+) -> APIResult<impl IntoResponse> {
+    let request: AdmissionRequest<DynamicObject> = match payload?.0.try_into() {
+        Ok(request) => request,
+        Err(err) => {
+            return Ok(Json(AdmissionResponse::invalid(err.to_string()).into_review()));
+        }
+    };
+    // Defaults to allow without a patch
+    let mut response = AdmissionResponse::from(&request);
+
+    // There is nothing to default on a deletion
+    let Some(object) = request.object else {
+        return Ok(Json(response.into_review()));
+    };
+
+    match mutate_bot_crd(&object) {
+        Ok(operations) if operations.is_empty() => (),
+        Ok(operations) => {
+            response = match serde_json::from_value::<json_patch::Patch>(Value::Array(operations)) {
+                Ok(patch) => response.with_patch(patch).unwrap_or_else(|err| AdmissionResponse::invalid(err.to_string())),
+                Err(err) => AdmissionResponse::invalid(err.to_string()),
+            };
+        },
+        Err(err) => {
+            response = response.deny(err.to_string());
+        }
+    }
+
+    Ok(Json(response.into_review()))
+}
+
+async fn validate_hyperopt_crd_endpoint(
+    Extension(_state): Extension<Arc<State>>,
+    payload: Result<Json<AdmissionReview<DynamicObject>>, JsonRejection>,
+) -> APIResult<impl IntoResponse> {
+    review(payload?, |object, _| validate_hyperopt_crd(object), no_warnings)
+}
+
+fn no_warnings(_payload: &DynamicObject) -> Vec<String> {
+    vec![]
+}
+
+fn review(
+    payload: Json<AdmissionReview<DynamicObject>>,
+    validate: impl Fn(&DynamicObject, Option<&DynamicObject>) -> AdmissionResult<()>,
+    warn: impl Fn(&DynamicObject) -> Vec<String>,
+) -> APIResult<Json<AdmissionReview<DynamicObject>>> {
     let request: AdmissionRequest<DynamicObject> = match payload.0.try_into() {
         Ok(request) => request,
         Err(err) => {
-            return Json(AdmissionResponse::invalid(err.to_string()).into_review());
+            return Ok(Json(AdmissionResponse::invalid(err.to_string()).into_review()));
         }
     };
+    let _span = info_span!(
+        "admission",
+        operation = ?request.operation,
+        kind = request.kind.kind.as_str(),
+        name = request.name.as_str(),
+        namespace = request.namespace.as_deref(),
+    ).entered();
     // Defaults to allow
     let mut response = AdmissionResponse::from(&request);
-    
-    // Validate any reserved config keys, and deny if found
-    match validate_bot_crd(&request.object.unwrap()) {
+
+    // Only a deletion has no object, which the webhooks aren't registered for
+    let Some(object) = request.object else {
+        return Ok(Json(response.deny(format!("{:?} requests have no object to validate", request.operation)).into_review()));
+    };
+    // The previous version of the object, to validate the changes made by an update
+    let old_object = match request.operation {
+        Operation::Update => request.old_object.as_ref(),
+        _ => None,
+    };
+
+    // Validate the object, and deny if invalid
+    match validate(&object, old_object) {
         Ok(_) => (),
         Err(err) => {
             response = response.deny(err.to_string());
+
+            // The violations are also returned as causes, for clients to tell the fields apart
+            if let AdmissionError::Violations(violations) = err {
+                response.result.details = Some(StatusDetails {
+                    name: String::new(),
+                    group: String::new(),
+                    kind: String::new(),
+                    uid: String::new(),
+                    causes: violations
+                        .into_iter()
+                        .map(|violation| StatusCause {
+                            reason: violation.reason,
+                            message: violation.message,
+                            field: violation.field,
+                        })
+                        .collect(),
+                    retry_after_seconds: 0,
+                });
+            }
         }
     }
 
+    // Warnings are shown to the client without rejecting the object
+    let warnings = warn(&object);
+    if !warnings.is_empty() {
+        response.warnings = Some(warnings);
+    }
+
     // Convert the response to a review and return it
-    Json(response.into_review())
-}
\ No newline at end of file
+    Ok(Json(response.into_review()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use ft_operator_common::config::WebhookConfig;
+
+    fn create_review(spec: Value) -> Json<AdmissionReview<DynamicObject>> {
+        Json(serde_json::from_value(json!({
+            "apiVersion": "admission.k8s.io/v1",
+            "kind": "AdmissionReview",
+            "request": {
+                "uid": "uid",
+                "kind": {"group": "freqtrade.io", "version": "v1alpha1", "kind": "Bot"},
+                "resource": {"group": "freqtrade.io", "version": "v1alpha1", "resource": "bots"},
+                "name": "bot",
+                "namespace": "default",
+                "operation": "CREATE",
+                "userInfo": {"username": "admin"},
+                "object": {
+                    "apiVersion": "freqtrade.io/v1alpha1",
+                    "kind": "Bot",
+                    "metadata": {"name": "bot", "namespace": "default"},
+                    "spec": spec,
+                },
+            },
+        })).unwrap())
+    }
+
+    fn reviewed(spec: Value) -> Value {
+        let config = WebhookConfig::default();
+        let Json(review) = review(
+            create_review(spec),
+            |object, old_object| validate_bot_crd(object, old_object, &config, &[]),
+            |object| bot_crd_warnings(object, &config),
+        ).unwrap();
+
+        serde_json::to_value(review).unwrap()
+    }
+
+    #[test]
+    fn warnings_are_returned_in_the_review() {
+        let review = reviewed(json!({
+            "exchange": "binance",
+            "strategy": {"name": "SampleStrategy", "configMapName": "strategy"},
+            "image": {"tag": "latest"},
+        }));
+
+        assert_eq!(review["response"]["allowed"], json!(true));
+        assert_eq!(review["response"]["warnings"], json!([
+            "image.tag `latest` can change on any restart of the pod, pin a version or set image.digest",
+        ]));
+    }
+
+    #[test]
+    fn reviews_without_warnings_leave_them_out() {
+        let review = reviewed(json!({
+            "exchange": "binance",
+            "strategy": {"name": "SampleStrategy", "configMapName": "strategy"},
+        }));
+
+        assert_eq!(review["response"]["allowed"], json!(true));
+        assert_eq!(review["response"].get("warnings"), None);
+    }
+}
@@ -0,0 +1,134 @@
+use axum::{
+    extract::{rejection::JsonRejection, Extension},
+    response::IntoResponse,
+    routing::post,
+    Router,
+    Json,
+};
+use std::sync::Arc;
+use kube::core::{conversion::{ConversionRequest, ConversionResponse, ConversionReview}, Status};
+
+use ft_operator_common::state::State;
+
+use crate::conversion::{convert, error::ConversionResult};
+use crate::error::APIResult;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/convert", post(convert_endpoint))
+}
+
+async fn convert_endpoint(
+    Extension(_state): Extension<Arc<State>>,
+    payload: Result<Json<ConversionReview>, JsonRejection>,
+) -> APIResult<impl IntoResponse> {
+    let request = match ConversionRequest::from_review(payload?.0) {
+        Ok(request) => request,
+        Err(err) => {
+            return Ok(Json(ConversionResponse::invalid(Status::failure(&err.to_string(), "InvalidRequest")).into_review()));
+        }
+    };
+    let desired_api_version = request.desired_api_version.clone();
+    let objects = request.objects.clone();
+    let response = ConversionResponse::for_request(request);
+
+    // The objects are converted all at once, a single failure fails the whole review
+    let converted = objects
+        .into_iter()
+        .map(|object| convert(object, &desired_api_version))
+        .collect::<ConversionResult<Vec<_>>>();
+
+    match converted {
+        Ok(objects) => Ok(Json(response.success(objects).into_review())),
+        Err(err) => Ok(Json(response.failure(Status::failure(&err.to_string(), "ConversionFailed")).into_review())),
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::{header::CONTENT_TYPE, Request}};
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use ft_operator_common::config::AppConfig;
+
+    async fn converted(desired_api_version: &str, objects: Vec<Value>) -> Value {
+        let router = router().layer(Extension(Arc::new(State::new(AppConfig::default()))));
+        let review = json!({
+            "apiVersion": "apiextensions.k8s.io/v1",
+            "kind": "ConversionReview",
+            "request": {"uid": "uid", "desiredAPIVersion": desired_api_version, "objects": objects},
+        });
+        let request = Request::post("/convert")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(review.to_string()))
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+
+        serde_json::from_slice::<Value>(&body).unwrap()["response"].clone()
+    }
+
+    fn v1alpha1_bot(name: &str) -> Value {
+        json!({
+            "apiVersion": "freqtrade.io/v1alpha1",
+            "kind": "Bot",
+            "metadata": {
+                "name": name,
+                "namespace": "default",
+                // The pod annotations of a Bot last written at v1alpha2
+                "annotations": {"freqtrade.io/pod-annotations": r#"{"prometheus.io/scrape":"true"}"#},
+            },
+            "spec": {
+                "exchange": "binance",
+                "strategy": {"name": "SampleStrategy", "configMapName": "strategy"},
+                "deployment": {"annotations": {"team": "trading"}},
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn bots_round_trip_through_v1alpha2() {
+        let original = v1alpha1_bot("bot");
+
+        let response = converted("freqtrade.io/v1alpha2", vec![original.clone()]).await;
+        assert_eq!(response["uid"], json!("uid"));
+        assert_eq!(response["result"]["status"], json!("Success"));
+        let v1alpha2 = response["convertedObjects"][0].clone();
+        assert_eq!(v1alpha2["apiVersion"], json!("freqtrade.io/v1alpha2"));
+        assert_eq!(v1alpha2["spec"]["deployment"]["podAnnotations"], json!({"prometheus.io/scrape": "true"}));
+        assert_eq!(v1alpha2["spec"]["deployment"]["annotations"], json!({"team": "trading"}));
+        assert_eq!(v1alpha2["metadata"]["annotations"].get("freqtrade.io/pod-annotations"), None);
+
+        let response = converted("freqtrade.io/v1alpha1", vec![v1alpha2]).await;
+        assert_eq!(response["result"]["status"], json!("Success"));
+        let v1alpha1 = &response["convertedObjects"][0];
+        assert_eq!(v1alpha1["apiVersion"], json!("freqtrade.io/v1alpha1"));
+        assert_eq!(v1alpha1["metadata"], original["metadata"]);
+        assert_eq!(v1alpha1["spec"]["deployment"], original["spec"]["deployment"]);
+    }
+
+    #[tokio::test]
+    async fn objects_at_the_desired_version_are_passed_through() {
+        let original = v1alpha1_bot("bot");
+
+        let response = converted("freqtrade.io/v1alpha1", vec![original.clone()]).await;
+
+        assert_eq!(response["result"]["status"], json!("Success"));
+        assert_eq!(response["convertedObjects"], json!([original]));
+    }
+
+    #[tokio::test]
+    async fn one_bad_object_fails_the_whole_review() {
+        let mut invalid = v1alpha1_bot("invalid");
+        invalid["spec"]["exchange"] = json!(1);
+
+        let response = converted("freqtrade.io/v1alpha2", vec![v1alpha1_bot("bot"), invalid]).await;
+
+        assert_eq!(response["result"]["status"], json!("Failure"));
+        assert_eq!(response["result"]["reason"], json!("ConversionFailed"));
+        assert!(response["result"]["message"].as_str().unwrap().starts_with("invalid object: "));
+        assert_eq!(response.get("convertedObjects").cloned().unwrap_or(json!([])), json!([]));
+    }
+}
@@ -1 +1,2 @@
 pub mod admission;
+pub mod conversion;
\ No newline at end of file
@@ -2,8 +2,10 @@
 //
 // SPDX-License-Identifier: ISC
 
-use std::{sync::Arc, time::Duration, net::SocketAddr};
+use std::{sync::Arc, time::{Duration, SystemTime}, net::SocketAddr};
 use axum::{
+    extract::DefaultBodyLimit,
+    http::Uri,
     routing::get,
     Router,
     Extension,
@@ -11,13 +13,14 @@ use axum::{
 use axum::response::{Json, IntoResponse};
 use axum_server::tls_rustls::RustlsConfig;
 use serde::Serialize;
-use tokio::signal;
+use tokio::{fs, signal, time::interval};
 
 use ft_operator_common::constant::APP_NAME;
 use ft_operator_common::state::State;
-use ft_operator_common::telemetry::create_trace_layer;
+use ft_operator_common::telemetry::{create_trace_layer, error, info};
 
-use crate::router::v1::admission;
+use crate::error::APIError;
+use crate::router::v1::{admission, conversion};
 
 #[derive(Serialize)]
 struct RootResponse {
@@ -26,12 +29,20 @@ struct RootResponse {
 }
 
 pub fn create_router(app_state: Arc<State>) -> Router {
+    let max_body_bytes = app_state.config().webhook.max_body_bytes;
+    // The metrics route is outside of the Extension layer
+    let metrics_state = app_state.clone();
+
     Router::new()
         .nest("/admission", admission::router())
+        .merge(conversion::router())
+        .fallback(|uri: Uri| async move { APIError::not_found(uri.path()) })
+        .layer(DefaultBodyLimit::max(max_body_bytes))
         .layer(Extension(app_state))
         .layer(create_trace_layer())
-        // Root endpoint after the tracing layer to ensure
-        // that the root endpoint is not traced
+        // Root and metrics endpoints after the tracing layer to ensure
+        // that they are not traced
+        .route("/metrics", get(move || metrics(metrics_state.clone())))
         .route("/", get(|| async {
             let response = RootResponse {
                 name: APP_NAME,
@@ -41,19 +52,89 @@ pub fn create_router(app_state: Arc<State>) -> Router {
         }))
 }
 
+async fn metrics(state: Arc<State>) -> impl IntoResponse {
+    format!(
+        "# HELP freqtrade_operator_webhook_validation_bypasses_total The number of Bots admitted with validations bypassed\n\
+         # TYPE freqtrade_operator_webhook_validation_bypasses_total counter\n\
+         freqtrade_operator_webhook_validation_bypasses_total {}\n",
+        state.diagnostics.validation_bypasses(),
+    )
+}
+
 pub async fn create_tls_config(cert_file: String, key_file: String) -> RustlsConfig {
     RustlsConfig::from_pem_file(cert_file, key_file)
         .await
         .expect("Failed to create TLS config")
 }
 
-pub async fn serve(addr: String, router: Router, tls_config: RustlsConfig) -> std::io::Result<()> {
-    axum_server::bind_rustls(addr.parse::<SocketAddr>().expect("Invalid address"), tls_config)
-        .serve(router.into_make_service())
-        .await
+/// Reload the TLS certificate and key whenever their files change
+///
+/// The files are checked every `period` since cert-manager renews them in place, and the
+/// previous certificate keeps being served when the new one can't be loaded.
+///
+/// # Arguments
+/// * `tls_config` - The TLS config served by the webhook
+/// * `cert_file` - The path of the certificate
+/// * `key_file` - The path of the key
+/// * `period` - How often the files are checked
+pub async fn reload_tls_config(tls_config: RustlsConfig, cert_file: String, key_file: String, period: Duration) {
+    async fn modified(path: &str) -> Option<SystemTime> {
+        fs::metadata(path).await.and_then(|metadata| metadata.modified()).ok()
+    }
+
+    let mut last_modified = (modified(&cert_file).await, modified(&key_file).await);
+    let mut ticker = interval(period);
+    // The first tick completes immediately
+    ticker.tick().await;
+
+    loop {
+        ticker.tick().await;
+
+        let current = (modified(&cert_file).await, modified(&key_file).await);
+        if current == last_modified {
+            continue;
+        }
+
+        match tls_config.reload_from_pem_file(&cert_file, &key_file).await {
+            Ok(_) => {
+                info!(event = "TlsReloaded", cert_file = cert_file.as_str());
+                last_modified = current;
+            },
+            // The files may be mid-update, so the reload is retried on the next check
+            Err(e) => error!(event = "TlsReloadFailed", cert_file = cert_file.as_str(), error = %e),
+        }
+    }
 }
 
-pub async fn shutdown_signal(handle: axum_server::Handle) {
+/// Serve the webhook until it is asked to stop
+///
+/// The webhook is served over plain HTTP without a TLS config, e.g. behind a service mesh
+/// terminating TLS. On SIGTERM or CTRL+C the server stops accepting connections and waits up to
+/// `shutdown_timeout` for the requests in flight to complete.
+pub async fn serve(addr: String, router: Router, tls_config: Option<RustlsConfig>, shutdown_timeout: Duration) -> std::io::Result<()> {
+    let handle = axum_server::Handle::new();
+    tokio::spawn(shutdown_signal(handle.clone(), shutdown_timeout));
+
+    serve_with_handle(addr, router, tls_config, handle).await
+}
+
+/// Serve the webhook until the `handle` is shut down
+async fn serve_with_handle(addr: String, router: Router, tls_config: Option<RustlsConfig>, handle: axum_server::Handle) -> std::io::Result<()> {
+    let addr = addr.parse::<SocketAddr>().expect("Invalid address");
+
+    match tls_config {
+        Some(tls_config) => axum_server::bind_rustls(addr, tls_config)
+            .handle(handle)
+            .serve(router.into_make_service())
+            .await,
+        None => axum_server::bind(addr)
+            .handle(handle)
+            .serve(router.into_make_service())
+            .await,
+    }
+}
+
+pub async fn shutdown_signal(handle: axum_server::Handle, timeout: Duration) {
     let ctrl_c = async {
         signal::ctrl_c()
             .await
@@ -76,5 +157,241 @@ pub async fn shutdown_signal(handle: axum_server::Handle) {
         _ = terminate => (),
     }
 
-    handle.graceful_shutdown(Some(Duration::from_secs(10)));
-}
\ No newline at end of file
+    info!(event = "ShuttingDown", timeout_seconds = timeout.as_secs());
+    handle.graceful_shutdown(Some(timeout));
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::{header::CONTENT_TYPE, Request, StatusCode}};
+    use http_body_util::BodyExt;
+    use serde_json::{json, Value};
+    use std::path::{Path, PathBuf};
+    use tempfile::TempDir;
+    use tokio::{io::{AsyncReadExt, AsyncWriteExt}, net::TcpStream};
+    use tower::ServiceExt;
+
+    use ft_operator_common::config::AppConfig;
+
+    async fn post(router: Router, path: &str, content_type: &str, body: impl Into<Body>) -> (StatusCode, Value) {
+        let request = Request::post(path).header(CONTENT_TYPE, content_type).body(body.into()).unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn router_with(configure: impl FnOnce(&mut AppConfig)) -> Router {
+        let mut config = AppConfig::default();
+        configure(&mut config);
+        create_router(Arc::new(State::new(config)))
+    }
+
+    fn review(request: Value) -> String {
+        json!({"apiVersion": "admission.k8s.io/v1", "kind": "AdmissionReview", "request": request}).to_string()
+    }
+
+    #[tokio::test]
+    async fn a_wrong_content_type_is_an_api_error() {
+        let (status, body) = post(router_with(|_| ()), "/admission/freqtrade.io/bot/validate", "text/plain", "{}").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], json!(40001));
+    }
+
+    #[tokio::test]
+    async fn an_invalid_admission_review_is_an_api_error() {
+        let router = router_with(|_| ());
+
+        for body in ["{", r#"{"kind": 1}"#] {
+            let (status, body) = post(router.clone(), "/admission/freqtrade.io/bot/validate", "application/json", body).await;
+
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["code"], json!(40002));
+        }
+    }
+
+    #[tokio::test]
+    async fn an_oversized_body_is_an_api_error() {
+        let router = router_with(|config| config.webhook.max_body_bytes = 64);
+        let body = review(json!({"uid": "uid", "padding": "x".repeat(128)}));
+        let (status, body) = post(router, "/admission/freqtrade.io/bot/validate", "application/json", body).await;
+
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body["code"], json!(41301));
+    }
+
+    #[tokio::test]
+    async fn an_unknown_path_is_an_api_error() {
+        let (status, body) = post(router_with(|_| ()), "/admission/freqtrade.io/unknown/validate", "application/json", "{}").await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], json!(40401));
+    }
+
+    #[tokio::test]
+    async fn a_review_without_an_object_is_denied() {
+        let body = review(json!({
+            "uid": "uid",
+            "kind": {"group": "freqtrade.io", "version": "v1alpha1", "kind": "Bot"},
+            "resource": {"group": "freqtrade.io", "version": "v1alpha1", "resource": "bots"},
+            "name": "bot",
+            "namespace": "default",
+            "operation": "DELETE",
+            "userInfo": {"username": "admin"},
+            "oldObject": {"apiVersion": "freqtrade.io/v1alpha1", "kind": "Bot", "metadata": {"name": "bot"}},
+        }));
+        let (status, body) = post(router_with(|_| ()), "/admission/freqtrade.io/bot/validate", "application/json", body).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["response"]["uid"], json!("uid"));
+        assert_eq!(body["response"]["allowed"], json!(false));
+    }
+
+    #[tokio::test]
+    async fn a_bot_without_type_metadata_is_denied() {
+        let body = review(json!({
+            "uid": "uid",
+            "kind": {"group": "freqtrade.io", "version": "v1alpha1", "kind": "Bot"},
+            "resource": {"group": "freqtrade.io", "version": "v1alpha1", "resource": "bots"},
+            "name": "bot",
+            "namespace": "default",
+            "operation": "CREATE",
+            "userInfo": {"username": "admin"},
+            "object": {"metadata": {"name": "bot"}, "spec": {}},
+        }));
+
+        for path in ["/admission/freqtrade.io/bot/validate", "/admission/freqtrade.io/bot/mutate"] {
+            let (status, body) = post(router_with(|_| ()), path, "application/json", body.clone()).await;
+
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body["response"]["allowed"], json!(false), "{}", path);
+            assert_eq!(body["response"]["status"]["message"], json!("apiVersion and kind are required"), "{}", path);
+        }
+    }
+
+    const RELOAD_PERIOD: Duration = Duration::from_millis(10);
+
+    /// Write a new self-signed certificate and key, swapping them in like cert-manager does
+    fn write_certificate(cert_file: &Path, key_file: &Path) {
+        let rcgen::CertifiedKey { cert, key_pair } = rcgen::generate_simple_self_signed(vec!["webhook".to_string()]).unwrap();
+        swap_file(cert_file, cert.pem());
+        swap_file(key_file, key_pair.serialize_pem());
+    }
+
+    fn swap_file(path: &Path, contents: impl AsRef<[u8]>) {
+        let new_path = path.with_extension("new");
+        std::fs::write(&new_path, contents).unwrap();
+        std::fs::rename(new_path, path).unwrap();
+    }
+
+    async fn reloading_tls_config() -> (TempDir, PathBuf, PathBuf, RustlsConfig) {
+        // The provider is installed by the binary, as both rustls backends are enabled
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+
+        let dir = tempfile::tempdir().unwrap();
+        let (cert_file, key_file) = (dir.path().join("tls.crt"), dir.path().join("tls.key"));
+        write_certificate(&cert_file, &key_file);
+
+        let paths = (cert_file.display().to_string(), key_file.display().to_string());
+        let tls_config = create_tls_config(paths.0.clone(), paths.1.clone()).await;
+        tokio::spawn(reload_tls_config(tls_config.clone(), paths.0, paths.1, RELOAD_PERIOD));
+        // Let the reload take note of the current files before they are swapped
+        tokio::time::sleep(RELOAD_PERIOD * 5).await;
+
+        (dir, cert_file, key_file, tls_config)
+    }
+
+    /// Whether the served TLS config is reloaded within a second of making a change
+    async fn reloaded_after(tls_config: &RustlsConfig, change: impl FnOnce()) -> bool {
+        let served = tls_config.get_inner();
+        change();
+
+        for _ in 0..100 {
+            if !Arc::ptr_eq(&served, &tls_config.get_inner()) {
+                return true;
+            }
+            tokio::time::sleep(RELOAD_PERIOD).await;
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn unchanged_certificates_are_not_reloaded() {
+        let (_dir, _, _, tls_config) = reloading_tls_config().await;
+
+        assert!(!reloaded_after(&tls_config, || ()).await);
+    }
+
+    #[tokio::test]
+    async fn renewed_certificates_are_reloaded() {
+        let (_dir, cert_file, key_file, tls_config) = reloading_tls_config().await;
+
+        assert!(reloaded_after(&tls_config, || write_certificate(&cert_file, &key_file)).await);
+    }
+
+    #[tokio::test]
+    async fn invalid_certificates_keep_the_served_one_until_fixed() {
+        let (_dir, cert_file, key_file, tls_config) = reloading_tls_config().await;
+
+        assert!(!reloaded_after(&tls_config, || swap_file(&cert_file, "not a certificate")).await);
+        assert!(reloaded_after(&tls_config, || write_certificate(&cert_file, &key_file)).await);
+    }
+
+    const REQUEST_DURATION: Duration = Duration::from_millis(200);
+
+    /// Serve a router with a slow route, returning the handle and address it is served on
+    async fn serve_slow_router() -> (axum_server::Handle, SocketAddr, tokio::task::JoinHandle<std::io::Result<()>>) {
+        let router = Router::new().route("/slow", get(|| async {
+            tokio::time::sleep(REQUEST_DURATION).await;
+            "done"
+        }));
+        let handle = axum_server::Handle::new();
+        let server = tokio::spawn(serve_with_handle("127.0.0.1:0".to_string(), router, None, handle.clone()));
+        let addr = handle.listening().await.unwrap();
+
+        (handle, addr, server)
+    }
+
+    /// Start a request to the slow route, to be read once the server is shutting down
+    async fn start_slow_request(addr: SocketAddr) -> TcpStream {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(b"GET /slow HTTP/1.1\r\nHost: webhook\r\nConnection: close\r\n\r\n").await.unwrap();
+        // Give the server time to start handling the request
+        tokio::time::sleep(REQUEST_DURATION / 4).await;
+        stream
+    }
+
+    async fn read_response(mut stream: TcpStream) -> String {
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response).await;
+        response
+    }
+
+    #[tokio::test]
+    async fn requests_in_flight_complete_on_shutdown() {
+        let (handle, addr, server) = serve_slow_router().await;
+        let stream = start_slow_request(addr).await;
+
+        handle.graceful_shutdown(Some(REQUEST_DURATION * 10));
+        let response = read_response(stream).await;
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+        assert!(response.ends_with("done"), "{response}");
+        server.await.unwrap().unwrap();
+        // No connections are accepted once the server stopped
+        assert!(TcpStream::connect(addr).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn requests_outlasting_the_shutdown_timeout_are_dropped() {
+        let (handle, addr, server) = serve_slow_router().await;
+        let stream = start_slow_request(addr).await;
+
+        handle.graceful_shutdown(Some(REQUEST_DURATION / 4));
+        let response = read_response(stream).await;
+
+        assert_eq!(response, "");
+        server.await.unwrap().unwrap();
+    }
+}
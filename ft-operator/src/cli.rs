@@ -2,6 +2,7 @@
 //
 // SPDX-License-Identifier: ISC
 
+use std::path::PathBuf;
 use clap::{Parser, Subcommand};
 
 #[derive(Parser, Debug)]
@@ -14,6 +15,8 @@ use clap::{Parser, Subcommand};
     )
 ]
 pub struct CliArgs {
+    #[clap(long, global = true, help = "Path to a JSON or YAML config file, overridden by the environment")]
+    pub config: Option<String>,
     #[clap(subcommand)]
     pub cmd: Option<Commands>,
 }
@@ -26,7 +29,16 @@ pub enum Commands {
             about = "Generate Custom Resource Definitions (CRDs) for the operator"
         )
     ]
-    Crds,
+    Crds {
+        #[clap(long, help = "Directory to write a YAML file per CRD to, or file to write all CRDs to, instead of stdout")]
+        output: Option<PathBuf>,
+        #[clap(long, help = "Only generate the CRD of this kind, e.g. Bot")]
+        kind: Option<String>,
+        #[clap(long, help = "Only generate the CRDs serving this version, e.g. v1alpha2")]
+        version: Option<String>,
+        #[clap(long, help = "PEM file of the CA of the webhook certificate, without which Bots are only served at v1alpha1")]
+        ca_bundle: Option<PathBuf>,
+    },
     #[
         clap(
             name = "controller",
@@ -41,4 +53,35 @@ pub enum Commands {
         )
     ]
     Webhook,
+    #[
+        clap(
+            name = "validate",
+            about = "Validate Bot manifests against the admission webhook's rules",
+        )
+    ]
+    Validate {
+        #[clap(help = "A YAML file, a directory of YAML files, or - for stdin")]
+        path: String,
+        #[clap(long, help = "Fail on warnings as well as errors")]
+        warnings_as_errors: bool,
+    },
+    #[
+        clap(
+            name = "template",
+            about = "Render the resources the controller creates for Bot manifests",
+        )
+    ]
+    Template {
+        #[clap(help = "A YAML file of Bots, or - for stdin")]
+        path: String,
+        #[clap(long, help = "File to write the resources to instead of stdout")]
+        output: Option<PathBuf>,
+    },
+    #[
+        clap(
+            name = "doctor",
+            about = "Check the cluster's prerequisites for the operator",
+        )
+    ]
+    Doctor,
 }
@@ -0,0 +1,144 @@
+// SPDX-FileCopyrightText: 2025 Timothy Pogue
+//
+// SPDX-License-Identifier: ISC
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use ft_operator_controller::crd::utils::{crd_to_yaml, generate_crds};
+
+/// Print the CRDs or write them to files
+///
+/// # Arguments
+/// * `output` - A directory to write a `<name>.yaml` file per CRD to, or a file to write all
+///   the CRDs to as a multi-document YAML, or `None` to print them
+/// * `kind` - Only output the CRD of this kind, case insensitive
+/// * `version` - Only output the CRDs serving this version
+/// * `ca_bundle` - A PEM file of the CA of the conversion webhook's certificate
+pub fn output_crds(output: Option<&Path>, kind: Option<&str>, version: Option<&str>, ca_bundle: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let ca_bundle = ca_bundle.map(fs::read).transpose()?;
+    let crds: Vec<_> = generate_crds(ca_bundle.as_deref())?
+        .into_iter()
+        .filter(|crd| kind.is_none_or(|kind| crd.spec.names.kind.eq_ignore_ascii_case(kind)))
+        .filter(|crd| version.is_none_or(|version| crd.spec.versions.iter().any(|v| v.name == version)))
+        .collect();
+
+    if crds.is_empty() {
+        return Err("no CRD matches the kind and version".into());
+    }
+
+    match output {
+        Some(dir) if dir.is_dir() => {
+            for crd in &crds {
+                let name = crd.metadata.name.as_deref().unwrap_or(&crd.spec.names.plural);
+                fs::write(dir.join(format!("{}.yaml", name)), crd_to_yaml(crd)?)?;
+            }
+        },
+        output => {
+            let documents = crds
+                .iter()
+                .map(|crd| Ok(format!("---\n{}\n", crd_to_yaml(crd)?)))
+                .collect::<Result<String, Box<dyn Error>>>()?;
+
+            match output {
+                Some(file) => fs::write(file, documents)?,
+                None => print!("{}", documents),
+            }
+        },
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn documents(path: &Path) -> Vec<serde_yaml::Value> {
+        let content = fs::read_to_string(path).unwrap();
+        serde_yaml::Deserializer::from_str(&content).map(|document| serde::Deserialize::deserialize(document).unwrap()).collect()
+    }
+
+    fn kinds(documents: &[serde_yaml::Value]) -> Vec<&str> {
+        documents.iter().map(|document| document["spec"]["names"]["kind"].as_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn all_crds_are_written_to_a_single_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("crds.yaml");
+
+        output_crds(Some(&file), None, None, None).unwrap();
+
+        assert_eq!(kinds(&documents(&file)), vec!["Bot", "BotFleet", "Strategy", "ExchangeCredentials", "Backtest", "Hyperopt", "DataDownload"]);
+    }
+
+    #[test]
+    fn a_file_per_crd_is_written_to_a_directory() {
+        let dir = tempfile::tempdir().unwrap();
+
+        output_crds(Some(dir.path()), None, None, None).unwrap();
+
+        let mut files: Vec<_> = fs::read_dir(dir.path()).unwrap().map(|entry| entry.unwrap().file_name().into_string().unwrap()).collect();
+        files.sort();
+        assert_eq!(files, vec![
+            "backtests.freqtrade.io.yaml",
+            "botfleets.freqtrade.io.yaml",
+            "bots.freqtrade.io.yaml",
+            "datadownloads.freqtrade.io.yaml",
+            "exchangecredentials.freqtrade.io.yaml",
+            "hyperopts.freqtrade.io.yaml",
+            "strategies.freqtrade.io.yaml",
+        ]);
+        assert_eq!(kinds(&documents(&dir.path().join("bots.freqtrade.io.yaml"))), vec!["Bot"]);
+    }
+
+    #[test]
+    fn crds_are_filtered_by_kind_and_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("crds.yaml");
+
+        output_crds(Some(&file), Some("botfleet"), None, None).unwrap();
+        assert_eq!(kinds(&documents(&file)), vec!["BotFleet"]);
+
+        output_crds(Some(&file), None, Some("v1alpha2"), None).unwrap();
+        assert_eq!(kinds(&documents(&file)), vec!["Bot"]);
+
+        output_crds(Some(&file), Some("Strategy"), Some("v1alpha1"), None).unwrap();
+        assert_eq!(kinds(&documents(&file)), vec!["Strategy"]);
+    }
+
+    #[test]
+    fn bots_are_served_at_v1alpha2_with_a_ca_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("crds.yaml");
+        let ca_bundle = dir.path().join("ca.crt");
+        let served = |documents: &[serde_yaml::Value]| -> Vec<bool> {
+            documents[0]["spec"]["versions"].as_sequence().unwrap().iter().map(|version| version["served"].as_bool().unwrap()).collect()
+        };
+
+        output_crds(Some(&file), Some("Bot"), None, None).unwrap();
+        let bot = documents(&file);
+        assert_eq!(served(&bot), vec![true, false]);
+        assert_eq!(bot[0]["spec"]["conversion"]["strategy"], "None");
+
+        fs::write(&ca_bundle, "ca").unwrap();
+        output_crds(Some(&file), Some("Bot"), None, Some(&ca_bundle)).unwrap();
+        let bot = documents(&file);
+        assert_eq!(served(&bot), vec![true, true]);
+        assert_eq!(bot[0]["spec"]["conversion"]["webhook"]["clientConfig"]["caBundle"], "Y2E=");
+    }
+
+    #[test]
+    fn no_matching_crd_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("crds.yaml");
+
+        for (kind, version) in [(Some("Unknown"), None), (None, Some("v1")), (Some("Strategy"), Some("v1alpha2"))] {
+            let err = output_crds(Some(&file), kind, version, None).unwrap_err();
+            assert_eq!(err.to_string(), "no CRD matches the kind and version");
+        }
+        assert!(!file.exists());
+    }
+}
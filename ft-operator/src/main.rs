@@ -3,22 +3,32 @@
 // SPDX-License-Identifier: ISC
 
 mod cli;
+mod crds;
+mod template;
+mod validate;
 
 use std::sync::Arc;
-use futures::StreamExt;
+use std::time::Duration;
+use futures::future::try_join_all;
 use std::process;
 use clap::Parser;
 use clap::CommandFactory;
 use rustls::crypto::aws_lc_rs;
 
 use ft_operator_common::config::AppConfigBuilder;
-use ft_operator_common::state::State;
-use ft_operator_common::telemetry::{error, info, setup_logging};
-use ft_operator_controller::controller::{context::Context, utils::{error_policy, create_k8s_client}, bot::BotController};
-use ft_operator_controller::crd::{v1alpha1::bot::Bot as V1Alpha1Bot, utils as crd_utils};
-use ft_operator_webhook::server::{create_router, create_tls_config, serve};
+use ft_operator_common::state::{reload_config, State};
+use ft_operator_common::telemetry::{error, info, set_log_filters, setup_logging, shutdown_telemetry, warn};
+use ft_operator_controller::controller::{context::Context, doctor::run_checks, health::{create_health_router, serve_health}, utils::{error_policy, create_k8s_client, spawn_controller}, bot::BotController, bot_fleet::BotFleetController, strategy::StrategyController, backtest::BacktestController, hyperopt::HyperoptController, data_download::DataDownloadController};
+use ft_operator_controller::crd::{v1alpha1::{backtest::Backtest as V1Alpha1Backtest, bot::Bot as V1Alpha1Bot, bot_fleet::BotFleet as V1Alpha1BotFleet, data_download::DataDownload as V1Alpha1DataDownload, hyperopt::Hyperopt as V1Alpha1Hyperopt, strategy::Strategy as V1Alpha1Strategy}};
+use ft_operator_webhook::server::{create_router, create_tls_config, reload_tls_config, serve};
 
 use crate::cli::{CliArgs, Commands};
+use crate::crds::output_crds;
+use crate::template::render_manifests;
+use crate::validate::validate_manifests;
+
+// How often the config file is checked for changes
+static CONFIG_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
 
 #[tokio::main]
 async fn main() {
@@ -30,7 +40,15 @@ async fn main() {
     setup_logging();
 
     match &args.cmd {
-        Some(Commands::Crds) => crd_utils::generate_crds(),
+        Some(Commands::Crds { output, kind, version, ca_bundle }) => {
+            if let Err(e) = output_crds(output.as_deref(), kind.as_deref(), version.as_deref(), ca_bundle.as_deref()) {
+                error!(
+                    event = "Error",
+                    error = %e,
+                );
+                process::exit(1);
+            }
+        },
         Some(Commands::Webhook) => {
             info!(
                 event = "Starting",
@@ -38,9 +56,13 @@ async fn main() {
             );
 
             // Load configuration
-            let config = AppConfigBuilder::default()
+            let config_file = args.config.clone();
+            let build_config = move || AppConfigBuilder::default()
+                .with_file_option(config_file.as_deref())
                 .with_env()
-                .build()
+                .with_tls_files_check()
+                .build();
+            let config = build_config()
                 .unwrap_or_else(|e| {
                     error!(
                         event = "Error",
@@ -48,23 +70,56 @@ async fn main() {
                     );
                     process::exit(1);
                 });
+            set_log_filters(&config.telemetry.log_filters);
 
             // Create necessary resources
-            let state = Arc::new(State { config: config.clone() });
+            let state = Arc::new(State::new(config.clone()));
+            // Pick up config file changes without restarting
+            if let Some(config_file) = args.config.clone() {
+                tokio::spawn(reload_config(state.clone(), config_file, CONFIG_RELOAD_INTERVAL, build_config));
+            }
 
             let addr = format!("{}:{}", config.webhook.host, config.webhook.port);
-            let tls_config = create_tls_config(config.webhook.tls.cert_file.to_string(), config.webhook.tls.key_file.to_string()).await;
+            let tls_config = match config.webhook.tls.enabled {
+                true => {
+                    let tls_config = create_tls_config(config.webhook.tls.cert_file.to_string(), config.webhook.tls.key_file.to_string()).await;
+                    // Pick up renewed certificates without restarting the webhook
+                    if config.webhook.tls.reload_interval_seconds > 0 {
+                        tokio::spawn(reload_tls_config(
+                            tls_config.clone(),
+                            config.webhook.tls.cert_file.to_string(),
+                            config.webhook.tls.key_file.to_string(),
+                            Duration::from_secs(config.webhook.tls.reload_interval_seconds),
+                        ));
+                    }
+                    Some(tls_config)
+                },
+                // The API server only calls webhooks over HTTPS, so something else must terminate TLS
+                false => {
+                    warn!(
+                        event = "TlsDisabled",
+                        message = "The webhook is served over plain HTTP, TLS must be terminated in front of it",
+                    );
+                    None
+                },
+            };
             let router = create_router(state.clone());
 
             // Run Webhook server
             info!(event = "Listening", address = addr.as_str());
-            serve(addr, router, tls_config).await.unwrap_or_else(|e| {
-                error!(
-                    event = "Error",
-                    error = %e,
-                );
-                process::exit(1);
-            });
+            let shutdown_timeout = Duration::from_secs(config.webhook.shutdown_timeout_seconds);
+            match serve(addr, router, tls_config, shutdown_timeout).await {
+                Ok(_) => info!(event = "Stopped"),
+                Err(e) => {
+                    error!(
+                        event = "Error",
+                        error = %e,
+                    );
+                    shutdown_telemetry();
+                    process::exit(1);
+                },
+            }
+            shutdown_telemetry();
         },
         Some(Commands::Controller) => {
             info!(
@@ -73,9 +128,13 @@ async fn main() {
             );
 
             // Load configuration
-            let config = AppConfigBuilder::default()
+            let config_file = args.config.clone();
+            let build_config = move || AppConfigBuilder::default()
+                .with_file_option(config_file.as_deref())
                 .with_env()
-                .build()
+                .with_watch_namespace_env()
+                .build();
+            let config = build_config()
                 .unwrap_or_else(|e| {
                     error!(
                         event = "Error",
@@ -83,9 +142,14 @@ async fn main() {
                     );
                     process::exit(1);
                 });
+            set_log_filters(&config.telemetry.log_filters);
 
             // Create necessary resources
-            let state = Arc::new(State { config: config.clone() });
+            let state = Arc::new(State::new(config.clone()));
+            // Pick up config file changes without restarting
+            if let Some(config_file) = args.config.clone() {
+                tokio::spawn(reload_config(state.clone(), config_file, CONFIG_RELOAD_INTERVAL, build_config));
+            }
             let client = create_k8s_client().await.unwrap_or_else(|e| {
                 error!(
                     event = "Error",
@@ -93,29 +157,124 @@ async fn main() {
                 );
                 process::exit(1);
             });
-            let controller_ctx = Arc::new(Context::new(client).with_state(state.clone()));
-
-            // Create CRD controllers
-            let v1alpha1_bot_controller = BotController::create_controller::<V1Alpha1Bot>(controller_ctx.clone()).await;
-
-            // Run CRD controllers
-            info!(event = "ControllerStarted", kind = "Bot", version = "v1alpha1");
-            let v1alpha1_bot_handle = tokio::spawn(async move {
-                v1alpha1_bot_controller.run(BotController::reconcile::<V1Alpha1Bot>, error_policy::<V1Alpha1Bot>, controller_ctx.clone())
-                    .for_each(|r| async move {
-                        match r {
-                            Ok(_) => info!(event = "Reconciled", kind = "Bot", version = "v1alpha1"),
-                            Err(e) => error!(event = "ReconcileError", error = %e),
-                        }
-                    })
-                    .await
-            });
+            let controller_ctx = Arc::new(Context::new(client, state.clone()));
+
+            // Watch the configured namespaces, or all namespaces when none are configured
+            let namespaces = config.controller.watched_namespaces();
+
+            // Serve the health probes, readiness follows the controllers' activity
+            let health_addr = format!("{}:{}", config.controller.health.host, config.controller.health.port);
+            let health_router = create_health_router(controller_ctx.clone());
+            info!(event = "Listening", address = health_addr.as_str());
+            let mut handles = vec![tokio::spawn(async move {
+                if let Err(e) = serve_health(health_addr, health_router).await {
+                    error!(event = "Error", error = %e);
+                }
+            })];
+
+            // Create and run the CRD controllers, one per watched namespace
+            for namespace in &namespaces {
+                let namespace_name = namespace.as_deref().unwrap_or("*");
+
+                let v1alpha1_bot_controller = BotController::create_controller::<V1Alpha1Bot>(controller_ctx.clone(), namespace.as_deref()).await;
+                info!(event = "ControllerStarted", kind = "Bot", version = "v1alpha1", namespace = namespace_name);
+                handles.push(spawn_controller(v1alpha1_bot_controller, BotController::reconcile::<V1Alpha1Bot>, error_policy::<V1Alpha1Bot>, controller_ctx.clone(), "Bot", "v1alpha1"));
+
+                let v1alpha1_bot_fleet_controller = BotFleetController::create_controller::<V1Alpha1BotFleet>(controller_ctx.clone(), namespace.as_deref()).await;
+                info!(event = "ControllerStarted", kind = "BotFleet", version = "v1alpha1", namespace = namespace_name);
+                handles.push(spawn_controller(v1alpha1_bot_fleet_controller, BotFleetController::reconcile::<V1Alpha1BotFleet>, error_policy::<V1Alpha1BotFleet>, controller_ctx.clone(), "BotFleet", "v1alpha1"));
+
+                let v1alpha1_strategy_controller = StrategyController::create_controller::<V1Alpha1Strategy>(controller_ctx.clone(), namespace.as_deref()).await;
+                info!(event = "ControllerStarted", kind = "Strategy", version = "v1alpha1", namespace = namespace_name);
+                handles.push(spawn_controller(v1alpha1_strategy_controller, StrategyController::reconcile::<V1Alpha1Strategy>, error_policy::<V1Alpha1Strategy>, controller_ctx.clone(), "Strategy", "v1alpha1"));
+
+                let v1alpha1_backtest_controller = BacktestController::create_controller::<V1Alpha1Backtest>(controller_ctx.clone(), namespace.as_deref()).await;
+                info!(event = "ControllerStarted", kind = "Backtest", version = "v1alpha1", namespace = namespace_name);
+                handles.push(spawn_controller(v1alpha1_backtest_controller, BacktestController::reconcile::<V1Alpha1Backtest>, error_policy::<V1Alpha1Backtest>, controller_ctx.clone(), "Backtest", "v1alpha1"));
+
+                let v1alpha1_hyperopt_controller = HyperoptController::create_controller::<V1Alpha1Hyperopt>(controller_ctx.clone(), namespace.as_deref()).await;
+                info!(event = "ControllerStarted", kind = "Hyperopt", version = "v1alpha1", namespace = namespace_name);
+                handles.push(spawn_controller(v1alpha1_hyperopt_controller, HyperoptController::reconcile::<V1Alpha1Hyperopt>, error_policy::<V1Alpha1Hyperopt>, controller_ctx.clone(), "Hyperopt", "v1alpha1"));
+
+                let v1alpha1_data_download_controller = DataDownloadController::create_controller::<V1Alpha1DataDownload>(controller_ctx.clone(), namespace.as_deref()).await;
+                info!(event = "ControllerStarted", kind = "DataDownload", version = "v1alpha1", namespace = namespace_name);
+                handles.push(spawn_controller(v1alpha1_data_download_controller, DataDownloadController::reconcile::<V1Alpha1DataDownload>, error_policy::<V1Alpha1DataDownload>, controller_ctx.clone(), "DataDownload", "v1alpha1"));
+            }
 
             // Wait for all controllers to finish
-            match tokio::try_join!(v1alpha1_bot_handle) {
+            match try_join_all(handles).await {
                 Ok(_) => info!(event = "Stopped"),
                 Err(e) => error!(event = "Error", error = %e),
             }
+            shutdown_telemetry();
+        },
+        Some(Commands::Validate { path, warnings_as_errors }) => {
+            let config = AppConfigBuilder::default()
+                .with_file_option(args.config.as_deref())
+                .with_env()
+                .build()
+                .unwrap_or_else(|e| {
+                    error!(
+                        event = "Error",
+                        error = %e,
+                    );
+                    process::exit(1);
+                });
+
+            match validate_manifests(path, &config.webhook, *warnings_as_errors) {
+                Ok(true) => (),
+                Ok(false) => process::exit(1),
+                Err(e) => {
+                    error!(
+                        event = "Error",
+                        error = %e,
+                    );
+                    process::exit(1);
+                },
+            }
+        },
+        Some(Commands::Template { path, output }) => {
+            let config = AppConfigBuilder::default()
+                .with_file_option(args.config.as_deref())
+                .with_env()
+                .build()
+                .unwrap_or_else(|e| {
+                    error!(
+                        event = "Error",
+                        error = %e,
+                    );
+                    process::exit(1);
+                });
+
+            if let Err(e) = render_manifests(path, output.as_deref(), &config) {
+                error!(
+                    event = "Error",
+                    error = %e,
+                );
+                process::exit(1);
+            }
+        },
+        Some(Commands::Doctor) => {
+            let client = create_k8s_client().await.unwrap_or_else(|e| {
+                error!(
+                    event = "Error",
+                    error = %e,
+                );
+                process::exit(1);
+            });
+
+            let results = run_checks(&client).await;
+            for result in &results {
+                let status = match result.passed {
+                    true => "PASS",
+                    false => "FAIL",
+                };
+                println!("[{}] {}: {}", status, result.name, result.message);
+            }
+
+            if results.iter().any(|result| !result.passed) {
+                process::exit(1);
+            }
         },
         None => {
             let mut cmd = CliArgs::command();
@@ -0,0 +1,51 @@
+// SPDX-FileCopyrightText: 2025 Timothy Pogue
+//
+// SPDX-License-Identifier: ISC
+
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use serde::Deserialize;
+use serde_json::Value;
+
+use ft_operator_common::config::AppConfig;
+use ft_operator_controller::controller::template::render_bot;
+
+/// Render the resources of the Bots of a YAML file as a multi-document YAML
+///
+/// Documents of other kinds are skipped.
+///
+/// # Arguments
+/// * `path` - The YAML file of Bots, or `-` for stdin
+/// * `output` - A file to write the resources to, or `None` to print them
+/// * `config` - The operator configuration holding the defaults
+pub fn render_manifests(path: &str, output: Option<&Path>, config: &AppConfig) -> Result<(), Box<dyn Error>> {
+    let content = match path {
+        "-" => {
+            let mut content = String::new();
+            io::stdin().read_to_string(&mut content)?;
+            content
+        },
+        path => fs::read_to_string(path)?,
+    };
+
+    let mut documents = String::new();
+    for document in serde_yaml::Deserializer::from_str(&content) {
+        let manifest = Value::deserialize(document)?;
+        if manifest.get("kind").and_then(Value::as_str) != Some("Bot") {
+            continue;
+        }
+
+        for resource in render_bot(manifest, config)? {
+            documents.push_str(&format!("---\n{}", serde_yaml::to_string(&resource)?));
+        }
+    }
+
+    match output {
+        Some(file) => fs::write(file, documents)?,
+        None => print!("{}", documents),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,201 @@
+// SPDX-FileCopyrightText: 2025 Timothy Pogue
+//
+// SPDX-License-Identifier: ISC
+
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+
+use ft_operator_common::config::WebhookConfig;
+use ft_operator_webhook::admission::{bot::validate_bot_manifest, error::AdmissionError};
+
+/// Validate the Bot manifests of a file, the YAML files of a directory, or stdin
+///
+/// Every finding is printed as `<file>:<line>: <severity>: <message>`, the line being the start
+/// of the document of the Bot. Documents of other kinds are skipped.
+///
+/// # Arguments
+/// * `path` - The file or directory to validate, or `-` for stdin
+/// * `config` - The webhook configuration the Bots are validated against
+/// * `warnings_as_errors` - Whether warnings fail the validation
+///
+/// # Returns
+/// A Result containing whether every Bot is valid
+pub fn validate_manifests(path: &str, config: &WebhookConfig, warnings_as_errors: bool) -> Result<bool, Box<dyn Error>> {
+    let sources = match path {
+        "-" => {
+            let mut content = String::new();
+            io::stdin().read_to_string(&mut content)?;
+            vec![("<stdin>".to_string(), content)]
+        },
+        path if Path::new(path).is_dir() => {
+            let mut files: Vec<PathBuf> = fs::read_dir(path)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<io::Result<_>>()?;
+            files.retain(|file| file.extension().is_some_and(|extension| extension == "yaml" || extension == "yml"));
+            files.sort();
+
+            files
+                .into_iter()
+                .map(|file| Ok((file.display().to_string(), fs::read_to_string(&file)?)))
+                .collect::<io::Result<_>>()?
+        },
+        path => vec![(path.to_string(), fs::read_to_string(path)?)],
+    };
+
+    let mut valid = true;
+    for (name, content) in &sources {
+        for (line, document) in split_documents(content) {
+            match validate_document(&document, config) {
+                Ok(None) => (),
+                Ok(Some(warnings)) => {
+                    for warning in &warnings {
+                        println!("{}:{}: warning: {}", name, line, warning);
+                    }
+                    if warnings_as_errors && !warnings.is_empty() {
+                        valid = false;
+                    }
+                },
+                Err((offset, errors)) => {
+                    for error in &errors {
+                        println!("{}:{}: error: {}", name, line + offset, error);
+                    }
+                    valid = false;
+                },
+            }
+        }
+    }
+
+    Ok(valid)
+}
+
+/// Split a multi-document YAML into its documents
+///
+/// # Returns
+/// The documents with the line they start at
+fn split_documents(content: &str) -> Vec<(usize, String)> {
+    let mut documents = vec![(1, String::new())];
+
+    for (i, line) in content.lines().enumerate() {
+        if line == "---" || line.starts_with("--- ") {
+            documents.push((i + 2, String::new()));
+            continue;
+        }
+        if let Some((_, document)) = documents.last_mut() {
+            document.push_str(line);
+            document.push('\n');
+        }
+    }
+
+    documents
+}
+
+/// Validate a YAML document if it is a Bot
+///
+/// # Returns
+/// A Result containing the warnings for the Bot, or `None` when the document isn't a Bot, or the
+/// line offset in the document and the errors denying it
+fn validate_document(document: &str, config: &WebhookConfig) -> Result<Option<Vec<String>>, (usize, Vec<String>)> {
+    let manifest: Value = serde_yaml::from_str(document).map_err(|e| {
+        let offset = e.location().map_or(0, |location| location.line().saturating_sub(1));
+        (offset, vec![format!("invalid YAML: {}", e)])
+    })?;
+
+    if manifest.is_null() || manifest.get("kind").and_then(Value::as_str).is_some_and(|kind| kind != "Bot") {
+        return Ok(None);
+    }
+
+    match validate_bot_manifest(manifest, config) {
+        Ok(warnings) => Ok(Some(warnings)),
+        Err(AdmissionError::Violations(violations)) => Err((
+            0,
+            violations
+                .into_iter()
+                .map(|violation| format!("{}: {}", violation.field, violation.message))
+                .collect(),
+        )),
+        Err(e) => Err((0, vec![e.to_string()])),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID_BOT: &str = "\
+apiVersion: freqtrade.io/v1alpha1
+kind: Bot
+metadata:
+  name: bot
+spec:
+  exchange: binance
+  strategy:
+    name: SampleStrategy
+    configMapName: strategy
+";
+
+    #[test]
+    fn documents_start_after_their_separator() {
+        let content = "kind: ConfigMap\n---\nkind: Bot\n--- # second\n\nkind: Bot\n";
+
+        assert_eq!(split_documents(content), vec![
+            (1, "kind: ConfigMap\n".to_string()),
+            (3, "kind: Bot\n".to_string()),
+            (5, "\nkind: Bot\n".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn documents_of_other_kinds_are_skipped() {
+        let config = WebhookConfig::default();
+
+        assert_eq!(validate_document("apiVersion: v1\nkind: ConfigMap\nmetadata:\n  name: strategy\n", &config), Ok(None));
+        assert_eq!(validate_document("", &config), Ok(None));
+        assert_eq!(validate_document(VALID_BOT, &config), Ok(Some(vec![])));
+    }
+
+    #[test]
+    fn yaml_errors_are_reported_at_their_line_in_the_document() {
+        let (offset, errors) = validate_document("kind: Bot\nmetadata:\n  name: bot: other\n", &WebhookConfig::default()).unwrap_err();
+
+        assert_eq!(offset, 2);
+        assert!(errors[0].starts_with("invalid YAML: "), "{}", errors[0]);
+    }
+
+    #[test]
+    fn violations_are_reported_with_their_field() {
+        let document = format!("{}  database: mysql:/bot\n", VALID_BOT);
+        let (offset, errors) = validate_document(&document, &WebhookConfig::default()).unwrap_err();
+
+        assert_eq!(offset, 0);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("spec.database: database `mysql:/bot` is invalid"), "{}", errors[0]);
+    }
+
+    fn validate(content: &str, warnings_as_errors: bool) -> bool {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("bots.yaml");
+        fs::write(&file, content).unwrap();
+
+        validate_manifests(file.to_str().unwrap(), &WebhookConfig::default(), warnings_as_errors).unwrap()
+    }
+
+    #[test]
+    fn a_violation_fails_the_validation() {
+        let invalid = format!("{}  database: mysql:/bot\n", VALID_BOT);
+
+        assert!(validate(VALID_BOT, false));
+        assert!(!validate(&format!("{}---\n{}", VALID_BOT, invalid), false));
+    }
+
+    #[test]
+    fn warnings_only_fail_the_validation_as_errors() {
+        let warned = format!("{}  image:\n    tag: latest\n", VALID_BOT);
+
+        assert!(validate(&warned, false));
+        assert!(!validate(&warned, true));
+        assert!(validate(VALID_BOT, true));
+    }
+}